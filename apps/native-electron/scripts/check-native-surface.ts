@@ -0,0 +1,99 @@
+// Fails when the hand-written `KolodaDb` / `NativeAddon` interfaces in `src/native.ts` drift from the
+// `#[napi]` exports in `src-rust/src/lib.rs`: a missing, extra or renamed method, or a changed arity.
+import { readFileSync } from "node:fs";
+import { join } from "node:path";
+
+const __dirname = import.meta.dirname!;
+const rustSource = readFileSync(join(__dirname, "..", "src-rust", "src", "lib.rs"), "utf8");
+const tsSource = readFileSync(join(__dirname, "..", "src", "native.ts"), "utf8");
+
+type Surface = Map<string, number>;
+
+const OPENERS = "([{<";
+const CLOSERS = ")]}>";
+
+// Splits the text between the parens opening at `start` into top-level arguments.
+function readArgs(source: string, start: number): string[] {
+  const args: string[] = [];
+  let depth = 0;
+  let current = "";
+  for (let i = start; i < source.length; i++) {
+    const char = source[i]!;
+    if (char === "=" && source[i + 1] === ">") {
+      current += "=>";
+      i++;
+      continue;
+    }
+    if (OPENERS.includes(char)) {
+      depth++;
+      if (depth === 1) continue;
+    } else if (CLOSERS.includes(char)) {
+      depth--;
+      if (depth === 0) break;
+    } else if (char === "," && depth === 1) {
+      args.push(current.trim());
+      current = "";
+      continue;
+    }
+    current += char;
+  }
+  if (current.trim()) args.push(current.trim());
+  return args;
+}
+
+function toCamelCase(name: string): string {
+  return name.replace(/_([a-z0-9])/g, (_, char: string) => char.toUpperCase());
+}
+
+function rustSurface(block: string): Surface {
+  const surface: Surface = new Map();
+  const exported = /#\[napi\]\s*(?:(?:\/\/\/[^\n]*|#\[[^\n]*\])\s*)*pub fn (\w+)\(/g;
+  for (const match of block.matchAll(exported)) {
+    const args = readArgs(block, match.index + match[0].length - 1).filter(
+      (arg) => !arg.endsWith("self") && !/:\s*Env$/.test(arg),
+    );
+    surface.set(toCamelCase(match[1]!), args.length);
+  }
+  return surface;
+}
+
+function tsSurface(name: string): Surface {
+  const start = tsSource.indexOf(`export interface ${name} {`);
+  if (start === -1) throw new Error(`interface ${name} not found in native.ts`);
+  const block = tsSource.slice(start, tsSource.indexOf("\n}", start));
+  const surface: Surface = new Map();
+  for (const match of block.matchAll(/^ {2}(\w+)\(/gm)) {
+    surface.set(match[1]!, readArgs(block, match.index + match[0].length - 1).length);
+  }
+  return surface;
+}
+
+function compare(label: string, rust: Surface, ts: Surface): string[] {
+  const problems: string[] = [];
+  for (const [method, arity] of rust) {
+    const declared = ts.get(method);
+    if (declared === undefined) problems.push(`${label}.${method} is exported but not declared`);
+    else if (declared !== arity) problems.push(`${label}.${method} takes ${arity} args but is declared with ${declared}`);
+  }
+  for (const method of ts.keys()) {
+    if (!rust.has(method)) problems.push(`${label}.${method} is declared but not exported`);
+  }
+  return problems;
+}
+
+const implStart = rustSource.indexOf("impl KolodaDb {");
+const topLevel = rustSource.slice(0, implStart);
+const methods = rustSource.slice(implStart);
+
+const problems = [
+  ...compare("KolodaDb", rustSurface(methods), tsSurface("KolodaDb")),
+  // WHY: the constructors are declared as `new () => ...` properties, not methods.
+  ...compare("NativeAddon", rustSurface(topLevel), tsSurface("NativeAddon")),
+];
+
+if (problems.length > 0) {
+  console.error(`src/native.ts is out of sync with src-rust/src/lib.rs:\n  ${problems.join("\n  ")}`);
+  process.exit(1);
+}
+
+console.log("src/native.ts matches the native addon exports");
@@ -1,28 +1,61 @@
 use koloda_core::app::db::Database;
-use koloda_core::app::error::AppError;
+use koloda_core::app::error::{error_codes, AppError};
 use koloda_core::app::init::{self as init_mod, SeedData};
+use koloda_core::app::secrets::SecretStoreOptions;
+use koloda_core::app::watch::{ChangeWatcher, DEFAULT_POLL_INTERVAL};
+use koloda_core::domain::changes::ChangeEvent;
 use koloda_core::domain::lessons::GetLessonsParams;
 use koloda_core::domain::reviews::GetReviewTotalsParams;
 use koloda_core::domain::settings::SettingsName;
+use koloda_core::domain::sync::SyncOptions;
+use koloda_core::domain::typed_answer::compare_typed_answer;
+use koloda_core::http::{AnkiConnectServer, AnkiConnectServerOptions, HttpServer, HttpServerOptions};
 use koloda_core::repo;
+use koloda_core::sync::{sync, HttpSyncTransport};
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::JsFunction;
 use napi_derive::napi;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use task::{task_context, JsCancellationToken, JsonTask};
 
-fn to_napi_error(err: AppError) -> Error {
-    let error_json = serde_json::json!({
-        "code": err.code,
-        "details": err.details
-    });
-    Error::from_reason(error_json.to_string())
+mod task;
+
+// INVARIANT: every error leaving the addon is an `Error<String>` whose status is the `AppError` code,
+// so napi throws a JS `Error` with `code` set to it and the details as `message`; `NativeError` in
+// `src/native.ts` reads both back.
+type Result<T> = std::result::Result<T, Error<String>>;
+
+fn to_napi_error(err: AppError) -> Error<String> {
+    Error::new(err.code, err.details.unwrap_or_default())
+}
+
+// WHY: napi's own failures (threadsafe functions, value conversion) carry a `Status`, not an app code.
+fn from_napi_error(err: Error) -> Error<String> {
+    to_napi_error(AppError::new(error_codes::UNKNOWN, Some(err.reason)))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> Result<T> {
+    serde_json::from_value(value).map_err(|e| {
+        to_napi_error(AppError::new(
+            error_codes::VALIDATION_COMMON_PAYLOAD,
+            Some(e.to_string()),
+        ))
+    })
 }
 
 fn parse_settings_name(name: &str) -> Result<SettingsName> {
-    name.parse::<SettingsName>()
-        .map_err(|e| Error::from_reason(e.to_string()))
+    name.parse::<SettingsName>().map_err(|e| {
+        to_napi_error(AppError::new(
+            error_codes::VALIDATION_COMMON_PAYLOAD,
+            Some(e.to_string()),
+        ))
+    })
 }
 
 fn to_value<T: serde::Serialize>(val: &T) -> Result<serde_json::Value> {
-    serde_json::to_value(val).map_err(|e| Error::from_reason(e.to_string()))
+    serde_json::to_value(val).map_err(|e| to_napi_error(AppError::from(e)))
 }
 
 fn extract_id(params: serde_json::Value) -> Result<i64> {
@@ -30,9 +63,7 @@ fn extract_id(params: serde_json::Value) -> Result<i64> {
     struct P {
         id: i64,
     }
-    serde_json::from_value::<P>(params)
-        .map(|p| p.id)
-        .map_err(|e| Error::from_reason(e.to_string()))
+    from_json::<P>(params).map(|p| p.id)
 }
 
 fn extract_name(params: serde_json::Value) -> Result<String> {
@@ -40,22 +71,43 @@ fn extract_name(params: serde_json::Value) -> Result<String> {
     struct P {
         name: String,
     }
-    serde_json::from_value::<P>(params)
-        .map(|p| p.name)
-        .map_err(|e| Error::from_reason(e.to_string()))
+    from_json::<P>(params).map(|p| p.name)
+}
+
+#[napi]
+pub fn restore_pre_migration_snapshot(db_path: String) -> Result<()> {
+    koloda_core::app::db::restore_pre_migration_snapshot(db_path).map_err(to_napi_error)
+}
+
+#[napi]
+pub fn has_pre_migration_snapshot(db_path: String) -> bool {
+    koloda_core::app::db::pre_migration_snapshot_path(Path::new(&db_path)).exists()
 }
 
 #[napi]
 pub struct KolodaDb {
     db: Database,
+    db_path: PathBuf,
+    db_dir: Option<PathBuf>,
+    http_server: Mutex<Option<HttpServer>>,
+    anki_connect_server: Mutex<Option<AnkiConnectServer>>,
+    change_watcher: Mutex<Option<ChangeWatcher>>,
 }
 
 #[napi]
 impl KolodaDb {
     #[napi(constructor)]
     pub fn new(db_path: String) -> Result<Self> {
-        let db = Database::init(db_path).map_err(to_napi_error)?;
-        Ok(Self { db })
+        let db_dir = Path::new(&db_path).parent().map(Path::to_path_buf);
+        let db = Database::init(db_path.clone()).map_err(to_napi_error)?;
+        Ok(Self {
+            db,
+            db_path: PathBuf::from(db_path),
+            db_dir,
+            http_server: Mutex::new(None),
+            anki_connect_server: Mutex::new(None),
+            change_watcher: Mutex::new(None),
+        })
     }
 
     #[napi]
@@ -69,14 +121,13 @@ impl KolodaDb {
 
     #[napi]
     pub fn seed_db(&self, data: serde_json::Value) -> Result<()> {
-        let data: SeedData = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data: SeedData = from_json(data)?;
         init_mod::seed_db(&self.db, data).map_err(to_napi_error)
     }
 
     #[napi]
     pub fn get_cards(&self, params: serde_json::Value) -> Result<serde_json::Value> {
-        let params: koloda_core::domain::cards::GetCardsParams =
-            serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let params: koloda_core::domain::cards::GetCardsParams = from_json(params)?;
         let cards = repo::cards::get_cards(&self.db, params.deck_id).map_err(to_napi_error)?;
         to_value(&cards)
     }
@@ -90,44 +141,190 @@ impl KolodaDb {
 
     #[napi]
     pub fn add_card(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let card = repo::cards::add_card(&self.db, data).map_err(to_napi_error)?;
         to_value(&card)
     }
 
     #[napi]
-    pub fn add_cards(&self, cards_data: serde_json::Value) -> Result<serde_json::Value> {
-        let cards = serde_json::from_value(cards_data).map_err(|e| Error::from_reason(e.to_string()))?;
-        let result = repo::cards::add_cards(&self.db, cards).map_err(to_napi_error)?;
+    pub fn add_cards(
+        &self,
+        cards_data: serde_json::Value,
+        options: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let cards = from_json(cards_data)?;
+        let options = match options {
+            Some(options) => from_json(options)?,
+            None => Default::default(),
+        };
+        let result = repo::cards::add_cards(&self.db, cards, options).map_err(to_napi_error)?;
         to_value(&result)
     }
 
+    #[napi]
+    pub fn add_cards_async(
+        &self,
+        cards_data: serde_json::Value,
+        options: Option<serde_json::Value>,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let cards = from_json(cards_data)?;
+        let options = match options {
+            Some(options) => from_json(options)?,
+            None => Default::default(),
+        };
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let result = repo::cards::add_cards_with_task(&db, cards, options, &task).map_err(to_napi_error)?;
+            to_value(&result)
+        }))
+    }
+
     #[napi]
     pub fn update_card(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let card = repo::cards::update_card(&self.db, data).map_err(to_napi_error)?;
         to_value(&card)
     }
 
     #[napi]
     pub fn delete_card(&self, data: serde_json::Value) -> Result<()> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         repo::cards::delete_card(&self.db, data).map_err(to_napi_error)
     }
 
     #[napi]
     pub fn delete_cards(&self, data: serde_json::Value) -> Result<()> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         repo::cards::delete_cards(&self.db, data).map_err(to_napi_error)
     }
 
     #[napi]
     pub fn reset_card_progress(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let card = repo::cards::reset_card_progress(&self.db, data).map_err(to_napi_error)?;
         to_value(&card)
     }
 
+    #[napi]
+    pub fn rebuild_card_progress(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data = from_json(data)?;
+        let result = repo::cards::rebuild_card_progress(&self.db, data).map_err(to_napi_error)?;
+        to_value(&result)
+    }
+
+    #[napi]
+    pub fn rebuild_card_progress_async(
+        &self,
+        data: serde_json::Value,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let data = from_json(data)?;
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let result = repo::cards::rebuild_card_progress_with_task(&db, data, &task).map_err(to_napi_error)?;
+            to_value(&result)
+        }))
+    }
+
+    #[napi]
+    pub fn get_card_plain_text(&self, params: serde_json::Value) -> Result<Option<String>> {
+        let id = extract_id(params)?;
+        repo::cards::get_card_plain_text(&self.db, id).map_err(to_napi_error)
+    }
+
+    #[napi]
+    pub fn render_card(&self, params: serde_json::Value) -> Result<Option<serde_json::Value>> {
+        let id = extract_id(params)?;
+        let rendered = repo::cards::render_card(&self.db, id).map_err(to_napi_error)?;
+        rendered.map(|r| to_value(&r)).transpose()
+    }
+
+    #[napi]
+    pub fn render_deck_cards(&self, params: serde_json::Value) -> Result<serde_json::Value> {
+        let id = extract_id(params)?;
+        let rendered = repo::cards::render_deck_cards(&self.db, id).map_err(to_napi_error)?;
+        to_value(&rendered)
+    }
+
+    #[napi]
+    pub fn move_cards(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data = from_json(data)?;
+        let cards = repo::cards::move_cards(&self.db, data).map_err(to_napi_error)?;
+        to_value(&cards)
+    }
+
+    #[napi]
+    pub fn copy_cards(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data = from_json(data)?;
+        let cards = repo::cards::copy_cards(&self.db, data).map_err(to_napi_error)?;
+        to_value(&cards)
+    }
+
+    #[napi]
+    pub fn convert_cards_template(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data = from_json(data)?;
+        let result = repo::cards::convert_cards_template(&self.db, data).map_err(to_napi_error)?;
+        to_value(&result)
+    }
+
+    #[napi]
+    pub fn find_replace(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data = from_json(data)?;
+        let result = repo::cards::find_replace(&self.db, data).map_err(to_napi_error)?;
+        to_value(&result)
+    }
+
+    #[napi]
+    pub fn find_replace_async(
+        &self,
+        data: serde_json::Value,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let data = from_json(data)?;
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let result = repo::cards::find_replace_with_task(&db, data, &task).map_err(to_napi_error)?;
+            to_value(&result)
+        }))
+    }
+
+    #[napi]
+    pub fn find_duplicate_cards(&self, params: serde_json::Value) -> Result<serde_json::Value> {
+        let params = from_json(params)?;
+        let groups = repo::cards::find_duplicate_cards(&self.db, params).map_err(to_napi_error)?;
+        to_value(&groups)
+    }
+
+    #[napi]
+    pub fn find_duplicate_cards_async(
+        &self,
+        params: serde_json::Value,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let params = from_json(params)?;
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let groups = repo::cards::find_duplicate_cards_with_task(&db, params, &task).map_err(to_napi_error)?;
+            to_value(&groups)
+        }))
+    }
+
+    #[napi]
+    pub fn merge_cards(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data = from_json(data)?;
+        let card = repo::cards::merge_cards(&self.db, data).map_err(to_napi_error)?;
+        to_value(&card)
+    }
+
     #[napi]
     pub fn get_algorithms(&self) -> Result<serde_json::Value> {
         let algorithms = repo::algorithms::get_algorithms(&self.db).map_err(to_napi_error)?;
@@ -143,28 +340,28 @@ impl KolodaDb {
 
     #[napi]
     pub fn add_algorithm(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let algorithm = repo::algorithms::add_algorithm(&self.db, data).map_err(to_napi_error)?;
         to_value(&algorithm)
     }
 
     #[napi]
     pub fn update_algorithm(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let algorithm = repo::algorithms::update_algorithm(&self.db, data).map_err(to_napi_error)?;
         to_value(&algorithm)
     }
 
     #[napi]
     pub fn clone_algorithm(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let algorithm = repo::algorithms::clone_algorithm(&self.db, data).map_err(to_napi_error)?;
         to_value(&algorithm)
     }
 
     #[napi]
     pub fn delete_algorithm(&self, data: serde_json::Value) -> Result<()> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         repo::algorithms::delete_algorithm(&self.db, data).map_err(to_napi_error)
     }
 
@@ -190,21 +387,67 @@ impl KolodaDb {
 
     #[napi]
     pub fn add_deck(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let deck = repo::decks::add_deck(&self.db, data).map_err(to_napi_error)?;
         to_value(&deck)
     }
 
     #[napi]
     pub fn update_deck(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let deck = repo::decks::update_deck(&self.db, data).map_err(to_napi_error)?;
         to_value(&deck)
     }
 
+    #[napi]
+    pub fn export_deck(&self, params: serde_json::Value) -> Result<serde_json::Value> {
+        let id = extract_id(params)?;
+        let bundle = repo::decks::export_deck(&self.db, id).map_err(to_napi_error)?;
+        to_value(&bundle)
+    }
+
+    #[napi]
+    pub fn export_deck_async(
+        &self,
+        params: serde_json::Value,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let id = extract_id(params)?;
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let bundle = repo::decks::export_deck_with_task(&db, id, &task).map_err(to_napi_error)?;
+            to_value(&bundle)
+        }))
+    }
+
+    #[napi]
+    pub fn import_deck(&self, bundle: serde_json::Value) -> Result<serde_json::Value> {
+        let bundle = from_json(bundle)?;
+        let deck = repo::decks::import_deck(&self.db, bundle).map_err(to_napi_error)?;
+        to_value(&deck)
+    }
+
+    #[napi]
+    pub fn import_deck_async(
+        &self,
+        bundle: serde_json::Value,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let bundle = from_json(bundle)?;
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let deck = repo::decks::import_deck_with_task(&db, bundle, &task).map_err(to_napi_error)?;
+            to_value(&deck)
+        }))
+    }
+
     #[napi]
     pub fn delete_deck(&self, data: serde_json::Value) -> Result<()> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         repo::decks::delete_deck(&self.db, data).map_err(to_napi_error)
     }
 
@@ -223,28 +466,28 @@ impl KolodaDb {
 
     #[napi]
     pub fn add_template(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let template = repo::templates::add_template(&self.db, data).map_err(to_napi_error)?;
         to_value(&template)
     }
 
     #[napi]
     pub fn update_template(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let template = repo::templates::update_template(&self.db, data).map_err(to_napi_error)?;
         to_value(&template)
     }
 
     #[napi]
     pub fn clone_template(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let template = repo::templates::clone_template(&self.db, data).map_err(to_napi_error)?;
         to_value(&template)
     }
 
     #[napi]
     pub fn delete_template(&self, data: serde_json::Value) -> Result<()> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         repo::templates::delete_template(&self.db, data).map_err(to_napi_error)
     }
 
@@ -270,7 +513,7 @@ impl KolodaDb {
             name: String,
             content: serde_json::Value,
         }
-        let p: P = serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let p: P = from_json(params)?;
         let name = parse_settings_name(&p.name)?;
         let settings = repo::settings::set_settings(&self.db, name, p.content).map_err(to_napi_error)?;
         to_value(&settings)
@@ -283,19 +526,83 @@ impl KolodaDb {
             name: String,
             content: serde_json::Value,
         }
-        let p: P = serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let p: P = from_json(params)?;
         let name = parse_settings_name(&p.name)?;
         let settings = repo::settings::patch_settings(&self.db, name, p.content).map_err(to_napi_error)?;
         to_value(&settings)
     }
 
+    #[napi]
+    pub fn get_settings_history(&self, params: serde_json::Value) -> Result<serde_json::Value> {
+        let params = from_json(params)?;
+        let history = repo::settings::get_settings_history(&self.db, params).map_err(to_napi_error)?;
+        to_value(&history)
+    }
+
+    #[napi]
+    pub fn diff_settings(&self, params: serde_json::Value) -> Result<serde_json::Value> {
+        let params = from_json(params)?;
+        let diff = repo::settings::diff_settings(&self.db, params).map_err(to_napi_error)?;
+        to_value(&diff)
+    }
+
+    #[napi]
+    pub fn rollback_settings(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data: koloda_core::domain::settings::RollbackSettingsData = from_json(data)?;
+        let settings = repo::settings::rollback_settings(&self.db, data.id).map_err(to_napi_error)?;
+        to_value(&settings)
+    }
+
+    #[napi]
+    pub fn export_settings_bundle(&self) -> Result<serde_json::Value> {
+        let bundle = repo::settings::export_settings_bundle(&self.db).map_err(to_napi_error)?;
+        to_value(&bundle)
+    }
+
+    #[napi]
+    pub fn export_settings_bundle_async(
+        &self,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let bundle = repo::settings::export_settings_bundle_with_task(&db, &task).map_err(to_napi_error)?;
+            to_value(&bundle)
+        }))
+    }
+
+    #[napi]
+    pub fn import_settings_bundle(&self, bundle: serde_json::Value) -> Result<serde_json::Value> {
+        let bundle = from_json(bundle)?;
+        let result = repo::settings::import_settings_bundle(&self.db, bundle).map_err(to_napi_error)?;
+        to_value(&result)
+    }
+
+    #[napi]
+    pub fn import_settings_bundle_async(
+        &self,
+        bundle: serde_json::Value,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let bundle = from_json(bundle)?;
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let result = repo::settings::import_settings_bundle_with_task(&db, bundle, &task).map_err(to_napi_error)?;
+            to_value(&result)
+        }))
+    }
+
     #[napi]
     pub fn get_conversation(&self, params: serde_json::Value) -> Result<Option<serde_json::Value>> {
         #[derive(serde::Deserialize)]
         struct P {
             id: String,
         }
-        let p: P = serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let p: P = from_json(params)?;
         let conversation = repo::conversations::get_conversation(&self.db, &p.id).map_err(to_napi_error)?;
         conversation.map(|c| to_value(&c)).transpose()
     }
@@ -308,8 +615,7 @@ impl KolodaDb {
 
     #[napi]
     pub fn set_conversation(&self, params: serde_json::Value) -> Result<serde_json::Value> {
-        let input: repo::conversations::SetConversationInput =
-            serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let input: repo::conversations::SetConversationInput = from_json(params)?;
         let conversation = repo::conversations::set_conversation(&self.db, input).map_err(to_napi_error)?;
         to_value(&conversation)
     }
@@ -320,57 +626,173 @@ impl KolodaDb {
         struct P {
             id: String,
         }
-        let p: P = serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let p: P = from_json(params)?;
         repo::conversations::delete_conversation(&self.db, &p.id).map_err(to_napi_error)
     }
 
     #[napi]
     pub fn get_lessons(&self, params: serde_json::Value) -> Result<serde_json::Value> {
-        let params: GetLessonsParams = serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let params: GetLessonsParams = from_json(params)?;
         let lessons = repo::lessons::get_lessons(&self.db, params).map_err(to_napi_error)?;
         to_value(&lessons)
     }
 
     #[napi]
     pub fn get_lesson_data(&self, params: serde_json::Value) -> Result<Option<serde_json::Value>> {
-        let params = serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let params = from_json(params)?;
         let data = repo::lessons::get_lesson_data(&self.db, &params).map_err(to_napi_error)?;
         data.map(|d| to_value(&d)).transpose()
     }
 
     #[napi]
     pub fn submit_lesson_result(&self, data: serde_json::Value) -> Result<()> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         repo::lessons::submit_lesson_result(&self.db, data).map_err(to_napi_error)
     }
 
+    #[napi]
+    pub fn compare_typed_answer(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data = from_json(data)?;
+        to_value(&compare_typed_answer(&data).map_err(to_napi_error)?)
+    }
+
     #[napi]
     pub fn get_reviews(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data = serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data = from_json(data)?;
         let reviews = repo::reviews::get_reviews(&self.db, data).map_err(to_napi_error)?;
         to_value(&reviews)
     }
 
     #[napi]
     pub fn get_review_totals(&self, params: serde_json::Value) -> Result<serde_json::Value> {
-        let params: GetReviewTotalsParams =
-            serde_json::from_value(params).map_err(|e| Error::from_reason(e.to_string()))?;
+        let params: GetReviewTotalsParams = from_json(params)?;
         let totals = repo::reviews::get_review_totals(&self.db, params).map_err(to_napi_error)?;
         to_value(&totals)
     }
 
+    #[napi]
+    pub fn get_review_totals_async(
+        &self,
+        params: serde_json::Value,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let params: GetReviewTotalsParams = from_json(params)?;
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let totals = repo::reviews::get_review_totals_with_task(&db, params, &task).map_err(to_napi_error)?;
+            to_value(&totals)
+        }))
+    }
+
     #[napi]
     pub fn get_todays_review_totals(&self) -> Result<serde_json::Value> {
         let totals = repo::reviews::get_todays_review_totals(&self.db).map_err(to_napi_error)?;
         to_value(&totals)
     }
 
+    #[napi]
+    pub fn get_changes(&self, params: serde_json::Value) -> Result<serde_json::Value> {
+        let params = from_json(params)?;
+        let page = repo::changes::get_changes(&self.db, params).map_err(to_napi_error)?;
+        to_value(&page)
+    }
+
+    #[napi]
+    pub fn get_latest_change_cursor(&self) -> Result<i64> {
+        repo::changes::get_latest_change_cursor(&self.db).map_err(to_napi_error)
+    }
+
+    /// Calls `callback` with batches of change events after `cursor` (default: the latest);
+    /// replaces an earlier subscription.
+    #[napi]
+    pub fn subscribe_changes(&self, callback: JsFunction, cursor: Option<i64>) -> Result<()> {
+        let cursor = match cursor {
+            Some(cursor) => cursor,
+            None => repo::changes::get_latest_change_cursor(&self.db).map_err(to_napi_error)?,
+        };
+        // WHY: Events arrive on the poller thread; the threadsafe function queues them onto the JS thread.
+        let callback: ThreadsafeFunction<Vec<ChangeEvent>, ErrorStrategy::Fatal> = callback
+            .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<Vec<ChangeEvent>>| {
+                serde_json::to_value(ctx.value)
+                    .map(|events| vec![events])
+                    .map_err(|e| Error::from_reason(e.to_string()))
+            })
+            .map_err(from_napi_error)?;
+
+        let mut slot = self.change_watcher_slot()?;
+        if let Some(watcher) = slot.take() {
+            watcher.stop();
+        }
+        *slot = Some(ChangeWatcher::start(
+            self.db.clone(),
+            cursor,
+            DEFAULT_POLL_INTERVAL,
+            move |events| {
+                callback.call(events, ThreadsafeFunctionCallMode::NonBlocking);
+            },
+        ));
+        Ok(())
+    }
+
+    #[napi]
+    pub fn unsubscribe_changes(&self) -> Result<()> {
+        if let Some(watcher) = self.change_watcher_slot()?.take() {
+            watcher.stop();
+        }
+        Ok(())
+    }
+
+    fn change_watcher_slot(&self) -> Result<MutexGuard<'_, Option<ChangeWatcher>>> {
+        self.change_watcher
+            .lock()
+            .map_err(|e| to_napi_error(AppError::new(error_codes::UNKNOWN, Some(e.to_string()))))
+    }
+
     #[napi]
     pub fn get_ai_profiles(&self) -> Result<serde_json::Value> {
         let profiles = repo::ai::get_ai_profiles(&self.db).map_err(to_napi_error)?;
         to_value(&profiles)
     }
 
+    // INVARIANT: Main-process only — the passphrase must never cross the renderer IPC boundary.
+    #[napi]
+    pub fn configure_secret_store(&self, options: serde_json::Value) -> Result<serde_json::Value> {
+        let mut options: SecretStoreOptions = from_json(options)?;
+        if options.directory.is_none() {
+            options.directory = self.db_dir.clone();
+        }
+        let backend = repo::ai::configure_ai_secret_store(&self.db, &options).map_err(to_napi_error)?;
+        to_value(&backend)
+    }
+
+    #[napi]
+    pub fn check_ai_secrets(&self) -> Result<serde_json::Value> {
+        let health = repo::ai::check_ai_secrets(&self.db).map_err(to_napi_error)?;
+        to_value(&health)
+    }
+
+    #[napi]
+    pub fn repair_ai_secrets(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data = from_json(data)?;
+        let health = repo::ai::repair_ai_secrets(&self.db, data).map_err(to_napi_error)?;
+        to_value(&health)
+    }
+
+    #[napi]
+    pub fn export_ai_secrets(&self, data: serde_json::Value) -> Result<String> {
+        let data: koloda_core::domain::ai::ExportAISecretsData = from_json(data)?;
+        repo::ai::export_ai_secrets(&self.db, &data.passphrase).map_err(to_napi_error)
+    }
+
+    #[napi]
+    pub fn import_ai_secrets(&self, data: serde_json::Value) -> Result<serde_json::Value> {
+        let data: koloda_core::domain::ai::ImportAISecretsData = from_json(data)?;
+        let result = repo::ai::import_ai_secrets(&self.db, &data.bundle, &data.passphrase).map_err(to_napi_error)?;
+        to_value(&result)
+    }
+
     // INVARIANT: Main-process only — usable secrets for host AI handlers.
     // Do not register as a renderer `cmd_*`.
     #[napi]
@@ -381,8 +803,7 @@ impl KolodaDb {
 
     #[napi]
     pub fn add_ai_profile(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data: koloda_core::domain::ai::AddProfileData =
-            serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data: koloda_core::domain::ai::AddProfileData = from_json(data)?;
         let profile = repo::ai::add_ai_profile(&self.db, data.title, data.secrets, data.whitelist_model_ids)
             .map_err(to_napi_error)?;
         to_value(&profile)
@@ -390,8 +811,7 @@ impl KolodaDb {
 
     #[napi]
     pub fn update_ai_profile(&self, data: serde_json::Value) -> Result<serde_json::Value> {
-        let data: koloda_core::domain::ai::UpdateProfileData =
-            serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data: koloda_core::domain::ai::UpdateProfileData = from_json(data)?;
         let profile =
             repo::ai::update_ai_profile(&self.db, &data.id, data.title, data.secrets, data.whitelist_model_ids)
                 .map_err(to_napi_error)?;
@@ -400,13 +820,153 @@ impl KolodaDb {
 
     #[napi]
     pub fn remove_ai_profile(&self, data: serde_json::Value) -> Result<()> {
-        let data: koloda_core::domain::ai::RemoveProfileData =
-            serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
+        let data: koloda_core::domain::ai::RemoveProfileData = from_json(data)?;
         repo::ai::remove_ai_profile(&self.db, &data.id).map_err(to_napi_error)
     }
 
+    /// Replaces a running server, so a new port or token takes effect immediately.
+    #[napi]
+    pub fn start_http_server(&self, options: Option<serde_json::Value>) -> Result<serde_json::Value> {
+        let options: HttpServerOptions = match options {
+            Some(options) => from_json(options)?,
+            None => Default::default(),
+        };
+        let mut slot = self.http_server_slot()?;
+        if let Some(server) = slot.take() {
+            server.stop();
+        }
+        let server = HttpServer::start(self.db.clone(), options).map_err(to_napi_error)?;
+        let info = to_value(server.info())?;
+        *slot = Some(server);
+        Ok(info)
+    }
+
+    #[napi]
+    pub fn stop_http_server(&self) -> Result<()> {
+        if let Some(server) = self.http_server_slot()?.take() {
+            server.stop();
+        }
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_http_server_info(&self) -> Result<Option<serde_json::Value>> {
+        self.http_server_slot()?
+            .as_ref()
+            .map(|server| to_value(server.info()))
+            .transpose()
+    }
+
+    fn http_server_slot(&self) -> Result<MutexGuard<'_, Option<HttpServer>>> {
+        self.http_server
+            .lock()
+            .map_err(|e| to_napi_error(AppError::new(error_codes::UNKNOWN, Some(e.to_string()))))
+    }
+
+    /// Replaces a running server, like `start_http_server`.
+    #[napi]
+    pub fn start_anki_connect_server(&self, options: Option<serde_json::Value>) -> Result<serde_json::Value> {
+        let options: AnkiConnectServerOptions = match options {
+            Some(options) => from_json(options)?,
+            None => Default::default(),
+        };
+        let mut slot = self.anki_connect_server_slot()?;
+        if let Some(server) = slot.take() {
+            server.stop();
+        }
+        let server = AnkiConnectServer::start(self.db.clone(), options).map_err(to_napi_error)?;
+        let info = to_value(server.info())?;
+        *slot = Some(server);
+        Ok(info)
+    }
+
+    #[napi]
+    pub fn stop_anki_connect_server(&self) -> Result<()> {
+        if let Some(server) = self.anki_connect_server_slot()?.take() {
+            server.stop();
+        }
+        Ok(())
+    }
+
+    #[napi]
+    pub fn get_anki_connect_server_info(&self) -> Result<Option<serde_json::Value>> {
+        self.anki_connect_server_slot()?
+            .as_ref()
+            .map(|server| to_value(server.info()))
+            .transpose()
+    }
+
+    fn anki_connect_server_slot(&self) -> Result<MutexGuard<'_, Option<AnkiConnectServer>>> {
+        self.anki_connect_server
+            .lock()
+            .map_err(|e| to_napi_error(AppError::new(error_codes::UNKNOWN, Some(e.to_string()))))
+    }
+
+    /// Pushes local changes to the sync server, then pulls the other devices' changes.
+    #[napi]
+    pub fn sync_async(&self, options: serde_json::Value) -> Result<AsyncTask<JsonTask>> {
+        let options: SyncOptions = from_json(options)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let report = sync(&db, &HttpSyncTransport::new(&options)).map_err(to_napi_error)?;
+            to_value(&report)
+        }))
+    }
+
+    #[napi]
+    pub fn check_database_async(&self) -> AsyncTask<JsonTask> {
+        let db = self.db.clone();
+        JsonTask::spawn(move || {
+            let report = repo::database_check::check_database(&db).map_err(to_napi_error)?;
+            to_value(&report)
+        })
+    }
+
+    #[napi]
+    pub fn repair_database_async(&self, data: serde_json::Value) -> Result<AsyncTask<JsonTask>> {
+        let data = from_json(data)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            let result = repo::database_check::repair_database(&db, data).map_err(to_napi_error)?;
+            to_value(&result)
+        }))
+    }
+
     #[napi]
     pub fn checkpoint(&self) -> Result<()> {
         self.db.checkpoint().map_err(to_napi_error)
     }
+
+    #[napi]
+    pub fn backup_async(
+        &self,
+        path: String,
+        token: Option<ClassInstance<JsCancellationToken>>,
+        on_progress: Option<JsFunction>,
+    ) -> Result<AsyncTask<JsonTask>> {
+        let path = PathBuf::from(path);
+        // INVARIANT: `Database::backup` deletes the target first, so it must never be the live file.
+        let canonical = |path: &Path| path.canonicalize().map_err(|e| to_napi_error(AppError::from(e)));
+        if path.exists() && canonical(&path)? == canonical(&self.db_path)? {
+            return Err(to_napi_error(AppError::new(
+                error_codes::VALIDATION_COMMON_PAYLOAD,
+                Some(format!("Backup path is the database itself: {}", path.display())),
+            )));
+        }
+        let task = task_context(token, on_progress)?;
+        let db = self.db.clone();
+        Ok(JsonTask::spawn(move || {
+            db.backup_with_task(&path, &task).map_err(to_napi_error)?;
+            Ok(serde_json::Value::Null)
+        }))
+    }
+
+    #[napi]
+    pub fn checkpoint_async(&self) -> AsyncTask<JsonTask> {
+        let db = self.db.clone();
+        JsonTask::spawn(move || {
+            db.checkpoint().map_err(to_napi_error)?;
+            Ok(serde_json::Value::Null)
+        })
+    }
 }
@@ -0,0 +1,112 @@
+//! Promise-returning wrappers for long repo calls, with progress and cancellation driven from JS.
+
+use koloda_core::app::error::{error_codes, AppError};
+use koloda_core::app::task::{CancellationToken, TaskContext, TaskProgress};
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{JsFunction, JsUnknown};
+use napi_derive::napi;
+
+type JsonJob = Box<dyn FnOnce() -> crate::Result<serde_json::Value> + Send>;
+
+/// Runs a repo call on the libuv pool and resolves with its JSON result.
+pub struct JsonTask {
+    job: Option<JsonJob>,
+    failure: Option<Error<String>>,
+}
+
+impl JsonTask {
+    pub fn spawn(job: impl FnOnce() -> crate::Result<serde_json::Value> + Send + 'static) -> AsyncTask<Self> {
+        AsyncTask::new(Self {
+            job: Some(Box::new(job)),
+            failure: None,
+        })
+    }
+}
+
+impl Task for JsonTask {
+    type Output = serde_json::Value;
+    type JsValue = JsUnknown;
+
+    // WHY: `Task` only carries a `Status` error, so the coded error waits here until `reject`.
+    fn compute(&mut self) -> Result<Self::Output> {
+        let result = match self.job.take() {
+            Some(job) => job(),
+            None => Err(crate::to_napi_error(AppError::new(
+                error_codes::UNKNOWN,
+                Some("Task already ran".to_string()),
+            ))),
+        };
+        result.map_err(|failure| {
+            let error = Error::from_reason(failure.reason.clone());
+            self.failure = Some(failure);
+            error
+        })
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        env.to_js_value(&output)
+    }
+
+    fn reject(&mut self, env: Env, err: Error) -> Result<Self::JsValue> {
+        match self.failure.take() {
+            Some(failure) => Err(Error::from(JsError::from(failure).into_unknown(env))),
+            None => Err(err),
+        }
+    }
+}
+
+#[napi(js_name = "CancellationToken")]
+pub struct JsCancellationToken {
+    inner: CancellationToken,
+}
+
+#[napi]
+impl JsCancellationToken {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self {
+            inner: CancellationToken::new(),
+        }
+    }
+
+    #[napi]
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    #[napi(getter)]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.is_cancelled()
+    }
+}
+
+impl Default for JsCancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// WHY: Progress fires on a pool thread; the threadsafe function queues it onto the JS thread.
+pub fn task_context(
+    token: Option<ClassInstance<JsCancellationToken>>,
+    on_progress: Option<JsFunction>,
+) -> crate::Result<TaskContext> {
+    let token = token.map(|token| token.inner.clone()).unwrap_or_default();
+    let task = TaskContext::new(token);
+    let Some(on_progress) = on_progress else {
+        return Ok(task);
+    };
+
+    let callback: ThreadsafeFunction<TaskProgress, ErrorStrategy::Fatal> = on_progress
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<TaskProgress>| {
+            serde_json::to_value(ctx.value)
+                .map(|progress| vec![progress])
+                .map_err(|e| Error::from_reason(e.to_string()))
+        })
+        .map_err(crate::from_napi_error)?;
+
+    Ok(task.with_progress(move |progress| {
+        callback.call(progress, ThreadsafeFunctionCallMode::NonBlocking);
+    }))
+}
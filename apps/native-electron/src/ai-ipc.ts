@@ -22,6 +22,7 @@ import {
 } from "@koloda/ai";
 import type { IpcMainInvokeEvent, WebContents } from "electron";
 import { ipcMain } from "electron";
+import { NativeError } from "./native";
 
 export const AI_STREAM_CHANNEL = "ai:stream";
 
@@ -79,20 +80,8 @@ function loadSecrets(db: KolodaDb, profileId: string): AISecrets {
   try {
     raw = db.getAiProfileSecrets(profileId);
   } catch (error) {
-    // WHY: NAPI AppError reasons are JSON `{ code, details }`.
-    if (error instanceof Error) {
-      const start = error.message.indexOf("{");
-      const end = error.message.lastIndexOf("}");
-      if (start !== -1 && end > start) {
-        try {
-          const parsed = JSON.parse(error.message.slice(start, end + 1)) as { code?: string; details?: string };
-          if (typeof parsed.code === "string") {
-            throw new AIError(parsed.code, parsed.details || parsed.code);
-          }
-        } catch (inner) {
-          if (isAIError(inner)) throw inner;
-        }
-      }
+    if (error instanceof NativeError) {
+      throw new AIError(error.code, error.details || error.code);
     }
     throw toAIError(error);
   }
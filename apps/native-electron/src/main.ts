@@ -1,9 +1,11 @@
 import { app, BrowserWindow, ipcMain, nativeTheme, net, screen } from "electron";
 import { readFileSync, writeFileSync } from "node:fs";
-import { createRequire } from "node:module";
 import os from "node:os";
 import { join } from "node:path";
 import { registerAiIpc } from "./ai-ipc";
+import { loadNativeAddon } from "./native";
+import type { CancellationToken, KolodaDb, NativeAddon } from "./native";
+import type { TaskProgress } from "./native-types";
 import {
   APP_SHUTDOWN_ACK_CHANNEL,
   APP_SHUTDOWN_REQUEST_CHANNEL,
@@ -44,12 +46,11 @@ function configureUserData() {
   }
 }
 
-function loadNativeAddon(): { KolodaDb: new (dbPath: string) => any } {
-  const req = createRequire(import.meta.url);
+function loadAddon(): NativeAddon {
   const addonPath = isDev
     ? join(__dirname, "..", "dist", "koloda_electron.node")
     : join(__dirname, "koloda_electron.node");
-  return req(addonPath);
+  return loadNativeAddon(addonPath);
 }
 
 function loadUiPrefs(): UiPrefsState {
@@ -344,7 +345,29 @@ function registerWindowIpc() {
   });
 }
 
-function registerDataIpc(db: any) {
+function registerDataIpc(db: KolodaDb, native: NativeAddon) {
+  // WHY: Long commands run as native async tasks. A renderer-supplied `taskId` routes
+  // `task_progress` events back to the sender and lets `cmd_cancel_task` stop the work.
+  const runningTasks = new Map<string, CancellationToken>();
+  const runTask = (
+    event: Electron.IpcMainInvokeEvent,
+    taskId: string | undefined,
+    start: (token: CancellationToken, onProgress: (progress: TaskProgress) => void) => Promise<unknown>,
+  ) => {
+    const token = new native.CancellationToken();
+    if (taskId) runningTasks.set(taskId, token);
+    const onProgress = (progress: TaskProgress) => {
+      if (taskId && !event.sender.isDestroyed()) event.sender.send("task_progress", { taskId, ...progress });
+    };
+    return start(token, onProgress).finally(() => {
+      if (taskId) runningTasks.delete(taskId);
+    });
+  };
+
+  ipcMain.handle("cmd_cancel_task", async (_event, { taskId }: any) => {
+    runningTasks.get(taskId)?.cancel();
+  });
+
   ipcMain.handle("get_db_status", async () => db.getDbStatus());
 
   ipcMain.handle("seed_db", async (_event, { data }: any) => {
@@ -355,11 +378,29 @@ function registerDataIpc(db: any) {
   ipcMain.handle("cmd_get_cards", async (_event, { params }: any) => db.getCards(params));
   ipcMain.handle("cmd_get_card", async (_event, args: any) => db.getCard(args));
   ipcMain.handle("cmd_add_card", async (_event, { data }: any) => db.addCard(data));
-  ipcMain.handle("cmd_add_cards", async (_event, { data }: any) => db.addCards(data));
+  ipcMain.handle("cmd_add_cards", async (event, { data, options, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.addCardsAsync(data, options, token, onProgress)),
+  );
   ipcMain.handle("cmd_update_card", async (_event, { data }: any) => db.updateCard(data));
   ipcMain.handle("cmd_delete_card", async (_event, { data }: any) => db.deleteCard(data));
   ipcMain.handle("cmd_delete_cards", async (_event, { data }: any) => db.deleteCards(data));
   ipcMain.handle("cmd_reset_card_progress", async (_event, { data }: any) => db.resetCardProgress(data));
+  ipcMain.handle("cmd_rebuild_card_progress", async (event, { data, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.rebuildCardProgressAsync(data, token, onProgress)),
+  );
+  ipcMain.handle("cmd_render_card", async (_event, args: any) => db.renderCard(args));
+  ipcMain.handle("cmd_get_card_plain_text", async (_event, args: any) => db.getCardPlainText(args));
+  ipcMain.handle("cmd_render_deck_cards", async (_event, args: any) => db.renderDeckCards(args));
+  ipcMain.handle("cmd_move_cards", async (_event, { data }: any) => db.moveCards(data));
+  ipcMain.handle("cmd_copy_cards", async (_event, { data }: any) => db.copyCards(data));
+  ipcMain.handle("cmd_convert_cards_template", async (_event, { data }: any) => db.convertCardsTemplate(data));
+  ipcMain.handle("cmd_find_replace", async (event, { data, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.findReplaceAsync(data, token, onProgress)),
+  );
+  ipcMain.handle("cmd_find_duplicate_cards", async (event, { params, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.findDuplicateCardsAsync(params, token, onProgress)),
+  );
+  ipcMain.handle("cmd_merge_cards", async (_event, { data }: any) => db.mergeCards(data));
 
   ipcMain.handle("cmd_get_algorithms", async () => db.getAlgorithms());
   ipcMain.handle("cmd_get_algorithm", async (_event, args: any) => db.getAlgorithm(args));
@@ -374,6 +415,12 @@ function registerDataIpc(db: any) {
   ipcMain.handle("cmd_add_deck", async (_event, { data }: any) => db.addDeck(data));
   ipcMain.handle("cmd_update_deck", async (_event, { data }: any) => db.updateDeck(data));
   ipcMain.handle("cmd_delete_deck", async (_event, { data }: any) => db.deleteDeck(data));
+  ipcMain.handle("cmd_export_deck", async (event, { params, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.exportDeckAsync(params, token, onProgress)),
+  );
+  ipcMain.handle("cmd_import_deck", async (event, { bundle, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.importDeckAsync(bundle, token, onProgress)),
+  );
 
   ipcMain.handle("cmd_get_templates", async () => db.getTemplates());
   ipcMain.handle("cmd_get_template", async (_event, args: any) => db.getTemplate(args));
@@ -386,6 +433,15 @@ function registerDataIpc(db: any) {
   ipcMain.handle("cmd_get_settings", async (_event, args: any) => db.getSettings(args));
   ipcMain.handle("cmd_set_settings", async (_event, args: any) => db.setSettings(args));
   ipcMain.handle("cmd_patch_settings", async (_event, args: any) => db.patchSettings(args));
+  ipcMain.handle("cmd_get_settings_history", async (_event, { params }: any) => db.getSettingsHistory(params));
+  ipcMain.handle("cmd_diff_settings", async (_event, { params }: any) => db.diffSettings(params));
+  ipcMain.handle("cmd_rollback_settings", async (_event, { data }: any) => db.rollbackSettings(data));
+  ipcMain.handle("cmd_export_settings_bundle", async (event, { taskId }: any = {}) =>
+    runTask(event, taskId, (token, onProgress) => db.exportSettingsBundleAsync(token, onProgress)),
+  );
+  ipcMain.handle("cmd_import_settings_bundle", async (event, { bundle, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.importSettingsBundleAsync(bundle, token, onProgress)),
+  );
 
   ipcMain.handle("cmd_get_conversation", async (_event, args: any) => db.getConversation(args));
   ipcMain.handle("cmd_get_conversations", async () => db.getConversations());
@@ -395,29 +451,62 @@ function registerDataIpc(db: any) {
   ipcMain.handle("cmd_get_lessons", async (_event, { params }: any) => db.getLessons(params));
   ipcMain.handle("cmd_get_lesson_data", async (_event, { params }: any) => db.getLessonData(params));
   ipcMain.handle("cmd_submit_lesson_result", async (_event, { data }: any) => db.submitLessonResult(data));
+  ipcMain.handle("cmd_compare_typed_answer", async (_event, { data }: any) => db.compareTypedAnswer(data));
 
   ipcMain.handle("cmd_get_reviews", async (_event, { data }: any) => db.getReviews(data));
-  ipcMain.handle("cmd_get_review_totals", async (_event, { data }: any) => db.getReviewTotals(data));
+  ipcMain.handle("cmd_get_review_totals", async (event, { data, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.getReviewTotalsAsync(data, token, onProgress)),
+  );
   ipcMain.handle("cmd_get_todays_review_totals", async () => db.getTodaysReviewTotals());
 
+  ipcMain.handle("cmd_get_changes", async (_event, { params }: any) => db.getChanges(params));
+  ipcMain.handle("cmd_get_latest_change_cursor", async () => db.getLatestChangeCursor());
+  // WHY: One subscription feeds every window; renderers listen on `db_changes` instead of re-querying.
+  db.subscribeChanges((events) => {
+    for (const win of BrowserWindow.getAllWindows()) {
+      if (!win.webContents.isDestroyed()) win.webContents.send("db_changes", events);
+    }
+  });
+
+  ipcMain.handle("cmd_start_http_server", async (_event, { options }: any) => db.startHttpServer(options));
+  ipcMain.handle("cmd_stop_http_server", async () => db.stopHttpServer());
+  ipcMain.handle("cmd_get_http_server_info", async () => db.getHttpServerInfo());
+  ipcMain.handle("cmd_start_anki_connect_server", async (_event, { options }: any) =>
+    db.startAnkiConnectServer(options),
+  );
+  ipcMain.handle("cmd_stop_anki_connect_server", async () => db.stopAnkiConnectServer());
+  ipcMain.handle("cmd_get_anki_connect_server_info", async () => db.getAnkiConnectServerInfo());
+  ipcMain.handle("cmd_sync", async (_event, { options }: any) => db.syncAsync(options));
+  ipcMain.handle("cmd_check_database", async () => db.checkDatabaseAsync());
+  ipcMain.handle("cmd_repair_database", async (_event, { data }: any) => db.repairDatabaseAsync(data));
+  ipcMain.handle("cmd_backup_database", async (event, { path, taskId }: any) =>
+    runTask(event, taskId, (token, onProgress) => db.backupAsync(path, token, onProgress)),
+  );
+
   ipcMain.handle("cmd_get_ai_profiles", async () => db.getAiProfiles());
   ipcMain.handle("cmd_add_ai_profile", async (_event, { data }: any) => db.addAiProfile(data));
   ipcMain.handle("cmd_update_ai_profile", async (_event, { data }: any) => db.updateAiProfile(data));
   ipcMain.handle("cmd_remove_ai_profile", async (_event, { data }: any) => db.removeAiProfile(data));
+  ipcMain.handle("cmd_check_ai_secrets", async () => db.checkAiSecrets());
+  ipcMain.handle("cmd_repair_ai_secrets", async (_event, { data }: any) => db.repairAiSecrets(data));
+  ipcMain.handle("cmd_export_ai_secrets", async (_event, { data }: any) => db.exportAiSecrets(data));
+  ipcMain.handle("cmd_import_ai_secrets", async (_event, { data }: any) => db.importAiSecrets(data));
 
   // INVARIANT: AI provider calls + secret loads stay in main. Do not add cmd_* for getAiProfileSecrets.
-  registerAiIpc(db);
+  // WHY: Rust serializes template field `type` as a plain string; ai-ipc keeps its narrower union.
+  registerAiIpc(db as unknown as Parameters<typeof registerAiIpc>[0]);
 }
 
 configureUserData();
 
 app.whenReady().then(() => {
-  const native = loadNativeAddon();
+  const native = loadAddon();
   const dbPath = join(app.getPath("userData"), "koloda.db");
   const db = new native.KolodaDb(dbPath);
+  db.configureSecretStore({ backend: "auto" });
 
   registerWindowIpc();
-  registerDataIpc(db);
+  registerDataIpc(db, native);
   createWindow();
 });
 
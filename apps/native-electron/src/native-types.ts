@@ -0,0 +1,536 @@
+// This file has been generated by Specta. DO NOT EDIT.
+
+export type AIProfile = { id: string; title: string | null; secrets?: AISecrets | null; hasSecrets?: boolean; whitelistModelIds?: string[] | null; createdAt: string }
+
+export type AIProfileSecretsHealth = { profileId: string; title: string | null; provider: string | null; status: AISecretsStatus }
+
+export type AISecrets = { provider: "openrouter"; apiKey: string | null } | { provider: "ollama"; baseUrl: string; apiKey?: string | null } | { provider: "lmstudio"; baseUrl: string; apiKey?: string | null } | { provider: "opencodeGo"; apiKey: string | null } | { provider: "opencodeZen"; apiKey: string | null } | { provider: "ollamaCloud"; apiKey: string | null }
+
+/**
+ * Plaintext inside an encrypted secrets export: profile id → API key.
+ */
+export type AISecretsBundle = { keys: Partial<{ [key in string]: string }> }
+
+/**
+ * Cross-check of AI profiles against the active secret store.
+ * 
+ * `orphaned_profile_ids` lists stored keys with no matching profile; it is `None` when the
+ * store cannot enumerate its keys.
+ */
+export type AISecretsHealth = { backend: SecretStoreBackend; profiles: AIProfileSecretsHealth[]; orphanedProfileIds: string[] | null }
+
+export type AISecretsRepair = { action: "rekey"; profileId: string; apiKey: string } | { action: "purge"; profileId: string }
+
+export type AISecretsStatus = "ok" | "missing-key"
+
+export type AISettings = { profiles: AIProfile[]; assistant?: AssistantSettings | null }
+
+export type AddCardsItemError = { code: string; details?: string | null; existingCardId?: number | null }
+
+/**
+ * Per-item batch-add result — mirrors `@koloda/srs` `InsertCardsResponse`.
+ * 
+ * Success items omit `error` (serialize as `{}`); failures keep the structured
+ * `code`/`details` so consumers can translate codes instead of parsing strings.
+ * `duplicate_of` is set only when `DuplicatePolicy::Skip` left the item out; `card_id`
+ * only when the item was inserted.
+ */
+export type AddCardsItemResult = { error?: AddCardsItemError | null; duplicateOf?: number | null; cardId?: number | null }
+
+/**
+ * Duplicate handling for `add_cards`, scoped to each item's target deck.
+ * 
+ * The key is the [`duplicate_key`] of `duplicate_field_id`, or of the template's first
+ * field (its sort field) when unset; only cards of the same template are compared, and an id
+ * the item's template lacks fails that item. Earlier items of the same batch count as existing cards.
+ */
+export type AddCardsOptions = { duplicatePolicy?: DuplicatePolicy; duplicateFieldId?: number | null }
+
+export type AddProfileData = { title: string | null; secrets: AISecrets | null; whitelistModelIds?: string[] | null }
+
+export type Algorithm = { id: number; title: string; content: AlgorithmFSRS; createdAt: string; updatedAt?: string | null }
+
+export type AlgorithmDeck = { id: number; title: string }
+
+export type AlgorithmFSRS = { type: string; retention: number; weights: string; isFuzzEnabled: boolean; learningSteps: ([number, string])[]; relearningSteps: ([number, string])[]; maximumInterval: number }
+
+export type AnkiConnectServerInfo = { port: number; requiresKey: boolean }
+
+export type AnkiConnectServerOptions = { 
+/**
+ * Defaults to AnkiConnect's 8765; `0` picks a free port.
+ */
+port?: number | null; 
+/**
+ * Required in every request when set, like AnkiConnect's `apiKey`.
+ */
+key?: string | null }
+
+export type ApiKeySecret = { api_key: string }
+
+export type AppError = { code: string; details: string | null }
+
+export type AssistantSettings = { temperature?: number; chatPromptTemplate: string | null }
+
+export type Card = { id: number; deckId: number; templateId: number; content: Partial<{ [key in string]: CardContentField }>; state: number; dueAt?: string | null; stability: number | null; difficulty: number | null; scheduledDays: number; learningSteps: number; reps: number; lapses: number; lastReviewedAt?: string | null; createdAt?: string; updatedAt?: string | null }
+
+export type CardContentField = { text: string }
+
+/**
+ * Source → target `TemplateField.id` pair used when cards change template.
+ * 
+ * Source fields without a pair are dropped; target fields without a pair stay empty.
+ */
+export type CardFieldMapping = { source: number; target: number }
+
+/**
+ * Cards targeted by bulk operations.
+ * 
+ * Both filters narrow the selection (AND); at least one must be non-empty.
+ */
+export type CardSelection = { deckIds?: number[] | null; cardIds?: number[] | null }
+
+export type ChangeEntity = "algorithm" | "template" | "deck" | "card" | "review" | "settings" | "conversation"
+
+export type ChangeEvent = { 
+/**
+ * Feed position; pass the last one seen as the next `cursor`.
+ */
+id: number; entity: ChangeEntity; 
+/**
+ * Row id as text; conversation ids are strings.
+ */
+entityId: string; operation: ChangeOperation; 
+/**
+ * Epoch ms.
+ */
+changedAt: number }
+
+export type ChangeOperation = "insert" | "update" | "delete"
+
+export type ChangesPage = { events: ChangeEvent[]; 
+/**
+ * Position after this page; unchanged when there were no events.
+ */
+cursor: number; hasMore: boolean }
+
+export type CloneAlgorithmData = { title: string; sourceId: number }
+
+export type CloneTemplateData = { title: string; sourceId: number }
+
+export type CompareTypedAnswerData = { expected: string; typed: string; options?: TypedAnswerOptions }
+
+export type Conversation = { id: string; title: string | null; state: JsonValue; createdAt?: number; updatedAt?: number | null }
+
+export type ConvertCardsTemplateData = { ids: number[]; templateId: number; fieldMapping: CardFieldMapping[] }
+
+export type ConvertCardsTemplateFailure = { cardId: number; code: string; details?: string | null }
+
+/**
+ * Outcome of a template conversion.
+ * 
+ * INVARIANT: non-empty `failed` means nothing was written — `converted` is then empty.
+ * `unlocked_template_ids` lists former templates that no card references any more.
+ */
+export type ConvertCardsTemplateResult = { converted: number[]; failed: ConvertCardsTemplateFailure[]; unlockedTemplateIds: number[] }
+
+export type CopyCardsData = { ids: number[]; deckId: number; fieldMapping?: CardFieldMapping[] | null; resetProgress?: boolean }
+
+export type CountedDailyLimit = { value: number; counts: boolean }
+
+export type DailyLimits = { total: number; untouched: CountedDailyLimit; learn: CountedDailyLimit; review: CountedDailyLimit }
+
+export type DatabaseCheckReport = { issues: DatabaseIssue[] }
+
+export type DatabaseIssue = { kind: DatabaseIssueKind; 
+/**
+ * Empty for `integrity` messages, which name their own location.
+ */
+table: string; rowId: number | null; details: string; isFixable: boolean }
+
+/**
+ * The fix `repair_database` applies is noted per kind; kinds without one are reported only.
+ */
+export type DatabaseIssueKind = 
+/**
+ * `PRAGMA integrity_check` message.
+ */
+"integrity" | 
+/**
+ * `PRAGMA foreign_key_check` row not covered by a more specific kind.
+ */
+"foreign-key" | 
+/**
+ * Template content that does not parse.
+ */
+"template-content" | 
+/**
+ * Fix: drop layout items whose field no longer exists, when any item remains.
+ */
+"template-layout" | 
+/**
+ * Card content that does not parse or fails `validate_content` for its template.
+ */
+"card-content" | 
+/**
+ * A reviewed state without `due_at`. Fix: rebuild progress from the card's reviews; only
+ * fixable when the card has a graded, non-ignored review to rebuild from.
+ */
+"card-progress" | 
+/**
+ * Fix: delete the review.
+ */
+"orphan-review" | 
+/**
+ * Fix: point the deck at the oldest algorithm.
+ */
+"deck-algorithm" | 
+/**
+ * Fix: restore the latest history entry that still normalizes.
+ */
+"settings"
+
+export type DbStatus = "blank" | "ok"
+
+export type Deck = { id: number; title: string; algorithmId: number; templateId: number; createdAt: string; updatedAt?: string | null }
+
+export type DeckBundle = { version: number; exportedAt: string; title: string; algorithm: DeckBundlePreset; template: DeckBundlePreset; cards?: DeckBundleCard[] }
+
+export type DeckBundleCard = { content: Partial<{ [key in string]: CardContentField }>; state?: number; dueAt?: string | null; stability?: number | null; difficulty?: number | null; scheduledDays?: number; learningSteps?: number; reps?: number; lapses?: number; lastReviewedAt?: string | null }
+
+/**
+ * `content` is the versioned JSON stored in the `algorithms` / `templates` row.
+ */
+export type DeckBundlePreset = { title: string; content: JsonValue }
+
+export type DeleteAlgorithmData = { id: number; successorId: number | null }
+
+export type DeleteCardData = { id: number }
+
+export type DeleteCardsData = { ids: number[] }
+
+export type DeleteDeckData = { id: number }
+
+export type DeleteTemplateData = { id: number }
+
+/**
+ * Versions are history entry ids; a missing `to_id` compares against the current content.
+ */
+export type DiffSettingsParams = { fromId: number; toId?: number | null }
+
+export type DuplicateCardGroup = { templateId: number; key: string; cardIds: number[] }
+
+export type DuplicatePolicy = "allow" | "reject" | "skip"
+
+export type ExportAISecretsData = { passphrase: string }
+
+export type FieldScript = "latin" | "cyrillic" | "greek" | "arabic" | "hebrew" | "devanagari" | "thai" | "han" | "hiragana" | "katakana" | "hangul"
+
+export type FindDuplicateCardsParams = { selection: CardSelection; fieldIds?: number[] | null }
+
+export type FindReplaceCardChange = { cardId: number; before: Partial<{ [key in string]: CardContentField }>; after: Partial<{ [key in string]: CardContentField }> }
+
+export type FindReplaceData = { selection: CardSelection; fieldId?: number | null; pattern: string; isRegex?: boolean; replacement: string; mode: FindReplaceMode }
+
+export type FindReplaceFailure = { cardId: number; code: string; details?: string | null }
+
+export type FindReplaceMode = "preview" | "apply"
+
+/**
+ * Outcome of a find & replace.
+ * 
+ * INVARIANT: non-empty `failed` means nothing was written — `is_applied` is then false,
+ * while `changes` still lists the replacements that would have passed validation.
+ */
+export type FindReplaceResult = { changes: FindReplaceCardChange[]; failed: FindReplaceFailure[]; isApplied: boolean }
+
+export type GetCardsParams = { deckId: number }
+
+export type GetChangesParams = { 
+/**
+ * Events after this position; `0` reads from the start.
+ */
+cursor?: number; 
+/**
+ * Defaults to `DEFAULT_CHANGES_LIMIT`.
+ */
+limit?: number | null }
+
+export type GetLessonDataParams = { dueAt?: number; filters: LessonFilters; amounts: LessonAmounts }
+
+export type GetLessonsParams = { dueAt?: number; filters: LessonFilters | null }
+
+export type GetReviewTotalsParams = { from: number; to: number }
+
+export type GetReviewsData = { cardId: number }
+
+export type GetSettingsHistoryParams = { name: SettingsName; limit?: number | null }
+
+export type HotkeysSettings = { form?: Partial<{ [key in string]: string[] }>; ui?: Partial<{ [key in string]: string[] }>; navigation?: Partial<{ [key in string]: string[] }>; grades?: Partial<{ [key in string]: string[] }>; ai?: Partial<{ [key in string]: string[] }> }
+
+export type HttpServerInfo = { port: number; token: string }
+
+export type HttpServerOptions = { 
+/**
+ * `0` or unset picks a free port.
+ */
+port?: number; 
+/**
+ * Generated when unset or blank.
+ */
+token?: string | null }
+
+export type ImportAISecretsData = { bundle: string; passphrase: string }
+
+export type ImportAISecretsResult = { imported: string[]; skipped: string[] }
+
+/**
+ * Nothing is written unless `errors` is empty; `applied` says whether the import committed.
+ */
+export type ImportSettingsBundleResult = { applied: boolean; errors: SettingsBundleSliceError[]; settings: SettingsName[]; algorithmIds: number[]; templateIds: number[] }
+
+export type InsertAlgorithmData = { title: string; content: AlgorithmFSRS }
+
+export type InsertCardData = { deckId: number; templateId: number; content: Partial<{ [key in string]: CardContentField }>; state: number | null; dueAt?: number | null; stability: number | null; difficulty: number | null; scheduledDays: number | null; learningSteps: number | null; reps: number | null; lapses: number | null; lastReviewedAt?: number | null }
+
+export type InsertDeckData = { title: string; algorithmId: number; templateId: number }
+
+export type InsertReviewData = { cardId: number; rating: number; state: number; dueAt?: number | null; stability: number; difficulty: number; scheduledDays: number; learningSteps: number; time: number; isIgnored: boolean; 
+/**
+ * What was typed for a `type` layout field, with its `compare_typed_answer` similarity.
+ */
+typedAnswer?: string | null; typedScore?: number | null }
+
+export type InsertTemplateData = { title: string; content: TemplateContent }
+
+export type InterfaceSettings = { language: string; scheme: string; lightTheme?: string; darkTheme?: string; motion: string }
+
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+
+export type LearnAheadLimit = [number, number]
+
+export type LearningDefaults = { algorithm?: number; template?: number }
+
+export type LearningSettings = { defaults: LearningDefaults; dailyLimits: DailyLimits; dayStartsAt: string; learnAheadLimit: LearnAheadLimit }
+
+export type LessonAmounts = { untouched: number; learn: number; review: number; total: number }
+
+export type LessonData = { cards: Card[]; decks: Deck[]; templates: LessonTemplate[]; algorithms: Algorithm[] }
+
+export type LessonDeck = { id: number; title: string; untouched: number; learn: number; review: number; total: number }
+
+export type LessonFilters = { deckIds: number[] | null }
+
+export type LessonResultData = { card: UpdateCardProgress; review: InsertReviewData }
+
+export type LessonTemplate = { id: number; title: string; fields: TemplateField[]; layout: LessonTemplateLayoutItem[]; createdAt: string; updatedAt?: string | null }
+
+export type LessonTemplateLayoutItem = { field: TemplateField | null; operation: string; fieldId: number }
+
+export type LessonsResult = { total: LessonAmounts; decks: LessonDeck[] }
+
+export type MergeCardsData = { keepId: number; mergeIds: number[] }
+
+export type MoveCardsData = { ids: number[]; deckId: number; fieldMapping?: CardFieldMapping[] | null }
+
+export type RebuildCardProgressData = { scope: RebuildCardProgressScope; id: number }
+
+/**
+ * `changed` counts the cards whose stored progress differed from the replayed one.
+ */
+export type RebuildCardProgressResult = { total: number; changed: number }
+
+/**
+ * What `id` in `RebuildCardProgressData` names: one card, every card of a deck, or every card
+ * of the decks using an algorithm.
+ */
+export type RebuildCardProgressScope = "card" | "deck" | "algorithm"
+
+export type RemoveProfileData = { id: string }
+
+export type RenderedCard = { cardId: number; question: string; answer: string }
+
+export type RepairAISecretsData = { repairs: AISecretsRepair[] }
+
+export type RepairDatabaseData = { kinds: DatabaseIssueKind[] }
+
+/**
+ * `report` is a fresh check after the fixes.
+ */
+export type RepairDatabaseResult = { fixed: number; report: DatabaseCheckReport }
+
+export type ResetCardProgressData = { id: number }
+
+export type Review = { id: number; cardId: number; rating: number; state: number; dueAt?: number | null; stability: number; difficulty: number; scheduledDays: number; learningSteps: number; time: number; isIgnored: boolean; typedAnswer: string | null; typedScore: number | null; createdAt: string }
+
+export type ReviewTotals = { untouched: number; learn: number; review: number; total: number }
+
+export type RollbackSettingsData = { id: number }
+
+/**
+ * Where provider secrets are kept; configured through `app::secrets`.
+ */
+export type SecretStoreBackend = "auto" | "keyring" | "encrypted-file"
+
+/**
+ * Runtime secret store selection.
+ * 
+ * `Keyring` is the OS store (Credential Manager on Windows). `Auto` keeps an existing encrypted
+ * file in `directory`, otherwise uses the keyring and falls back to the encrypted file when the
+ * keyring is unavailable. The file is sealed with `passphrase`, or with a machine-bound key file
+ * next to it when no passphrase is given.
+ */
+export type SecretStoreOptions = { backend?: SecretStoreBackend; directory?: string | null; passphrase?: string | null }
+
+export type SeedData = { algorithm: InsertAlgorithmData; template: InsertTemplateData; settings: SeedSettings }
+
+export type SeedSettings = { interface: JsonValue; learning: JsonValue; hotkeys: JsonValue }
+
+export type SetConversationInput = { id: string; state: JsonValue; title?: string | null; updatedAt?: number | null }
+
+export type Settings = { id: number; name: SettingsName; content: JsonValue; createdAt: number; updatedAt: number | null }
+
+export type SettingsBundle = { version: number; exportedAt: string; settings?: SettingsBundleSlice[]; algorithms?: JsonValue[]; templates?: JsonValue[] }
+
+export type SettingsBundleSlice = { name: SettingsName; content: JsonValue }
+
+/**
+ * `slice` is `settings/<name>`, `algorithms/<index>` or `templates/<index>`.
+ */
+export type SettingsBundleSliceError = { slice: string; code: string; details: string | null }
+
+export type SettingsDiffEntry = { path: string; before: JsonValue | null; after: JsonValue | null }
+
+/**
+ * One recorded change of a settings slice; `patch` is the JSON merge-patch from old to new.
+ */
+export type SettingsHistoryEntry = { id: number; name: SettingsName; oldContent: JsonValue | null; newContent: JsonValue; patch: JsonValue; createdAt: number }
+
+export type SettingsName = "interface" | "learning" | "hotkeys" | "ai"
+
+export type SyncOptions = { 
+/**
+ * Base URL of the sync server, e.g. `http://192.168.1.10:8770`.
+ */
+url: string; token: string }
+
+/**
+ * A change that broke a constraint on the receiving side, with the database's message.
+ */
+export type SyncRejectedChange = { table: SyncTable; uid: string; reason: string }
+
+export type SyncReport = { pushed: number; pulled: number; skipped: number; 
+/**
+ * Pushed changes the server rejected, to be pushed again next sync, and pulled ones this
+ * device could not store.
+ */
+rejected: SyncRejectedChange[] }
+
+/**
+ * Synced tables, parents first.
+ */
+export type SyncTable = "algorithms" | "templates" | "decks" | "cards" | "reviews" | "settings"
+
+export type TaskProgress = { done: number; total: number }
+
+export type Template = { id: number; title: string; content: TemplateContent; isLocked: boolean; createdAt: string; updatedAt?: string | null }
+
+export type TemplateContent = { fields: TemplateField[]; layout: TemplateLayoutItem[]; sides?: TemplateSides | null }
+
+export type TemplateDeck = { id: number; title: string }
+
+export type TemplateField = { id: number; title: string; type: string; isRequired: boolean; rules?: TemplateFieldRules | null }
+
+/**
+ * Checks on a field's plain text when cards are written; blank values pass all but `is_required`.
+ */
+export type TemplateFieldRules = { 
+/**
+ * In characters.
+ */
+maxLength: number | null; 
+/**
+ * Regex the whole text must match.
+ */
+pattern: string | null; 
+/**
+ * Letters must belong to one of these scripts; digits, punctuation and spaces always pass.
+ */
+allowedScripts: FieldScript[]; 
+/**
+ * Checked by the repo inside every card write (add, update, move, copy, convert,
+ * find-and-replace, deck import), against the other cards of the deck.
+ */
+uniqueInDeck: boolean }
+
+export type TemplateLayoutItem = { field: number; operation: string }
+
+/**
+ * Mustache-like markup per side; see `template_render` for the syntax.
+ */
+export type TemplateSides = { question: string; answer: string }
+
+export type TodaysReviewTotals = { dailyLimits: DailyLimits; reviewTotals: ReviewTotals; meta: TodaysReviewTotalsMeta }
+
+export type TodaysReviewTotalsMeta = { isUntouchedOverTheLimit: boolean; isLearnOverTheLimit: boolean; isReviewOverTheLimit: boolean; isTotalOverTheLimit: boolean }
+
+export type TypedAnswerComparison = { isCorrect: boolean; 
+/**
+ * `0.0..=1.0`; `1.0` when both answers normalize to the same text.
+ */
+similarity: number; 
+/**
+ * The alternative the typed answer matched best, as written in the expected text.
+ */
+expected: string; diff: TypedAnswerDiffPart[]; 
+/**
+ * `Good` when correct, `Hard` from `HARD_SIMILARITY`, otherwise `Again`; only with `suggest_rating`.
+ */
+suggestedRating: number | null }
+
+export type TypedAnswerDiffKind = "equal" | 
+/**
+ * In the expected answer but not typed.
+ */
+"missing" | 
+/**
+ * Typed but not in the expected answer.
+ */
+"extra"
+
+export type TypedAnswerDiffPart = { kind: TypedAnswerDiffKind; text: string }
+
+export type TypedAnswerOptions = { ignoreCase: boolean; 
+/**
+ * Drops every character that is neither alphanumeric nor whitespace.
+ */
+ignorePunctuation: boolean; ignoreDiacritics: boolean; 
+/**
+ * Treats whitespace runs as one space and ignores leading and trailing whitespace.
+ */
+collapseWhitespace: boolean; 
+/**
+ * Splits the expected text into accepted alternatives, e.g. `"/"` for `"color / colour"`.
+ */
+alternativesDelimiter: string | null; suggestRating: boolean }
+
+export type UpdateAlgorithmData = { id: number; values: UpdateAlgorithmValues }
+
+export type UpdateAlgorithmValues = { title: string; content: AlgorithmFSRS }
+
+export type UpdateCardData = { id: number; values: UpdateCardValues }
+
+export type UpdateCardProgress = { id: number; state: number; dueAt: number; stability: number; difficulty: number; scheduledDays: number; learningSteps: number; reps: number; lapses: number; lastReviewedAt?: number | null }
+
+export type UpdateCardValues = { content: Partial<{ [key in string]: CardContentField }> }
+
+export type UpdateDeckData = { id: number; values: UpdateDeckValues }
+
+export type UpdateDeckValues = { title: string; algorithmId: number; templateId: number }
+
+export type UpdateProfileData = { id: string; title: string | null; secrets: AISecrets | null; whitelistModelIds?: string[] | null }
+
+export type UpdateTemplateData = { id: number; values: UpdateTemplateValues }
+
+export type UpdateTemplateValues = { title: string; content: TemplateContent }
+
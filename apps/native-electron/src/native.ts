@@ -0,0 +1,318 @@
+import { createRequire } from "node:module";
+import type {
+  AddCardsItemResult,
+  AddCardsOptions,
+  AddProfileData,
+  AIProfile,
+  AISecrets,
+  AISecretsHealth,
+  Algorithm,
+  AlgorithmDeck,
+  AnkiConnectServerInfo,
+  AnkiConnectServerOptions,
+  CloneAlgorithmData,
+  CloneTemplateData,
+  Card,
+  ChangeEvent,
+  ChangesPage,
+  CompareTypedAnswerData,
+  Conversation,
+  ConvertCardsTemplateData,
+  ConvertCardsTemplateResult,
+  CopyCardsData,
+  DatabaseCheckReport,
+  DbStatus,
+  Deck,
+  DeckBundle,
+  DeleteAlgorithmData,
+  DeleteCardData,
+  DeleteCardsData,
+  DeleteDeckData,
+  DeleteTemplateData,
+  DiffSettingsParams,
+  DuplicateCardGroup,
+  ExportAISecretsData,
+  FindDuplicateCardsParams,
+  FindReplaceData,
+  FindReplaceResult,
+  GetCardsParams,
+  GetChangesParams,
+  GetLessonDataParams,
+  GetLessonsParams,
+  GetReviewsData,
+  GetReviewTotalsParams,
+  GetSettingsHistoryParams,
+  HttpServerInfo,
+  HttpServerOptions,
+  ImportAISecretsData,
+  ImportAISecretsResult,
+  ImportSettingsBundleResult,
+  InsertAlgorithmData,
+  InsertCardData,
+  InsertDeckData,
+  InsertTemplateData,
+  JsonValue,
+  LessonData,
+  LessonResultData,
+  LessonsResult,
+  MergeCardsData,
+  MoveCardsData,
+  RebuildCardProgressData,
+  RebuildCardProgressResult,
+  RemoveProfileData,
+  RenderedCard,
+  RepairAISecretsData,
+  RepairDatabaseData,
+  RepairDatabaseResult,
+  ResetCardProgressData,
+  Review,
+  ReviewTotals,
+  RollbackSettingsData,
+  SecretStoreBackend,
+  SecretStoreOptions,
+  SeedData,
+  SetConversationInput,
+  Settings,
+  SettingsBundle,
+  SettingsDiffEntry,
+  SettingsHistoryEntry,
+  SettingsName,
+  SyncOptions,
+  SyncReport,
+  TaskProgress,
+  Template,
+  TemplateDeck,
+  TodaysReviewTotals,
+  TypedAnswerComparison,
+  UpdateAlgorithmData,
+  UpdateCardData,
+  UpdateDeckData,
+  UpdateProfileData,
+  UpdateTemplateData,
+} from "./native-types";
+
+type IdParams = { id: number };
+type OnProgress = (progress: TaskProgress) => void;
+
+export interface CancellationToken {
+  cancel(): void;
+  readonly isCancelled: boolean;
+}
+
+// WHY: Hand-written against the generated `native-types.ts`; argument and result shapes come
+// from the Rust serde types, so a renamed field fails `typecheck` instead of a runtime call.
+// `scripts/check-native-surface.ts` (run by `check-bindings`) catches method and arity drift.
+export interface KolodaDb {
+  getDbStatus(): DbStatus;
+  seedDb(data: SeedData): void;
+
+  getCards(params: GetCardsParams): Card[];
+  getCard(params: IdParams): Card | null;
+  addCard(data: InsertCardData): Card;
+  addCards(data: InsertCardData[], options?: AddCardsOptions | null): AddCardsItemResult[];
+  addCardsAsync(
+    data: InsertCardData[],
+    options?: AddCardsOptions | null,
+    token?: CancellationToken | null,
+    onProgress?: OnProgress | null,
+  ): Promise<AddCardsItemResult[]>;
+  updateCard(data: UpdateCardData): Card;
+  deleteCard(data: DeleteCardData): void;
+  deleteCards(data: DeleteCardsData): void;
+  resetCardProgress(data: ResetCardProgressData): Card;
+  rebuildCardProgress(data: RebuildCardProgressData): RebuildCardProgressResult;
+  rebuildCardProgressAsync(
+    data: RebuildCardProgressData,
+    token?: CancellationToken | null,
+    onProgress?: OnProgress | null,
+  ): Promise<RebuildCardProgressResult>;
+  renderCard(params: IdParams): RenderedCard | null;
+  renderDeckCards(params: IdParams): RenderedCard[];
+  getCardPlainText(params: IdParams): string | null;
+  moveCards(data: MoveCardsData): Card[];
+  copyCards(data: CopyCardsData): Card[];
+  convertCardsTemplate(data: ConvertCardsTemplateData): ConvertCardsTemplateResult;
+  findReplace(data: FindReplaceData): FindReplaceResult;
+  findReplaceAsync(
+    data: FindReplaceData,
+    token?: CancellationToken | null,
+    onProgress?: OnProgress | null,
+  ): Promise<FindReplaceResult>;
+  findDuplicateCards(params: FindDuplicateCardsParams): DuplicateCardGroup[];
+  findDuplicateCardsAsync(
+    params: FindDuplicateCardsParams,
+    token?: CancellationToken | null,
+    onProgress?: OnProgress | null,
+  ): Promise<DuplicateCardGroup[]>;
+  mergeCards(data: MergeCardsData): Card;
+
+  getAlgorithms(): Algorithm[];
+  getAlgorithm(params: IdParams): Algorithm | null;
+  addAlgorithm(data: InsertAlgorithmData): Algorithm;
+  updateAlgorithm(data: UpdateAlgorithmData): Algorithm;
+  cloneAlgorithm(data: CloneAlgorithmData): Algorithm;
+  deleteAlgorithm(data: DeleteAlgorithmData): void;
+  getAlgorithmDecks(params: IdParams): AlgorithmDeck[];
+
+  getDecks(): Deck[];
+  getDeck(params: IdParams): Deck | null;
+  addDeck(data: InsertDeckData): Deck;
+  updateDeck(data: UpdateDeckData): Deck;
+  deleteDeck(data: DeleteDeckData): void;
+  exportDeck(params: IdParams): DeckBundle;
+  exportDeckAsync(
+    params: IdParams,
+    token?: CancellationToken | null,
+    onProgress?: OnProgress | null,
+  ): Promise<DeckBundle>;
+  importDeck(bundle: DeckBundle): Deck;
+  importDeckAsync(bundle: DeckBundle, token?: CancellationToken | null, onProgress?: OnProgress | null): Promise<Deck>;
+
+  getTemplates(): Template[];
+  getTemplate(params: IdParams): Template | null;
+  addTemplate(data: InsertTemplateData): Template;
+  updateTemplate(data: UpdateTemplateData): Template;
+  cloneTemplate(data: CloneTemplateData): Template;
+  deleteTemplate(data: DeleteTemplateData): void;
+  getTemplateDecks(params: IdParams): TemplateDeck[];
+
+  getSettings(params: { name: SettingsName }): Settings | null;
+  setSettings(params: { name: SettingsName; content: JsonValue }): Settings;
+  patchSettings(params: { name: SettingsName; content: JsonValue }): Settings;
+  getSettingsHistory(params: GetSettingsHistoryParams): SettingsHistoryEntry[];
+  diffSettings(params: DiffSettingsParams): SettingsDiffEntry[];
+  rollbackSettings(data: RollbackSettingsData): Settings;
+  exportSettingsBundle(): SettingsBundle;
+  exportSettingsBundleAsync(token?: CancellationToken | null, onProgress?: OnProgress | null): Promise<SettingsBundle>;
+  importSettingsBundle(bundle: SettingsBundle): ImportSettingsBundleResult;
+  importSettingsBundleAsync(
+    bundle: SettingsBundle,
+    token?: CancellationToken | null,
+    onProgress?: OnProgress | null,
+  ): Promise<ImportSettingsBundleResult>;
+
+  getConversation(params: { id: string }): Conversation | null;
+  getConversations(): Conversation[];
+  setConversation(params: SetConversationInput): Conversation;
+  deleteConversation(params: { id: string }): void;
+
+  getLessons(params: GetLessonsParams): LessonsResult;
+  getLessonData(params: GetLessonDataParams): LessonData | null;
+  submitLessonResult(data: LessonResultData): void;
+  compareTypedAnswer(data: CompareTypedAnswerData): TypedAnswerComparison;
+
+  getReviews(data: GetReviewsData): Review[];
+  getReviewTotals(params: GetReviewTotalsParams): ReviewTotals;
+  getReviewTotalsAsync(
+    params: GetReviewTotalsParams,
+    token?: CancellationToken | null,
+    onProgress?: OnProgress | null,
+  ): Promise<ReviewTotals>;
+  getTodaysReviewTotals(): TodaysReviewTotals;
+
+  getChanges(params: GetChangesParams): ChangesPage;
+  getLatestChangeCursor(): number;
+  subscribeChanges(callback: (events: ChangeEvent[]) => void, cursor?: number | null): void;
+  unsubscribeChanges(): void;
+
+  getAiProfiles(): AIProfile[];
+  configureSecretStore(options: SecretStoreOptions): SecretStoreBackend;
+  checkAiSecrets(): AISecretsHealth;
+  repairAiSecrets(data: RepairAISecretsData): AISecretsHealth;
+  exportAiSecrets(data: ExportAISecretsData): string;
+  importAiSecrets(data: ImportAISecretsData): ImportAISecretsResult;
+  getAiProfileSecrets(profileId: string): AISecrets | null;
+  addAiProfile(data: AddProfileData): AIProfile;
+  updateAiProfile(data: UpdateProfileData): AIProfile;
+  removeAiProfile(data: RemoveProfileData): void;
+
+  startHttpServer(options?: HttpServerOptions | null): HttpServerInfo;
+  stopHttpServer(): void;
+  getHttpServerInfo(): HttpServerInfo | null;
+  startAnkiConnectServer(options?: AnkiConnectServerOptions | null): AnkiConnectServerInfo;
+  stopAnkiConnectServer(): void;
+  getAnkiConnectServerInfo(): AnkiConnectServerInfo | null;
+  syncAsync(options: SyncOptions): Promise<SyncReport>;
+
+  checkDatabaseAsync(): Promise<DatabaseCheckReport>;
+  repairDatabaseAsync(data: RepairDatabaseData): Promise<RepairDatabaseResult>;
+  backupAsync(path: string, token?: CancellationToken | null, onProgress?: OnProgress | null): Promise<null>;
+  checkpoint(): void;
+  checkpointAsync(): Promise<null>;
+}
+
+export interface NativeAddon {
+  KolodaDb: new (dbPath: string) => KolodaDb;
+  CancellationToken: new () => CancellationToken;
+  restorePreMigrationSnapshot(dbPath: string): void;
+  hasPreMigrationSnapshot(dbPath: string): boolean;
+}
+
+/** Error thrown by the addon; napi sets `code` to the `AppError` code and `message` to its details. */
+export class NativeError extends Error {
+  readonly code: string;
+  readonly details: string | null;
+
+  constructor(code: string, details: string | null) {
+    // INVARIANT: IPC forwards only `message` to the renderer, whose `invoke` parses `{ code, details }` back out.
+    super(JSON.stringify({ code, details }));
+    this.name = "NativeError";
+    this.code = code;
+    this.details = details;
+  }
+
+  static from(error: unknown): unknown {
+    if (!(error instanceof Error) || error instanceof NativeError) return error;
+    const { code } = error as { code?: unknown };
+    if (typeof code !== "string") return error;
+    return new NativeError(code, error.message || null);
+  }
+}
+
+function withNativeErrors<T extends object>(target: T): T {
+  return new Proxy(target, {
+    get(object, property) {
+      const value = Reflect.get(object, property, object);
+      if (typeof value !== "function") return value;
+
+      return (...args: unknown[]) => {
+        try {
+          const result = value.apply(object, args);
+          return result instanceof Promise
+            ? result.catch((error: unknown) => {
+              throw NativeError.from(error);
+            })
+            : result;
+        } catch (error) {
+          throw NativeError.from(error);
+        }
+      };
+    },
+  });
+}
+
+export function loadNativeAddon(addonPath: string): NativeAddon {
+  const req = createRequire(import.meta.url);
+  const addon = req(addonPath) as NativeAddon;
+
+  return {
+    KolodaDb: class {
+      constructor(dbPath: string) {
+        try {
+          return withNativeErrors(new addon.KolodaDb(dbPath));
+        } catch (error) {
+          throw NativeError.from(error);
+        }
+      }
+    } as unknown as NativeAddon["KolodaDb"],
+    CancellationToken: addon.CancellationToken,
+    restorePreMigrationSnapshot: (dbPath) => {
+      try {
+        addon.restorePreMigrationSnapshot(dbPath);
+      } catch (error) {
+        throw NativeError.from(error);
+      }
+    },
+    hasPreMigrationSnapshot: (dbPath) => addon.hasPreMigrationSnapshot(dbPath),
+  };
+}
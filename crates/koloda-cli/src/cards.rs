@@ -0,0 +1,238 @@
+use std::process::ExitCode;
+
+use clap::{Args, ValueEnum};
+use koloda_core::app::db::Database;
+use koloda_core::app::error::{error_codes, AppError};
+use koloda_core::domain::cards::{AddCardsOptions, CardContent, CardContentField, DuplicatePolicy, InsertCardData};
+use koloda_core::domain::templates::TemplateField;
+use koloda_core::repo::{cards, decks, templates};
+use serde_json::Value;
+
+use crate::input::{self, payload_error};
+use crate::output::Output;
+
+#[derive(Args)]
+pub struct AddArgs {
+    /// Deck to add the cards to; they use the deck's template.
+    #[arg(long)]
+    deck: i64,
+    /// `json`: an array or one object per line, keyed by field id or title, or an array of
+    /// texts in field order. `csv`: a header row of field ids or titles, then one card per row.
+    #[arg(long, value_enum, default_value_t = InputFormat::Json)]
+    format: InputFormat,
+    /// What to do with a card whose key field matches a card already in the deck.
+    #[arg(long, value_enum, default_value_t = Duplicates::Allow)]
+    duplicates: Duplicates,
+    /// Field compared for duplicates; defaults to the template's first field.
+    #[arg(long)]
+    key_field: Option<i64>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum InputFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Duplicates {
+    Allow,
+    Reject,
+    Skip,
+}
+
+impl From<Duplicates> for DuplicatePolicy {
+    fn from(duplicates: Duplicates) -> Self {
+        match duplicates {
+            Duplicates::Allow => DuplicatePolicy::Allow,
+            Duplicates::Reject => DuplicatePolicy::Reject,
+            Duplicates::Skip => DuplicatePolicy::Skip,
+        }
+    }
+}
+
+/// Exactly one of `--card` and `--deck` is required.
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+pub struct RenderArgs {
+    #[arg(long)]
+    card: Option<i64>,
+    /// Every card of the deck, in creation order.
+    #[arg(long)]
+    deck: Option<i64>,
+}
+
+/// Items fail on their own; the exit status is non-zero when any of them did.
+pub fn add(db: &Database, output: &Output, args: AddArgs) -> Result<ExitCode, AppError> {
+    let deck = decks::get_deck(db, args.deck)?.ok_or_else(|| {
+        AppError::new(
+            error_codes::NOT_FOUND_CARDS_ADD_DECK,
+            Some(format!("Deck id: {}", args.deck)),
+        )
+    })?;
+    let template = templates::get_template(db, deck.template_id)?.ok_or_else(|| {
+        AppError::new(
+            error_codes::NOT_FOUND_CARDS_ADD_TEMPLATE,
+            Some(format!("Template id: {}", deck.template_id)),
+        )
+    })?;
+
+    let input = input::read(None)?;
+    let contents = match args.format {
+        InputFormat::Json => parse_json(&input, &template.content.fields)?,
+        InputFormat::Csv => parse_csv(&input, &template.content.fields)?,
+    };
+
+    let data = contents
+        .into_iter()
+        .map(|content| InsertCardData {
+            deck_id: deck.id,
+            template_id: template.id,
+            content,
+            state: None,
+            due_at: None,
+            stability: None,
+            difficulty: None,
+            scheduled_days: None,
+            learning_steps: None,
+            reps: None,
+            lapses: None,
+            last_reviewed_at: None,
+        })
+        .collect();
+    let options = AddCardsOptions {
+        duplicate_policy: args.duplicates.into(),
+        duplicate_field_id: args.key_field,
+    };
+    let results = cards::add_cards(db, data, options)?;
+
+    let failed = results.iter().filter(|result| result.error.is_some()).count();
+    output.print(&results, |results| {
+        let skipped = results.iter().filter(|result| result.duplicate_of.is_some()).count();
+        let mut text = format!(
+            "Added {} cards, skipped {} duplicates, {} failed\n",
+            results.len() - skipped - failed,
+            skipped,
+            failed
+        );
+        for (index, result) in results.iter().enumerate() {
+            if let Some(error) = &result.error {
+                text.push_str(&format!(
+                    "  #{}: {} {}\n",
+                    index,
+                    error.code,
+                    error.details.as_deref().unwrap_or_default()
+                ));
+            }
+        }
+        text
+    })?;
+
+    Ok(if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn parse_json(input: &str, fields: &[TemplateField]) -> Result<Vec<CardContent>, AppError> {
+    let trimmed = input.trim_start();
+    let items: Vec<Value> = if trimmed.starts_with('[') {
+        input::from_json(trimmed)?
+    } else {
+        trimmed
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(input::from_json)
+            .collect::<Result<_, _>>()?
+    };
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            json_content(item, fields).map_err(|error| payload_error(format!("Card {}: {}", index, error)))
+        })
+        .collect()
+}
+
+fn json_content(item: Value, fields: &[TemplateField]) -> Result<CardContent, String> {
+    let pairs: Vec<(i64, Value)> = match item {
+        Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| Ok((resolve_field(fields, &key)?, value)))
+            .collect::<Result<_, String>>()?,
+        Value::Array(values) => {
+            if values.len() > fields.len() {
+                return Err(format!("{} values for {} fields", values.len(), fields.len()));
+            }
+            fields.iter().map(|field| field.id).zip(values).collect()
+        }
+        other => return Err(format!("Expected an object or array, got {}", other)),
+    };
+
+    pairs
+        .into_iter()
+        .map(|(field_id, value)| match value {
+            Value::String(text) => Ok((field_id.to_string(), CardContentField { text })),
+            other => Err(format!("Field {} must be a string, got {}", field_id, other)),
+        })
+        .collect()
+}
+
+fn parse_csv(input: &str, fields: &[TemplateField]) -> Result<Vec<CardContent>, AppError> {
+    let mut reader = csv::ReaderBuilder::new().from_reader(input.as_bytes());
+    let columns = reader
+        .headers()
+        .map_err(|e| payload_error(e.to_string()))?
+        .iter()
+        .map(|header| resolve_field(fields, header))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(payload_error)?;
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| payload_error(e.to_string()))?;
+            Ok(columns
+                .iter()
+                .zip(record.iter())
+                .map(|(field_id, text)| (field_id.to_string(), CardContentField { text: text.to_string() }))
+                .collect())
+        })
+        .collect()
+}
+
+/// A key names a field by id, or by title ignoring case.
+fn resolve_field(fields: &[TemplateField], key: &str) -> Result<i64, String> {
+    let key = key.trim();
+    fields
+        .iter()
+        .find(|field| field.id.to_string() == key)
+        .or_else(|| fields.iter().find(|field| field.title.eq_ignore_ascii_case(key)))
+        .map(|field| field.id)
+        .ok_or_else(|| format!("Unknown field: {}", key))
+}
+
+pub fn render(db: &Database, output: &Output, args: RenderArgs) -> Result<ExitCode, AppError> {
+    let rendered = match (args.card, args.deck) {
+        (Some(id), _) => vec![cards::render_card(db, id)?.ok_or_else(|| {
+            AppError::new(
+                error_codes::NOT_FOUND_CARDS_RENDER_CARD,
+                Some(format!("Card id: {}", id)),
+            )
+        })?],
+        (_, Some(id)) => cards::render_deck_cards(db, id)?,
+        (None, None) => return Err(payload_error("Pass --card or --deck".to_string())),
+    };
+
+    output.print(&rendered, |rendered| {
+        rendered
+            .iter()
+            .map(|card| format!("# {}\n{}\n\n{}\n", card.card_id, card.question, card.answer))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+
+    Ok(ExitCode::SUCCESS)
+}
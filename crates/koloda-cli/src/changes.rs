@@ -0,0 +1,46 @@
+use std::process::ExitCode;
+use std::thread;
+
+use clap::Args;
+use koloda_core::app::db::Database;
+use koloda_core::app::error::AppError;
+use koloda_core::app::watch::DEFAULT_POLL_INTERVAL;
+use koloda_core::domain::changes::{ChangeEvent, GetChangesParams};
+use koloda_core::repo::changes;
+
+use crate::output::Output;
+
+#[derive(Args)]
+pub struct ChangesArgs {
+    /// Print events after this feed position.
+    #[arg(long, default_value_t = 0)]
+    cursor: i64,
+    /// Keep polling for new events until interrupted.
+    #[arg(long)]
+    follow: bool,
+}
+
+/// One event per line: JSON with `--json`, else `<id> <entity> <entityId> <operation>`.
+pub fn list(db: &Database, output: &Output, args: ChangesArgs) -> Result<ExitCode, AppError> {
+    let mut cursor = args.cursor;
+
+    loop {
+        let page = changes::get_changes(db, GetChangesParams { cursor, limit: None })?;
+        for event in &page.events {
+            output.print_line(event, describe)?;
+        }
+        cursor = page.cursor;
+
+        if page.has_more {
+            continue;
+        }
+        if !args.follow {
+            return Ok(ExitCode::SUCCESS);
+        }
+        thread::sleep(DEFAULT_POLL_INTERVAL);
+    }
+}
+
+fn describe(event: &ChangeEvent) -> String {
+    format!("{} {} {} {}", event.id, event.entity, event.entity_id, event.operation)
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Args;
+use koloda_core::app::db::Database;
+use koloda_core::app::error::AppError;
+use koloda_core::domain::deck_bundle::DeckBundle;
+use koloda_core::domain::lessons::GetLessonsParams;
+use koloda_core::domain::time::default_now;
+use koloda_core::repo::{decks, lessons};
+use serde_json::json;
+
+use crate::input;
+use crate::output::Output;
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Deck to export.
+    #[arg(long)]
+    deck: i64,
+    /// Write the bundle to this file instead of stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Bundle written by `koloda export`; read from stdin when omitted.
+    file: Option<PathBuf>,
+}
+
+pub fn list(db: &Database, output: &Output) -> Result<ExitCode, AppError> {
+    let lessons = lessons::get_lessons(
+        db,
+        GetLessonsParams {
+            due_at: default_now(),
+            filters: None,
+        },
+    )?;
+
+    output.print(&lessons, |lessons| {
+        let mut text = format!("{:>6} {:>6} {:>6} {:>6}  {}\n", "ID", "NEW", "LEARN", "REVIEW", "TITLE");
+        for deck in &lessons.decks {
+            text.push_str(&format!(
+                "{:>6} {:>6} {:>6} {:>6}  {}\n",
+                deck.id, deck.untouched, deck.learn, deck.review, deck.title
+            ));
+        }
+        text
+    })?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn export(db: &Database, output: &Output, args: ExportArgs) -> Result<ExitCode, AppError> {
+    let bundle = decks::export_deck(db, args.deck)?;
+
+    let Some(path) = args.output else {
+        // WHY: The bundle is the payload, so it is printed as JSON with or without `--json`.
+        Output::new(true).print(&bundle, |_| String::new())?;
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    fs::write(&path, serde_json::to_string_pretty(&bundle)?)?;
+    let summary = json!({ "path": path, "cards": bundle.cards.len() });
+    output.print(&summary, |_| {
+        format!("Exported {} cards to {}", bundle.cards.len(), path.display())
+    })?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn import(db: &Database, output: &Output, args: ImportArgs) -> Result<ExitCode, AppError> {
+    let bundle: DeckBundle = input::from_json(&input::read(args.file.as_deref())?)?;
+    let cards = bundle.cards.len();
+
+    let deck = decks::import_deck(db, bundle)?;
+    output.print(&deck, |deck| {
+        format!("Imported {} cards into deck {} ({})", cards, deck.id, deck.title)
+    })?;
+
+    Ok(ExitCode::SUCCESS)
+}
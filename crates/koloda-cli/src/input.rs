@@ -0,0 +1,26 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use koloda_core::app::error::{error_codes, AppError};
+use serde::de::DeserializeOwned;
+
+/// Reads `path`, or all of stdin when there is none.
+pub fn read(path: Option<&Path>) -> Result<String, AppError> {
+    match path {
+        Some(path) => Ok(fs::read_to_string(path)?),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+pub fn payload_error(details: String) -> AppError {
+    AppError::new(error_codes::VALIDATION_COMMON_PAYLOAD, Some(details))
+}
+
+pub fn from_json<T: DeserializeOwned>(input: &str) -> Result<T, AppError> {
+    serde_json::from_str(input).map_err(|e| payload_error(e.to_string()))
+}
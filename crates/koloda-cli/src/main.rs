@@ -0,0 +1,106 @@
+//! `koloda` — headless access to a koloda database through the `koloda-core` repos.
+//!
+//! Every command takes `--json` for machine-readable stdout; errors go to stderr as
+//! `{ code, details }` with a non-zero exit status.
+
+mod cards;
+mod changes;
+mod decks;
+mod input;
+mod maintenance;
+mod output;
+mod review;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use koloda_core::app::db::Database;
+use koloda_core::app::error::{error_codes, AppError};
+
+use crate::output::Output;
+
+#[derive(Parser)]
+#[command(name = "koloda", version, about = "Headless access to a koloda database")]
+struct Cli {
+    /// Path to an existing koloda SQLite database.
+    #[arg(long, env = "KOLODA_DB")]
+    db: PathBuf,
+    /// Print JSON instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List decks with the number of cards due now.
+    Decks,
+    /// Show today's review totals against the daily limits.
+    Totals,
+    /// Add cards read from stdin.
+    Add(cards::AddArgs),
+    /// Export a deck with its algorithm, template and cards.
+    Export(decks::ExportArgs),
+    /// Import a deck bundle as a new deck.
+    Import(decks::ImportArgs),
+    /// Write a consistent copy of the database.
+    Backup(maintenance::BackupArgs),
+    /// Run SQLite integrity, foreign key and domain checks; `--fix` repairs the given issue kinds.
+    Check(maintenance::CheckArgs),
+    /// Review due cards in the terminal; prompts are written to stderr.
+    Review(review::ReviewArgs),
+    /// Print the change feed; `--follow` keeps streaming new events.
+    Changes(changes::ChangesArgs),
+    /// Push local changes to a sync server and pull the other devices' changes.
+    Sync(maintenance::SyncArgs),
+    /// Recompute card progress by replaying reviews with the deck's current algorithm.
+    Rebuild(maintenance::RebuildArgs),
+    /// Print the question and answer HTML of a card or a whole deck, as the app renders them.
+    Render(cards::RenderArgs),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let output = Output::new(cli.json);
+
+    match run(cli.command, &cli.db, &output) {
+        Ok(code) => code,
+        Err(error) => {
+            output.error(&error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command, db_path: &Path, output: &Output) -> Result<ExitCode, AppError> {
+    let db = open_database(db_path)?;
+
+    match command {
+        Command::Decks => decks::list(&db, output),
+        Command::Totals => maintenance::totals(&db, output),
+        Command::Add(args) => cards::add(&db, output, args),
+        Command::Export(args) => decks::export(&db, output, args),
+        Command::Import(args) => decks::import(&db, output, args),
+        Command::Backup(args) => maintenance::backup(&db, output, db_path, args),
+        Command::Check(args) => maintenance::check(&db, output, args),
+        Command::Review(args) => review::run(&db, output, args),
+        Command::Changes(args) => changes::list(&db, output, args),
+        Command::Sync(args) => maintenance::sync_with_server(&db, output, args),
+        Command::Rebuild(args) => maintenance::rebuild(&db, output, args),
+        Command::Render(args) => cards::render(&db, output, args),
+    }
+}
+
+// WHY: `Database::init` creates missing files; a typo in `--db` must not leave an empty database behind.
+fn open_database(path: &Path) -> Result<Database, AppError> {
+    if !path.is_file() {
+        return Err(AppError::new(
+            error_codes::NOT_FOUND_DB_FILE,
+            Some(format!("No database at {}", path.display())),
+        ));
+    }
+
+    Database::init(path)
+}
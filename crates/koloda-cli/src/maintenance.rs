@@ -0,0 +1,173 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Args;
+use koloda_core::app::db::Database;
+use koloda_core::app::error::AppError;
+use koloda_core::domain::cards::{RebuildCardProgressData, RebuildCardProgressScope};
+use koloda_core::domain::database_check::{DatabaseIssue, DatabaseIssueKind, RepairDatabaseData};
+use koloda_core::domain::sync::SyncOptions;
+use koloda_core::repo::{cards, database_check, reviews};
+use koloda_core::sync::{sync, HttpSyncTransport};
+use serde_json::json;
+
+use crate::input::payload_error;
+use crate::output::Output;
+
+#[derive(Args)]
+pub struct BackupArgs {
+    /// Where to write the copy; an existing file there is replaced.
+    path: PathBuf,
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Apply the automatic fix for an issue kind, e.g. `orphan-review`; repeatable.
+    #[arg(long, value_name = "KIND")]
+    fix: Vec<DatabaseIssueKind>,
+}
+
+#[derive(Args)]
+pub struct SyncArgs {
+    /// Base URL of the sync server, e.g. `http://192.168.1.10:8770`.
+    #[arg(long, env = "KOLODA_SYNC_URL")]
+    server: String,
+    #[arg(long, env = "KOLODA_SYNC_TOKEN")]
+    token: String,
+}
+
+/// Exactly one of the scopes is required.
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+pub struct RebuildArgs {
+    #[arg(long)]
+    card: Option<i64>,
+    #[arg(long)]
+    deck: Option<i64>,
+    /// Every deck using this algorithm.
+    #[arg(long)]
+    algorithm: Option<i64>,
+}
+
+pub fn totals(db: &Database, output: &Output) -> Result<ExitCode, AppError> {
+    let totals = reviews::get_todays_review_totals(db)?;
+
+    output.print(&totals, |totals| {
+        let reviewed = &totals.review_totals;
+        let limits = &totals.daily_limits;
+        format!(
+            "untouched {}\nlearn     {}\nreview    {}\ntotal     {}",
+            progress(reviewed.untouched, limits.untouched.value),
+            progress(reviewed.learn, limits.learn.value),
+            progress(reviewed.review, limits.review.value),
+            progress(reviewed.total, limits.total),
+        )
+    })?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+// WHY: A zero limit means "no limit" in learning settings, as in the lesson UI.
+fn progress(done: i64, limit: u32) -> String {
+    match limit {
+        0 => done.to_string(),
+        limit => format!("{}/{}", done, limit),
+    }
+}
+
+pub fn backup(db: &Database, output: &Output, db_path: &Path, args: BackupArgs) -> Result<ExitCode, AppError> {
+    // INVARIANT: `Database::backup` deletes the target first, so it must never be the live file.
+    if args.path.exists() && fs::canonicalize(&args.path)? == fs::canonicalize(db_path)? {
+        return Err(payload_error(format!(
+            "Backup path is the database itself: {}",
+            args.path.display()
+        )));
+    }
+
+    db.backup(&args.path)?;
+
+    let summary = json!({ "path": args.path });
+    output.print(&summary, |_| format!("Backed up to {}", args.path.display()))?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The exit status is non-zero when any issue is left after the fixes.
+pub fn check(db: &Database, output: &Output, args: CheckArgs) -> Result<ExitCode, AppError> {
+    let (fixed, report) = if args.fix.is_empty() {
+        (0, database_check::check_database(db)?)
+    } else {
+        let result = database_check::repair_database(db, RepairDatabaseData { kinds: args.fix })?;
+        (result.fixed, result.report)
+    };
+
+    let summary = json!({ "ok": report.is_ok(), "fixed": fixed, "issues": report.issues });
+    output.print(&summary, |_| {
+        let mut lines: Vec<String> = report.issues.iter().map(describe_issue).collect();
+        if fixed > 0 {
+            lines.push(format!("fixed {}", fixed));
+        }
+        if report.is_ok() {
+            lines.push("ok".to_string());
+        }
+        lines.join("\n")
+    })?;
+
+    Ok(if report.is_ok() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn describe_issue(issue: &DatabaseIssue) -> String {
+    let location = match issue.row_id {
+        Some(id) => format!("{} {}", issue.table, id),
+        None => issue.table.clone(),
+    };
+    let fixable = if issue.is_fixable { " (fixable)" } else { "" };
+    format!("{} {}: {}{}", issue.kind, location.trim(), issue.details, fixable)
+}
+
+pub fn sync_with_server(db: &Database, output: &Output, args: SyncArgs) -> Result<ExitCode, AppError> {
+    let transport = HttpSyncTransport::new(&SyncOptions {
+        url: args.server,
+        token: args.token,
+    });
+    let report = sync(db, &transport)?;
+
+    output.print(&report, |report| {
+        let mut text = format!(
+            "pushed  {}\npulled  {}\nskipped {}",
+            report.pushed, report.pulled, report.skipped
+        );
+        for change in &report.rejected {
+            text.push_str(&format!(
+                "\nrejected {} {}: {}",
+                change.table.as_str(),
+                change.uid,
+                change.reason
+            ));
+        }
+        text
+    })?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn rebuild(db: &Database, output: &Output, args: RebuildArgs) -> Result<ExitCode, AppError> {
+    let (scope, id) = match (args.card, args.deck, args.algorithm) {
+        (Some(id), _, _) => (RebuildCardProgressScope::Card, id),
+        (_, Some(id), _) => (RebuildCardProgressScope::Deck, id),
+        (_, _, Some(id)) => (RebuildCardProgressScope::Algorithm, id),
+        (None, None, None) => return Err(payload_error("Pass --card, --deck or --algorithm".to_string())),
+    };
+    let result = cards::rebuild_card_progress(db, RebuildCardProgressData { scope, id })?;
+
+    output.print(&result, |result| {
+        format!("Rebuilt {} cards, {} changed", result.total, result.changed)
+    })?;
+
+    Ok(ExitCode::SUCCESS)
+}
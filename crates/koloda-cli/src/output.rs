@@ -0,0 +1,60 @@
+use std::io::Write;
+
+use koloda_core::app::error::AppError;
+use serde::Serialize;
+
+pub struct Output {
+    json: bool,
+}
+
+impl Output {
+    pub fn new(json: bool) -> Self {
+        Self { json }
+    }
+
+    /// Prints `value` as pretty JSON with `--json`, otherwise its `text` rendering.
+    pub fn print<T: Serialize>(&self, value: &T, text: impl FnOnce(&T) -> String) -> Result<(), AppError> {
+        let rendered = if self.json {
+            serde_json::to_string_pretty(value)?
+        } else {
+            text(value)
+        };
+
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", rendered.trim_end())?;
+
+        Ok(())
+    }
+
+    /// Like `print`, but JSON stays on one line so streams can be read line by line.
+    pub fn print_line<T: Serialize>(&self, value: &T, text: impl FnOnce(&T) -> String) -> Result<(), AppError> {
+        let rendered = if self.json {
+            serde_json::to_string(value)?
+        } else {
+            text(value)
+        };
+
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", rendered)?;
+        stdout.flush()?;
+
+        Ok(())
+    }
+
+    pub fn error(&self, error: &AppError) {
+        let rendered = if self.json {
+            serde_json::to_string(error).unwrap_or_else(|_| error.code.clone())
+        } else {
+            match &error.details {
+                Some(details) => format!("error: {}: {}", error.code, details),
+                None => format!("error: {}", error.code),
+            }
+        };
+
+        let mut stderr = std::io::stderr().lock();
+        // WHY: Nothing is left to report a failed stderr write to; the exit status still signals the error.
+        if writeln!(stderr, "{}", rendered).is_err() {
+            std::process::exit(1);
+        }
+    }
+}
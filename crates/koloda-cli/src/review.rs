@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::Args;
+use koloda_core::app::db::Database;
+use koloda_core::app::error::AppError;
+use koloda_core::domain::cards::Card;
+use koloda_core::domain::fsrs::{FsrsScheduler, Rating};
+use koloda_core::domain::lessons::{
+    GetLessonDataParams, GetLessonsParams, LessonAmounts, LessonFilters, LessonTemplate,
+};
+use koloda_core::domain::time::default_now;
+use koloda_core::domain::typed_answer::{compare_typed_answer, CompareTypedAnswerData, TypedAnswerOptions};
+use koloda_core::repo::lessons;
+use serde::Serialize;
+
+use crate::input::payload_error;
+use crate::output::Output;
+
+const MAX_REVIEW_TIME_MS: u128 = 60 * 60 * 1000;
+
+#[derive(Args)]
+pub struct ReviewArgs {
+    /// Only review cards from this deck; repeat for several decks.
+    #[arg(long = "deck")]
+    decks: Vec<i64>,
+    /// Maximum number of cards in the session.
+    #[arg(long, default_value_t = 20)]
+    limit: i64,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewSummary {
+    reviewed: usize,
+    again: usize,
+    hard: usize,
+    good: usize,
+    easy: usize,
+}
+
+impl ReviewSummary {
+    fn record(&mut self, rating: Rating) {
+        self.reviewed += 1;
+        match rating {
+            Rating::Again => self.again += 1,
+            Rating::Hard => self.hard += 1,
+            Rating::Good => self.good += 1,
+            Rating::Easy => self.easy += 1,
+        }
+    }
+}
+
+/// Due learn and review cards come first, then new ones, up to `--limit`. Each grade is
+/// submitted as soon as it is given, so quitting keeps everything graded so far.
+pub fn run(db: &Database, output: &Output, args: ReviewArgs) -> Result<ExitCode, AppError> {
+    let now = default_now();
+    let filters = LessonFilters {
+        deck_ids: (!args.decks.is_empty()).then_some(args.decks),
+    };
+    let available = lessons::get_lessons(
+        db,
+        GetLessonsParams {
+            due_at: now,
+            filters: Some(filters.clone()),
+        },
+    )?
+    .total;
+    let params = GetLessonDataParams {
+        due_at: now,
+        filters,
+        amounts: session_amounts(&available, args.limit.max(0)),
+    };
+
+    let mut summary = ReviewSummary::default();
+    if let Some(data) = lessons::get_lesson_data(db, &params)? {
+        let templates: HashMap<i64, &LessonTemplate> = data.templates.iter().map(|t| (t.id, t)).collect();
+        let schedulers = data
+            .algorithms
+            .iter()
+            .map(|algorithm| Ok((algorithm.id, FsrsScheduler::new(&algorithm.content)?)))
+            .collect::<Result<HashMap<_, _>, AppError>>()?;
+        let deck_algorithms: HashMap<i64, i64> = data.decks.iter().map(|d| (d.id, d.algorithm_id)).collect();
+
+        let mut prompt = Prompt::new();
+        for (index, card) in data.cards.iter().enumerate() {
+            let template = templates.get(&card.template_id).copied();
+            let scheduler = deck_algorithms
+                .get(&card.deck_id)
+                .and_then(|algorithm_id| schedulers.get(algorithm_id))
+                .ok_or_else(|| payload_error(format!("No algorithm for deck {}", card.deck_id)))?;
+
+            prompt.say(&format!("\n[{}/{}]", index + 1, data.cards.len()))?;
+            let started = Instant::now();
+            let Some((rating, typed)) = study_card(&mut prompt, card, template)? else {
+                break;
+            };
+
+            let mut result = scheduler.repeat(card, rating, default_now());
+            result.review.time = i32::try_from(started.elapsed().as_millis().min(MAX_REVIEW_TIME_MS)).unwrap_or(0);
+            if let Some(comparison) = typed {
+                result.review.typed_answer = Some(comparison.typed);
+                result.review.typed_score = Some(comparison.similarity);
+            }
+            lessons::submit_lesson_result(db, result)?;
+            summary.record(rating);
+        }
+    }
+
+    output.print(&summary, |summary| {
+        format!(
+            "Reviewed {} cards (again {}, hard {}, good {}, easy {})",
+            summary.reviewed, summary.again, summary.hard, summary.good, summary.easy
+        )
+    })?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn session_amounts(available: &LessonAmounts, limit: i64) -> LessonAmounts {
+    let learn = available.learn.min(limit);
+    let review = available.review.min(limit - learn);
+    let untouched = available.untouched.min(limit - learn - review);
+
+    LessonAmounts {
+        untouched,
+        learn,
+        review,
+        total: untouched + learn + review,
+    }
+}
+
+struct TypedAnswer {
+    typed: String,
+    similarity: f64,
+}
+
+/// `None` when the user quits or stdin closes. The review keeps the first typed answer.
+fn study_card(
+    prompt: &mut Prompt,
+    card: &Card,
+    template: Option<&LessonTemplate>,
+) -> Result<Option<(Rating, Option<TypedAnswer>)>, AppError> {
+    let field_text = |field_id: i64| {
+        card.content
+            .get(&field_id.to_string())
+            .map(|field| field.text.as_str())
+            .unwrap_or_default()
+    };
+    let layout = template.map(|template| template.layout.as_slice()).unwrap_or_default();
+    let title = |field_id: i64| {
+        layout
+            .iter()
+            .find(|item| item.field_id == field_id)
+            .and_then(|item| item.field.as_ref())
+            .map_or_else(|| field_id.to_string(), |field| field.title.clone())
+    };
+
+    let mut answers = Vec::new();
+    for item in layout {
+        match item.operation.as_str() {
+            "display" => prompt.say(&format!("{}: {}", title(item.field_id), field_text(item.field_id)))?,
+            "type" => {
+                let Some(typed) = prompt.ask(&format!("{}? ", title(item.field_id)))? else {
+                    return Ok(None);
+                };
+                answers.push((item.field_id, Some(typed)));
+            }
+            _ => answers.push((item.field_id, None)),
+        }
+    }
+
+    if answers.iter().all(|(_, typed)| typed.is_none()) && prompt.ask("Press Enter to show the answer")?.is_none() {
+        return Ok(None);
+    }
+    let mut first_typed = None;
+    for (field_id, typed) in answers {
+        let text = field_text(field_id);
+        let Some(typed) = typed else {
+            prompt.say(&format!("{}: {}", title(field_id), text))?;
+            continue;
+        };
+        let comparison = compare_typed_answer(&CompareTypedAnswerData {
+            expected: text.to_string(),
+            typed: typed.clone(),
+            options: TypedAnswerOptions::default(),
+        })?;
+        if comparison.is_correct {
+            prompt.say(&format!("{}: {} ✓", title(field_id), text))?;
+        } else {
+            prompt.say(&format!(
+                "{}: {} (you typed: {}, {:.0}% similar)",
+                title(field_id),
+                text,
+                typed,
+                comparison.similarity * 100.0
+            ))?;
+        }
+        first_typed.get_or_insert(TypedAnswer {
+            typed,
+            similarity: comparison.similarity,
+        });
+    }
+
+    loop {
+        let Some(answer) = prompt.ask("Grade [1] again [2] hard [3] good [4] easy [q] quit: ")? else {
+            return Ok(None);
+        };
+        let answer = answer.trim();
+        if answer.eq_ignore_ascii_case("q") {
+            return Ok(None);
+        }
+        if let Some(rating) = answer.parse().ok().and_then(Rating::from_i32) {
+            return Ok(Some((rating, first_typed)));
+        }
+    }
+}
+
+// WHY: The session talks on stderr so stdout carries only the summary, which `--json` keeps parseable.
+struct Prompt {
+    lines: std::io::Lines<std::io::StdinLock<'static>>,
+}
+
+impl Prompt {
+    fn new() -> Self {
+        Self {
+            lines: std::io::stdin().lock().lines(),
+        }
+    }
+
+    fn say(&self, text: &str) -> Result<(), AppError> {
+        writeln!(std::io::stderr(), "{}", text)?;
+        Ok(())
+    }
+
+    fn ask(&mut self, question: &str) -> Result<Option<String>, AppError> {
+        let mut stderr = std::io::stderr();
+        write!(stderr, "{}", question)?;
+        stderr.flush()?;
+
+        match self.lines.next() {
+            Some(line) => Ok(Some(line?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use koloda_core::app::db::Database;
+use koloda_core::domain::algorithms::InsertAlgorithmData;
+use koloda_core::domain::decks::InsertDeckData;
+use koloda_core::domain::templates::{InsertTemplateData, TemplateContent, TemplateField, TemplateLayoutItem};
+use koloda_core::repo::{algorithms, cards, decks, templates};
+use serde_json::{json, Value};
+
+struct Fixture {
+    dir: PathBuf,
+    db: PathBuf,
+    deck_id: i64,
+}
+
+impl Fixture {
+    fn new(prefix: &str) -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Clock went backwards")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("koloda-cli-{}-{}-{}", prefix, std::process::id(), nanos));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        let db_path = dir.join("koloda.db");
+
+        let db = Database::init(&db_path).expect("database should initialize");
+        let algorithm = algorithms::add_algorithm(
+            &db,
+            InsertAlgorithmData {
+                title: "FSRS".to_string(),
+                content: serde_json::from_value(json!({
+                    "type": "fsrs",
+                    "retention": 90.0,
+                    "weights": "0.4197,1.1869,3.0412,15.2441,7.1434,0.6477,1.0007,0.0754,1.6598,0.1719,1.1178,1.4699,0.134,0.016,1.7101,0.1543,0.9369,2.9664,0.714,0.201,0.0059",
+                    "isFuzzEnabled": false,
+                    "learningSteps": [[10, "m"]],
+                    "relearningSteps": [[10, "m"]],
+                    "maximumInterval": 36500,
+                }))
+                .expect("valid FSRS fixture"),
+            },
+        )
+        .expect("algorithm should be created");
+        let template = templates::add_template(
+            &db,
+            InsertTemplateData {
+                title: "Basic".to_string(),
+                content: TemplateContent {
+                    fields: vec![field(1, "Front", true), field(2, "Back", false)],
+                    layout: vec![layout(1, "display"), layout(2, "reveal")],
+                    sides: None,
+                },
+            },
+        )
+        .expect("template should be created");
+        let deck = decks::add_deck(
+            &db,
+            InsertDeckData {
+                title: "Spanish".to_string(),
+                algorithm_id: algorithm.id,
+                template_id: template.id,
+            },
+        )
+        .expect("deck should be created");
+
+        Self {
+            dir,
+            db: db_path,
+            deck_id: deck.id,
+        }
+    }
+
+    fn run(&self, args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_koloda"))
+            .arg("--db")
+            .arg(&self.db)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("koloda should start");
+        child
+            .stdin
+            .take()
+            .expect("stdin should be piped")
+            .write_all(stdin.as_bytes())
+            .expect("stdin should accept input");
+        child.wait_with_output().expect("koloda should finish")
+    }
+
+    fn run_json(&self, args: &[&str], stdin: &str) -> Value {
+        let output = self.run(&[&["--json"], args].concat(), stdin);
+        assert!(
+            output.status.success(),
+            "koloda {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        serde_json::from_slice(&output.stdout).expect("stdout should be JSON")
+    }
+
+    fn card_fronts(&self, deck_id: i64) -> Vec<String> {
+        let db = Database::init(&self.db).expect("database should open");
+        let mut fronts: Vec<_> = cards::get_cards(&db, deck_id)
+            .expect("cards should load")
+            .into_iter()
+            .map(|card| {
+                card.content
+                    .get("1")
+                    .map(|field| field.text.clone())
+                    .unwrap_or_default()
+            })
+            .collect();
+        fronts.sort();
+        fronts
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        if let Err(error) = fs::remove_dir_all(&self.dir) {
+            eprintln!("failed to remove {}: {}", self.dir.display(), error);
+        }
+    }
+}
+
+fn field(id: i64, title: &str, is_required: bool) -> TemplateField {
+    TemplateField {
+        id,
+        title: title.to_string(),
+        field_type: "text".to_string(),
+        is_required,
+        rules: None,
+    }
+}
+
+fn layout(field: i64, operation: &str) -> TemplateLayoutItem {
+    TemplateLayoutItem {
+        field,
+        operation: operation.to_string(),
+    }
+}
+
+fn deck_arg(id: i64) -> String {
+    id.to_string()
+}
+
+// ===== DECKS =====
+
+#[test]
+fn test_decks_lists_due_counts() {
+    let fixture = Fixture::new("decks");
+    let deck = deck_arg(fixture.deck_id);
+    fixture.run_json(&["add", "--deck", &deck], r#"[["hola", "hello"], ["adiós", "bye"]]"#);
+
+    let lessons = fixture.run_json(&["decks"], "");
+
+    assert_eq!(lessons["decks"][0]["title"], "Spanish");
+    assert_eq!(lessons["decks"][0]["untouched"], 2);
+}
+
+#[test]
+fn test_missing_database_is_not_created() {
+    let dir = std::env::temp_dir().join(format!("koloda-cli-missing-{}", std::process::id()));
+    let path = dir.join("missing.db");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_koloda"))
+        .args(["--json", "--db"])
+        .arg(&path)
+        .arg("decks")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let error: Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["code"], "not-found.db.file");
+    assert!(!Path::new(&path).exists());
+}
+
+// ===== ADD =====
+
+#[test]
+fn test_add_json_lines_by_field_title() {
+    let fixture = Fixture::new("add-json");
+    let deck = deck_arg(fixture.deck_id);
+
+    let results = fixture.run_json(
+        &["add", "--deck", &deck],
+        "{\"front\": \"hola\", \"Back\": \"hello\"}\n{\"1\": \"adiós\"}\n",
+    );
+
+    assert_eq!(results.as_array().unwrap().len(), 2);
+    assert_eq!(fixture.card_fronts(fixture.deck_id), vec!["adiós", "hola"]);
+}
+
+#[test]
+fn test_add_csv_skips_duplicates() {
+    let fixture = Fixture::new("add-csv");
+    let deck = deck_arg(fixture.deck_id);
+    fixture.run_json(&["add", "--deck", &deck], r#"[["hola"]]"#);
+
+    let output = fixture.run(
+        &["add", "--deck", &deck, "--format", "csv", "--duplicates", "skip"],
+        "Front,Back\nhola,hello\ngracias,thanks\n",
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("Added 1 cards, skipped 1 duplicates, 0 failed"),
+        "{}",
+        stdout
+    );
+    assert_eq!(fixture.card_fronts(fixture.deck_id), vec!["gracias", "hola"]);
+}
+
+#[test]
+fn test_add_reports_failed_items() {
+    let fixture = Fixture::new("add-failed");
+    let deck = deck_arg(fixture.deck_id);
+
+    let output = fixture.run(&["--json", "add", "--deck", &deck], r#"[["hola"], [""]]"#);
+
+    assert!(!output.status.success());
+    let results: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(results[0]["error"].is_null());
+    assert_eq!(results[1]["error"]["code"], "validation.cards.content.field-empty");
+}
+
+#[test]
+fn test_add_unknown_field_fails() {
+    let fixture = Fixture::new("add-unknown");
+    let deck = deck_arg(fixture.deck_id);
+
+    let output = fixture.run(&["add", "--deck", &deck], r#"[{"Extra": "x"}]"#);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Card 0: Unknown field: Extra"), "{}", stderr);
+}
+
+// ===== EXPORT / IMPORT =====
+
+#[test]
+fn test_export_then_import_copies_deck() {
+    let fixture = Fixture::new("export");
+    let deck = deck_arg(fixture.deck_id);
+    fixture.run_json(&["add", "--deck", &deck], r#"[["hola"], ["adiós"]]"#);
+    let bundle_path = fixture.dir.join("spanish.json");
+    let bundle = bundle_path.to_str().unwrap();
+
+    let summary = fixture.run_json(&["export", "--deck", &deck, "--output", bundle], "");
+    assert_eq!(summary["cards"], 2);
+    let imported = fixture.run_json(&["import", bundle], "");
+
+    let copy_id = imported["id"].as_i64().unwrap();
+    assert_ne!(copy_id, fixture.deck_id);
+    assert_eq!(imported["title"], "Spanish");
+    assert_eq!(fixture.card_fronts(copy_id), vec!["adiós", "hola"]);
+}
+
+#[test]
+fn test_import_reads_bundle_from_stdin() {
+    let fixture = Fixture::new("import-stdin");
+    let deck = deck_arg(fixture.deck_id);
+    fixture.run_json(&["add", "--deck", &deck], r#"[["hola"]]"#);
+    let bundle = fixture.run(&["export", "--deck", &deck], "");
+
+    let imported = fixture.run_json(&["import"], &String::from_utf8(bundle.stdout).unwrap());
+
+    assert_eq!(fixture.card_fronts(imported["id"].as_i64().unwrap()), vec!["hola"]);
+}
+
+// ===== MAINTENANCE =====
+
+#[test]
+fn test_backup_and_check() {
+    let fixture = Fixture::new("backup");
+    let backup_path = fixture.dir.join("backup.db");
+    let backup = backup_path.to_str().unwrap();
+
+    fixture.run_json(&["backup", backup], "");
+    let report = fixture.run_json(&["check"], "");
+
+    assert!(backup_path.is_file());
+    assert_eq!(report["ok"], true);
+}
+
+#[test]
+fn test_check_fix_takes_issue_kinds() {
+    let fixture = Fixture::new("check-fix");
+
+    let report = fixture.run_json(&["check", "--fix", "card-progress", "--fix", "orphan-review"], "");
+    let unknown = fixture.run(&["check", "--fix", "everything"], "");
+
+    assert_eq!(report["ok"], true);
+    assert_eq!(report["fixed"], 0);
+    assert!(!unknown.status.success());
+}
+
+#[test]
+fn test_backup_refuses_live_database() {
+    let fixture = Fixture::new("backup-self");
+
+    let output = fixture.run(&["backup", fixture.db.to_str().unwrap()], "");
+
+    assert!(!output.status.success());
+    assert!(fixture.db.is_file());
+}
+
+#[test]
+fn test_rebuild_requires_one_scope() {
+    let fixture = Fixture::new("rebuild");
+    let deck = deck_arg(fixture.deck_id);
+    fixture.run_json(&["add", "--deck", &deck], r#"[["hola"]]"#);
+    fixture.run_json(&["review", "--deck", &deck], "\n3\n");
+
+    let result = fixture.run_json(&["rebuild", "--deck", &deck], "");
+    let both = fixture.run(&["rebuild", "--deck", &deck, "--card", "1"], "");
+
+    assert_eq!(result["total"], 1);
+    assert_eq!(result["changed"], 0);
+    assert!(!both.status.success());
+}
+
+#[test]
+fn test_changes_lists_events_after_cursor() {
+    let fixture = Fixture::new("changes");
+
+    let output = fixture.run(&["--json", "changes", "--cursor", "1"], "");
+    let events: Vec<Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line should be JSON"))
+        .collect();
+
+    assert!(output.status.success());
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["entity"], "template");
+    assert_eq!(events[1]["entity"], "deck");
+    assert_eq!(events[1]["entityId"], fixture.deck_id.to_string());
+    assert_eq!(events[1]["operation"], "insert");
+}
+
+// ===== RENDER =====
+
+#[test]
+fn test_render_deck_uses_layout_html() {
+    let fixture = Fixture::new("render");
+    let deck = deck_arg(fixture.deck_id);
+    fixture.run_json(&["add", "--deck", &deck], r#"[["<b>hola</b>", "hello"]]"#);
+
+    let rendered = fixture.run_json(&["render", "--deck", &deck], "");
+    let missing = fixture.run(&["render", "--card", "404"], "");
+
+    assert_eq!(
+        rendered[0]["question"],
+        r#"<div class="field">&lt;b&gt;hola&lt;/b&gt;</div>"#
+    );
+    assert!(rendered[0]["answer"]
+        .as_str()
+        .unwrap()
+        .ends_with(r#"<div class="field">hello</div>"#));
+    assert!(!missing.status.success());
+}
+
+// ===== REVIEW =====
+
+#[test]
+fn test_review_grades_cards_from_stdin() {
+    let fixture = Fixture::new("review");
+    let deck = deck_arg(fixture.deck_id);
+    fixture.run_json(&["add", "--deck", &deck], r#"[["hola"], ["adiós"]]"#);
+
+    let summary = fixture.run_json(&["review", "--deck", &deck], "\n3\n\n1\n");
+
+    assert_eq!(summary["reviewed"], 2);
+    assert_eq!(summary["good"], 1);
+    assert_eq!(summary["again"], 1);
+    let lessons = fixture.run_json(&["decks"], "");
+    assert_eq!(lessons["decks"][0]["untouched"], 0);
+}
+
+#[test]
+fn test_review_quit_keeps_earlier_grades() {
+    let fixture = Fixture::new("review-quit");
+    let deck = deck_arg(fixture.deck_id);
+    fixture.run_json(&["add", "--deck", &deck], r#"[["hola"], ["adiós"]]"#);
+
+    let summary = fixture.run_json(&["review"], "\n4\nq\n");
+
+    assert_eq!(summary["reviewed"], 1);
+    assert_eq!(summary["easy"], 1);
+}
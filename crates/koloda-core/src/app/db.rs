@@ -1,14 +1,20 @@
-use rusqlite::{Connection, Transaction};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Transaction};
 use serde::de::DeserializeOwned;
 use std::{
-    path::PathBuf,
-    sync::{Arc, Mutex},
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    sync::{Arc, Mutex, MutexGuard, TryLockError},
+    time::Duration,
 };
 
-use crate::app::error::AppError;
+use crate::app::error::{error_codes, AppError};
+use crate::app::task::TaskContext;
+use crate::domain::versioning::VersionedJson;
 use crate::migrations;
 
 pub const MIGRATIONS_TABLE: &str = "_migrations";
+const SNAPSHOT_SUFFIX: &str = ".pre-migration";
 
 pub fn parse_json_column<T: DeserializeOwned>(column: usize, json: &str) -> Result<T, rusqlite::Error> {
     serde_json::from_str(json).map_err(|e| {
@@ -17,11 +23,167 @@ pub fn parse_json_column<T: DeserializeOwned>(column: usize, json: &str) -> Resu
     })
 }
 
+pub fn parse_versioned_json_column<T: VersionedJson>(column: usize, json: &str) -> Result<T, rusqlite::Error> {
+    T::from_versioned(parse_json_column(column, json)?)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+/// Copy of the database taken right before the last batch of pending migrations ran.
+pub fn pre_migration_snapshot_path(db_path: &Path) -> PathBuf {
+    let mut path = db_path.as_os_str().to_owned();
+    path.push(SNAPSHOT_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Replaces the database file with its pre-migration snapshot. Every connection to `db_path`
+/// must be closed first; the snapshot is kept so a failed restart can be restored again.
+pub fn restore_pre_migration_snapshot(db_path: impl AsRef<Path>) -> Result<(), AppError> {
+    let db_path = db_path.as_ref();
+    let snapshot_path = pre_migration_snapshot_path(db_path);
+    if !snapshot_path.exists() {
+        return Err(AppError::new(
+            error_codes::NOT_FOUND_DB_SNAPSHOT,
+            Some(format!("No snapshot at {}", snapshot_path.display())),
+        ));
+    }
+
+    let mut tmp_path = db_path.as_os_str().to_owned();
+    tmp_path.push(".restore");
+    let tmp_path = PathBuf::from(tmp_path);
+    fs::copy(&snapshot_path, &tmp_path)?;
+
+    // WHY: WAL/SHM sidecars belong to the migrated file; SQLite would replay them onto the snapshot.
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let sidecar = PathBuf::from(sidecar);
+        if sidecar.exists() {
+            fs::remove_file(sidecar)?;
+        }
+    }
+    fs::rename(&tmp_path, db_path)?;
+
+    Ok(())
+}
+
+fn prepare_migrations(conn: &Connection, db_path: &Path) -> Result<(), AppError> {
+    let latest = migrations::runner()
+        .get_migrations()
+        .iter()
+        .map(|migration| migration.version())
+        .max()
+        .unwrap_or(0);
+
+    match applied_schema_version(conn)? {
+        Some(applied) if applied > latest => Err(AppError::new(
+            error_codes::DB_SCHEMA_NEWER,
+            Some(format!(
+                "Database schema version {} is newer than this app supports ({})",
+                applied, latest
+            )),
+        )),
+        Some(applied) if applied < latest => snapshot_database(conn, &pre_migration_snapshot_path(db_path)),
+        // WHY: A blank database has nothing to lose, so first-run migrations skip the snapshot.
+        _ => Ok(()),
+    }
+}
+
+fn applied_schema_version(conn: &Connection) -> Result<Option<u32>, AppError> {
+    let table_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        params![MIGRATIONS_TABLE],
+        |row| row.get(0),
+    )?;
+    if !table_exists {
+        return Ok(None);
+    }
+
+    let version: Option<u32> = conn
+        .query_row(&format!("SELECT MAX(version) FROM {}", MIGRATIONS_TABLE), [], |row| {
+            row.get(0)
+        })
+        .optional()?
+        .flatten();
+
+    Ok(version)
+}
+
+fn snapshot_database(conn: &Connection, snapshot_path: &Path) -> Result<(), AppError> {
+    if snapshot_path.exists() {
+        fs::remove_file(snapshot_path)?;
+    }
+    let target = snapshot_path.to_str().ok_or_else(|| {
+        AppError::new(
+            error_codes::UNKNOWN,
+            Some(format!("Snapshot path is not valid UTF-8: {}", snapshot_path.display())),
+        )
+    })?;
+
+    // WHY: `VACUUM INTO` writes a consistent copy that already includes un-checkpointed WAL pages.
+    conn.execute("VACUUM INTO ?1", params![target])?;
+
+    Ok(())
+}
+
+const READ_POOL_SIZE: usize = 4;
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
-// INVARIANT: single SQLite connection — acceptable for a single-user desktop app.
-// `with_conn` / `with_transaction` serialize all access; long reads block writes.
+// INVARIANT: one writer plus a small pool of read-only connections. `with_conn` and
+// `with_transaction` use the writer; `with_read` borrows a reader, so it can only read.
+// WAL lets readers keep their snapshot while the writer commits, so long reads no longer block grading.
 pub struct Database {
-    conn: Arc<Mutex<Connection>>,
+    writer: Arc<Mutex<Connection>>,
+    readers: Arc<ReadPool>,
+}
+
+struct ReadPool {
+    connections: Vec<Mutex<Connection>>,
+    next: AtomicUsize,
+}
+
+impl ReadPool {
+    fn open(uri: &str) -> Result<Self, AppError> {
+        let flags = OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX;
+        let connections = (0..READ_POOL_SIZE)
+            .map(|_| {
+                let conn = Connection::open_with_flags(uri, flags)?;
+                // WHY: The read-only flag still allows TEMP tables; `query_only` rejects every write.
+                conn.pragma_update(None, "query_only", "ON")?;
+                conn.busy_timeout(BUSY_TIMEOUT)?;
+                Ok(Mutex::new(conn))
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        Ok(Self {
+            connections,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    fn empty() -> Self {
+        Self {
+            connections: Vec::new(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    // WHY: Prefer any idle reader; only when all are busy wait on the next one in rotation.
+    fn acquire(&self) -> Result<Option<MutexGuard<'_, Connection>>, AppError> {
+        for conn in &self.connections {
+            match conn.try_lock() {
+                Ok(guard) => return Ok(Some(guard)),
+                Err(TryLockError::Poisoned(err)) => return Err(crate::app::error::from_db_lock_error(err)),
+                Err(TryLockError::WouldBlock) => {}
+            }
+        }
+
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.connections.len().max(1);
+        match self.connections.get(index) {
+            Some(conn) => Ok(Some(conn.lock().map_err(crate::app::error::from_db_lock_error)?)),
+            None => Ok(None),
+        }
+    }
 }
 
 impl Database {
@@ -35,22 +197,42 @@ impl Database {
 
         conn.pragma_update(None, "journal_mode", "WAL")?;
         conn.pragma_update(None, "foreign_keys", "ON")?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
 
+        prepare_migrations(&conn, &db_path)?;
         migrations::runner().run(&mut conn).map_err(AppError::from)?;
 
+        let uri = db_path.to_str().ok_or_else(|| {
+            AppError::new(
+                error_codes::UNKNOWN,
+                Some(format!("Database path is not valid UTF-8: {}", db_path.display())),
+            )
+        })?;
+        let readers = ReadPool::open(uri)?;
+
         Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
+            writer: Arc::new(Mutex::new(conn)),
+            readers: Arc::new(readers),
         })
     }
 
     pub fn with_conn<T>(&self, f: impl FnOnce(&Connection) -> Result<T, AppError>) -> Result<T, AppError> {
-        let guard = self.conn.lock().map_err(crate::app::error::from_db_lock_error)?;
-
+        let guard = self.writer.lock().map_err(crate::app::error::from_db_lock_error)?;
         f(&guard)
     }
 
+    /// Runs `f` on a read-only pooled connection, or on the writer when the database has no
+    /// read pool (`new`, `in_memory`). Writes inside `f` fail on a pooled connection.
+    pub fn with_read<T>(&self, f: impl FnOnce(&Connection) -> Result<T, AppError>) -> Result<T, AppError> {
+        if let Some(guard) = self.readers.acquire()? {
+            return f(&guard);
+        }
+
+        self.with_conn(f)
+    }
+
     pub fn with_transaction<T>(&self, f: impl FnOnce(&Transaction<'_>) -> Result<T, AppError>) -> Result<T, AppError> {
-        let mut guard = self.conn.lock().map_err(crate::app::error::from_db_lock_error)?;
+        let mut guard = self.writer.lock().map_err(crate::app::error::from_db_lock_error)?;
         let tx = guard.transaction().map_err(AppError::from)?;
         let result = f(&tx)?;
         tx.commit().map_err(AppError::from)?;
@@ -59,18 +241,67 @@ impl Database {
     }
 
     pub fn checkpoint(&self) -> Result<(), AppError> {
-        let guard = self.conn.lock().map_err(crate::app::error::from_db_lock_error)?;
+        let guard = self.writer.lock().map_err(crate::app::error::from_db_lock_error)?;
         guard.execute("PRAGMA wal_checkpoint(TRUNCATE)", [])?;
 
         Ok(())
     }
 
+    /// Writes a consistent copy of the database to `path`, replacing any file already there.
+    pub fn backup(&self, path: impl AsRef<Path>) -> Result<(), AppError> {
+        self.backup_with_task(path, &TaskContext::default())
+    }
+
+    /// `VACUUM INTO` is a single statement, so cancelling only takes effect before the copy starts.
+    pub fn backup_with_task(&self, path: impl AsRef<Path>, task: &TaskContext) -> Result<(), AppError> {
+        task.step(0, 1)?;
+        let guard = self.writer.lock().map_err(crate::app::error::from_db_lock_error)?;
+        task.check()?;
+        snapshot_database(&guard, path.as_ref())?;
+        task.progress(1, 1);
+
+        Ok(())
+    }
+
+    /// SQLite `integrity_check` plus `foreign_key_check`; an empty list means no problems.
+    pub fn integrity_check(&self) -> Result<Vec<String>, AppError> {
+        self.with_read(|conn| {
+            let mut problems = conn
+                .prepare("PRAGMA integrity_check")?
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            problems.retain(|message| message != "ok");
+
+            let mut stmt = conn.prepare("PRAGMA foreign_key_check")?;
+            let violations = stmt.query_map([], |row| {
+                let table: String = row.get(0)?;
+                let rowid: Option<i64> = row.get(1)?;
+                let parent: String = row.get(2)?;
+                Ok(format!(
+                    "{} row {} references a missing {} row",
+                    table,
+                    rowid.map_or_else(|| "?".to_string(), |id| id.to_string()),
+                    parent
+                ))
+            })?;
+            for violation in violations {
+                problems.push(violation?);
+            }
+
+            Ok(problems)
+        })
+    }
+
+    /// Wraps a single connection that serves both reads and writes.
     pub fn new(conn: Connection) -> Self {
         Self {
-            conn: Arc::new(Mutex::new(conn)),
+            writer: Arc::new(Mutex::new(conn)),
+            readers: Arc::new(ReadPool::empty()),
         }
     }
 
+    // WHY: A single private connection; shared-cache memory databases take table locks that
+    // fail with SQLITE_LOCKED and ignore the busy timeout. `with_read` falls back to it.
     pub fn in_memory() -> Result<Self, AppError> {
         let mut conn = Connection::open_in_memory().map_err(AppError::from)?;
         conn.pragma_update(None, "foreign_keys", "ON")?;
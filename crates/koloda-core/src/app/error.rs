@@ -8,6 +8,18 @@ pub mod error_codes {
     pub const DB_UPDATE: &str = "db.update";
     pub const DB_DELETE: &str = "db.delete";
     pub const DB_CLONE: &str = "db.clone";
+    pub const DB_SCHEMA_NEWER: &str = "db.schema-newer";
+
+    pub const TASK_CANCELLED: &str = "task.cancelled";
+
+    pub const HTTP_BIND: &str = "http.bind";
+    pub const HTTP_UNAUTHORIZED: &str = "http.unauthorized";
+    pub const HTTP_METHOD_NOT_ALLOWED: &str = "http.method-not-allowed";
+
+    pub const SYNC_REQUEST: &str = "sync.request";
+
+    pub const SECRET_STORE_UNAVAILABLE: &str = "secret-store.unavailable";
+    pub const SECRET_STORE_DECRYPT: &str = "secret-store.decrypt";
 
     pub const NOT_FOUND_ALGORITHMS_CLONE_SOURCE: &str = "not-found.algorithms.clone.source";
     pub const NOT_FOUND_ALGORITHMS_DELETE_SUCCESSOR: &str = "not-found.algorithms.delete.successor";
@@ -16,15 +28,40 @@ pub mod error_codes {
     pub const NOT_FOUND_CARDS_ADD_TEMPLATE: &str = "not-found.cards.add.template";
     pub const NOT_FOUND_CARDS_UPDATE_CARD: &str = "not-found.cards.update.card";
     pub const NOT_FOUND_CARDS_UPDATE_TEMPLATE: &str = "not-found.cards.update.template";
+    pub const NOT_FOUND_CARDS_MOVE_CARD: &str = "not-found.cards.move.card";
+    pub const NOT_FOUND_CARDS_MOVE_DECK: &str = "not-found.cards.move.deck";
+    pub const NOT_FOUND_CARDS_MOVE_TEMPLATE: &str = "not-found.cards.move.template";
+    pub const NOT_FOUND_CARDS_COPY_CARD: &str = "not-found.cards.copy.card";
+    pub const NOT_FOUND_CARDS_COPY_DECK: &str = "not-found.cards.copy.deck";
+    pub const NOT_FOUND_CARDS_COPY_TEMPLATE: &str = "not-found.cards.copy.template";
+    pub const NOT_FOUND_CARDS_CONVERT_CARD: &str = "not-found.cards.convert.card";
+    pub const NOT_FOUND_CARDS_CONVERT_TEMPLATE: &str = "not-found.cards.convert.template";
+    pub const NOT_FOUND_CARDS_MERGE_CARD: &str = "not-found.cards.merge.card";
+    pub const NOT_FOUND_CARDS_REBUILD_CARD: &str = "not-found.cards.rebuild.card";
+    pub const NOT_FOUND_CARDS_REBUILD_DECK: &str = "not-found.cards.rebuild.deck";
+    pub const NOT_FOUND_CARDS_REBUILD_ALGORITHM: &str = "not-found.cards.rebuild.algorithm";
+    pub const NOT_FOUND_CARDS_RENDER_CARD: &str = "not-found.cards.render.card";
+    pub const NOT_FOUND_CARDS_RENDER_DECK: &str = "not-found.cards.render.deck";
+    pub const NOT_FOUND_CARDS_RENDER_TEMPLATE: &str = "not-found.cards.render.template";
     pub const NOT_FOUND_DECKS_ADD_ALGORITHM: &str = "not-found.decks.add.algorithm";
     pub const NOT_FOUND_DECKS_ADD_TEMPLATE: &str = "not-found.decks.add.template";
     pub const NOT_FOUND_DECKS_UPDATE_DECK: &str = "not-found.decks.update.deck";
     pub const NOT_FOUND_DECKS_UPDATE_ALGORITHM: &str = "not-found.decks.update.algorithm";
     pub const NOT_FOUND_DECKS_UPDATE_TEMPLATE: &str = "not-found.decks.update.template";
+    pub const NOT_FOUND_DECKS_EXPORT_DECK: &str = "not-found.decks.export.deck";
     pub const NOT_FOUND_AI_PROFILE: &str = "not-found.ai.profile";
+    pub const NOT_FOUND_SETTINGS_HISTORY_ENTRY: &str = "not-found.settings.history.entry";
+    pub const NOT_FOUND_DB_SNAPSHOT: &str = "not-found.db.snapshot";
+    pub const NOT_FOUND_DB_FILE: &str = "not-found.db.file";
+    pub const NOT_FOUND_HTTP_ROUTE: &str = "not-found.http.route";
+    pub const NOT_FOUND_ANKI_CONNECT_DECK: &str = "not-found.anki-connect.deck";
+    pub const NOT_FOUND_ANKI_CONNECT_MODEL: &str = "not-found.anki-connect.model";
+    pub const NOT_FOUND_ANKI_CONNECT_ACTION: &str = "not-found.anki-connect.action";
 
     pub const VALIDATION_COMMON_TITLE_TOO_SHORT: &str = "validation.common.title.too-short";
     pub const VALIDATION_COMMON_TITLE_TOO_LONG: &str = "validation.common.title.too-long";
+    pub const VALIDATION_COMMON_CONTENT_VERSION: &str = "validation.common.content.version";
+    pub const VALIDATION_COMMON_PAYLOAD: &str = "validation.common.payload";
 
     pub const VALIDATION_SETTINGS_LEARNING_DAILY_LIMITS_UNTOUCHED_EXCEEDS_TOTAL: &str =
         "validation.settings-learning.daily-limits.untouched-exceeds-total";
@@ -38,6 +75,17 @@ pub mod error_codes {
         "validation.settings-learning.learn-ahead-limit.minutes-range";
     pub const VALIDATION_SETTINGS_LEARNING_DAY_STARTS_AT: &str = "validation.settings-learning.day-starts-at";
 
+    pub const VALIDATION_SETTINGS_HISTORY_NAME_MISMATCH: &str = "validation.settings.history.name-mismatch";
+
+    pub const VALIDATION_SETTINGS_BUNDLE_VERSION: &str = "validation.settings.bundle.version";
+    pub const VALIDATION_SETTINGS_BUNDLE_DUPLICATE_SLICE: &str = "validation.settings.bundle.duplicate-slice";
+
+    pub const VALIDATION_DECK_BUNDLE_VERSION: &str = "validation.deck.bundle.version";
+
+    pub const VALIDATION_ANKI_CONNECT_QUERY: &str = "validation.anki-connect.query";
+
+    pub const VALIDATION_SYNC_VERSION: &str = "validation.sync.version";
+
     pub const VALIDATION_SETTINGS_HOTKEYS_DUPLICATE_KEYS: &str = "validation.settings-hotkeys.duplicate-keys";
 
     pub const VALIDATION_SETTINGS_INTERFACE_LANGUAGE: &str = "validation.settings-interface.language";
@@ -59,8 +107,23 @@ pub mod error_codes {
     pub const VALIDATION_TEMPLATES_LAYOUT_TOO_FEW: &str = "validation.templates.layout.too-few";
     pub const VALIDATION_TEMPLATES_UPDATE_LOCKED: &str = "validation.templates.update-locked";
     pub const VALIDATION_TEMPLATES_DELETE_LOCKED: &str = "validation.templates.delete-locked";
+    pub const VALIDATION_TEMPLATES_SIDES_SYNTAX: &str = "validation.templates.sides.syntax";
+    pub const VALIDATION_TEMPLATES_FIELDS_RULES: &str = "validation.templates.fields.rules";
 
     pub const VALIDATION_CARDS_CONTENT_FIELD_EMPTY: &str = "validation.cards.content.field-empty";
+    pub const VALIDATION_CARDS_CONTENT_FIELD_TOO_LONG: &str = "validation.cards.content.field-too-long";
+    pub const VALIDATION_CARDS_CONTENT_FIELD_PATTERN: &str = "validation.cards.content.field-pattern";
+    pub const VALIDATION_CARDS_CONTENT_FIELD_SCRIPT: &str = "validation.cards.content.field-script";
+    pub const VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE: &str = "validation.cards.content.field-not-unique";
+    pub const VALIDATION_CARDS_FIELD_MAPPING_MISSING: &str = "validation.cards.field-mapping.missing";
+    pub const VALIDATION_CARDS_FIELD_MAPPING_INVALID: &str = "validation.cards.field-mapping.invalid";
+    pub const VALIDATION_CARDS_SELECTION_EMPTY: &str = "validation.cards.selection.empty";
+    pub const VALIDATION_CARDS_FIND_REPLACE_PATTERN_EMPTY: &str = "validation.cards.find-replace.pattern-empty";
+    pub const VALIDATION_CARDS_FIND_REPLACE_PATTERN_INVALID: &str = "validation.cards.find-replace.pattern-invalid";
+    pub const VALIDATION_CARDS_MERGE_SOURCES: &str = "validation.cards.merge.sources";
+    pub const VALIDATION_CARDS_MERGE_TEMPLATE: &str = "validation.cards.merge.template";
+    pub const VALIDATION_CARDS_ADD_DUPLICATE: &str = "validation.cards.add.duplicate";
+    pub const VALIDATION_CARDS_ADD_DUPLICATE_FIELD: &str = "validation.cards.add.duplicate-field";
 
     pub const VALIDATION_REVIEWS_RATING: &str = "validation.reviews.rating";
     pub const VALIDATION_REVIEWS_STATE: &str = "validation.reviews.state";
@@ -69,6 +132,8 @@ pub mod error_codes {
     pub const VALIDATION_REVIEWS_SCHEDULED_DAYS: &str = "validation.reviews.scheduled-days";
     pub const VALIDATION_REVIEWS_LEARNING_STEPS: &str = "validation.reviews.learning-steps";
     pub const VALIDATION_REVIEWS_TIME: &str = "validation.reviews.time";
+    pub const VALIDATION_REVIEWS_TYPED_SCORE: &str = "validation.reviews.typed-score";
+    pub const VALIDATION_TYPED_ANSWER_TOO_LONG: &str = "validation.typed-answer.too-long";
 
     pub const VALIDATION_CARDS_PROGRESS_STATE: &str = "validation.cards-progress.state";
     pub const VALIDATION_CARDS_PROGRESS_STABILITY: &str = "validation.cards-progress.stability";
@@ -84,6 +149,7 @@ pub mod error_codes {
     pub const VALIDATION_SETTINGS_AI_PROFILES_WHITELIST_MODEL_IDS: &str =
         "validation.settings-ai.profiles.whitelist-model-ids";
     pub const VALIDATION_AI_PROVIDERS_PROFILE_ID_DUPLICATE: &str = "validation.ai-providers.profile-id.duplicate";
+    pub const VALIDATION_AI_SECRETS_PASSPHRASE: &str = "validation.ai-secrets.passphrase";
     pub const VALIDATION_ASSISTANT_SETTINGS_TEMPERATURE_RANGE: &str = "validation.assistant-settings.temperature-range";
 
     pub const VALIDATION_LESSONS_RESULT_CARD_REVIEW_MISMATCH: &str = "validation.lessons.result.card-review-mismatch";
@@ -93,6 +159,7 @@ pub mod error_codes {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 pub struct AppError {
     pub code: String,
     pub details: Option<String>,
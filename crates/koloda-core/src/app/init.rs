@@ -8,9 +8,11 @@ use crate::domain::algorithms::InsertAlgorithmData;
 use crate::domain::settings::SettingsName;
 use crate::domain::settings_learning::LearningSettings;
 use crate::domain::templates::InsertTemplateData;
+use crate::domain::versioning::VersionedJson;
 use crate::repo::{algorithms, settings, templates};
 
 #[derive(serde::Serialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "lowercase")]
 pub enum DbStatus {
     Blank,
@@ -18,6 +20,7 @@ pub enum DbStatus {
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct SeedData {
     pub algorithm: InsertAlgorithmData,
@@ -26,6 +29,7 @@ pub struct SeedData {
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct SeedSettings {
     pub interface: Value,
@@ -54,7 +58,7 @@ fn seed_db_impl(db: &Database, data: SeedData) -> Result<(), AppError> {
     let interface = SettingsName::Interface.normalize(data.settings.interface)?;
     let hotkeys = SettingsName::Hotkeys.normalize(data.settings.hotkeys)?;
     let now = get_current_timestamp()?;
-    let mut learning_settings: LearningSettings = serde_json::from_value(data.settings.learning).map_err(|e| {
+    let mut learning_settings = LearningSettings::from_versioned(data.settings.learning).map_err(|e| {
         AppError::new(
             error_codes::VALIDATION_SEED_LEARNING_SETTINGS,
             Some(format!("learning settings must be valid LearningSettings JSON: {e}")),
@@ -87,7 +91,7 @@ fn seed_db_impl(db: &Database, data: SeedData) -> Result<(), AppError> {
 
 fn get_settings_names(db: &Database) -> Result<Vec<String>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let mut stmt = conn.prepare("SELECT name FROM settings")?;
             let names = stmt
                 .query_map([], |row| row.get(0))?
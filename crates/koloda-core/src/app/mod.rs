@@ -1,4 +1,5 @@
-//! DB connection, init/seed, keyring secrets, clock/UUID helpers.
+//! DB connection, init/seed, keyring / encrypted-file secrets, task cancellation, change-feed
+//! watching, clock/UUID helpers.
 //!
 //! `error` (`AppError` + `error_codes`) is the crate-wide error type used by domain and repo.
 
@@ -6,4 +7,6 @@ pub mod db;
 pub mod error;
 pub mod init;
 pub mod secrets;
+pub mod task;
 pub mod utility;
+pub mod watch;
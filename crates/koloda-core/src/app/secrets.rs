@@ -1,8 +1,11 @@
-use crate::app::error::AppError;
-use std::sync::{Arc, LazyLock};
+use crate::app::error::{error_codes, AppError};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
 
-#[cfg(debug_assertions)]
-use std::sync::RwLock;
+mod file_store;
+pub use crate::domain::ai::SecretStoreBackend;
+pub use file_store::{open_with_passphrase, seal_with_passphrase, EncryptedFileSecretStore, FileSecretKey};
 
 #[cfg(not(target_os = "windows"))]
 use std::collections::HashMap;
@@ -11,6 +14,8 @@ use std::collections::HashMap;
 use std::sync::{RwLockReadGuard, RwLockWriteGuard};
 
 const STORE_ID: &str = "koloda";
+const PROBE_KEY: &str = "koloda-probe";
+const INDEX_KEY: &str = "koloda-index";
 
 // WHY: Kept under `debug_assertions` (not `cfg(test)`). Integration tests in
 // `tests/` are a separate crate and link a non-`test` build of this library, so
@@ -42,18 +47,72 @@ pub fn get_secret_store() -> Result<Arc<dyn SecretStore>, AppError> {
         return Ok(Arc::clone(store));
     }
     drop(guard);
-    Ok(Arc::clone(&*REAL_SECRET_STORE))
+    Ok(Arc::clone(&read_active_secret_store()?.store))
 }
 
 #[cfg(not(debug_assertions))]
 pub fn get_secret_store() -> Result<Arc<dyn SecretStore>, AppError> {
-    Ok(Arc::clone(&*REAL_SECRET_STORE))
+    Ok(Arc::clone(&read_active_secret_store()?.store))
 }
 
 pub trait SecretStore: Send + Sync {
     fn get(&self, key: &str) -> Result<Option<String>, AppError>;
     fn set(&self, key: &str, value: &str) -> Result<(), AppError>;
     fn remove(&self, key: &str) -> Result<(), AppError>;
+
+    /// Stored keys, or `None` when the backend cannot enumerate its entries.
+    fn keys(&self) -> Result<Option<Vec<String>>, AppError> {
+        Ok(None)
+    }
+}
+
+// WHY: OS keyrings cannot list entries, so the keyring-backed stores keep their own key list
+// under `INDEX_KEY`. Diagnostics rely on it to find orphaned entries; keys written before the
+// index existed stay invisible until they are written again.
+struct KeyIndex {
+    lock: Mutex<()>,
+}
+
+impl KeyIndex {
+    fn new() -> Self {
+        Self { lock: Mutex::new(()) }
+    }
+
+    fn parse(raw: Option<String>) -> Result<Vec<String>, AppError> {
+        match raw {
+            Some(raw) => Ok(serde_json::from_str(&raw)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn list(&self, get: impl Fn(&str) -> Result<Option<String>, AppError>) -> Result<Vec<String>, AppError> {
+        Self::parse(get(INDEX_KEY)?)
+    }
+
+    fn update(
+        &self,
+        get: impl Fn(&str) -> Result<Option<String>, AppError>,
+        set: impl Fn(&str, &str) -> Result<(), AppError>,
+        key: &str,
+        is_present: bool,
+    ) -> Result<(), AppError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_poisoned| AppError::new("secret-store", Some("Key index lock poisoned".to_string())))?;
+        let mut keys = Self::parse(get(INDEX_KEY)?)?;
+        let was_present = keys.iter().any(|k| k == key);
+        if was_present == is_present {
+            return Ok(());
+        }
+
+        if is_present {
+            keys.push(key.to_string());
+        } else {
+            keys.retain(|k| k != key);
+        }
+        set(INDEX_KEY, &serde_json::to_string(&keys)?)
+    }
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -62,6 +121,7 @@ pub trait SecretStore: Send + Sync {
 pub struct KeyringSecretStore {
     service: &'static str,
     cache: RwLock<HashMap<String, String>>,
+    index: KeyIndex,
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -70,6 +130,7 @@ impl KeyringSecretStore {
         Self {
             service,
             cache: RwLock::new(HashMap::new()),
+            index: KeyIndex::new(),
         }
     }
 
@@ -123,8 +184,8 @@ impl KeyringSecretStore {
 }
 
 #[cfg(not(target_os = "windows"))]
-impl SecretStore for KeyringSecretStore {
-    fn get(&self, key: &str) -> Result<Option<String>, AppError> {
+impl KeyringSecretStore {
+    fn get_cached(&self, key: &str) -> Result<Option<String>, AppError> {
         {
             let cache = self.read_cache()?;
             if let Some(value) = cache.get(key) {
@@ -142,24 +203,44 @@ impl SecretStore for KeyringSecretStore {
         }
     }
 
-    fn set(&self, key: &str, value: &str) -> Result<(), AppError> {
+    fn set_cached(&self, key: &str, value: &str) -> Result<(), AppError> {
         self.set_to_keyring(key, value)?;
         let mut cache = self.write_cache()?;
         cache.insert(key.to_string(), value.to_string());
         Ok(())
     }
+}
+
+#[cfg(not(target_os = "windows"))]
+impl SecretStore for KeyringSecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, AppError> {
+        self.get_cached(key)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), AppError> {
+        self.set_cached(key, value)?;
+        self.index
+            .update(|k| self.get_cached(k), |k, v| self.set_cached(k, v), key, true)
+    }
 
     fn remove(&self, key: &str) -> Result<(), AppError> {
         self.remove_from_keyring(key)?;
-        let mut cache = self.write_cache()?;
-        cache.remove(key);
-        Ok(())
+        {
+            let mut cache = self.write_cache()?;
+            cache.remove(key);
+        }
+        self.index
+            .update(|k| self.get_cached(k), |k, v| self.set_cached(k, v), key, false)
+    }
+
+    fn keys(&self) -> Result<Option<Vec<String>>, AppError> {
+        self.index.list(|k| self.get_cached(k)).map(Some)
     }
 }
 
 #[cfg(target_os = "windows")]
 mod windows_store {
-    use super::SecretStore;
+    use super::{KeyIndex, SecretStore};
     use crate::app::error::AppError;
     use std::collections::HashMap;
     use std::ffi::OsStr;
@@ -176,6 +257,7 @@ mod windows_store {
     pub struct WindowsCredentialStore {
         service: &'static str,
         cache: RwLock<HashMap<String, String>>,
+        index: KeyIndex,
     }
 
     impl WindowsCredentialStore {
@@ -183,6 +265,7 @@ mod windows_store {
             Self {
                 service,
                 cache: RwLock::new(HashMap::new()),
+                index: KeyIndex::new(),
             }
         }
 
@@ -315,8 +398,8 @@ mod windows_store {
         }
     }
 
-    impl SecretStore for WindowsCredentialStore {
-        fn get(&self, key: &str) -> Result<Option<String>, AppError> {
+    impl WindowsCredentialStore {
+        fn get_cached(&self, key: &str) -> Result<Option<String>, AppError> {
             {
                 let cache = self.read_cache()?;
                 if let Some(value) = cache.get(key) {
@@ -334,18 +417,37 @@ mod windows_store {
             }
         }
 
-        fn set(&self, key: &str, value: &str) -> Result<(), AppError> {
+        fn set_cached(&self, key: &str, value: &str) -> Result<(), AppError> {
             self.set_to_windows(key, value)?;
             let mut cache = self.write_cache()?;
             cache.insert(key.to_string(), value.to_string());
             Ok(())
         }
+    }
+
+    impl SecretStore for WindowsCredentialStore {
+        fn get(&self, key: &str) -> Result<Option<String>, AppError> {
+            self.get_cached(key)
+        }
+
+        fn set(&self, key: &str, value: &str) -> Result<(), AppError> {
+            self.set_cached(key, value)?;
+            self.index
+                .update(|k| self.get_cached(k), |k, v| self.set_cached(k, v), key, true)
+        }
 
         fn remove(&self, key: &str) -> Result<(), AppError> {
             self.remove_from_windows(key)?;
-            let mut cache = self.write_cache()?;
-            cache.remove(key);
-            Ok(())
+            {
+                let mut cache = self.write_cache()?;
+                cache.remove(key);
+            }
+            self.index
+                .update(|k| self.get_cached(k), |k, v| self.set_cached(k, v), key, false)
+        }
+
+        fn keys(&self) -> Result<Option<Vec<String>>, AppError> {
+            self.index.list(|k| self.get_cached(k)).map(Some)
         }
     }
 }
@@ -353,14 +455,153 @@ mod windows_store {
 #[cfg(target_os = "windows")]
 pub use windows_store::WindowsCredentialStore;
 
-static REAL_SECRET_STORE: LazyLock<Arc<dyn SecretStore>> = LazyLock::new(|| create_secret_store(STORE_ID));
+/// Runtime secret store selection.
+///
+/// `Keyring` is the OS store (Credential Manager on Windows). `Auto` keeps an existing encrypted
+/// file in `directory`, otherwise uses the keyring and falls back to the encrypted file when the
+/// keyring is unavailable. The file is sealed with `passphrase`, or with a machine-bound key file
+/// next to it when no passphrase is given.
+#[derive(Clone, Default, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SecretStoreOptions {
+    #[serde(default)]
+    pub backend: SecretStoreBackend,
+    #[serde(default)]
+    pub directory: Option<PathBuf>,
+    #[serde(default)]
+    pub passphrase: Option<String>,
+}
+
+// INVARIANT: `backend` is the resolved backend — never `Auto`.
+pub struct SelectedSecretStore {
+    pub backend: SecretStoreBackend,
+    pub store: Arc<dyn SecretStore>,
+}
+
+static ACTIVE_SECRET_STORE: LazyLock<RwLock<SelectedSecretStore>> = LazyLock::new(|| {
+    RwLock::new(SelectedSecretStore {
+        backend: SecretStoreBackend::Keyring,
+        store: platform_secret_store(STORE_ID),
+    })
+});
+
+fn active_store_lock_poisoned() -> AppError {
+    AppError::new("secret-store", Some("Active secret store lock poisoned".to_string()))
+}
+
+fn read_active_secret_store() -> Result<std::sync::RwLockReadGuard<'static, SelectedSecretStore>, AppError> {
+    ACTIVE_SECRET_STORE
+        .read()
+        .map_err(|_poisoned| active_store_lock_poisoned())
+}
+
+pub fn get_secret_store_backend() -> Result<SecretStoreBackend, AppError> {
+    Ok(read_active_secret_store()?.backend)
+}
 
 #[cfg(target_os = "windows")]
-pub fn create_secret_store(service: &'static str) -> Arc<dyn SecretStore> {
+fn platform_secret_store(service: &'static str) -> Arc<dyn SecretStore> {
     Arc::new(WindowsCredentialStore::new(service))
 }
 
 #[cfg(not(target_os = "windows"))]
-pub fn create_secret_store(service: &'static str) -> Arc<dyn SecretStore> {
+fn platform_secret_store(service: &'static str) -> Arc<dyn SecretStore> {
     Arc::new(KeyringSecretStore::new(service))
 }
+
+pub fn secret_file_path(directory: &Path, service: &str) -> PathBuf {
+    directory.join(format!("{}.secrets", service))
+}
+
+fn encrypted_file_store(service: &str, options: &SecretStoreOptions) -> Result<SelectedSecretStore, AppError> {
+    let directory = options.directory.as_ref().ok_or_else(|| {
+        AppError::new(
+            error_codes::SECRET_STORE_UNAVAILABLE,
+            Some("Encrypted file secret store requires a directory".to_string()),
+        )
+    })?;
+    let key = match options.passphrase.as_ref() {
+        Some(passphrase) => FileSecretKey::Passphrase(passphrase.clone()),
+        None => FileSecretKey::KeyFile(directory.join(format!("{}.key", service))),
+    };
+    let store = EncryptedFileSecretStore::open(secret_file_path(directory, service), key)?;
+
+    Ok(SelectedSecretStore {
+        backend: SecretStoreBackend::EncryptedFile,
+        store: Arc::new(store),
+    })
+}
+
+pub fn create_secret_store(
+    service: &'static str,
+    options: &SecretStoreOptions,
+) -> Result<SelectedSecretStore, AppError> {
+    match options.backend {
+        SecretStoreBackend::Keyring => Ok(SelectedSecretStore {
+            backend: SecretStoreBackend::Keyring,
+            store: platform_secret_store(service),
+        }),
+        SecretStoreBackend::EncryptedFile => encrypted_file_store(service, options),
+        SecretStoreBackend::Auto => {
+            // WHY: Once secrets landed in the file they stay there; switching back to the keyring
+            // only because it came up again would hide every stored key.
+            if let Some(directory) = options.directory.as_ref() {
+                if secret_file_path(directory, service).exists() {
+                    return encrypted_file_store(service, options);
+                }
+            }
+
+            let store = platform_secret_store(service);
+            match store.get(PROBE_KEY) {
+                Ok(_) => Ok(SelectedSecretStore {
+                    backend: SecretStoreBackend::Keyring,
+                    store,
+                }),
+                Err(_keyring_err) if options.directory.is_some() => encrypted_file_store(service, options),
+                Err(keyring_err) => Err(AppError::new(
+                    error_codes::SECRET_STORE_UNAVAILABLE,
+                    keyring_err.details,
+                )),
+            }
+        }
+    }
+}
+
+/// Copies every present key into `to`, then removes the copied keys from `from`.
+pub fn migrate_secrets(from: &dyn SecretStore, to: &dyn SecretStore, keys: &[String]) -> Result<Vec<String>, AppError> {
+    let mut migrated = Vec::new();
+    for key in keys {
+        if let Some(value) = from.get(key)? {
+            to.set(key, &value)?;
+            migrated.push(key.clone());
+        }
+    }
+
+    // WHY: Remove only after every copy landed, so a failed write never loses a secret.
+    for key in &migrated {
+        from.remove(key)?;
+    }
+
+    Ok(migrated)
+}
+
+pub fn configure_secret_store(
+    options: &SecretStoreOptions,
+    migrate_keys: &[String],
+) -> Result<SecretStoreBackend, AppError> {
+    let selected = create_secret_store(STORE_ID, options)?;
+    let mut active = ACTIVE_SECRET_STORE
+        .write()
+        .map_err(|_poisoned| active_store_lock_poisoned())?;
+
+    // WHY: A keyring that just failed the probe cannot be read, so there is nothing to move;
+    // the fallback store starts empty instead of failing startup.
+    if active.backend != selected.backend && active.store.get(PROBE_KEY).is_ok() {
+        migrate_secrets(&*active.store, &*selected.store, migrate_keys)?;
+    }
+
+    let backend = selected.backend;
+    *active = selected;
+    Ok(backend)
+}
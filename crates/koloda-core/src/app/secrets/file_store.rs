@@ -0,0 +1,303 @@
+//! Encrypted file-backed `SecretStore` for hosts without a keyring daemon.
+//!
+//! One file per service; the whole entry map is sealed with ChaCha20-Poly1305 on every write.
+
+use super::SecretStore;
+use crate::app::error::{error_codes, AppError};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+const FILE_VERSION: u32 = 1;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+pub enum FileSecretKey {
+    Passphrase(String),
+    /// Machine-bound random key; created on first use.
+    KeyFile(PathBuf),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum FileKdf {
+    Argon2id,
+    KeyFile,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedFile {
+    version: u32,
+    kdf: FileKdf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    salt: Option<String>,
+    nonce: String,
+    ciphertext: String,
+}
+
+// INVARIANT: `entries` always mirrors the decrypted file. Writes seal a copy and only swap it
+// in after the file was replaced, so a failed write leaves both memory and disk unchanged.
+pub struct EncryptedFileSecretStore {
+    path: PathBuf,
+    kdf: FileKdf,
+    salt: Option<String>,
+    cipher: ChaCha20Poly1305,
+    entries: RwLock<HashMap<String, String>>,
+}
+
+fn store_error(details: String) -> AppError {
+    AppError::new("secret-store-file", Some(details))
+}
+
+fn decrypt_error(details: &str) -> AppError {
+    AppError::new(error_codes::SECRET_STORE_DECRYPT, Some(details.to_string()))
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn decode_base64(value: &str, what: &str) -> Result<Vec<u8>, AppError> {
+    BASE64
+        .decode(value)
+        .map_err(|e| store_error(format!("Invalid {} encoding: {}", what, e)))
+}
+
+fn derive_passphrase_key(passphrase: &str, salt: &[u8]) -> Result<[u8; KEY_LEN], AppError> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| store_error(format!("Failed to derive key from passphrase: {}", e)))?;
+    Ok(key)
+}
+
+fn load_or_create_key_file(path: &Path) -> Result<[u8; KEY_LEN], AppError> {
+    if path.exists() {
+        let raw = fs::read_to_string(path)?;
+        let bytes = decode_base64(raw.trim(), "key file")?;
+        return <[u8; KEY_LEN]>::try_from(bytes.as_slice())
+            .map_err(|_wrong_len| store_error(format!("Key file {} must hold {} bytes", path.display(), KEY_LEN)));
+    }
+
+    let key = random_bytes::<KEY_LEN>();
+    write_private_file(path, BASE64.encode(key).as_bytes())?;
+    Ok(key)
+}
+
+// WHY: Secrets and key files must never be world-readable, even for the instant between
+// create and chmod, so the mode is set at open time on Unix.
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+fn read_encrypted_file(path: &Path) -> Result<Option<EncryptedFile>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let raw = fs::read(path)?;
+    let file: EncryptedFile = serde_json::from_slice(&raw)?;
+    if file.version != FILE_VERSION {
+        return Err(store_error(format!(
+            "Unsupported secret file version {} in {}",
+            file.version,
+            path.display()
+        )));
+    }
+
+    Ok(Some(file))
+}
+
+fn seal_envelope(
+    cipher: &ChaCha20Poly1305,
+    kdf: FileKdf,
+    salt: Option<String>,
+    plaintext: &[u8],
+) -> Result<EncryptedFile, AppError> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| store_error(format!("Failed to encrypt secrets: {}", e)))?;
+
+    Ok(EncryptedFile {
+        version: FILE_VERSION,
+        kdf,
+        salt,
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn open_envelope(cipher: &ChaCha20Poly1305, file: &EncryptedFile) -> Result<Vec<u8>, AppError> {
+    let nonce = decode_base64(&file.nonce, "nonce")?;
+    if nonce.len() != NONCE_LEN {
+        return Err(store_error("Secret file nonce has the wrong length".to_string()));
+    }
+    let ciphertext = decode_base64(&file.ciphertext, "ciphertext")?;
+
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_auth_failed| decrypt_error("Wrong passphrase or key file, or the secret file was modified"))
+}
+
+/// Seals `plaintext` with a passphrase in the secret-file envelope, for portable exports.
+pub fn seal_with_passphrase(plaintext: &[u8], passphrase: &str) -> Result<String, AppError> {
+    let salt = BASE64.encode(random_bytes::<SALT_LEN>());
+    let key = derive_passphrase_key(passphrase, &decode_base64(&salt, "salt")?)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    let file = seal_envelope(&cipher, FileKdf::Argon2id, Some(salt), plaintext)?;
+
+    Ok(serde_json::to_string(&file)?)
+}
+
+pub fn open_with_passphrase(sealed: &str, passphrase: &str) -> Result<Vec<u8>, AppError> {
+    let file: EncryptedFile = serde_json::from_str(sealed)?;
+    if file.version != FILE_VERSION || file.kdf != FileKdf::Argon2id {
+        return Err(store_error("Unsupported secrets export format".to_string()));
+    }
+    let salt = file
+        .salt
+        .as_ref()
+        .ok_or_else(|| store_error("Secrets export is missing its salt".to_string()))?;
+    let key = derive_passphrase_key(passphrase, &decode_base64(salt, "salt")?)?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+
+    open_envelope(&cipher, &file)
+}
+
+impl EncryptedFileSecretStore {
+    pub fn open(path: impl Into<PathBuf>, key: FileSecretKey) -> Result<Self, AppError> {
+        let path = path.into();
+        let existing = read_encrypted_file(&path)?;
+
+        let (kdf, salt, key_bytes) = match key {
+            FileSecretKey::Passphrase(passphrase) => {
+                let salt = match existing.as_ref() {
+                    Some(file) if file.kdf != FileKdf::Argon2id => {
+                        return Err(decrypt_error("Secret file is sealed with a key file, not a passphrase"));
+                    }
+                    Some(file) => file
+                        .salt
+                        .clone()
+                        .ok_or_else(|| store_error("Secret file is missing its salt".to_string()))?,
+                    None => BASE64.encode(random_bytes::<SALT_LEN>()),
+                };
+                let key_bytes = derive_passphrase_key(&passphrase, &decode_base64(&salt, "salt")?)?;
+                (FileKdf::Argon2id, Some(salt), key_bytes)
+            }
+            FileSecretKey::KeyFile(key_path) => {
+                if existing.as_ref().is_some_and(|file| file.kdf != FileKdf::KeyFile) {
+                    return Err(decrypt_error("Secret file is sealed with a passphrase, not a key file"));
+                }
+                (FileKdf::KeyFile, None, load_or_create_key_file(&key_path)?)
+            }
+        };
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        let entries = match existing {
+            Some(file) => Self::decrypt_entries(&cipher, &file)?,
+            None => HashMap::new(),
+        };
+
+        Ok(Self {
+            path,
+            kdf,
+            salt,
+            cipher,
+            entries: RwLock::new(entries),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn decrypt_entries(cipher: &ChaCha20Poly1305, file: &EncryptedFile) -> Result<HashMap<String, String>, AppError> {
+        Ok(serde_json::from_slice(&open_envelope(cipher, file)?)?)
+    }
+
+    fn persist(&self, entries: &HashMap<String, String>) -> Result<(), AppError> {
+        let file = seal_envelope(&self.cipher, self.kdf, self.salt.clone(), &serde_json::to_vec(entries)?)?;
+
+        // WHY: write-then-rename so a crash mid-write never leaves a truncated, undecryptable file.
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+        write_private_file(&tmp_path, &serde_json::to_vec(&file)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    fn read_entries(&self) -> Result<RwLockReadGuard<'_, HashMap<String, String>>, AppError> {
+        self.entries
+            .read()
+            .map_err(|_poisoned| store_error("Secret file lock poisoned".to_string()))
+    }
+
+    fn write_entries(&self) -> Result<RwLockWriteGuard<'_, HashMap<String, String>>, AppError> {
+        self.entries
+            .write()
+            .map_err(|_poisoned| store_error("Secret file lock poisoned".to_string()))
+    }
+}
+
+impl SecretStore for EncryptedFileSecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, AppError> {
+        Ok(self.read_entries()?.get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), AppError> {
+        let mut entries = self.write_entries()?;
+        let mut next = entries.clone();
+        next.insert(key.to_string(), value.to_string());
+        self.persist(&next)?;
+        *entries = next;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), AppError> {
+        let mut entries = self.write_entries()?;
+        if !entries.contains_key(key) {
+            return Ok(());
+        }
+        let mut next = entries.clone();
+        next.remove(key);
+        self.persist(&next)?;
+        *entries = next;
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Option<Vec<String>>, AppError> {
+        let mut keys: Vec<String> = self.read_entries()?.keys().cloned().collect();
+        keys.sort();
+        Ok(Some(keys))
+    }
+}
@@ -0,0 +1,85 @@
+//! Cancellation and progress reporting for long-running repo operations.
+//!
+//! Hosts create a `TaskContext`, hand a clone of its `CancellationToken` to whoever may cancel,
+//! and pass the context to a `*_with_task` repo function.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::app::error::{error_codes, AppError};
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+pub type ProgressCallback = Arc<dyn Fn(TaskProgress) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub struct TaskContext {
+    token: CancellationToken,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl TaskContext {
+    pub fn new(token: CancellationToken) -> Self {
+        Self {
+            token,
+            on_progress: None,
+        }
+    }
+
+    pub fn with_progress(mut self, on_progress: impl Fn(TaskProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(on_progress));
+        self
+    }
+
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.is_cancelled() {
+            return Err(AppError::new(error_codes::TASK_CANCELLED, None));
+        }
+
+        Ok(())
+    }
+
+    pub fn progress(&self, done: usize, total: usize) {
+        if let Some(on_progress) = &self.on_progress {
+            on_progress(TaskProgress { done, total });
+        }
+    }
+
+    /// Reports `done` of `total`, then checks for cancellation before the next unit of work.
+    pub fn step(&self, done: usize, total: usize) -> Result<(), AppError> {
+        self.progress(done, total);
+        self.check()
+    }
+}
@@ -0,0 +1,89 @@
+//! Polls the change feed on a background thread and hands new events to a callback, for hosts
+//! that want push-style notifications (NAPI `subscribeChanges`).
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::app::db::Database;
+use crate::domain::changes::{ChangeEvent, GetChangesParams};
+use crate::repo::changes::get_changes;
+
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Delivers events after `cursor` until stopped or dropped; both wait for the poller to exit.
+pub struct ChangeWatcher {
+    stopped: Arc<(Mutex<bool>, Condvar)>,
+    poller: Option<JoinHandle<()>>,
+}
+
+impl ChangeWatcher {
+    pub fn start(
+        db: Database,
+        cursor: i64,
+        interval: Duration,
+        on_events: impl Fn(Vec<ChangeEvent>) + Send + 'static,
+    ) -> Self {
+        let stopped = Arc::new((Mutex::new(false), Condvar::new()));
+        let poller_stopped = Arc::clone(&stopped);
+        let poller = thread::spawn(move || poll(&db, cursor, interval, &poller_stopped, on_events));
+
+        Self {
+            stopped,
+            poller: Some(poller),
+        }
+    }
+
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for ChangeWatcher {
+    fn drop(&mut self) {
+        let (lock, wake) = &*self.stopped;
+        if let Ok(mut stopped) = lock.lock() {
+            *stopped = true;
+        }
+        wake.notify_all();
+        if let Some(poller) = self.poller.take() {
+            // WHY: The poller only fails by panicking, and the panic has already been reported.
+            poller.join().unwrap_or_default();
+        }
+    }
+}
+
+fn poll(
+    db: &Database,
+    mut cursor: i64,
+    interval: Duration,
+    stopped: &(Mutex<bool>, Condvar),
+    on_events: impl Fn(Vec<ChangeEvent>),
+) {
+    let (lock, wake) = stopped;
+    loop {
+        let mut has_more = false;
+        // WHY: Read errors are transient (a busy or locked file); the next tick retries from the same cursor.
+        if let Ok(page) = get_changes(db, GetChangesParams { cursor, limit: None }) {
+            cursor = page.cursor;
+            has_more = page.has_more;
+            if !page.events.is_empty() {
+                on_events(page.events);
+            }
+        }
+
+        let Ok(guard) = lock.lock() else {
+            return;
+        };
+        if *guard {
+            return;
+        }
+        if has_more {
+            continue;
+        }
+        match wake.wait_timeout_while(guard, interval, |stopped| !*stopped) {
+            Ok((guard, _)) if !*guard => continue,
+            _ => return,
+        }
+    }
+}
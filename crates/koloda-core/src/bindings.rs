@@ -0,0 +1,163 @@
+//! `specta` registry behind the generated TypeScript bindings (`ts` feature).
+//!
+//! Output is `apps/native-electron/src/native-types.ts`; `tests/ts_bindings_tests.rs` fails when it is stale.
+//! Build with `--features ts,http` so the HTTP server types are included, as the NAPI crate does.
+
+use specta::TypeCollection;
+
+use crate::app::{error, init, secrets, task};
+use crate::domain::{
+    ai, algorithms, algorithms_fsrs, cards, changes, conversations, database_check, deck_bundle, decks, lessons,
+    reviews, settings, settings_ai, settings_bundle, settings_hotkeys, settings_interface, settings_learning, sync,
+    template_render, templates, typed_answer,
+};
+use crate::repo::conversations::SetConversationInput;
+
+/// Every IPC-facing type; dependencies are pulled in transitively.
+pub fn type_collection() -> TypeCollection {
+    let mut types = TypeCollection::default();
+    types
+        .register::<ai::AIProfile>()
+        .register::<ai::AISecrets>()
+        .register::<ai::ApiKeySecret>()
+        .register::<ai::AddProfileData>()
+        .register::<ai::UpdateProfileData>()
+        .register::<ai::RemoveProfileData>()
+        .register::<ai::AISecretsStatus>()
+        .register::<ai::AIProfileSecretsHealth>()
+        .register::<ai::AISecretsHealth>()
+        .register::<ai::AISecretsRepair>()
+        .register::<ai::RepairAISecretsData>()
+        .register::<ai::ExportAISecretsData>()
+        .register::<ai::ImportAISecretsData>()
+        .register::<ai::ImportAISecretsResult>()
+        .register::<ai::AISecretsBundle>()
+        .register::<algorithms::Algorithm>()
+        .register::<algorithms::InsertAlgorithmData>()
+        .register::<algorithms::UpdateAlgorithmValues>()
+        .register::<algorithms::UpdateAlgorithmData>()
+        .register::<algorithms::CloneAlgorithmData>()
+        .register::<algorithms::DeleteAlgorithmData>()
+        .register::<algorithms::AlgorithmDeck>()
+        .register::<algorithms_fsrs::AlgorithmFSRS>()
+        .register::<cards::CardContentField>()
+        .register::<cards::Card>()
+        .register::<cards::InsertCardData>()
+        .register::<cards::UpdateCardValues>()
+        .register::<cards::UpdateCardData>()
+        .register::<cards::UpdateCardProgress>()
+        .register::<cards::DeleteCardData>()
+        .register::<cards::DeleteCardsData>()
+        .register::<cards::ResetCardProgressData>()
+        .register::<cards::RebuildCardProgressScope>()
+        .register::<cards::RebuildCardProgressData>()
+        .register::<cards::RebuildCardProgressResult>()
+        .register::<cards::CardFieldMapping>()
+        .register::<cards::MoveCardsData>()
+        .register::<cards::CopyCardsData>()
+        .register::<cards::ConvertCardsTemplateData>()
+        .register::<cards::ConvertCardsTemplateResult>()
+        .register::<cards::ConvertCardsTemplateFailure>()
+        .register::<cards::CardSelection>()
+        .register::<cards::FindReplaceMode>()
+        .register::<cards::FindReplaceData>()
+        .register::<cards::FindReplaceCardChange>()
+        .register::<cards::FindReplaceResult>()
+        .register::<cards::FindDuplicateCardsParams>()
+        .register::<cards::DuplicateCardGroup>()
+        .register::<cards::MergeCardsData>()
+        .register::<cards::AddCardsItemResult>()
+        .register::<cards::AddCardsItemError>()
+        .register::<cards::DuplicatePolicy>()
+        .register::<cards::AddCardsOptions>()
+        .register::<cards::GetCardsParams>()
+        .register::<conversations::Conversation>()
+        .register::<decks::Deck>()
+        .register::<decks::InsertDeckData>()
+        .register::<decks::UpdateDeckValues>()
+        .register::<decks::UpdateDeckData>()
+        .register::<decks::DeleteDeckData>()
+        .register::<deck_bundle::DeckBundle>()
+        .register::<lessons::LessonDeck>()
+        .register::<lessons::LessonsResult>()
+        .register::<lessons::LessonFilters>()
+        .register::<lessons::LessonAmounts>()
+        .register::<lessons::LessonTemplateLayoutItem>()
+        .register::<lessons::LessonTemplate>()
+        .register::<lessons::LessonData>()
+        .register::<lessons::GetLessonDataParams>()
+        .register::<lessons::LessonResultData>()
+        .register::<lessons::GetLessonsParams>()
+        .register::<reviews::Review>()
+        .register::<reviews::InsertReviewData>()
+        .register::<reviews::GetReviewsData>()
+        .register::<reviews::GetReviewTotalsParams>()
+        .register::<reviews::ReviewTotals>()
+        .register::<reviews::TodaysReviewTotalsMeta>()
+        .register::<reviews::TodaysReviewTotals>()
+        .register::<settings::SettingsName>()
+        .register::<settings::SettingsHistoryEntry>()
+        .register::<settings::GetSettingsHistoryParams>()
+        .register::<settings::DiffSettingsParams>()
+        .register::<settings::SettingsDiffEntry>()
+        .register::<settings::RollbackSettingsData>()
+        .register::<settings::Settings>()
+        .register::<settings_ai::AssistantSettings>()
+        .register::<settings_ai::AISettings>()
+        .register::<settings_bundle::SettingsBundle>()
+        .register::<settings_bundle::SettingsBundleSlice>()
+        .register::<settings_bundle::SettingsBundleSliceError>()
+        .register::<settings_bundle::ImportSettingsBundleResult>()
+        .register::<settings_hotkeys::HotkeysSettings>()
+        .register::<settings_interface::InterfaceSettings>()
+        .register::<settings_learning::LearningDefaults>()
+        .register::<settings_learning::LearningSettings>()
+        .register::<settings_learning::DailyLimits>()
+        .register::<settings_learning::CountedDailyLimit>()
+        .register::<settings_learning::LearnAheadLimit>()
+        .register::<templates::Template>()
+        .register::<templates::TemplateContent>()
+        .register::<templates::TemplateField>()
+        .register::<templates::TemplateFieldRules>()
+        .register::<templates::FieldScript>()
+        .register::<templates::TemplateLayoutItem>()
+        .register::<templates::TemplateSides>()
+        .register::<templates::InsertTemplateData>()
+        .register::<templates::UpdateTemplateValues>()
+        .register::<templates::UpdateTemplateData>()
+        .register::<templates::CloneTemplateData>()
+        .register::<templates::DeleteTemplateData>()
+        .register::<templates::TemplateDeck>()
+        .register::<template_render::RenderedCard>()
+        .register::<typed_answer::TypedAnswerOptions>()
+        .register::<typed_answer::CompareTypedAnswerData>()
+        .register::<typed_answer::TypedAnswerDiffKind>()
+        .register::<typed_answer::TypedAnswerDiffPart>()
+        .register::<typed_answer::TypedAnswerComparison>()
+        .register::<changes::ChangeEvent>()
+        .register::<changes::GetChangesParams>()
+        .register::<changes::ChangesPage>()
+        .register::<database_check::DatabaseIssueKind>()
+        .register::<database_check::DatabaseIssue>()
+        .register::<database_check::DatabaseCheckReport>()
+        .register::<database_check::RepairDatabaseData>()
+        .register::<database_check::RepairDatabaseResult>()
+        .register::<sync::SyncOptions>()
+        .register::<sync::SyncReport>()
+        .register::<error::AppError>()
+        .register::<init::DbStatus>()
+        .register::<init::SeedData>()
+        .register::<init::SeedSettings>()
+        .register::<secrets::SecretStoreBackend>()
+        .register::<secrets::SecretStoreOptions>()
+        .register::<task::TaskProgress>()
+        .register::<SetConversationInput>();
+    #[cfg(feature = "http")]
+    types
+        .register::<crate::http::HttpServerOptions>()
+        .register::<crate::http::HttpServerInfo>()
+        .register::<crate::http::AnkiConnectServerOptions>()
+        .register::<crate::http::AnkiConnectServerInfo>();
+
+    types
+}
@@ -3,6 +3,7 @@
 //! Settings JSON stores redacted secrets (`apiKey: null`); real keys live in the keyring via `repo::ai`.
 
 use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 
 use crate::app::error::{error_codes, AppError};
 use crate::domain::time::{deserialize_timestamp, serialize_timestamp};
@@ -17,6 +18,7 @@ pub const AI_PROVIDERS: &[&str] = &[
 ];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct AIProfile {
     pub id: String,
@@ -32,6 +34,7 @@ pub struct AIProfile {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub whitelist_model_ids: Option<Vec<String>>,
     #[serde(serialize_with = "serialize_timestamp", deserialize_with = "deserialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
     pub created_at: i64,
 }
 
@@ -46,6 +49,7 @@ where
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(tag = "provider", rename_all = "camelCase")]
 pub enum AISecrets {
     #[serde(rename = "openrouter")]
@@ -95,11 +99,13 @@ pub enum AISecrets {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 pub struct ApiKeySecret {
     pub api_key: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct AddProfileData {
     pub title: Option<String>,
@@ -118,6 +124,7 @@ where
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateProfileData {
     pub id: String,
@@ -130,11 +137,130 @@ pub struct UpdateProfileData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct RemoveProfileData {
     pub id: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "kebab-case")]
+pub enum AISecretsStatus {
+    Ok,
+    MissingKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct AIProfileSecretsHealth {
+    pub profile_id: String,
+    pub title: Option<String>,
+    pub provider: Option<String>,
+    pub status: AISecretsStatus,
+}
+
+/// Where provider secrets are kept; configured through `app::secrets`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretStoreBackend {
+    #[default]
+    Auto,
+    Keyring,
+    EncryptedFile,
+}
+
+/// Cross-check of AI profiles against the active secret store.
+///
+/// `orphaned_profile_ids` lists stored keys with no matching profile; it is `None` when the
+/// store cannot enumerate its keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct AISecretsHealth {
+    pub backend: SecretStoreBackend,
+    pub profiles: Vec<AIProfileSecretsHealth>,
+    pub orphaned_profile_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+// WHY: Per-variant `rename_all` instead of `rename_all_fields`, which specta ignores.
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum AISecretsRepair {
+    #[serde(rename_all = "camelCase")]
+    Rekey { profile_id: String, api_key: String },
+    #[serde(rename_all = "camelCase")]
+    Purge { profile_id: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct RepairAISecretsData {
+    pub repairs: Vec<AISecretsRepair>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAISecretsData {
+    pub passphrase: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ImportAISecretsData {
+    pub bundle: String,
+    pub passphrase: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ImportAISecretsResult {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Plaintext inside an encrypted secrets export: profile id → API key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct AISecretsBundle {
+    pub keys: BTreeMap<String, String>,
+}
+
+impl RepairAISecretsData {
+    pub fn validate(&self) -> Result<(), AppError> {
+        for repair in &self.repairs {
+            if let AISecretsRepair::Rekey { api_key, .. } = repair {
+                if api_key.trim().is_empty() {
+                    return Err(AppError::new(
+                        error_codes::VALIDATION_SETTINGS_AI_PROVIDERS_API_KEY,
+                        Some("apiKey is required".to_string()),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An export sealed with an empty passphrase is readable by anyone who gets the file.
+pub fn validate_secrets_passphrase(passphrase: &str) -> Result<(), AppError> {
+    if passphrase.trim().is_empty() {
+        return Err(AppError::new(
+            error_codes::VALIDATION_AI_SECRETS_PASSPHRASE,
+            Some("passphrase is required".to_string()),
+        ));
+    }
+    Ok(())
+}
+
 impl AISecrets {
     pub fn provider(&self) -> &'static str {
         match self {
@@ -158,6 +284,16 @@ impl AISecrets {
         }
     }
 
+    pub fn requires_api_key(&self) -> bool {
+        match self {
+            AISecrets::OpenRouter { .. }
+            | AISecrets::OpencodeGo { .. }
+            | AISecrets::OpencodeZen { .. }
+            | AISecrets::OllamaCloud { .. } => true,
+            AISecrets::Ollama { .. } | AISecrets::LmStudio { .. } => false,
+        }
+    }
+
     fn require_api_key_for_input(api_key: &Option<String>, provider: &str) -> Result<(), AppError> {
         match api_key.as_deref().map(str::trim) {
             Some(key) if !key.is_empty() => Ok(()),
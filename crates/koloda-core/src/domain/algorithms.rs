@@ -8,18 +8,22 @@ use crate::domain::common::validate_title;
 use crate::domain::time::{serialize_optional_timestamp, serialize_timestamp};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct Algorithm {
     pub id: i64,
     pub title: String,
     pub content: AlgorithmFSRS,
     #[serde(serialize_with = "serialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
     pub created_at: i64,
     #[serde(default, serialize_with = "serialize_optional_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
     pub updated_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct InsertAlgorithmData {
     pub title: String,
@@ -27,6 +31,7 @@ pub struct InsertAlgorithmData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateAlgorithmValues {
     pub title: String,
@@ -34,6 +39,7 @@ pub struct UpdateAlgorithmValues {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateAlgorithmData {
     pub id: i64,
@@ -41,6 +47,7 @@ pub struct UpdateAlgorithmData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct CloneAlgorithmData {
     pub title: String,
@@ -48,6 +55,7 @@ pub struct CloneAlgorithmData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteAlgorithmData {
     pub id: i64,
@@ -55,6 +63,7 @@ pub struct DeleteAlgorithmData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct AlgorithmDeck {
     pub id: i64,
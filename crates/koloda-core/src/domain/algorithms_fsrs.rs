@@ -3,11 +3,13 @@
 use serde::{Deserialize, Serialize};
 
 use crate::app::error::{error_codes, AppError};
+use crate::domain::versioning::{JsonMigration, VersionedJson};
 
 pub const LEARNING_STEP_UNITS: &[&str] = &["s", "m", "h", "d"];
 pub const FSRS6_WEIGHT_COUNT: usize = 21;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct AlgorithmFSRS {
     #[serde(rename = "type")]
@@ -20,6 +22,10 @@ pub struct AlgorithmFSRS {
     pub maximum_interval: i64,
 }
 
+impl VersionedJson for AlgorithmFSRS {
+    const MIGRATIONS: &'static [JsonMigration] = &[];
+}
+
 impl AlgorithmFSRS {
     pub fn validate(&self) -> Result<(), AppError> {
         if self.algorithm_type != "fsrs" {
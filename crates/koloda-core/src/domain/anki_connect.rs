@@ -0,0 +1,320 @@
+//! AnkiConnect compatibility — note payloads and the `findNotes` query subset.
+//!
+//! A note is one card: its id doubles as the note id, a model is a template and a note's
+//! fields are the template fields matched by title. Tags are accepted and dropped.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::app::error::{error_codes, AppError};
+use crate::domain::cards::{Card, CardContent, CardContentField};
+use crate::domain::markdown::markdown_to_text;
+use crate::domain::templates::{Template, TemplateField};
+
+/// Protocol version reported by the `version` action.
+pub const ANKI_CONNECT_VERSION: u32 = 6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnkiNote {
+    pub deck_name: String,
+    pub model_name: String,
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub options: AnkiNoteOptions,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnkiNoteOptions {
+    #[serde(default)]
+    pub allow_duplicate: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnkiNoteInfo {
+    pub note_id: i64,
+    pub model_name: String,
+    pub tags: Vec<String>,
+    pub fields: HashMap<String, AnkiNoteField>,
+    pub cards: Vec<i64>,
+    /// Last change in seconds, like Anki's `mod`.
+    #[serde(rename = "mod")]
+    pub modified: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnkiNoteField {
+    pub value: String,
+    pub order: usize,
+}
+
+impl AnkiNote {
+    /// Field names match template field titles ignoring case; unknown names are dropped,
+    /// as AnkiConnect does.
+    pub fn content(&self, fields: &[TemplateField]) -> CardContent {
+        self.fields
+            .iter()
+            .filter_map(|(name, value)| {
+                let field = fields
+                    .iter()
+                    .find(|field| field.title.eq_ignore_ascii_case(name.trim()))?;
+                Some((field.id.to_string(), CardContentField { text: value.clone() }))
+            })
+            .collect()
+    }
+}
+
+impl AnkiNoteInfo {
+    pub fn new(card: &Card, template: &Template) -> Self {
+        let fields = template
+            .content
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(order, field)| {
+                let value = card
+                    .content
+                    .get(&field.id.to_string())
+                    .map(|content| content.text.clone())
+                    .unwrap_or_default();
+                (field.title.clone(), AnkiNoteField { value, order })
+            })
+            .collect();
+
+        Self {
+            note_id: card.id,
+            model_name: template.title.clone(),
+            tags: Vec::new(),
+            fields,
+            cards: vec![card.id],
+            modified: card.updated_at.unwrap_or(card.created_at) / 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AnkiQueryTerm {
+    Deck(String),
+    Note(String),
+    Ids(Vec<i64>),
+    Field { name: String, pattern: String },
+    Tag,
+    Text(String),
+}
+
+/// The `findNotes` search subset: space-separated terms that all must match.
+///
+/// Supports `"quoted text"`, `-negation`, `deck:`, `note:`, `nid:` / `cid:` id lists, `tag:`
+/// (never matches; tags are not stored), `field:value` as a whole-field match and bare text
+/// as a substring of any field. Matching ignores case; `*` and `_` are wildcards.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnkiQuery {
+    terms: Vec<(bool, AnkiQueryTerm)>,
+}
+
+impl AnkiQuery {
+    pub fn parse(query: &str) -> Result<Self, AppError> {
+        let mut terms = Vec::new();
+        for (token, is_quoted) in tokenize(query)? {
+            let (negated, token) = match token.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest.to_string()),
+                _ => (false, token),
+            };
+            if !is_quoted && (token.eq_ignore_ascii_case("or") || token.contains(['(', ')'])) {
+                return Err(query_error(format!("Unsupported syntax: {}", token)));
+            }
+            if !is_quoted && token.eq_ignore_ascii_case("and") {
+                continue;
+            }
+            terms.push((negated, parse_term(&token)?));
+        }
+        Ok(Self { terms })
+    }
+
+    pub fn matches(&self, card: &Card, deck_title: &str, template: &Template) -> bool {
+        self.terms
+            .iter()
+            .all(|(negated, term)| term_matches(term, card, deck_title, template) != *negated)
+    }
+
+    /// Whether a card in this deck can match: the `deck:` terms depend on the deck alone.
+    pub fn matches_deck(&self, deck_title: &str) -> bool {
+        self.terms.iter().all(|(negated, term)| match term {
+            AnkiQueryTerm::Deck(pattern) => wildcard_match(pattern, deck_title) != *negated,
+            _ => true,
+        })
+    }
+
+    /// Whether a card using this template can match: `note:` terms must hold, a `field:` term
+    /// needs the field to exist and a plain `tag:` term rules everything out.
+    pub fn matches_template(&self, template: &Template) -> bool {
+        self.terms.iter().all(|(negated, term)| match term {
+            AnkiQueryTerm::Note(pattern) => wildcard_match(pattern, &template.title) != *negated,
+            AnkiQueryTerm::Field { name, .. } if !negated => template
+                .content
+                .fields
+                .iter()
+                .any(|field| field.title.eq_ignore_ascii_case(name)),
+            AnkiQueryTerm::Tag => *negated,
+            _ => true,
+        })
+    }
+
+    /// Card ids every `nid:` / `cid:` term allows, or `None` when the query has no such term.
+    pub fn ids(&self) -> Option<Vec<i64>> {
+        self.terms
+            .iter()
+            .filter_map(|(negated, term)| match term {
+                AnkiQueryTerm::Ids(ids) if !negated => Some(ids),
+                _ => None,
+            })
+            .fold(None, |allowed: Option<Vec<i64>>, ids| {
+                Some(match allowed {
+                    Some(allowed) => allowed.into_iter().filter(|id| ids.contains(id)).collect(),
+                    None => ids.clone(),
+                })
+            })
+    }
+}
+
+/// Splits on whitespace outside double quotes; quotes group but are not kept.
+/// The flag marks tokens that were quoted as a whole.
+fn tokenize(query: &str) -> Result<Vec<(String, bool)>, AppError> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut in_quotes = false;
+    let mut is_quoted = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                is_quoted |= token.is_empty();
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !token.is_empty() {
+                    tokens.push((std::mem::take(&mut token), is_quoted));
+                }
+                is_quoted = false;
+            }
+            c => token.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(query_error("Unterminated quote".to_string()));
+    }
+    if !token.is_empty() {
+        tokens.push((token, is_quoted));
+    }
+    Ok(tokens)
+}
+
+fn parse_term(token: &str) -> Result<AnkiQueryTerm, AppError> {
+    let Some((prefix, value)) = token.split_once(':').filter(|(prefix, _)| !prefix.is_empty()) else {
+        return Ok(AnkiQueryTerm::Text(token.to_string()));
+    };
+
+    Ok(match prefix.to_lowercase().as_str() {
+        "deck" => AnkiQueryTerm::Deck(value.to_string()),
+        "note" => AnkiQueryTerm::Note(value.to_string()),
+        "nid" | "cid" => AnkiQueryTerm::Ids(
+            value
+                .split(',')
+                .map(|id| {
+                    id.trim()
+                        .parse()
+                        .map_err(|e: std::num::ParseIntError| query_error(e.to_string()))
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        "tag" => AnkiQueryTerm::Tag,
+        _ => AnkiQueryTerm::Field {
+            name: prefix.to_string(),
+            pattern: value.to_string(),
+        },
+    })
+}
+
+fn term_matches(term: &AnkiQueryTerm, card: &Card, deck_title: &str, template: &Template) -> bool {
+    // WHY: markdown fields are searched by what the reader sees, not by their markup.
+    let field_text = |field: &TemplateField| {
+        let text = card
+            .content
+            .get(&field.id.to_string())
+            .map(|content| content.text.as_str())
+            .unwrap_or_default();
+        if field.is_markdown() {
+            Cow::Owned(markdown_to_text(text))
+        } else {
+            Cow::Borrowed(text)
+        }
+    };
+
+    match term {
+        AnkiQueryTerm::Deck(pattern) => wildcard_match(pattern, deck_title),
+        AnkiQueryTerm::Note(pattern) => wildcard_match(pattern, &template.title),
+        AnkiQueryTerm::Ids(ids) => ids.contains(&card.id),
+        AnkiQueryTerm::Field { name, pattern } => template
+            .content
+            .fields
+            .iter()
+            .filter(|field| field.title.eq_ignore_ascii_case(name))
+            .any(|field| wildcard_match(pattern, &field_text(field))),
+        AnkiQueryTerm::Tag => false,
+        AnkiQueryTerm::Text(text) => {
+            let pattern = format!("*{}*", text);
+            template
+                .content
+                .fields
+                .iter()
+                .any(|field| wildcard_match(&pattern, &field_text(field)))
+        }
+    }
+}
+
+/// Whole-string, case-insensitive match where `*` is any run and `_` any single character.
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+
+    // INVARIANT: `matched[j]` is whether the pattern prefix read so far matches `text[..j]`.
+    let mut matched = vec![false; text.len() + 1];
+    if let Some(first) = matched.first_mut() {
+        *first = true;
+    }
+    for p in pattern {
+        let mut next = vec![false; text.len() + 1];
+        for (j, is_match) in matched.iter().enumerate() {
+            if !is_match {
+                continue;
+            }
+            match p {
+                '*' => next.iter_mut().skip(j).for_each(|slot| *slot = true),
+                '_' => {
+                    if let Some(slot) = next.get_mut(j + 1) {
+                        *slot = true;
+                    }
+                }
+                p => {
+                    if text.get(j) == Some(&p) {
+                        if let Some(slot) = next.get_mut(j + 1) {
+                            *slot = true;
+                        }
+                    }
+                }
+            }
+        }
+        matched = next;
+    }
+    matched.last().copied().unwrap_or(false)
+}
+
+fn query_error(details: String) -> AppError {
+    AppError::new(error_codes::VALIDATION_ANKI_CONNECT_QUERY, Some(details))
+}
@@ -2,15 +2,20 @@
 //!
 //! `CardState` ints map to lesson/review SQL buckets (see `CardState` below).
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 use crate::app::error::{error_codes, AppError};
+use crate::domain::markdown::{is_markdown_blank, markdown_to_text};
 use crate::domain::progress::{
     validate_difficulty, validate_lapses, validate_learning_steps, validate_reps, validate_scheduled_days,
     validate_stability, validate_state,
 };
-use crate::domain::templates::TemplateField;
+use crate::domain::templates::{TemplateField, TemplateFieldRules};
 use crate::domain::time::{
     default_now, deserialize_optional_timestamp, deserialize_timestamp, serialize_optional_timestamp,
     serialize_timestamp,
@@ -41,15 +46,23 @@ impl CardState {
     pub fn is_valid(state: i32) -> bool {
         (Self::MIN..=Self::MAX).contains(&state)
     }
+
+    pub fn from_i32(state: i32) -> Option<Self> {
+        [Self::New, Self::Learning, Self::Review, Self::Relearning]
+            .into_iter()
+            .find(|candidate| candidate.as_i32() == state)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct CardContentField {
     pub text: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct Card {
     pub id: i64,
@@ -58,6 +71,7 @@ pub struct Card {
     pub content: CardContent,
     pub state: i32,
     #[serde(default, serialize_with = "serialize_optional_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
     pub due_at: Option<i64>,
     pub stability: Option<f64>,
     pub difficulty: Option<f64>,
@@ -66,14 +80,18 @@ pub struct Card {
     pub reps: i32,
     pub lapses: i32,
     #[serde(default, serialize_with = "serialize_optional_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
     pub last_reviewed_at: Option<i64>,
     #[serde(default = "default_now", serialize_with = "serialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
     pub created_at: i64,
     #[serde(default, serialize_with = "serialize_optional_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
     pub updated_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct InsertCardData {
     pub deck_id: i64,
@@ -93,12 +111,14 @@ pub struct InsertCardData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateCardValues {
     pub content: CardContent,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateCardData {
     pub id: i64,
@@ -106,6 +126,7 @@ pub struct UpdateCardData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateCardProgress {
     pub id: i64,
@@ -123,40 +144,245 @@ pub struct UpdateCardProgress {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteCardData {
     pub id: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteCardsData {
     pub ids: Vec<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct ResetCardProgressData {
     pub id: i64,
 }
 
+/// What `id` in `RebuildCardProgressData` names: one card, every card of a deck, or every card
+/// of the decks using an algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "lowercase")]
+pub enum RebuildCardProgressScope {
+    Card,
+    Deck,
+    Algorithm,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct RebuildCardProgressData {
+    pub scope: RebuildCardProgressScope,
+    pub id: i64,
+}
+
+/// `changed` counts the cards whose stored progress differed from the replayed one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct RebuildCardProgressResult {
+    pub total: usize,
+    pub changed: usize,
+}
+
+/// Source → target `TemplateField.id` pair used when cards change template.
+///
+/// Source fields without a pair are dropped; target fields without a pair stay empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct CardFieldMapping {
+    pub source: i64,
+    pub target: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct MoveCardsData {
+    pub ids: Vec<i64>,
+    pub deck_id: i64,
+    #[serde(default)]
+    pub field_mapping: Option<Vec<CardFieldMapping>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct CopyCardsData {
+    pub ids: Vec<i64>,
+    pub deck_id: i64,
+    #[serde(default)]
+    pub field_mapping: Option<Vec<CardFieldMapping>>,
+    #[serde(default)]
+    pub reset_progress: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertCardsTemplateData {
+    pub ids: Vec<i64>,
+    pub template_id: i64,
+    pub field_mapping: Vec<CardFieldMapping>,
+}
+
+/// Outcome of a template conversion.
+///
+/// INVARIANT: non-empty `failed` means nothing was written — `converted` is then empty.
+/// `unlocked_template_ids` lists former templates that no card references any more.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertCardsTemplateResult {
+    pub converted: Vec<i64>,
+    pub failed: Vec<ConvertCardsTemplateFailure>,
+    pub unlocked_template_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertCardsTemplateFailure {
+    pub card_id: i64,
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+/// Cards targeted by bulk operations.
+///
+/// Both filters narrow the selection (AND); at least one must be non-empty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct CardSelection {
+    #[serde(default)]
+    pub deck_ids: Option<Vec<i64>>,
+    #[serde(default)]
+    pub card_ids: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "lowercase")]
+pub enum FindReplaceMode {
+    Preview,
+    Apply,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct FindReplaceData {
+    pub selection: CardSelection,
+    #[serde(default)]
+    pub field_id: Option<i64>,
+    pub pattern: String,
+    #[serde(default)]
+    pub is_regex: bool,
+    pub replacement: String,
+    pub mode: FindReplaceMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct FindReplaceCardChange {
+    pub card_id: i64,
+    pub before: CardContent,
+    pub after: CardContent,
+}
+
+/// Outcome of a find & replace.
+///
+/// INVARIANT: non-empty `failed` means nothing was written — `is_applied` is then false,
+/// while `changes` still lists the replacements that would have passed validation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct FindReplaceResult {
+    pub changes: Vec<FindReplaceCardChange>,
+    pub failed: Vec<FindReplaceFailure>,
+    pub is_applied: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct FindReplaceFailure {
+    pub card_id: i64,
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum FindReplaceMatcher {
+    Literal(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct FindDuplicateCardsParams {
+    pub selection: CardSelection,
+    #[serde(default)]
+    pub field_ids: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCardGroup {
+    pub template_id: i64,
+    pub key: String,
+    pub card_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct MergeCardsData {
+    pub keep_id: i64,
+    pub merge_ids: Vec<i64>,
+}
+
 /// Per-item batch-add result — mirrors `@koloda/srs` `InsertCardsResponse`.
 ///
 /// Success items omit `error` (serialize as `{}`); failures keep the structured
 /// `code`/`details` so consumers can translate codes instead of parsing strings.
+/// `duplicate_of` is set only when `DuplicatePolicy::Skip` left the item out; `card_id`
+/// only when the item was inserted.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct AddCardsItemResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<AddCardsItemError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_id: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct AddCardsItemError {
     pub code: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub existing_card_id: Option<i64>,
 }
 
 impl From<AppError> for AddCardsItemError {
@@ -164,13 +390,40 @@ impl From<AppError> for AddCardsItemError {
         Self {
             code: err.code,
             details: err.details,
+            existing_card_id: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    #[default]
+    Allow,
+    Reject,
+    Skip,
+}
+
+/// Duplicate handling for `add_cards`, scoped to each item's target deck.
+///
+/// The key is the [`duplicate_key`] of `duplicate_field_id`, or of the template's first
+/// field (its sort field) when unset; only cards of the same template are compared, and an id
+/// the item's template lacks fails that item. Earlier items of the same batch count as existing cards.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct AddCardsOptions {
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
+    #[serde(default)]
+    pub duplicate_field_id: Option<i64>,
+}
+
 pub type AddCardsResponse = Vec<AddCardsItemResult>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct GetCardsParams {
     pub deck_id: i64,
@@ -178,7 +431,11 @@ pub struct GetCardsParams {
 
 impl InsertCardData {
     pub fn validate(&self, template_fields: &[TemplateField]) -> Result<(), AppError> {
-        validate_content(&self.content, template_fields)?;
+        self.validate_with(&ContentValidator::new(template_fields))
+    }
+
+    pub fn validate_with(&self, validator: &ContentValidator<'_>) -> Result<(), AppError> {
+        validator.validate(&self.content)?;
         validate_state(self.state.unwrap_or(0), error_codes::VALIDATION_CARDS_PROGRESS_STATE)?;
         if let Some(stability) = self.stability {
             validate_stability(stability, error_codes::VALIDATION_CARDS_PROGRESS_STABILITY)?;
@@ -225,25 +482,351 @@ impl UpdateCardProgress {
     }
 }
 
-fn validate_content(content: &CardContent, template_fields: &[TemplateField]) -> Result<(), AppError> {
-    for field in template_fields {
-        if field.is_required {
-            let field_key = field.id.to_string();
-            let field_value = content.get(&field_key).ok_or_else(|| {
-                AppError::new(
-                    error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY,
-                    Some(format!("Field id: {}", field.id)),
-                )
-            })?;
+impl CardSelection {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let has_deck_ids = self.deck_ids.as_ref().is_some_and(|ids| !ids.is_empty());
+        let has_card_ids = self.card_ids.as_ref().is_some_and(|ids| !ids.is_empty());
+        if !has_deck_ids && !has_card_ids {
+            return Err(AppError::new(error_codes::VALIDATION_CARDS_SELECTION_EMPTY, None));
+        }
+        Ok(())
+    }
+}
+
+impl FindReplaceData {
+    pub fn validate(&self) -> Result<FindReplaceMatcher, AppError> {
+        self.selection.validate()?;
+
+        if self.pattern.is_empty() {
+            return Err(AppError::new(
+                error_codes::VALIDATION_CARDS_FIND_REPLACE_PATTERN_EMPTY,
+                None,
+            ));
+        }
+
+        if !self.is_regex {
+            return Ok(FindReplaceMatcher::Literal(self.pattern.clone()));
+        }
+
+        Regex::new(&self.pattern).map(FindReplaceMatcher::Regex).map_err(|e| {
+            AppError::new(
+                error_codes::VALIDATION_CARDS_FIND_REPLACE_PATTERN_INVALID,
+                Some(e.to_string()),
+            )
+        })
+    }
+}
+
+/// Applies `matcher` to one field (`field_id`) or every field; `None` when nothing matched.
+///
+/// Regex replacements expand `$1` / `${name}` capture references; literal ones are verbatim.
+pub fn replace_in_content(
+    content: &CardContent,
+    field_id: Option<i64>,
+    matcher: &FindReplaceMatcher,
+    replacement: &str,
+) -> Option<CardContent> {
+    let field_key = field_id.map(|id| id.to_string());
+    let mut is_changed = false;
+
+    let replaced = content
+        .iter()
+        .map(|(key, field)| {
+            if field_key.as_ref().is_some_and(|k| k != key) {
+                return (key.clone(), field.clone());
+            }
+
+            let text = match matcher {
+                FindReplaceMatcher::Literal(pattern) => field.text.replace(pattern.as_str(), replacement),
+                FindReplaceMatcher::Regex(regex) => regex.replace_all(&field.text, replacement).into_owned(),
+            };
+            if text != field.text {
+                is_changed = true;
+            }
+
+            (key.clone(), CardContentField { text })
+        })
+        .collect();
+
+    is_changed.then_some(replaced)
+}
+
+impl MergeCardsData {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.merge_ids.is_empty() || self.merge_ids.contains(&self.keep_id) {
+            return Err(AppError::new(
+                error_codes::VALIDATION_CARDS_MERGE_SOURCES,
+                Some(format!("Keep id: {}", self.keep_id)),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Comparison form of field text: diacritics stripped (NFD minus combining marks),
+/// control characters dropped, lowercased, whitespace runs collapsed to one space and trimmed.
+pub fn normalize_card_text(text: &str) -> String {
+    let folded: String = text
+        .nfd()
+        .filter(|c| !is_combining_mark(*c) && (!c.is_control() || c.is_whitespace()))
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A field's `rules` with their regexes built once.
+struct CompiledFieldRules<'a> {
+    rules: &'a TemplateFieldRules,
+    pattern: Option<Result<Regex, regex::Error>>,
+    scripts: Option<Result<Regex, regex::Error>>,
+}
+
+impl<'a> CompiledFieldRules<'a> {
+    fn new(rules: &'a TemplateFieldRules) -> Self {
+        Self {
+            rules,
+            pattern: rules.pattern_regex(),
+            scripts: rules.scripts_regex(),
+        }
+    }
+
+    fn validate(&self, field_id: i64, text: &str) -> Result<(), AppError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        let failed = |code: &str| AppError::new(code, Some(format!("Field id: {}", field_id)));
+
+        if self
+            .rules
+            .max_length
+            .is_some_and(|max_length| text.chars().count() > max_length as usize)
+        {
+            return Err(failed(error_codes::VALIDATION_CARDS_CONTENT_FIELD_TOO_LONG));
+        }
+        // WHY: rules are checked when the template is saved; a regex that still fails to build
+        // rejects the value rather than letting it through unchecked.
+        if let Some(pattern) = &self.pattern {
+            if !pattern.as_ref().is_ok_and(|pattern| pattern.is_match(text)) {
+                return Err(failed(error_codes::VALIDATION_CARDS_CONTENT_FIELD_PATTERN));
+            }
+        }
+        if let Some(scripts) = &self.scripts {
+            if !scripts.as_ref().is_ok_and(|scripts| scripts.is_match(text)) {
+                return Err(failed(error_codes::VALIDATION_CARDS_CONTENT_FIELD_SCRIPT));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `content` with markdown fields reduced to their plain text, for search and duplicate checks.
+pub fn plain_text_content(content: &CardContent, fields: &[TemplateField]) -> CardContent {
+    content
+        .iter()
+        .map(|(key, value)| {
+            let is_markdown = fields
+                .iter()
+                .any(|field| field.is_markdown() && field.id.to_string() == *key);
+            let text = if is_markdown {
+                markdown_to_text(&value.text)
+            } else {
+                value.text.clone()
+            };
+            (key.clone(), CardContentField { text })
+        })
+        .collect()
+}
+
+/// `Title: text` lines in template field order, skipping blank fields — the card as AI context.
+pub fn card_plain_text(content: &CardContent, fields: &[TemplateField]) -> String {
+    let plain = plain_text_content(content, fields);
+    fields
+        .iter()
+        .filter_map(|field| {
+            let text = plain.get(&field.id.to_string())?.text.trim();
+            (!text.is_empty()).then(|| format!("{}: {}", field.title, text))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Duplicate-detection key over `field_ids` (every content field when `None`).
+///
+/// `None` when all compared fields normalize to empty — blank cards are not duplicates.
+pub fn duplicate_key(content: &CardContent, field_ids: Option<&[i64]>) -> Option<String> {
+    let mut ids: Vec<i64> = match field_ids {
+        Some(ids) => ids.to_vec(),
+        None => content.keys().filter_map(|key| key.parse::<i64>().ok()).collect(),
+    };
+    ids.sort_unstable();
+    ids.dedup();
+
+    let parts: Vec<String> = ids
+        .into_iter()
+        .map(|id| {
+            let text = content
+                .get(&id.to_string())
+                .map(|f| f.text.as_str())
+                .unwrap_or_default();
+            format!("{id}={}", normalize_card_text(text))
+        })
+        .collect();
+
+    if parts.iter().all(|part| part.ends_with('=')) {
+        return None;
+    }
+
+    // WHY: U+001F (unit separator) is stripped by normalization, so keys of
+    // different field splits ("a b" + "c" vs "a" + "b c") never collide.
+    Some(parts.join("\u{1f}"))
+}
+
+/// Groups `(card id, template id, content)` triples sharing a template and a [`duplicate_key`];
+/// singletons are dropped.
+///
+/// Field ids only mean something within one template, so cards of different templates never group.
+/// Groups and their ids keep the input order of each group's first card.
+pub fn group_duplicate_cards<'a>(
+    cards: impl IntoIterator<Item = (i64, i64, &'a CardContent)>,
+    field_ids: Option<&[i64]>,
+) -> Vec<DuplicateCardGroup> {
+    let mut groups: Vec<DuplicateCardGroup> = Vec::new();
+    let mut index_by_key: HashMap<(i64, String), usize> = HashMap::new();
 
-            if field_value.text.is_empty() {
-                return Err(AppError::new(
-                    error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY,
-                    Some(format!("Field id: {}", field.id)),
-                ));
+    for (id, template_id, content) in cards {
+        let Some(key) = duplicate_key(content, field_ids) else {
+            continue;
+        };
+        match index_by_key.entry((template_id, key)) {
+            Entry::Occupied(entry) => {
+                if let Some(group) = groups.get_mut(*entry.get()) {
+                    group.card_ids.push(id);
+                }
+            }
+            Entry::Vacant(entry) => {
+                groups.push(DuplicateCardGroup {
+                    template_id,
+                    key: entry.key().1.clone(),
+                    card_ids: vec![id],
+                });
+                entry.insert(groups.len() - 1);
             }
         }
     }
 
+    groups.retain(|group| group.card_ids.len() > 1);
+    groups
+}
+
+/// Rewrites `content` keys from source to target field ids.
+///
+/// `None` keeps the content as-is (same template on both sides).
+pub fn remap_content(content: &CardContent, mapping: Option<&[CardFieldMapping]>) -> CardContent {
+    let Some(mapping) = mapping else {
+        return content.clone();
+    };
+
+    mapping
+        .iter()
+        .filter_map(|pair| {
+            content
+                .get(&pair.source.to_string())
+                .map(|field| (pair.target.to_string(), field.clone()))
+        })
+        .collect()
+}
+
+pub fn validate_field_mapping(
+    mapping: &[CardFieldMapping],
+    source_fields: &[TemplateField],
+    target_fields: &[TemplateField],
+) -> Result<(), AppError> {
+    let mut seen_targets = HashSet::new();
+
+    for pair in mapping {
+        if !source_fields.iter().any(|f| f.id == pair.source) {
+            return Err(AppError::new(
+                error_codes::VALIDATION_CARDS_FIELD_MAPPING_INVALID,
+                Some(format!("Unknown source field id: {}", pair.source)),
+            ));
+        }
+        if !target_fields.iter().any(|f| f.id == pair.target) {
+            return Err(AppError::new(
+                error_codes::VALIDATION_CARDS_FIELD_MAPPING_INVALID,
+                Some(format!("Unknown target field id: {}", pair.target)),
+            ));
+        }
+        if !seen_targets.insert(pair.target) {
+            return Err(AppError::new(
+                error_codes::VALIDATION_CARDS_FIELD_MAPPING_INVALID,
+                Some(format!("Duplicate target field id: {}", pair.target)),
+            ));
+        }
+    }
+
     Ok(())
 }
+
+/// Checks required fields and each field's `rules`, except `unique_in_deck`, which needs the deck.
+pub fn validate_content(content: &CardContent, template_fields: &[TemplateField]) -> Result<(), AppError> {
+    ContentValidator::new(template_fields).validate(content)
+}
+
+/// `validate_content` for one template, with the rule regexes built once for a batch of cards.
+pub struct ContentValidator<'a> {
+    fields: &'a [TemplateField],
+    rules: HashMap<i64, CompiledFieldRules<'a>>,
+}
+
+impl<'a> ContentValidator<'a> {
+    pub fn new(fields: &'a [TemplateField]) -> Self {
+        let rules = fields
+            .iter()
+            .filter_map(|field| Some((field.id, CompiledFieldRules::new(field.rules.as_ref()?))))
+            .collect();
+        Self { fields, rules }
+    }
+
+    pub fn fields(&self) -> &'a [TemplateField] {
+        self.fields
+    }
+
+    pub fn validate(&self, content: &CardContent) -> Result<(), AppError> {
+        for field in self.fields {
+            if let (Some(rules), Some(value)) = (self.rules.get(&field.id), content.get(&field.id.to_string())) {
+                let text = if field.is_markdown() {
+                    markdown_to_text(&value.text)
+                } else {
+                    value.text.clone()
+                };
+                rules.validate(field.id, &text)?;
+            }
+
+            if field.is_required {
+                let field_key = field.id.to_string();
+                let field_value = content.get(&field_key).ok_or_else(|| {
+                    AppError::new(
+                        error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY,
+                        Some(format!("Field id: {}", field.id)),
+                    )
+                })?;
+
+                let is_blank = if field.is_markdown() {
+                    is_markdown_blank(&field_value.text)
+                } else {
+                    field_value.text.is_empty()
+                };
+                if is_blank {
+                    return Err(AppError::new(
+                        error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY,
+                        Some(format!("Field id: {}", field.id)),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! Change feed — one event per row written, in write order, for hosts that react to changes
+//! instead of re-querying.
+//!
+//! Recorded by triggers (migration V7), so every write path is covered: lessons show up as
+//! `card` updates and `review` inserts, sync pulls as the rows they touch.
+//!
+//! The feed keeps its newest 10 000 events (migration V9); a cursor older than that resumes at
+//! the oldest kept event.
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[strum(serialize_all = "kebab_case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeEntity {
+    Algorithm,
+    Template,
+    Deck,
+    Card,
+    Review,
+    Settings,
+    Conversation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[strum(serialize_all = "kebab_case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEvent {
+    /// Feed position; pass the last one seen as the next `cursor`.
+    pub id: i64,
+    pub entity: ChangeEntity,
+    /// Row id as text; conversation ids are strings.
+    pub entity_id: String,
+    pub operation: ChangeOperation,
+    /// Epoch ms.
+    pub changed_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct GetChangesParams {
+    /// Events after this position; `0` reads from the start.
+    #[serde(default)]
+    pub cursor: i64,
+    /// Defaults to `DEFAULT_CHANGES_LIMIT`.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ChangesPage {
+    pub events: Vec<ChangeEvent>,
+    /// Position after this page; unchanged when there were no events.
+    pub cursor: i64,
+    pub has_more: bool,
+}
+
+pub const DEFAULT_CHANGES_LIMIT: u32 = 500;
@@ -0,0 +1,75 @@
+//! Database checks — SQLite integrity plus the domain rules the schema cannot express.
+//!
+//! `repo::database_check::check_database` reports every issue; `repair_database` applies the
+//! fixes for the kinds it is given and reports what is left.
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+/// The fix `repair_database` applies is noted per kind; kinds without one are reported only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[strum(serialize_all = "kebab_case")]
+#[serde(rename_all = "kebab-case")]
+pub enum DatabaseIssueKind {
+    /// `PRAGMA integrity_check` message.
+    Integrity,
+    /// `PRAGMA foreign_key_check` row not covered by a more specific kind.
+    ForeignKey,
+    /// Template content that does not parse.
+    TemplateContent,
+    /// Fix: drop layout items whose field no longer exists, when any item remains.
+    TemplateLayout,
+    /// Card content that does not parse or fails `validate_content` for its template.
+    CardContent,
+    /// A reviewed state without `due_at`. Fix: rebuild progress from the card's reviews; only
+    /// fixable when the card has a graded, non-ignored review to rebuild from.
+    CardProgress,
+    /// Fix: delete the review.
+    OrphanReview,
+    /// Fix: point the deck at the oldest algorithm.
+    DeckAlgorithm,
+    /// Fix: restore the latest history entry that still normalizes.
+    Settings,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseIssue {
+    pub kind: DatabaseIssueKind,
+    /// Empty for `integrity` messages, which name their own location.
+    pub table: String,
+    pub row_id: Option<i64>,
+    pub details: String,
+    pub is_fixable: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseCheckReport {
+    pub issues: Vec<DatabaseIssue>,
+}
+
+impl DatabaseCheckReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct RepairDatabaseData {
+    pub kinds: Vec<DatabaseIssueKind>,
+}
+
+/// `report` is a fresh check after the fixes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct RepairDatabaseResult {
+    pub fixed: usize,
+    pub report: DatabaseCheckReport,
+}
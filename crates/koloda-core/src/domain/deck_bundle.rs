@@ -0,0 +1,155 @@
+//! Portable deck bundle — one deck with its algorithm preset, template and cards.
+//!
+//! Cards keep their FSRS progress; the review log is not exported.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app::error::{error_codes, AppError};
+use crate::domain::algorithms::InsertAlgorithmData;
+use crate::domain::algorithms_fsrs::AlgorithmFSRS;
+use crate::domain::cards::{Card, CardContent, InsertCardData};
+use crate::domain::common::validate_title;
+use crate::domain::templates::{InsertTemplateData, TemplateContent};
+use crate::domain::time::{
+    deserialize_optional_timestamp, deserialize_timestamp, serialize_optional_timestamp, serialize_timestamp,
+};
+use crate::domain::versioning::VersionedJson;
+
+pub const DECK_BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct DeckBundle {
+    pub version: u32,
+    #[serde(serialize_with = "serialize_timestamp", deserialize_with = "deserialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
+    pub exported_at: i64,
+    pub title: String,
+    pub algorithm: DeckBundlePreset,
+    pub template: DeckBundlePreset,
+    #[serde(default)]
+    pub cards: Vec<DeckBundleCard>,
+}
+
+/// `content` is the versioned JSON stored in the `algorithms` / `templates` row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct DeckBundlePreset {
+    pub title: String,
+    pub content: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct DeckBundleCard {
+    pub content: CardContent,
+    #[serde(default)]
+    pub state: i32,
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_timestamp",
+        deserialize_with = "deserialize_optional_timestamp"
+    )]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
+    pub due_at: Option<i64>,
+    #[serde(default)]
+    pub stability: Option<f64>,
+    #[serde(default)]
+    pub difficulty: Option<f64>,
+    #[serde(default)]
+    pub scheduled_days: i32,
+    #[serde(default)]
+    pub learning_steps: i32,
+    #[serde(default)]
+    pub reps: i32,
+    #[serde(default)]
+    pub lapses: i32,
+    #[serde(
+        default,
+        serialize_with = "serialize_optional_timestamp",
+        deserialize_with = "deserialize_optional_timestamp"
+    )]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
+    pub last_reviewed_at: Option<i64>,
+}
+
+impl From<Card> for DeckBundleCard {
+    fn from(card: Card) -> Self {
+        Self {
+            content: card.content,
+            state: card.state,
+            due_at: card.due_at,
+            stability: card.stability,
+            difficulty: card.difficulty,
+            scheduled_days: card.scheduled_days,
+            learning_steps: card.learning_steps,
+            reps: card.reps,
+            lapses: card.lapses,
+            last_reviewed_at: card.last_reviewed_at,
+        }
+    }
+}
+
+impl DeckBundleCard {
+    pub fn into_insert(self, deck_id: i64, template_id: i64) -> InsertCardData {
+        InsertCardData {
+            deck_id,
+            template_id,
+            content: self.content,
+            state: Some(self.state),
+            due_at: self.due_at,
+            stability: self.stability,
+            difficulty: self.difficulty,
+            scheduled_days: Some(self.scheduled_days),
+            learning_steps: Some(self.learning_steps),
+            reps: Some(self.reps),
+            lapses: Some(self.lapses),
+            last_reviewed_at: self.last_reviewed_at,
+        }
+    }
+}
+
+/// Bundle contents that passed validation; cards are checked against the template on import.
+#[derive(Debug, Clone)]
+pub struct ParsedDeckBundle {
+    pub title: String,
+    pub algorithm: InsertAlgorithmData,
+    pub template: InsertTemplateData,
+    pub cards: Vec<DeckBundleCard>,
+}
+
+impl DeckBundle {
+    pub fn parse(self) -> Result<ParsedDeckBundle, AppError> {
+        if self.version != DECK_BUNDLE_VERSION {
+            return Err(AppError::new(
+                error_codes::VALIDATION_DECK_BUNDLE_VERSION,
+                Some(format!("Unsupported deck bundle version: {}", self.version)),
+            ));
+        }
+
+        let algorithm = InsertAlgorithmData {
+            title: self.algorithm.title,
+            content: AlgorithmFSRS::from_versioned(self.algorithm.content)?,
+        };
+        algorithm.validate()?;
+
+        let template = InsertTemplateData {
+            title: self.template.title,
+            content: TemplateContent::from_versioned(self.template.content)?,
+        };
+        template.validate()?;
+
+        validate_title(&self.title)?;
+
+        Ok(ParsedDeckBundle {
+            title: self.title,
+            algorithm,
+            template,
+            cards: self.cards,
+        })
+    }
+}
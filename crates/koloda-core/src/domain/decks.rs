@@ -7,6 +7,7 @@ use crate::domain::common::validate_title;
 use crate::domain::time::{serialize_optional_timestamp, serialize_timestamp};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct Deck {
     pub id: i64,
@@ -14,12 +15,15 @@ pub struct Deck {
     pub algorithm_id: i64,
     pub template_id: i64,
     #[serde(serialize_with = "serialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
     pub created_at: i64,
     #[serde(default, serialize_with = "serialize_optional_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
     pub updated_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct InsertDeckData {
     pub title: String,
@@ -28,6 +32,7 @@ pub struct InsertDeckData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateDeckValues {
     pub title: String,
@@ -36,6 +41,7 @@ pub struct UpdateDeckValues {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateDeckData {
     pub id: i64,
@@ -43,6 +49,7 @@ pub struct UpdateDeckData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteDeckData {
     pub id: i64,
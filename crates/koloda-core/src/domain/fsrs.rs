@@ -0,0 +1,366 @@
+//! FSRS-6 scheduler — a port of the `ts-fsrs` `repeat` the lesson UI grades with.
+//!
+//! Fuzz is never applied, so `is_fuzz_enabled` presets get the unfuzzed `ts-fsrs` interval.
+
+use crate::app::error::{error_codes, AppError};
+use crate::domain::algorithms_fsrs::{AlgorithmFSRS, FSRS6_WEIGHT_COUNT};
+use crate::domain::cards::{Card, CardState, UpdateCardProgress};
+use crate::domain::lessons::LessonResultData;
+use crate::domain::progress::DIFFICULTY_MAX;
+use crate::domain::reviews::{InsertReviewData, Review};
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+const STABILITY_MIN: f64 = 0.001;
+const DIFFICULTY_FLOOR: f64 = 1.0;
+
+/// Grade buttons, numbered like `ts-fsrs` `Rating` and stored as `reviews.rating`.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rating {
+    Again = 1,
+    Hard = 2,
+    Good = 3,
+    Easy = 4,
+}
+
+impl Rating {
+    pub const ALL: [Rating; 4] = [Rating::Again, Rating::Hard, Rating::Good, Rating::Easy];
+
+    pub const fn as_i32(self) -> i32 {
+        self as i32
+    }
+
+    pub fn from_i32(rating: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|candidate| candidate.as_i32() == rating)
+    }
+
+    fn value(self) -> f64 {
+        f64::from(self.as_i32())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FsrsScheduler {
+    weights: [f64; FSRS6_WEIGHT_COUNT],
+    retention: f64,
+    learning_steps: Vec<i64>,
+    relearning_steps: Vec<i64>,
+    maximum_interval: i64,
+    decay: f64,
+    factor: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MemoryState {
+    stability: f64,
+    difficulty: f64,
+}
+
+impl FsrsScheduler {
+    pub fn new(algorithm: &AlgorithmFSRS) -> Result<Self, AppError> {
+        algorithm.validate()?;
+
+        let mut weights = [0.0; FSRS6_WEIGHT_COUNT];
+        for (weight, part) in weights.iter_mut().zip(algorithm.weights.split(',')) {
+            *weight = part.trim().parse().map_err(|e: std::num::ParseFloatError| {
+                AppError::new(error_codes::VALIDATION_ALGORITHM_FSRS_WEIGHTS, Some(e.to_string()))
+            })?;
+        }
+
+        let decay = -weights[20];
+        if decay >= 0.0 {
+            return Err(AppError::new(
+                error_codes::VALIDATION_ALGORITHM_FSRS_WEIGHTS,
+                Some("Decay weight must be positive".to_string()),
+            ));
+        }
+
+        Ok(Self {
+            weights,
+            retention: algorithm.retention / 100.0,
+            learning_steps: algorithm.learning_steps.iter().map(step_ms).collect(),
+            relearning_steps: algorithm.relearning_steps.iter().map(step_ms).collect(),
+            maximum_interval: algorithm.maximum_interval,
+            decay,
+            factor: 0.9f64.powf(1.0 / decay) - 1.0,
+        })
+    }
+
+    /// Grades `card` at `now` (epoch ms). The review row logs the pre-review progress, as
+    /// `ts-fsrs` does; its `time` is left at 0 for the caller to fill in.
+    pub fn repeat(&self, card: &Card, rating: Rating, now: i64) -> LessonResultData {
+        let state = CardState::from_i32(card.state).unwrap_or(CardState::New);
+        let previous = MemoryState {
+            stability: card.stability.unwrap_or(0.0).max(STABILITY_MIN),
+            difficulty: card.difficulty.unwrap_or(0.0).clamp(DIFFICULTY_FLOOR, DIFFICULTY_MAX),
+        };
+        let elapsed_days = card
+            .last_reviewed_at
+            .map(|last| (now.div_euclid(DAY_MS) - last.div_euclid(DAY_MS)).max(0))
+            .unwrap_or(0);
+
+        let mut progress = UpdateCardProgress {
+            id: card.id,
+            state: state.as_i32(),
+            due_at: now,
+            stability: 0.0,
+            difficulty: 0.0,
+            scheduled_days: 0,
+            learning_steps: 0,
+            reps: card.reps.saturating_add(1),
+            lapses: card.lapses,
+            last_reviewed_at: Some(now),
+        };
+
+        let memory = match state {
+            CardState::New => self.init_state(rating),
+            _ => self.next_state(previous, elapsed_days, rating),
+        };
+        progress.stability = memory.stability;
+        progress.difficulty = memory.difficulty;
+
+        let (steps, step_state) = match state {
+            CardState::New | CardState::Learning => (&self.learning_steps, CardState::Learning),
+            CardState::Review | CardState::Relearning => (&self.relearning_steps, CardState::Relearning),
+        };
+
+        match state {
+            CardState::Review if rating != Rating::Again => {
+                let interval = self.review_interval(previous, elapsed_days, rating);
+                schedule_days(&mut progress, CardState::Review, interval, now);
+            }
+            CardState::Review => {
+                progress.lapses = card.lapses.saturating_add(1);
+                match steps.first() {
+                    Some(step) => schedule_step(&mut progress, step_state, *step, 0, now),
+                    None => schedule_days(
+                        &mut progress,
+                        CardState::Review,
+                        self.next_interval(memory.stability),
+                        now,
+                    ),
+                }
+            }
+            _ => match learning_step(steps, card.learning_steps, rating) {
+                Some((step, next_step)) => schedule_step(&mut progress, step_state, step, next_step, now),
+                None => schedule_days(
+                    &mut progress,
+                    CardState::Review,
+                    self.next_interval(memory.stability),
+                    now,
+                ),
+            },
+        }
+
+        LessonResultData {
+            review: InsertReviewData {
+                card_id: card.id,
+                rating: rating.as_i32(),
+                state: state.as_i32(),
+                due_at: card.last_reviewed_at.or(card.due_at),
+                stability: card.stability.unwrap_or(0.0),
+                difficulty: card.difficulty.unwrap_or(0.0),
+                scheduled_days: card.scheduled_days,
+                learning_steps: card.learning_steps,
+                time: 0,
+                is_ignored: false,
+                typed_answer: None,
+                typed_score: None,
+            },
+            card: progress,
+        }
+    }
+
+    /// `card` with its progress rebuilt by grading its non-ignored `reviews` from new, oldest
+    /// first, each at its `created_at`. Reviews without a grade (e.g. manual rescheduling) are skipped.
+    /// `None` when no review is left to grade: there is nothing to rebuild from, and resetting to
+    /// new would wipe progress that came from elsewhere (e.g. an import).
+    pub fn replay(&self, card: &Card, reviews: &[Review]) -> Option<Card> {
+        let mut graded: Vec<(&Review, Rating)> = reviews
+            .iter()
+            .filter(|review| !review.is_ignored)
+            .filter_map(|review| Rating::from_i32(review.rating).map(|rating| (review, rating)))
+            .collect();
+        if graded.is_empty() {
+            return None;
+        }
+        graded.sort_by_key(|(review, _)| (review.created_at, review.id));
+
+        let mut replayed = Card {
+            state: CardState::New.as_i32(),
+            due_at: None,
+            stability: Some(0.0),
+            difficulty: Some(0.0),
+            scheduled_days: 0,
+            learning_steps: 0,
+            reps: 0,
+            lapses: 0,
+            last_reviewed_at: None,
+            ..card.clone()
+        };
+        for (review, rating) in graded {
+            let progress = self.repeat(&replayed, rating, review.created_at).card;
+            replayed.state = progress.state;
+            replayed.due_at = Some(progress.due_at);
+            replayed.stability = Some(progress.stability);
+            replayed.difficulty = Some(progress.difficulty);
+            replayed.scheduled_days = progress.scheduled_days;
+            replayed.learning_steps = progress.learning_steps;
+            replayed.reps = progress.reps;
+            replayed.lapses = progress.lapses;
+            replayed.last_reviewed_at = progress.last_reviewed_at;
+        }
+        Some(replayed)
+    }
+
+    // WHY: `ts-fsrs` computes all three passing intervals and forces hard < good < easy.
+    fn review_interval(&self, previous: MemoryState, elapsed_days: i64, rating: Rating) -> i64 {
+        let interval = |rating| self.next_interval(self.next_state(previous, elapsed_days, rating).stability);
+        let hard = interval(Rating::Hard);
+        let good = interval(Rating::Good);
+        let easy = interval(Rating::Easy);
+
+        let hard = hard.min(good);
+        let good = good.max(hard + 1);
+        let easy = easy.max(good + 1);
+        match rating {
+            Rating::Hard => hard,
+            Rating::Easy => easy,
+            Rating::Again | Rating::Good => good,
+        }
+    }
+
+    fn init_state(&self, rating: Rating) -> MemoryState {
+        MemoryState {
+            stability: self.weight(rating.as_i32() - 1).max(STABILITY_MIN),
+            difficulty: self.init_difficulty(rating).clamp(DIFFICULTY_FLOOR, DIFFICULTY_MAX),
+        }
+    }
+
+    fn next_state(&self, memory: MemoryState, elapsed_days: i64, rating: Rating) -> MemoryState {
+        let MemoryState { stability, difficulty } = memory;
+        let retrievability = self.forgetting_curve(elapsed_days, stability);
+
+        let stability = if elapsed_days == 0 {
+            self.next_short_term_stability(stability, rating)
+        } else if rating == Rating::Again {
+            let floor = stability / (self.weights[17] * self.weights[18]).exp();
+            floor.min(self.next_forget_stability(difficulty, stability, retrievability))
+        } else {
+            self.next_recall_stability(difficulty, stability, retrievability, rating)
+        };
+
+        MemoryState {
+            stability: stability.max(STABILITY_MIN),
+            difficulty: self.next_difficulty(difficulty, rating),
+        }
+    }
+
+    fn forgetting_curve(&self, elapsed_days: i64, stability: f64) -> f64 {
+        (1.0 + self.factor * elapsed_days as f64 / stability).powf(self.decay)
+    }
+
+    fn next_interval(&self, stability: f64) -> i64 {
+        let interval = stability / self.factor * (self.retention.powf(1.0 / self.decay) - 1.0);
+        (interval.round() as i64).clamp(1, self.maximum_interval.max(1))
+    }
+
+    fn init_difficulty(&self, rating: Rating) -> f64 {
+        self.weights[4] - (self.weights[5] * (rating.value() - 1.0)).exp() + 1.0
+    }
+
+    fn next_difficulty(&self, difficulty: f64, rating: Rating) -> f64 {
+        let delta = -self.weights[6] * (rating.value() - 3.0);
+        let damped = difficulty + delta * (DIFFICULTY_MAX - difficulty) / 9.0;
+        let reverted = self.weights[7] * self.init_difficulty(Rating::Easy) + (1.0 - self.weights[7]) * damped;
+        reverted.clamp(DIFFICULTY_FLOOR, DIFFICULTY_MAX)
+    }
+
+    fn next_recall_stability(&self, difficulty: f64, stability: f64, retrievability: f64, rating: Rating) -> f64 {
+        let w = &self.weights;
+        let hard_penalty = if rating == Rating::Hard { w[15] } else { 1.0 };
+        let easy_bonus = if rating == Rating::Easy { w[16] } else { 1.0 };
+        stability
+            * (1.0
+                + w[8].exp()
+                    * (11.0 - difficulty)
+                    * stability.powf(-w[9])
+                    * (((1.0 - retrievability) * w[10]).exp() - 1.0)
+                    * hard_penalty
+                    * easy_bonus)
+    }
+
+    fn next_forget_stability(&self, difficulty: f64, stability: f64, retrievability: f64) -> f64 {
+        let w = &self.weights;
+        w[11] * difficulty.powf(-w[12]) * ((stability + 1.0).powf(w[13]) - 1.0) * ((1.0 - retrievability) * w[14]).exp()
+    }
+
+    fn next_short_term_stability(&self, stability: f64, rating: Rating) -> f64 {
+        let w = &self.weights;
+        let increase = (w[17] * (rating.value() - 3.0 + w[18])).exp() * stability.powf(-w[19]);
+        let increase = if rating >= Rating::Good {
+            increase.max(1.0)
+        } else {
+            increase
+        };
+        stability * increase
+    }
+
+    fn weight(&self, index: i32) -> f64 {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.weights.get(index))
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+/// `ts-fsrs` basic learning-steps strategy: `Some((step_ms, next_step))` keeps the card in
+/// (re)learning, `None` graduates it.
+fn learning_step(steps: &[i64], current: i32, rating: Rating) -> Option<(i64, i32)> {
+    let current = usize::try_from(current).unwrap_or(0);
+    let first = *steps.first()?;
+    if current >= steps.len() {
+        return None;
+    }
+
+    match rating {
+        Rating::Again => Some((first, 0)),
+        Rating::Hard => {
+            let step = match steps.get(1) {
+                Some(second) => (first + second) / 2,
+                None => first * 3 / 2,
+            };
+            Some((step, i32::try_from(current).unwrap_or(0)))
+        }
+        Rating::Good => {
+            let next = current + 1;
+            steps.get(next).map(|step| (*step, i32::try_from(next).unwrap_or(0)))
+        }
+        Rating::Easy => None,
+    }
+}
+
+fn schedule_step(progress: &mut UpdateCardProgress, state: CardState, step: i64, next_step: i32, now: i64) {
+    progress.state = state.as_i32();
+    progress.due_at = now.saturating_add(step);
+    progress.scheduled_days = i32::try_from(step / DAY_MS).unwrap_or(i32::MAX);
+    progress.learning_steps = next_step;
+}
+
+fn schedule_days(progress: &mut UpdateCardProgress, state: CardState, interval: i64, now: i64) {
+    progress.state = state.as_i32();
+    progress.due_at = now.saturating_add(interval.saturating_mul(DAY_MS));
+    progress.scheduled_days = i32::try_from(interval).unwrap_or(i32::MAX);
+    progress.learning_steps = 0;
+}
+
+fn step_ms((amount, unit): &(i64, String)) -> i64 {
+    let unit_ms = match unit.as_str() {
+        "s" => 1000,
+        "m" => 60 * 1000,
+        "h" => 60 * 60 * 1000,
+        _ => DAY_MS,
+    };
+    amount.saturating_mul(unit_ms)
+}
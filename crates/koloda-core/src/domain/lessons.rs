@@ -11,6 +11,7 @@ use crate::domain::reviews::InsertReviewData;
 use crate::domain::time::{default_now, deserialize_timestamp, serialize_optional_timestamp, serialize_timestamp};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LessonDeck {
     pub id: i64,
@@ -22,6 +23,7 @@ pub struct LessonDeck {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LessonsResult {
     pub total: LessonAmounts,
@@ -29,12 +31,14 @@ pub struct LessonsResult {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LessonFilters {
     pub deck_ids: Option<Vec<i64>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LessonAmounts {
     pub untouched: i64,
@@ -53,6 +57,7 @@ impl LessonAmounts {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LessonTemplateLayoutItem {
     pub field: Option<TemplateField>,
@@ -61,6 +66,7 @@ pub struct LessonTemplateLayoutItem {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LessonTemplate {
     pub id: i64,
@@ -68,12 +74,15 @@ pub struct LessonTemplate {
     pub fields: Vec<TemplateField>,
     pub layout: Vec<LessonTemplateLayoutItem>,
     #[serde(serialize_with = "serialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
     pub created_at: i64,
     #[serde(default, serialize_with = "serialize_optional_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
     pub updated_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LessonData {
     pub cards: Vec<Card>,
@@ -83,6 +92,7 @@ pub struct LessonData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct GetLessonDataParams {
     #[serde(default = "default_now", deserialize_with = "deserialize_timestamp")]
@@ -98,6 +108,7 @@ impl GetLessonDataParams {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LessonResultData {
     pub card: UpdateCardProgress,
@@ -129,6 +140,7 @@ fn validate_lesson_amount(value: i64) -> Result<(), AppError> {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct GetLessonsParams {
     #[serde(default = "default_now", deserialize_with = "deserialize_timestamp")]
@@ -0,0 +1,201 @@
+//! Markdown fields — rendering to sanitized HTML and extraction of plain text.
+//!
+//! Rendering is CommonMark by `pulldown-cmark`, with strikethrough and tables, and single newlines
+//! kept as `<br>`. The HTML, embedded tags included, then goes through `ammonia` with an allowlist
+//! of tags and attributes, so unsafe URLs are dropped the way a browser would read them.
+
+use ammonia::{Builder, UrlRelative};
+use pulldown_cmark::{html, Event, Options, Parser};
+use regex::{Captures, Regex};
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "details",
+    "div",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "rb",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+/// Dropped together with everything up to their closing tag.
+const DROPPED_TAGS: &[&str] = &[
+    "script", "style", "iframe", "object", "embed", "noscript", "template", "textarea", "svg", "math",
+];
+const BLOCK_TAGS: &[&str] = &[
+    "blockquote",
+    "br",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "p",
+    "pre",
+    "tr",
+];
+const URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+/// Emphasis and code markers left over without a pair, e.g. the `**` of an empty `**bold**`.
+const MARKER_CHARS: &[char] = &['*', '_', '~', '`'];
+
+static SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(|| {
+    let mut builder = Builder::empty();
+    builder
+        .tags(ALLOWED_TAGS.iter().copied().collect())
+        .clean_content_tags(DROPPED_TAGS.iter().copied().collect())
+        .generic_attributes(HashSet::from(["class", "title"]))
+        .tag_attributes(HashMap::from([
+            ("a", HashSet::from(["href"])),
+            ("img", HashSet::from(["src", "alt"])),
+            ("td", HashSet::from(["colspan", "rowspan"])),
+            ("th", HashSet::from(["colspan", "rowspan"])),
+        ]))
+        .url_schemes(URL_SCHEMES.iter().copied().collect())
+        .url_relative(UrlRelative::PassThrough)
+        .link_rel(None);
+    builder
+});
+static HTML_TAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<(/?)([A-Za-z][A-Za-z0-9]*)((?:[^>\x22]|\x22[^\x22]*\x22)*)>").expect("html tag pattern is valid")
+});
+static IMAGE_ALT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\salt="([^"]*)""#).expect("image alt pattern is valid"));
+static ENTITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(amp|lt|gt|quot|nbsp);").expect("entity pattern is valid"));
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Markdown to HTML that is safe to show in the card view.
+pub fn render_markdown(source: &str) -> String {
+    let parser =
+        Parser::new_ext(source, Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES).map(|event| match event {
+            Event::SoftBreak => Event::HardBreak,
+            event => event,
+        });
+    let mut html = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut html, parser);
+    // WHY: the writer ends every block with a newline; the card view has no use for them and
+    // they would show up as stray text nodes in layouts. Code blocks keep theirs, since their
+    // text is escaped and never holds a `>` right before a newline.
+    sanitize_html(&html.replace(">\n", ">"))
+}
+
+/// The text a reader sees: no markup, entities decoded, one line per block, spaces collapsed.
+/// Images count as their `alt` text.
+pub fn markdown_to_text(source: &str) -> String {
+    html_to_text(&render_markdown(source))
+}
+
+/// Whether a required markdown field would show nothing: no text beyond stray markers and no image.
+pub fn is_markdown_blank(source: &str) -> bool {
+    let html = render_markdown(source);
+    !html.contains("<img")
+        && html_to_text(&html)
+            .chars()
+            .all(|c| c.is_whitespace() || MARKER_CHARS.contains(&c))
+}
+
+/// Keeps allowlisted tags with their allowlisted attributes; other tags are dropped but their
+/// text is kept, except for `DROPPED_TAGS`, whose content goes too. `href` and `src` keep only
+/// relative URLs and `URL_SCHEMES`.
+pub fn sanitize_html(html: &str) -> String {
+    SANITIZER.clean(html).to_string()
+}
+
+/// Undoes the escaping of serialized HTML, which only ever writes these references.
+fn decode_serialized_entities(text: &str) -> String {
+    ENTITY
+        .replace_all(text, |captures: &Captures| {
+            match captures.get(1).map_or("", |entity| entity.as_str()) {
+                "amp" => "&",
+                "lt" => "<",
+                "gt" => ">",
+                "quot" => "\"",
+                _ => "\u{a0}",
+            }
+        })
+        .into_owned()
+}
+
+/// Plain text of `sanitize_html` output, whose tags are well formed and attributes double-quoted.
+fn html_to_text(html: &str) -> String {
+    let spaced = HTML_TAG.replace_all(html, |captures: &Captures| {
+        let name = captures
+            .get(2)
+            .map(|name| name.as_str().to_ascii_lowercase())
+            .unwrap_or_default();
+        if name == "img" {
+            let alt = captures
+                .get(3)
+                .and_then(|attributes| IMAGE_ALT.captures(attributes.as_str()))
+                .and_then(|alt| alt.get(1))
+                .map_or("", |alt| alt.as_str());
+            format!(" {} ", alt)
+        } else if BLOCK_TAGS.contains(&name.as_str()) {
+            "\n".to_string()
+        } else {
+            String::new()
+        }
+    });
+
+    decode_serialized_entities(&spaced)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -10,18 +10,29 @@
 pub mod ai;
 pub mod algorithms;
 pub mod algorithms_fsrs;
+pub mod anki_connect;
 pub mod cards;
+pub mod changes;
 pub mod common;
 pub mod conversations;
+pub mod database_check;
+pub mod deck_bundle;
 pub mod decks;
+pub mod fsrs;
 pub mod learning_day;
 pub mod lessons;
+pub mod markdown;
 pub mod progress;
 pub mod reviews;
 pub mod settings;
 pub mod settings_ai;
+pub mod settings_bundle;
 pub mod settings_hotkeys;
 pub mod settings_interface;
 pub mod settings_learning;
+pub mod sync;
+pub mod template_render;
 pub mod templates;
 pub mod time;
+pub mod typed_answer;
+pub mod versioning;
@@ -13,6 +13,7 @@ const RATING_MIN: i32 = 1;
 const RATING_MAX: i32 = 4;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct Review {
     pub id: i64,
@@ -31,11 +32,15 @@ pub struct Review {
     pub learning_steps: i32,
     pub time: i32,
     pub is_ignored: bool,
+    pub typed_answer: Option<String>,
+    pub typed_score: Option<f64>,
     #[serde(serialize_with = "serialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
     pub created_at: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct InsertReviewData {
     pub card_id: i64,
@@ -49,6 +54,11 @@ pub struct InsertReviewData {
     pub learning_steps: i32,
     pub time: i32,
     pub is_ignored: bool,
+    /// What was typed for a `type` layout field, with its `compare_typed_answer` similarity.
+    #[serde(default)]
+    pub typed_answer: Option<String>,
+    #[serde(default)]
+    pub typed_score: Option<f64>,
 }
 
 impl InsertReviewData {
@@ -60,17 +70,20 @@ impl InsertReviewData {
         validate_scheduled_days(self.scheduled_days, error_codes::VALIDATION_REVIEWS_SCHEDULED_DAYS)?;
         validate_learning_steps(self.learning_steps, error_codes::VALIDATION_REVIEWS_LEARNING_STEPS)?;
         validate_time(self.time)?;
+        validate_typed_score(self.typed_score)?;
         Ok(())
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct GetReviewsData {
     pub card_id: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct GetReviewTotalsParams {
     pub from: i64,
@@ -78,6 +91,7 @@ pub struct GetReviewTotalsParams {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct ReviewTotals {
     pub untouched: i64,
@@ -87,6 +101,7 @@ pub struct ReviewTotals {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct TodaysReviewTotalsMeta {
     pub is_untouched_over_the_limit: bool,
@@ -96,6 +111,7 @@ pub struct TodaysReviewTotalsMeta {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct TodaysReviewTotals {
     pub daily_limits: DailyLimits,
@@ -191,3 +207,13 @@ fn validate_time(time: i32) -> Result<(), AppError> {
     }
     Ok(())
 }
+
+fn validate_typed_score(score: Option<f64>) -> Result<(), AppError> {
+    if let Some(score) = score.filter(|score| !(0.0..=1.0).contains(score)) {
+        return Err(AppError::new(
+            error_codes::VALIDATION_REVIEWS_TYPED_SCORE,
+            Some(format!("Invalid typed answer score: {}", score)),
+        ));
+    }
+    Ok(())
+}
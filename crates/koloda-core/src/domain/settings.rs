@@ -9,8 +9,10 @@ use crate::domain::settings_ai::AISettings;
 use crate::domain::settings_hotkeys::HotkeysSettings;
 use crate::domain::settings_interface::InterfaceSettings;
 use crate::domain::settings_learning::LearningSettings;
+use crate::domain::versioning::VersionedJson;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[strum(serialize_all = "kebab_case")]
 #[serde(rename_all = "kebab-case")]
 pub enum SettingsName {
@@ -20,7 +22,59 @@ pub enum SettingsName {
     Ai,
 }
 
+/// Entries kept per settings slice; older ones are pruned on every recorded write.
+pub const SETTINGS_HISTORY_LIMIT: u32 = 100;
+
+/// One recorded change of a settings slice; `patch` is the JSON merge-patch from old to new.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsHistoryEntry {
+    pub id: i64,
+    pub name: SettingsName,
+    pub old_content: Option<Value>,
+    pub new_content: Value,
+    pub patch: Value,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct GetSettingsHistoryParams {
+    pub name: SettingsName,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// Versions are history entry ids; a missing `to_id` compares against the current content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct DiffSettingsParams {
+    pub from_id: i64,
+    #[serde(default)]
+    pub to_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsDiffEntry {
+    pub path: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct RollbackSettingsData {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct Settings {
     pub id: i64,
@@ -31,50 +85,119 @@ pub struct Settings {
 }
 
 impl SettingsName {
+    pub const ALL: [SettingsName; 4] = [
+        SettingsName::Interface,
+        SettingsName::Learning,
+        SettingsName::Hotkeys,
+        SettingsName::Ai,
+    ];
+
+    /// Whether the slice syncs between devices. AI settings hold per-device profiles and keyring
+    /// references, so they stay local.
+    pub fn is_synced(&self) -> bool {
+        !matches!(self, SettingsName::Ai)
+    }
+
     pub fn validate(&self, content: &Value) -> Result<(), AppError> {
         match self {
             SettingsName::Interface => {
-                let settings: InterfaceSettings = serde_json::from_value(content.clone())?;
+                let settings = InterfaceSettings::from_versioned(content.clone())?;
                 settings.validate()
             }
             SettingsName::Learning => {
-                let settings: LearningSettings = serde_json::from_value(content.clone())?;
+                let settings = LearningSettings::from_versioned(content.clone())?;
                 settings.validate()
             }
             SettingsName::Hotkeys => {
-                let settings: HotkeysSettings = serde_json::from_value(content.clone())?;
+                let settings = HotkeysSettings::from_versioned(content.clone())?;
                 settings.validate()
             }
             SettingsName::Ai => {
-                let settings: AISettings = serde_json::from_value(content.clone())?;
+                let settings = AISettings::from_versioned(content.clone())?;
                 settings.validate()
             }
         }
     }
 
+    /// Runs the slice's version migrations, validates, and returns the canonical JSON stamped with the current version.
     pub fn normalize(&self, content: Value) -> Result<Value, AppError> {
         match self {
             SettingsName::Interface => {
-                let settings: InterfaceSettings = serde_json::from_value(content)?;
+                let settings = InterfaceSettings::from_versioned(content)?;
                 settings.validate()?;
-                Ok(serde_json::to_value(settings)?)
+                settings.to_versioned()
             }
             SettingsName::Learning => {
-                let settings: LearningSettings = serde_json::from_value(content)?;
+                let settings = LearningSettings::from_versioned(content)?;
                 settings.validate()?;
-                Ok(serde_json::to_value(settings)?)
+                settings.to_versioned()
             }
             SettingsName::Hotkeys => {
-                let mut settings: HotkeysSettings = serde_json::from_value(content)?;
+                let mut settings = HotkeysSettings::from_versioned(content)?;
                 settings.fill_defaults();
                 settings.validate()?;
-                Ok(serde_json::to_value(settings)?)
+                settings.to_versioned()
             }
             SettingsName::Ai => {
-                let settings: AISettings = serde_json::from_value(content)?;
+                let settings = AISettings::from_versioned(content)?;
                 settings.validate()?;
-                Ok(serde_json::to_value(settings)?)
+                settings.to_versioned()
+            }
+        }
+    }
+}
+
+/// JSON merge-patch (RFC 7386) that turns `old` into `new`.
+pub fn merge_patch_between(old: &Value, new: &Value) -> Value {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let mut patch = serde_json::Map::new();
+            for (key, old_value) in old_map {
+                match new_map.get(key) {
+                    Some(new_value) if new_value == old_value => {}
+                    Some(new_value) => {
+                        patch.insert(key.clone(), merge_patch_between(old_value, new_value));
+                    }
+                    None => {
+                        patch.insert(key.clone(), Value::Null);
+                    }
+                }
+            }
+            for (key, new_value) in new_map {
+                if !old_map.contains_key(key) {
+                    patch.insert(key.clone(), new_value.clone());
+                }
+            }
+            Value::Object(patch)
+        }
+        _ => new.clone(),
+    }
+}
+
+// WHY: Objects diff per key so a hotkey or limit change shows up as one path; arrays and
+// scalars compare whole, matching merge-patch semantics where arrays are replaced wholesale.
+pub fn diff_settings_content(before: &Value, after: &Value) -> Vec<SettingsDiffEntry> {
+    let mut entries = Vec::new();
+    collect_diff(String::new(), Some(before), Some(after), &mut entries);
+    entries
+}
+
+fn collect_diff(path: String, before: Option<&Value>, after: Option<&Value>, entries: &mut Vec<SettingsDiffEntry>) {
+    match (before, after) {
+        (Some(Value::Object(before_map)), Some(Value::Object(after_map))) => {
+            let mut keys: Vec<&String> = before_map.keys().chain(after_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                collect_diff(child, before_map.get(key), after_map.get(key), entries);
             }
         }
+        (before, after) if before != after => entries.push(SettingsDiffEntry {
+            path,
+            before: before.cloned(),
+            after: after.cloned(),
+        }),
+        _ => {}
     }
 }
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 use crate::app::error::{error_codes, AppError};
+use crate::domain::versioning::{JsonMigration, VersionedJson};
 
 pub use crate::domain::ai::{AIProfile, AISecrets};
 
@@ -23,6 +24,7 @@ fn default_assistant_temperature() -> f64 {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct AssistantSettings {
     #[serde(default = "default_assistant_temperature")]
@@ -40,6 +42,7 @@ impl Default for AssistantSettings {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct AISettings {
     pub profiles: Vec<AIProfile>,
@@ -47,6 +50,10 @@ pub struct AISettings {
     pub assistant: Option<AssistantSettings>,
 }
 
+impl VersionedJson for AISettings {
+    const MIGRATIONS: &'static [JsonMigration] = &[];
+}
+
 impl AISettings {
     pub fn validate(&self) -> Result<(), AppError> {
         self.validate_for_storage()
@@ -68,6 +75,29 @@ impl AISettings {
         self.validate_invariants()
     }
 
+    /// Folds an imported (redacted) slice into the local one. Local profiles are never dropped,
+    /// so their keyring entries stay reachable; a profile known locally keeps its `has_secrets`,
+    /// and a new one starts without secrets until its key is entered or imported.
+    pub fn merge_import(mut self, imported: AISettings) -> AISettings {
+        for profile in imported.profiles {
+            match self.profiles.iter_mut().find(|local| local.id == profile.id) {
+                Some(local) => {
+                    let has_secrets = local.has_secrets;
+                    *local = AIProfile { has_secrets, ..profile };
+                }
+                None => self.profiles.push(AIProfile {
+                    has_secrets: false,
+                    ..profile
+                }),
+            }
+        }
+        if imported.assistant.is_some() {
+            self.assistant = imported.assistant;
+        }
+
+        self
+    }
+
     // INVARIANT: Profile ids must be unique; assistant.temperature must be finite and in range.
     fn validate_invariants(&self) -> Result<(), AppError> {
         let mut seen: HashSet<&str> = HashSet::with_capacity(self.profiles.len());
@@ -0,0 +1,160 @@
+//! Portable settings bundle — every `SettingsName` slice plus algorithm presets and templates.
+//!
+//! AI profiles travel without API keys; keys move separately through the encrypted secrets export.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app::error::{error_codes, AppError};
+use crate::domain::algorithms::InsertAlgorithmData;
+use crate::domain::algorithms_fsrs::AlgorithmFSRS;
+use crate::domain::settings::SettingsName;
+use crate::domain::templates::{InsertTemplateData, TemplateContent};
+use crate::domain::time::{deserialize_timestamp, serialize_timestamp};
+use crate::domain::versioning::VersionedJson;
+
+pub const SETTINGS_BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsBundle {
+    pub version: u32,
+    #[serde(serialize_with = "serialize_timestamp", deserialize_with = "deserialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
+    pub exported_at: i64,
+    #[serde(default)]
+    pub settings: Vec<SettingsBundleSlice>,
+    // WHY: Raw values so one malformed preset is reported as its own slice error instead of
+    // failing the whole bundle at deserialization.
+    #[serde(default)]
+    pub algorithms: Vec<Value>,
+    #[serde(default)]
+    pub templates: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsBundleSlice {
+    pub name: SettingsName,
+    pub content: Value,
+}
+
+/// `slice` is `settings/<name>`, `algorithms/<index>` or `templates/<index>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsBundleSliceError {
+    pub slice: String,
+    pub code: String,
+    pub details: Option<String>,
+}
+
+impl SettingsBundleSliceError {
+    pub fn new(slice: String, error: AppError) -> Self {
+        Self {
+            slice,
+            code: error.code,
+            details: error.details,
+        }
+    }
+}
+
+/// Nothing is written unless `errors` is empty; `applied` says whether the import committed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSettingsBundleResult {
+    pub applied: bool,
+    pub errors: Vec<SettingsBundleSliceError>,
+    pub settings: Vec<SettingsName>,
+    pub algorithm_ids: Vec<i64>,
+    pub template_ids: Vec<i64>,
+}
+
+/// Bundle contents that passed validation; settings content is already normalized.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedSettingsBundle {
+    pub settings: Vec<(SettingsName, Value)>,
+    pub algorithms: Vec<(usize, InsertAlgorithmData)>,
+    pub templates: Vec<(usize, InsertTemplateData)>,
+}
+
+impl SettingsBundle {
+    pub fn validate_version(&self) -> Result<(), AppError> {
+        if self.version != SETTINGS_BUNDLE_VERSION {
+            return Err(AppError::new(
+                error_codes::VALIDATION_SETTINGS_BUNDLE_VERSION,
+                Some(format!("Unsupported settings bundle version: {}", self.version)),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn parse(self) -> (ParsedSettingsBundle, Vec<SettingsBundleSliceError>) {
+        let mut parsed = ParsedSettingsBundle::default();
+        let mut errors = Vec::new();
+
+        for slice in self.settings {
+            let slice_id = format!("settings/{}", slice.name);
+            if parsed.settings.iter().any(|(name, _)| *name == slice.name) {
+                errors.push(SettingsBundleSliceError::new(
+                    slice_id,
+                    AppError::new(error_codes::VALIDATION_SETTINGS_BUNDLE_DUPLICATE_SLICE, None),
+                ));
+                continue;
+            }
+            let content = slice
+                .name
+                .validate(&slice.content)
+                .and_then(|()| slice.name.normalize(slice.content));
+            match content {
+                Ok(content) => parsed.settings.push((slice.name, content)),
+                Err(error) => errors.push(SettingsBundleSliceError::new(slice_id, error)),
+            }
+        }
+
+        for (index, value) in self.algorithms.into_iter().enumerate() {
+            let algorithm = parse_preset(value).and_then(|(title, content)| {
+                let data = InsertAlgorithmData {
+                    title,
+                    content: AlgorithmFSRS::from_versioned(content)?,
+                };
+                data.validate().map(|()| data)
+            });
+            match algorithm {
+                Ok(data) => parsed.algorithms.push((index, data)),
+                Err(error) => errors.push(SettingsBundleSliceError::new(format!("algorithms/{}", index), error)),
+            }
+        }
+
+        for (index, value) in self.templates.into_iter().enumerate() {
+            let template = parse_preset(value).and_then(|(title, content)| {
+                let data = InsertTemplateData {
+                    title,
+                    content: TemplateContent::from_versioned(content)?,
+                };
+                data.validate().map(|()| data)
+            });
+            match template {
+                Ok(data) => parsed.templates.push((index, data)),
+                Err(error) => errors.push(SettingsBundleSliceError::new(format!("templates/{}", index), error)),
+            }
+        }
+
+        (parsed, errors)
+    }
+}
+
+#[derive(Deserialize)]
+struct BundlePreset {
+    title: String,
+    content: Value,
+}
+
+fn parse_preset(value: Value) -> Result<(String, Value), AppError> {
+    let preset: BundlePreset = serde_json::from_value(value)?;
+    Ok((preset.title, preset.content))
+}
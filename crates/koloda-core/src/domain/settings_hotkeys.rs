@@ -5,6 +5,7 @@ use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
 use crate::app::error::{error_codes, AppError};
+use crate::domain::versioning::{JsonMigration, VersionedJson};
 
 const FORM_KEYS: &[&str] = &["submit", "reset"];
 const UI_KEYS: &[&str] = &[
@@ -34,6 +35,7 @@ const AI_KEYS: &[&str] = &[
 ];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct HotkeysSettings {
     #[serde(default)]
@@ -48,6 +50,10 @@ pub struct HotkeysSettings {
     pub ai: HashMap<String, Vec<String>>,
 }
 
+impl VersionedJson for HotkeysSettings {
+    const MIGRATIONS: &'static [JsonMigration] = &[];
+}
+
 impl HotkeysSettings {
     pub fn validate(&self) -> Result<(), AppError> {
         for scope in [&self.form, &self.ui, &self.navigation, &self.grades, &self.ai] {
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::app::error::{error_codes, AppError};
+use crate::domain::versioning::{JsonMigration, VersionedJson};
 
 pub const LANGUAGES: &[&str] = &["en", "ru"];
 pub const SCHEMES: &[&str] = &["light", "dark", "system"];
@@ -19,6 +20,7 @@ fn default_dark_theme() -> String {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct InterfaceSettings {
     pub language: String,
@@ -30,6 +32,10 @@ pub struct InterfaceSettings {
     pub motion: String,
 }
 
+impl VersionedJson for InterfaceSettings {
+    const MIGRATIONS: &'static [JsonMigration] = &[];
+}
+
 impl InterfaceSettings {
     pub fn validate(&self) -> Result<(), AppError> {
         if !LANGUAGES.contains(&self.language.as_str()) {
@@ -2,11 +2,14 @@
 //!
 //! `day_starts_at` must use zero-padded `hh:mm`; keep in sync with TS `parseDayStartsAt`.
 
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::app::error::{error_codes, AppError};
+use crate::domain::versioning::{JsonMigration, VersionedJson};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LearningDefaults {
     #[serde(default)]
@@ -16,6 +19,7 @@ pub struct LearningDefaults {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LearningSettings {
     pub defaults: LearningDefaults,
@@ -24,6 +28,23 @@ pub struct LearningSettings {
     pub learn_ahead_limit: LearnAheadLimit,
 }
 
+impl VersionedJson for LearningSettings {
+    const MIGRATIONS: &'static [JsonMigration] = &[migrate_v1_to_v2];
+}
+
+// WHY: v1 stored each daily limit as a bare number that always counted toward the total.
+fn migrate_v1_to_v2(mut content: Value) -> Result<Value, AppError> {
+    if let Some(limits) = content.get_mut("dailyLimits").and_then(Value::as_object_mut) {
+        for key in ["untouched", "learn", "review"] {
+            if let Some(limit) = limits.get_mut(key).filter(|limit| limit.is_number()) {
+                *limit = serde_json::json!({ "value": limit.take(), "counts": true });
+            }
+        }
+    }
+
+    Ok(content)
+}
+
 impl LearningSettings {
     pub fn validate(&self) -> Result<(), AppError> {
         self.daily_limits.validate()?;
@@ -71,6 +92,7 @@ fn day_starts_at_error(value: &str) -> AppError {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct DailyLimits {
     pub total: u32,
@@ -106,33 +128,16 @@ impl DailyLimits {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct CountedDailyLimit {
     pub value: u32,
     pub counts: bool,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase", untagged)]
-enum CountedDailyLimitInput {
-    Value(u32),
-    Object { value: u32, counts: bool },
-}
-
-impl<'de> Deserialize<'de> for CountedDailyLimit {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        match CountedDailyLimitInput::deserialize(deserializer)? {
-            CountedDailyLimitInput::Value(value) => Ok(Self { value, counts: true }),
-            CountedDailyLimitInput::Object { value, counts } => Ok(Self { value, counts }),
-        }
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct LearnAheadLimit(
     pub u32, // hours
@@ -0,0 +1,295 @@
+//! Sync protocol — row changes exchanged between a database and a sync server.
+//!
+//! Rows are identified across devices by their `uid`, and references travel as the parent's
+//! `uid`. Conflict rules, applied by `repo::sync::apply_changes`:
+//! - content (presets, decks, card fields, settings): last writer wins on `updated_at`;
+//! - reviews: union, so reviews made on different devices are all kept;
+//! - card progress: the server's, recomputed from the card's reviews;
+//! - deletes win over edits, and a deleted `uid` is never recreated;
+//! - a change that breaks a constraint is rejected and reported; the pushing device logs it again.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app::error::{error_codes, AppError};
+use crate::domain::cards::Card;
+
+pub const SYNC_PROTOCOL_VERSION: u32 = 1;
+
+/// Synced tables, parents first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub enum SyncTable {
+    Algorithms,
+    Templates,
+    Decks,
+    Cards,
+    Reviews,
+    Settings,
+}
+
+impl SyncTable {
+    pub const ALL: [SyncTable; 6] = [
+        SyncTable::Algorithms,
+        SyncTable::Templates,
+        SyncTable::Decks,
+        SyncTable::Cards,
+        SyncTable::Reviews,
+        SyncTable::Settings,
+    ];
+
+    /// SQL table name, also stored in `sync_changes.table_name`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            SyncTable::Algorithms => "algorithms",
+            SyncTable::Templates => "templates",
+            SyncTable::Decks => "decks",
+            SyncTable::Cards => "cards",
+            SyncTable::Reviews => "reviews",
+            SyncTable::Settings => "settings",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|table| table.as_str() == name)
+    }
+}
+
+/// One row's latest state; `row: null` means the row was deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncChange {
+    pub uid: String,
+    /// Epoch ms of the change on the device that logged it.
+    pub changed_at: i64,
+    #[serde(flatten)]
+    pub row: SyncRow,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "table", content = "row", rename_all = "camelCase")]
+pub enum SyncRow {
+    Algorithms(Option<SyncPreset>),
+    Templates(Option<SyncPreset>),
+    Decks(Option<SyncDeck>),
+    Cards(Option<SyncCard>),
+    Reviews(Option<SyncReview>),
+    Settings(Option<SyncSetting>),
+}
+
+impl SyncRow {
+    pub fn table(&self) -> SyncTable {
+        match self {
+            SyncRow::Algorithms(_) => SyncTable::Algorithms,
+            SyncRow::Templates(_) => SyncTable::Templates,
+            SyncRow::Decks(_) => SyncTable::Decks,
+            SyncRow::Cards(_) => SyncTable::Cards,
+            SyncRow::Reviews(_) => SyncTable::Reviews,
+            SyncRow::Settings(_) => SyncTable::Settings,
+        }
+    }
+
+    pub fn is_delete(&self) -> bool {
+        match self {
+            SyncRow::Algorithms(row) | SyncRow::Templates(row) => row.is_none(),
+            SyncRow::Decks(row) => row.is_none(),
+            SyncRow::Cards(row) => row.is_none(),
+            SyncRow::Reviews(row) => row.is_none(),
+            SyncRow::Settings(row) => row.is_none(),
+        }
+    }
+}
+
+/// An `algorithms` or `templates` row; `content` is the stored versioned JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPreset {
+    pub title: String,
+    pub content: Value,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncDeck {
+    pub title: String,
+    pub algorithm_uid: String,
+    pub template_uid: String,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncCard {
+    pub deck_uid: String,
+    pub template_uid: String,
+    pub content: Value,
+    #[serde(flatten)]
+    pub progress: SyncCardProgress,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncCardProgress {
+    pub state: i32,
+    pub due_at: Option<i64>,
+    pub stability: Option<f64>,
+    pub difficulty: Option<f64>,
+    pub scheduled_days: i32,
+    pub learning_steps: i32,
+    pub reps: i32,
+    pub lapses: i32,
+    pub last_reviewed_at: Option<i64>,
+}
+
+impl From<&Card> for SyncCardProgress {
+    fn from(card: &Card) -> Self {
+        Self {
+            state: card.state,
+            due_at: card.due_at,
+            stability: card.stability,
+            difficulty: card.difficulty,
+            scheduled_days: card.scheduled_days,
+            learning_steps: card.learning_steps,
+            reps: card.reps,
+            lapses: card.lapses,
+            last_reviewed_at: card.last_reviewed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReview {
+    pub card_uid: String,
+    pub rating: i32,
+    pub state: i32,
+    pub due_at: Option<i64>,
+    pub stability: f64,
+    pub difficulty: f64,
+    pub scheduled_days: i32,
+    pub learning_steps: i32,
+    pub time: i32,
+    pub is_ignored: bool,
+    #[serde(default)]
+    pub typed_answer: Option<String>,
+    #[serde(default)]
+    pub typed_score: Option<f64>,
+    pub created_at: i64,
+}
+
+/// Settings are matched by `name`; their `uid` differs between devices.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncSetting {
+    pub name: String,
+    pub content: Value,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPushRequest {
+    pub version: u32,
+    pub device_id: String,
+    pub changes: Vec<SyncChange>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncApplyResult {
+    pub applied: usize,
+    /// Changes that lost a conflict or reference rows the receiver does not have.
+    pub skipped: usize,
+    /// Changes the receiver could not store; the pushing device logs them again.
+    #[serde(default)]
+    pub rejected: Vec<SyncRejectedChange>,
+}
+
+/// A change that broke a constraint on the receiving side, with the database's message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SyncRejectedChange {
+    pub table: SyncTable,
+    pub uid: String,
+    pub reason: String,
+}
+
+/// `cursor` is the server change-log position the device has already pulled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPullRequest {
+    pub version: u32,
+    pub device_id: String,
+    pub cursor: i64,
+}
+
+/// A page of a change log, pulled from the server or pending a push; `cursor` is the last log id it covers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncBatch {
+    pub changes: Vec<SyncChange>,
+    pub cursor: i64,
+    pub has_more: bool,
+}
+
+/// How far a device has pushed its own change log and pulled the server's.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncCursors {
+    pub pushed: i64,
+    pub pulled: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SyncOptions {
+    /// Base URL of the sync server, e.g. `http://192.168.1.10:8770`.
+    pub url: String,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    pub skipped: usize,
+    /// Pushed changes the server rejected, to be pushed again next sync, and pulled ones this
+    /// device could not store.
+    pub rejected: Vec<SyncRejectedChange>,
+}
+
+/// Which side of the protocol applies changes; only the server recomputes progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncRole {
+    Client,
+    Server,
+}
+
+/// Last writer wins on the content clock (`updated_at`, else `created_at`); ties keep the local row.
+pub fn incoming_wins(local_clock: i64, incoming_clock: i64) -> bool {
+    incoming_clock > local_clock
+}
+
+pub fn content_clock(created_at: i64, updated_at: Option<i64>) -> i64 {
+    updated_at.unwrap_or(created_at)
+}
+
+pub fn validate_protocol_version(version: u32) -> Result<(), AppError> {
+    if version != SYNC_PROTOCOL_VERSION {
+        return Err(AppError::new(
+            error_codes::VALIDATION_SYNC_VERSION,
+            Some(format!("Expected {}, got {}", SYNC_PROTOCOL_VERSION, version)),
+        ));
+    }
+    Ok(())
+}
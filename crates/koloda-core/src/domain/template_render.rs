@@ -0,0 +1,294 @@
+//! Card rendering — template sides turned into question and answer HTML, shared by the UI,
+//! CLI, HTTP API and exports.
+//!
+//! Mustache-like syntax over field titles:
+//! - `{{Field}}` inserts the field, HTML-escaped, or rendered by `markdown` for markdown fields;
+//! - `{{#Field}}…{{/Field}}` keeps its body when the field is not blank, `{{^Field}}…{{/Field}}` when it is;
+//! - `{{hint:Field}}`, `{{furigana:Field}}` (`漢字[かんじ]` → ruby), `{{text:Field}}` (drops markup);
+//!   filters chain and apply right to left, e.g. `{{text:furigana:Field}}`;
+//! - `{{!…}}` is a comment; the answer side may include `{{FrontSide}}`.
+//!
+//! Templates without `sides` render their `layout`: display fields on the question,
+//! the question plus reveal and type fields on the answer.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+use crate::app::error::{error_codes, AppError};
+use crate::domain::cards::{Card, CardContent};
+use crate::domain::markdown::{escape_html, render_markdown};
+use crate::domain::templates::{TemplateContent, TemplateField};
+
+const FRONT_SIDE: &str = "FrontSide";
+const ANSWER_SEPARATOR: &str = r#"<hr id="answer">"#;
+
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").expect("tag pattern is valid"));
+// WHY: Anki's reading syntax — the base runs back to the previous space, which is dropped.
+static READING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r" ?([^ >]+?)\[(.+?)\]").expect("reading pattern is valid"));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedCard {
+    pub card_id: i64,
+    pub question: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Question,
+    Answer,
+}
+
+impl Side {
+    fn name(self) -> &'static str {
+        match self {
+            Side::Question => "question",
+            Side::Answer => "answer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    Text,
+    Hint,
+    Furigana,
+}
+
+impl Filter {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Filter::Text),
+            "hint" => Some(Filter::Hint),
+            "furigana" => Some(Filter::Furigana),
+            _ => None,
+        }
+    }
+
+    fn apply(self, html: String, title: &str) -> String {
+        match self {
+            Filter::Text => TAG.replace_all(&html, "").into_owned(),
+            Filter::Furigana => READING
+                .replace_all(&html, "<ruby><rb>$1</rb><rt>$2</rt></ruby>")
+                .into_owned(),
+            Filter::Hint if html.trim().is_empty() => html,
+            Filter::Hint => format!(
+                r#"<details class="hint"><summary>{}</summary>{}</details>"#,
+                escape_html(title),
+                html
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Field {
+        field_id: i64,
+        filters: Vec<Filter>,
+    },
+    FrontSide,
+    Section {
+        field_id: i64,
+        is_inverted: bool,
+        body: Vec<Node>,
+    },
+}
+
+struct OpenSection {
+    name: String,
+    field_id: i64,
+    is_inverted: bool,
+    body: Vec<Node>,
+}
+
+/// Checks the syntax of `content.sides` and that every tag names one of `content.fields`.
+pub fn validate_template_sides(content: &TemplateContent) -> Result<(), AppError> {
+    if let Some(sides) = &content.sides {
+        parse(&sides.question, &content.fields, Side::Question)?;
+        parse(&sides.answer, &content.fields, Side::Answer)?;
+    }
+    Ok(())
+}
+
+pub fn render_card(template: &TemplateContent, card: &Card) -> Result<RenderedCard, AppError> {
+    let (question, answer) = render_content(template, &card.content)?;
+    Ok(RenderedCard {
+        card_id: card.id,
+        question,
+        answer,
+    })
+}
+
+/// Question and answer HTML for `content`; field text is escaped before filters run.
+pub fn render_content(template: &TemplateContent, content: &CardContent) -> Result<(String, String), AppError> {
+    let (question, answer) = match &template.sides {
+        Some(sides) => (
+            parse(&sides.question, &template.fields, Side::Question)?,
+            parse(&sides.answer, &template.fields, Side::Answer)?,
+        ),
+        None => layout_sides(template),
+    };
+
+    let mut question_html = String::new();
+    let mut answer_html = String::new();
+    render_nodes(&question, template, content, "", &mut question_html);
+    render_nodes(&answer, template, content, &question_html, &mut answer_html);
+    Ok((question_html, answer_html))
+}
+
+fn syntax_error(side: Side, message: String) -> AppError {
+    AppError::new(
+        error_codes::VALIDATION_TEMPLATES_SIDES_SYNTAX,
+        Some(format!("{}: {}", side.name(), message)),
+    )
+}
+
+fn find_field(fields: &[TemplateField], name: &str, side: Side) -> Result<i64, AppError> {
+    fields
+        .iter()
+        .find(|field| field.title.trim() == name)
+        .map(|field| field.id)
+        .ok_or_else(|| syntax_error(side, format!("Unknown field: {}", name)))
+}
+
+fn parse(source: &str, fields: &[TemplateField], side: Side) -> Result<Vec<Node>, AppError> {
+    let mut root = Vec::new();
+    let mut open: Vec<OpenSection> = Vec::new();
+    let mut rest = source;
+
+    while let Some((text, after)) = rest.split_once("{{") {
+        let Some((tag, tail)) = after.split_once("}}") else {
+            return Err(syntax_error(side, "Unclosed tag".to_string()));
+        };
+        rest = tail;
+        let nodes = open.last_mut().map_or(&mut root, |section| &mut section.body);
+        if !text.is_empty() {
+            nodes.push(Node::Text(text.to_string()));
+        }
+
+        let tag = tag.trim();
+        if tag.starts_with('!') {
+            continue;
+        }
+        if let Some((is_inverted, name)) = tag
+            .strip_prefix('#')
+            .map(|name| (false, name))
+            .or_else(|| tag.strip_prefix('^').map(|name| (true, name)))
+        {
+            let name = name.trim();
+            open.push(OpenSection {
+                name: name.to_string(),
+                field_id: find_field(fields, name, side)?,
+                is_inverted,
+                body: Vec::new(),
+            });
+        } else if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            let section = open
+                .pop()
+                .filter(|section| section.name == name)
+                .ok_or_else(|| syntax_error(side, format!("Unexpected closing tag: {}", name)))?;
+            let nodes = open.last_mut().map_or(&mut root, |parent| &mut parent.body);
+            nodes.push(Node::Section {
+                field_id: section.field_id,
+                is_inverted: section.is_inverted,
+                body: section.body,
+            });
+        } else if side == Side::Answer && tag == FRONT_SIDE {
+            nodes.push(Node::FrontSide);
+        } else {
+            let mut parts: Vec<&str> = tag.split(':').map(str::trim).collect();
+            let name = parts.pop().unwrap_or_default();
+            let filters = parts
+                .into_iter()
+                .map(|part| Filter::parse(part).ok_or_else(|| syntax_error(side, format!("Unknown filter: {}", part))))
+                .collect::<Result<Vec<_>, _>>()?;
+            nodes.push(Node::Field {
+                field_id: find_field(fields, name, side)?,
+                filters,
+            });
+        }
+    }
+
+    if let Some(section) = open.last() {
+        return Err(syntax_error(side, format!("Unclosed section: {}", section.name)));
+    }
+    if !rest.is_empty() {
+        root.push(Node::Text(rest.to_string()));
+    }
+    Ok(root)
+}
+
+fn layout_sides(template: &TemplateContent) -> (Vec<Node>, Vec<Node>) {
+    let layout_nodes = |operations: &[&str]| -> Vec<Node> {
+        template
+            .layout
+            .iter()
+            .filter(|item| operations.contains(&item.operation.as_str()))
+            .map(|item| Node::Section {
+                field_id: item.field,
+                is_inverted: false,
+                body: vec![
+                    Node::Text(r#"<div class="field">"#.to_string()),
+                    Node::Field {
+                        field_id: item.field,
+                        filters: Vec::new(),
+                    },
+                    Node::Text("</div>".to_string()),
+                ],
+            })
+            .collect()
+    };
+
+    let question = layout_nodes(&["display"]);
+    let mut answer = vec![Node::FrontSide, Node::Text(ANSWER_SEPARATOR.to_string())];
+    answer.extend(layout_nodes(&["reveal", "type"]));
+    (question, answer)
+}
+
+fn field_text(content: &CardContent, field_id: i64) -> &str {
+    content
+        .get(&field_id.to_string())
+        .map(|field| field.text.as_str())
+        .unwrap_or_default()
+}
+
+fn render_nodes(nodes: &[Node], template: &TemplateContent, content: &CardContent, front_side: &str, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::FrontSide => out.push_str(front_side),
+            Node::Field { field_id, filters } => {
+                let field = template.fields.iter().find(|field| field.id == *field_id);
+                let title = field.map(|field| field.title.as_str()).unwrap_or_default();
+                let text = field_text(content, *field_id);
+                let html = match field {
+                    Some(field) if field.is_markdown() => render_markdown(text),
+                    _ => escape_html(text),
+                };
+                out.push_str(
+                    &filters
+                        .iter()
+                        .rev()
+                        .fold(html, |html, filter| filter.apply(html, title)),
+                );
+            }
+            Node::Section {
+                field_id,
+                is_inverted,
+                body,
+            } => {
+                let is_blank = field_text(content, *field_id).trim().is_empty();
+                if is_blank == *is_inverted {
+                    render_nodes(body, template, content, front_side, out);
+                }
+            }
+        }
+    }
+}
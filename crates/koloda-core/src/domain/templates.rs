@@ -1,17 +1,24 @@
 //! Card templates — mirrors `@koloda/srs` `templateValidation`.
 //!
 //! `is_locked` is computed on read when cards reference the template; inserts omit it.
+//! Optional `sides` hold the question/answer markup rendered by `template_render`.
+//! Optional field `rules` are checked on the card side, by `cards::validate_content`.
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::app::error::{error_codes, AppError};
 use crate::domain::common::validate_title;
+use crate::domain::template_render::validate_template_sides;
 use crate::domain::time::{serialize_optional_timestamp, serialize_timestamp};
+use crate::domain::versioning::{JsonMigration, VersionedJson};
 
-const FIELD_TYPES: &[&str] = &["text", "markdown"];
+pub const FIELD_TYPE_MARKDOWN: &str = "markdown";
+const FIELD_TYPES: &[&str] = &["text", FIELD_TYPE_MARKDOWN];
 const LAYOUT_OPERATIONS: &[&str] = &["display", "reveal", "type"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct Template {
     pub id: i64,
@@ -19,19 +26,34 @@ pub struct Template {
     pub content: TemplateContent,
     pub is_locked: bool,
     #[serde(serialize_with = "serialize_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = String))]
     pub created_at: i64,
     #[serde(default, serialize_with = "serialize_optional_timestamp")]
+    #[cfg_attr(feature = "ts", specta(type = Option<String>))]
     pub updated_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct TemplateContent {
     pub fields: Vec<TemplateField>,
     pub layout: Vec<TemplateLayoutItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sides: Option<TemplateSides>,
 }
 
+/// Mustache-like markup per side; see `template_render` for the syntax.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateSides {
+    pub question: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct TemplateField {
     pub id: i64,
@@ -39,9 +61,45 @@ pub struct TemplateField {
     #[serde(rename = "type")]
     pub field_type: String,
     pub is_required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<TemplateFieldRules>,
+}
+
+/// Checks on a field's plain text when cards are written; blank values pass all but `is_required`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase", default)]
+pub struct TemplateFieldRules {
+    /// In characters.
+    pub max_length: Option<u32>,
+    /// Regex the whole text must match.
+    pub pattern: Option<String>,
+    /// Letters must belong to one of these scripts; digits, punctuation and spaces always pass.
+    pub allowed_scripts: Vec<FieldScript>,
+    /// Checked by the repo inside every card write (add, update, move, copy, convert,
+    /// find-and-replace, deck import), against the other cards of the deck.
+    pub unique_in_deck: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub enum FieldScript {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Han,
+    Hiragana,
+    Katakana,
+    Hangul,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct TemplateLayoutItem {
     pub field: i64,
@@ -49,6 +107,7 @@ pub struct TemplateLayoutItem {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct InsertTemplateData {
     pub title: String,
@@ -56,6 +115,7 @@ pub struct InsertTemplateData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTemplateValues {
     pub title: String,
@@ -63,6 +123,7 @@ pub struct UpdateTemplateValues {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTemplateData {
     pub id: i64,
@@ -70,6 +131,7 @@ pub struct UpdateTemplateData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct CloneTemplateData {
     pub title: String,
@@ -77,18 +139,74 @@ pub struct CloneTemplateData {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct DeleteTemplateData {
     pub id: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct TemplateDeck {
     pub id: i64,
     pub title: String,
 }
 
+impl TemplateField {
+    pub fn is_markdown(&self) -> bool {
+        self.field_type == FIELD_TYPE_MARKDOWN
+    }
+
+    pub fn is_unique_in_deck(&self) -> bool {
+        self.rules.as_ref().is_some_and(|rules| rules.unique_in_deck)
+    }
+}
+
+impl TemplateFieldRules {
+    /// `pattern` anchored at both ends.
+    pub fn pattern_regex(&self) -> Option<Result<Regex, regex::Error>> {
+        self.pattern
+            .as_deref()
+            .map(|pattern| Regex::new(&format!("^(?:{})$", pattern)))
+    }
+
+    /// Matches text made only of `allowed_scripts` and script-neutral characters.
+    pub fn scripts_regex(&self) -> Option<Result<Regex, regex::Error>> {
+        if self.allowed_scripts.is_empty() {
+            return None;
+        }
+        let classes: String = self
+            .allowed_scripts
+            .iter()
+            .map(|script| format!(r"\p{{{}}}", script.unicode_name()))
+            .collect();
+        Some(Regex::new(&format!(r"^[\p{{Common}}\p{{Inherited}}{}]*$", classes)))
+    }
+}
+
+impl FieldScript {
+    fn unicode_name(self) -> &'static str {
+        match self {
+            Self::Latin => "Latin",
+            Self::Cyrillic => "Cyrillic",
+            Self::Greek => "Greek",
+            Self::Arabic => "Arabic",
+            Self::Hebrew => "Hebrew",
+            Self::Devanagari => "Devanagari",
+            Self::Thai => "Thai",
+            Self::Han => "Han",
+            Self::Hiragana => "Hiragana",
+            Self::Katakana => "Katakana",
+            Self::Hangul => "Hangul",
+        }
+    }
+}
+
+impl VersionedJson for TemplateContent {
+    const MIGRATIONS: &'static [JsonMigration] = &[];
+}
+
 impl InsertTemplateData {
     pub fn validate(&self) -> Result<(), AppError> {
         validate_title(&self.title)?;
@@ -124,6 +242,12 @@ fn validate_template_content(
         }
     }
 
+    for field in &content.fields {
+        if let Some(rules) = &field.rules {
+            validate_field_rules(field.id, rules)?;
+        }
+    }
+
     for item in &content.layout {
         if !LAYOUT_OPERATIONS.contains(&item.operation.as_str()) {
             return Err(AppError::new(
@@ -143,6 +267,8 @@ fn validate_template_content(
         }
     }
 
+    validate_template_sides(content)?;
+
     if let Some(orig) = original {
         validate_locked_template_fields(&orig.fields, &content.fields)?;
     }
@@ -150,6 +276,24 @@ fn validate_template_content(
     Ok(())
 }
 
+fn validate_field_rules(field_id: i64, rules: &TemplateFieldRules) -> Result<(), AppError> {
+    let invalid = |reason: String| {
+        AppError::new(
+            error_codes::VALIDATION_TEMPLATES_FIELDS_RULES,
+            Some(format!("Field id: {}: {}", field_id, reason)),
+        )
+    };
+
+    if rules.max_length == Some(0) {
+        return Err(invalid("maxLength must be positive".to_string()));
+    }
+    if let Some(Err(error)) = rules.pattern_regex() {
+        return Err(invalid(format!("Invalid pattern: {}", error)));
+    }
+
+    Ok(())
+}
+
 fn validate_locked_template_fields(original: &[TemplateField], updated: &[TemplateField]) -> Result<(), AppError> {
     let updated_ids: std::collections::HashSet<i64> = updated.iter().map(|f| f.id).collect();
 
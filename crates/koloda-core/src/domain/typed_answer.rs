@@ -0,0 +1,283 @@
+//! Type-in answer comparison for the `type` layout operation.
+//!
+//! Both texts are split into units — one per character, with whitespace runs and ignored
+//! characters folded into a neighbour — so the diff shows the original text while matching
+//! on the normalized form. `similarity` is `2 * common / (expected + typed)` units.
+//!
+//! The diff table takes `expected * typed` cells, so both texts are capped at
+//! `MAX_TYPED_ANSWER_LENGTH` characters.
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::app::error::{error_codes, AppError};
+use crate::domain::fsrs::Rating;
+
+/// A typed answer at least this similar to the expected one suggests `Hard` instead of `Again`.
+pub const HARD_SIMILARITY: f64 = 0.8;
+/// Longest expected or typed text, in characters, that can be compared.
+pub const MAX_TYPED_ANSWER_LENGTH: usize = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase", default)]
+pub struct TypedAnswerOptions {
+    pub ignore_case: bool,
+    /// Drops every character that is neither alphanumeric nor whitespace.
+    pub ignore_punctuation: bool,
+    pub ignore_diacritics: bool,
+    /// Treats whitespace runs as one space and ignores leading and trailing whitespace.
+    pub collapse_whitespace: bool,
+    /// Splits the expected text into accepted alternatives, e.g. `"/"` for `"color / colour"`.
+    pub alternatives_delimiter: Option<String>,
+    pub suggest_rating: bool,
+}
+
+impl Default for TypedAnswerOptions {
+    fn default() -> Self {
+        Self {
+            ignore_case: true,
+            ignore_punctuation: false,
+            ignore_diacritics: false,
+            collapse_whitespace: true,
+            alternatives_delimiter: None,
+            suggest_rating: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct CompareTypedAnswerData {
+    pub expected: String,
+    pub typed: String,
+    #[serde(default)]
+    pub options: TypedAnswerOptions,
+}
+
+impl CompareTypedAnswerData {
+    pub fn validate(&self) -> Result<(), AppError> {
+        for (name, text) in [("Expected", &self.expected), ("Typed", &self.typed)] {
+            let length = text.chars().count();
+            if length > MAX_TYPED_ANSWER_LENGTH {
+                return Err(AppError::new(
+                    error_codes::VALIDATION_TYPED_ANSWER_TOO_LONG,
+                    Some(format!(
+                        "{} answer has {} characters, at most {} can be compared",
+                        name, length, MAX_TYPED_ANSWER_LENGTH
+                    )),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub enum TypedAnswerDiffKind {
+    Equal,
+    /// In the expected answer but not typed.
+    Missing,
+    /// Typed but not in the expected answer.
+    Extra,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct TypedAnswerDiffPart {
+    pub kind: TypedAnswerDiffKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct TypedAnswerComparison {
+    pub is_correct: bool,
+    /// `0.0..=1.0`; `1.0` when both answers normalize to the same text.
+    pub similarity: f64,
+    /// The alternative the typed answer matched best, as written in the expected text.
+    pub expected: String,
+    pub diff: Vec<TypedAnswerDiffPart>,
+    /// `Good` when correct, `Hard` from `HARD_SIMILARITY`, otherwise `Again`; only with `suggest_rating`.
+    pub suggested_rating: Option<i32>,
+}
+
+struct Unit {
+    key: String,
+    text: String,
+}
+
+/// Compares `typed` against every alternative of `expected`; the first best match wins.
+pub fn compare_typed_answer(data: &CompareTypedAnswerData) -> Result<TypedAnswerComparison, AppError> {
+    data.validate()?;
+    let options = &data.options;
+    let typed = split_units(&data.typed, options);
+
+    let mut best: Option<TypedAnswerComparison> = None;
+    for alternative in alternatives(&data.expected, options) {
+        let comparison = compare_units(alternative, &split_units(alternative, options), &typed);
+        if best
+            .as_ref()
+            .map_or(true, |best| comparison.similarity > best.similarity)
+        {
+            best = Some(comparison);
+        }
+    }
+    let mut comparison =
+        best.unwrap_or_else(|| compare_units(&data.expected, &split_units(&data.expected, options), &typed));
+
+    if options.suggest_rating {
+        let rating = if comparison.is_correct {
+            Rating::Good
+        } else if comparison.similarity >= HARD_SIMILARITY {
+            Rating::Hard
+        } else {
+            Rating::Again
+        };
+        comparison.suggested_rating = Some(rating.as_i32());
+    }
+    Ok(comparison)
+}
+
+fn alternatives<'a>(expected: &'a str, options: &TypedAnswerOptions) -> Vec<&'a str> {
+    match options
+        .alternatives_delimiter
+        .as_deref()
+        .filter(|delimiter| !delimiter.is_empty())
+    {
+        Some(delimiter) => expected
+            .split(delimiter)
+            .map(str::trim)
+            .filter(|alternative| !alternative.is_empty())
+            .collect(),
+        None => vec![expected],
+    }
+}
+
+fn split_units(text: &str, options: &TypedAnswerOptions) -> Vec<Unit> {
+    let mut units: Vec<Unit> = Vec::new();
+    // WHY: ignored characters before the first unit still belong in the diff text.
+    let mut leading = String::new();
+    let mut in_whitespace = false;
+
+    // WHY: NFC first, so precomposed and decomposed accents compare equal when diacritics count.
+    for ch in text.nfc() {
+        let is_space = ch.is_whitespace() && options.collapse_whitespace;
+        let is_mark = is_combining_mark(ch);
+        let is_ignored = (is_mark && options.ignore_diacritics)
+            || (options.ignore_punctuation && !is_mark && !ch.is_alphanumeric() && !ch.is_whitespace());
+
+        match units.last_mut() {
+            Some(last) if is_space && in_whitespace => last.text.push(ch),
+            Some(last) if is_ignored => last.text.push(ch),
+            Some(last) if is_mark => {
+                last.key.push(ch);
+                last.text.push(ch);
+            }
+            None if is_space || is_ignored || is_mark => leading.push(ch),
+            _ => {
+                let key = if is_space {
+                    " ".to_string()
+                } else {
+                    normalize_char(ch, options)
+                };
+                let mut text = std::mem::take(&mut leading);
+                text.push(ch);
+                units.push(Unit { key, text });
+            }
+        }
+        in_whitespace = is_space || (in_whitespace && is_ignored);
+    }
+
+    if in_whitespace && units.last().is_some_and(|last| last.key == " ") {
+        units.pop();
+    }
+    units
+}
+
+fn normalize_char(ch: char, options: &TypedAnswerOptions) -> String {
+    let key: String = if options.ignore_diacritics {
+        ch.nfd().filter(|c| !is_combining_mark(*c)).collect()
+    } else {
+        ch.to_string()
+    };
+    if options.ignore_case {
+        key.chars().flat_map(char::to_lowercase).collect()
+    } else {
+        key
+    }
+}
+
+fn compare_units(expected_text: &str, expected: &[Unit], typed: &[Unit]) -> TypedAnswerComparison {
+    let width = typed.len() + 1;
+    // INVARIANT: `common[i * width + j]` is the longest common subsequence of `expected[i..]` and `typed[j..]`.
+    let mut common = vec![0_usize; (expected.len() + 1) * width];
+    for (i, a) in expected.iter().enumerate().rev() {
+        for (j, b) in typed.iter().enumerate().rev() {
+            let value = if a.key == b.key {
+                cell(&common, width, i + 1, j + 1) + 1
+            } else {
+                cell(&common, width, i + 1, j).max(cell(&common, width, i, j + 1))
+            };
+            if let Some(slot) = common.get_mut(i * width + j) {
+                *slot = value;
+            }
+        }
+    }
+
+    let mut diff: Vec<TypedAnswerDiffPart> = Vec::new();
+    let mut push = |kind: TypedAnswerDiffKind, text: &str| match diff.last_mut() {
+        Some(last) if last.kind == kind => last.text.push_str(text),
+        _ => diff.push(TypedAnswerDiffPart {
+            kind,
+            text: text.to_string(),
+        }),
+    };
+    let (mut i, mut j) = (0, 0);
+    loop {
+        match (expected.get(i), typed.get(j)) {
+            (Some(a), Some(b)) if a.key == b.key => {
+                push(TypedAnswerDiffKind::Equal, &a.text);
+                i += 1;
+                j += 1;
+            }
+            (Some(a), Some(_)) if cell(&common, width, i + 1, j) >= cell(&common, width, i, j + 1) => {
+                push(TypedAnswerDiffKind::Missing, &a.text);
+                i += 1;
+            }
+            (_, Some(b)) => {
+                push(TypedAnswerDiffKind::Extra, &b.text);
+                j += 1;
+            }
+            (Some(a), None) => {
+                push(TypedAnswerDiffKind::Missing, &a.text);
+                i += 1;
+            }
+            (None, None) => break,
+        }
+    }
+
+    let total = expected.len() + typed.len();
+    let matched = cell(&common, width, 0, 0);
+    TypedAnswerComparison {
+        is_correct: matched == expected.len() && matched == typed.len(),
+        similarity: if total == 0 {
+            1.0
+        } else {
+            (2 * matched) as f64 / total as f64
+        },
+        expected: expected_text.to_string(),
+        diff,
+        suggested_rating: None,
+    }
+}
+
+fn cell(common: &[usize], width: usize, i: usize, j: usize) -> usize {
+    common.get(i * width + j).copied().unwrap_or_default()
+}
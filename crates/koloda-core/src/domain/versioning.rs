@@ -0,0 +1,96 @@
+//! Versioned JSON documents — settings slices, `algorithms.content` and `templates.content`.
+//!
+//! Stored JSON carries a top-level `version`; content without one is version 1. Reads run the
+//! forward migration chain before deserializing, writes stamp the current version.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::app::error::{error_codes, AppError};
+
+pub const VERSION_KEY: &str = "version";
+
+/// One forward step; `MIGRATIONS[n]` turns version `n + 1` into version `n + 2`.
+pub type JsonMigration = fn(Value) -> Result<Value, AppError>;
+
+// INVARIANT: steps must also accept content already in the newer shape. Clients that predate
+// versioning send current-shape JSON without `version`, which is read as version 1.
+pub trait VersionedJson: Serialize + DeserializeOwned {
+    const MIGRATIONS: &'static [JsonMigration];
+
+    fn current_version() -> u64 {
+        Self::MIGRATIONS.len() as u64 + 1
+    }
+
+    fn from_versioned(content: Value) -> Result<Self, AppError> {
+        let mut content = migrate_json(content, Self::MIGRATIONS)?;
+        if let Value::Object(map) = &mut content {
+            map.remove(VERSION_KEY);
+        }
+
+        Ok(serde_json::from_value(content)?)
+    }
+
+    fn to_versioned(&self) -> Result<Value, AppError> {
+        let mut content = serde_json::to_value(self)?;
+        stamp_version(&mut content, Self::current_version())?;
+
+        Ok(content)
+    }
+}
+
+/// Brings `content` up to the chain's current version and stamps it; the shape is not validated.
+pub fn migrate_json(mut content: Value, migrations: &[JsonMigration]) -> Result<Value, AppError> {
+    let current = migrations.len() as u64 + 1;
+    let version = content_version(&content)?;
+    if version > current {
+        return Err(AppError::new(
+            error_codes::VALIDATION_COMMON_CONTENT_VERSION,
+            Some(format!(
+                "Content version {} is newer than supported {}",
+                version, current
+            )),
+        ));
+    }
+
+    for step in migrations
+        .iter()
+        .skip(usize::try_from(version - 1).unwrap_or(usize::MAX))
+    {
+        content = step(content)?;
+    }
+    stamp_version(&mut content, current)?;
+
+    Ok(content)
+}
+
+fn content_version(content: &Value) -> Result<u64, AppError> {
+    let Value::Object(map) = content else {
+        return Err(version_error("Versioned content must be a JSON object"));
+    };
+
+    match map.get(VERSION_KEY) {
+        None | Some(Value::Null) => Ok(1),
+        Some(value) => value
+            .as_u64()
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| version_error(&format!("Invalid content version: {}", value))),
+    }
+}
+
+fn stamp_version(content: &mut Value, version: u64) -> Result<(), AppError> {
+    let Value::Object(map) = content else {
+        return Err(version_error("Versioned content must be a JSON object"));
+    };
+    map.insert(VERSION_KEY.to_string(), Value::from(version));
+
+    Ok(())
+}
+
+fn version_error(details: &str) -> AppError {
+    AppError::new(
+        error_codes::VALIDATION_COMMON_CONTENT_VERSION,
+        Some(details.to_string()),
+    )
+}
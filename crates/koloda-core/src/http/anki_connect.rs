@@ -0,0 +1,248 @@
+//! AnkiConnect protocol endpoint, so tools such as Yomitan can add cards without changes.
+//!
+//! `POST /` with `{ action, version, params, key }`. Version 5 and later answer
+//! `{ result, error }`; older versions answer the bare result and only wrap errors.
+//! Errors are strings, as AnkiConnect clients expect, and the status is 200 except for
+//! refused origins.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::net::Ipv4Addr;
+use tiny_http::{Method, Request, Response};
+
+use super::{header, read_body, tokens_match, with_cors, Listener};
+use crate::app::db::Database;
+use crate::app::error::{error_codes, AppError};
+use crate::domain::anki_connect::{AnkiNote, ANKI_CONNECT_VERSION};
+use crate::repo::anki_connect;
+
+const DEFAULT_PORT: u16 = 8765;
+const DEFAULT_REQUEST_VERSION: u32 = 4;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct AnkiConnectServerOptions {
+    /// Defaults to AnkiConnect's 8765; `0` picks a free port.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Required in every request when set, like AnkiConnect's `apiKey`.
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct AnkiConnectServerInfo {
+    pub port: u16,
+    pub requires_key: bool,
+}
+
+/// Serves until stopped or dropped; both wait for in-flight requests to finish.
+pub struct AnkiConnectServer {
+    _listener: Listener,
+    info: AnkiConnectServerInfo,
+}
+
+impl AnkiConnectServer {
+    pub fn start(db: Database, options: AnkiConnectServerOptions) -> Result<Self, AppError> {
+        let key = options.key.filter(|key| !key.trim().is_empty());
+        let requires_key = key.is_some();
+        let listener = Listener::bind(
+            Ipv4Addr::LOCALHOST.into(),
+            options.port.unwrap_or(DEFAULT_PORT),
+            move |request| respond(&db, key.as_deref(), request),
+        )?;
+
+        Ok(Self {
+            info: AnkiConnectServerInfo {
+                port: listener.port,
+                requires_key,
+            },
+            _listener: listener,
+        })
+    }
+
+    pub fn info(&self) -> &AnkiConnectServerInfo {
+        &self.info
+    }
+
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    action: String,
+    #[serde(default)]
+    version: Option<u32>,
+    #[serde(default)]
+    params: Value,
+    #[serde(default)]
+    key: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelParams {
+    model_name: String,
+}
+
+#[derive(Deserialize)]
+struct NoteParams {
+    note: AnkiNote,
+}
+
+#[derive(Deserialize)]
+struct NotesParams {
+    notes: Vec<AnkiNote>,
+}
+
+#[derive(Deserialize)]
+struct QueryParams {
+    query: String,
+}
+
+#[derive(Deserialize)]
+struct NoteIdsParams {
+    notes: Vec<i64>,
+}
+
+/// Runs one AnkiConnect action. `requestPermission` depends on the server and is answered there.
+pub fn invoke(db: &Database, action: &str, params: Value) -> Result<Value, AppError> {
+    match action {
+        "version" => Ok(json!(ANKI_CONNECT_VERSION)),
+        "deckNames" => to_value(anki_connect::deck_names(db)?),
+        "modelNames" => to_value(anki_connect::model_names(db)?),
+        "modelFieldNames" => to_value(anki_connect::model_field_names(
+            db,
+            &parse::<ModelParams>(params)?.model_name,
+        )?),
+        "addNote" => to_value(anki_connect::add_note(db, parse::<NoteParams>(params)?.note)?),
+        "addNotes" => {
+            let results = anki_connect::add_notes(db, parse::<NotesParams>(params)?.notes)?;
+            to_value(results.into_iter().map(Result::ok).collect::<Vec<_>>())
+        }
+        "canAddNotes" => to_value(anki_connect::can_add_notes(db, &parse::<NotesParams>(params)?.notes)?),
+        "findNotes" => to_value(anki_connect::find_notes(db, &parse::<QueryParams>(params)?.query)?),
+        "notesInfo" => {
+            let notes = anki_connect::notes_info(db, &parse::<NoteIdsParams>(params)?.notes)?;
+            // WHY: AnkiConnect answers `{}` for missing notes so results stay aligned with the ids.
+            Ok(Value::Array(
+                notes
+                    .into_iter()
+                    .map(|note| note.map_or_else(|| Ok(json!({})), to_value))
+                    .collect::<Result<_, _>>()?,
+            ))
+        }
+        _ => Err(AppError::new(
+            error_codes::NOT_FOUND_ANKI_CONNECT_ACTION,
+            Some(action.to_string()),
+        )),
+    }
+}
+
+fn respond(db: &Database, key: Option<&str>, mut request: Request) -> std::io::Result<()> {
+    if *request.method() == Method::Options {
+        return request.respond(with_cors(Response::empty(204)));
+    }
+    if *request.method() != Method::Post {
+        return request.respond(with_cors(Response::from_string(format!(
+            "AnkiConnect v.{}",
+            ANKI_CONNECT_VERSION
+        ))));
+    }
+    if !origin_allowed(&request) {
+        let error = AppError::new(error_codes::HTTP_UNAUTHORIZED, Some("Origin not allowed".to_string()));
+        return request.respond(json_response(envelope_error(&error)).with_status_code(403));
+    }
+
+    let (version, result) = match read_envelope(&mut request) {
+        Ok(envelope) => (
+            envelope.version.unwrap_or(DEFAULT_REQUEST_VERSION),
+            handle(db, key, envelope),
+        ),
+        Err(error) => (ANKI_CONNECT_VERSION, Err(error)),
+    };
+    let body = match (version, result) {
+        (version, Ok(result)) if version <= DEFAULT_REQUEST_VERSION => result,
+        (_, Ok(result)) => json!({ "result": result, "error": null }),
+        (_, Err(error)) => envelope_error(&error),
+    };
+    request.respond(with_cors(json_response(body)))
+}
+
+fn read_envelope(request: &mut Request) -> Result<Envelope, AppError> {
+    parse(read_body(request)?)
+}
+
+fn handle(db: &Database, key: Option<&str>, envelope: Envelope) -> Result<Value, AppError> {
+    if envelope.action == "requestPermission" {
+        return Ok(json!({
+            "permission": "granted",
+            "requireApiKey": key.is_some(),
+            "version": ANKI_CONNECT_VERSION,
+        }));
+    }
+    if let Some(key) = key {
+        let given = envelope.key.as_deref().unwrap_or_default();
+        if !tokens_match(given, key) {
+            return Err(AppError::new(error_codes::HTTP_UNAUTHORIZED, None));
+        }
+    }
+    invoke(db, &envelope.action, envelope.params)
+}
+
+// WHY: Any web page can POST to localhost; refusing other web origins keeps sites from adding
+// cards behind the user's back. Extensions and non-browser clients send other or no origins.
+fn origin_allowed(request: &Request) -> bool {
+    let Some(origin) = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Origin"))
+        .map(|header| header.value.as_str().trim().to_lowercase())
+    else {
+        return true;
+    };
+    if origin == "null" {
+        return false;
+    }
+    let Some(authority) = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+    else {
+        return true;
+    };
+    let host = match authority.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => authority.split([':', '/']).next().unwrap_or_default(),
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "::1")
+}
+
+fn envelope_error(error: &AppError) -> Value {
+    let message = match &error.details {
+        Some(details) => format!("{}: {}", error.code, details),
+        None => error.code.clone(),
+    };
+    json!({ "result": null, "error": message })
+}
+
+fn json_response(body: Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    let response = Response::from_string(body.to_string());
+    match header("Content-Type", "application/json") {
+        Some(content_type) => response.with_header(content_type),
+        None => response,
+    }
+}
+
+fn parse<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, AppError> {
+    serde_json::from_value(params)
+        .map_err(|e| AppError::new(error_codes::VALIDATION_COMMON_PAYLOAD, Some(e.to_string())))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, AppError> {
+    Ok(serde_json::to_value(value)?)
+}
@@ -0,0 +1,272 @@
+//! Localhost JSON API over the repo layer, for extensions and scripts that write to the desktop
+//! database while the app runs.
+//!
+//! `POST /api/<command>` with `Authorization: Bearer <token>` and the command's JSON payload;
+//! commands are listed in `routes::dispatch`. Errors come back as `AppError` JSON with a status
+//! derived from the code. `GET /health` needs no token.
+//!
+//! `AnkiConnectServer` serves the AnkiConnect protocol on its own port; see `anki_connect`.
+//! `SyncServer` serves the sync protocol, optionally beyond localhost; see `sync`.
+
+mod anki_connect;
+mod routes;
+mod sync;
+
+use std::io::Read;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::app::db::Database;
+use crate::app::error::{error_codes, AppError};
+
+pub use anki_connect::{invoke, AnkiConnectServer, AnkiConnectServerInfo, AnkiConnectServerOptions};
+pub use routes::dispatch;
+pub use sync::{SyncServer, SyncServerInfo, SyncServerOptions};
+
+const WORKER_COUNT: usize = 4;
+const MAX_BODY_BYTES: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct HttpServerOptions {
+    /// `0` or unset picks a free port.
+    #[serde(default)]
+    pub port: u16,
+    /// Generated when unset or blank.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct HttpServerInfo {
+    pub port: u16,
+    pub token: String,
+}
+
+/// Serves until stopped or dropped; both wait for in-flight requests to finish.
+pub struct HttpServer {
+    _listener: Listener,
+    info: HttpServerInfo,
+}
+
+impl HttpServer {
+    pub fn start(db: Database, options: HttpServerOptions) -> Result<Self, AppError> {
+        let token = match options.token {
+            Some(token) if !token.trim().is_empty() => token,
+            _ => uuid::Uuid::new_v4().simple().to_string(),
+        };
+        let handler_token = token.clone();
+        let listener = Listener::bind(Ipv4Addr::LOCALHOST.into(), options.port, move |request| {
+            respond(&db, &handler_token, request)
+        })?;
+
+        Ok(Self {
+            info: HttpServerInfo {
+                port: listener.port,
+                token,
+            },
+            _listener: listener,
+        })
+    }
+
+    pub fn info(&self) -> &HttpServerInfo {
+        &self.info
+    }
+
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+/// Listener with a fixed pool of worker threads; dropping it stops the workers.
+struct Listener {
+    server: Arc<Server>,
+    stopping: Arc<AtomicBool>,
+    workers: Vec<JoinHandle<()>>,
+    port: u16,
+}
+
+impl Listener {
+    fn bind<F>(ip: IpAddr, port: u16, handler: F) -> Result<Self, AppError>
+    where
+        F: Fn(Request) -> std::io::Result<()> + Clone + Send + 'static,
+    {
+        let server =
+            Server::http((ip, port)).map_err(|e| AppError::new(error_codes::HTTP_BIND, Some(e.to_string())))?;
+        let port = server.server_addr().to_ip().map_or(port, |addr| addr.port());
+
+        let server = Arc::new(server);
+        let stopping = Arc::new(AtomicBool::new(false));
+        let workers = (0..WORKER_COUNT)
+            .map(|_| {
+                let server = Arc::clone(&server);
+                let stopping = Arc::clone(&stopping);
+                let handler = handler.clone();
+                thread::spawn(move || serve(&server, &stopping, handler))
+            })
+            .collect();
+
+        Ok(Self {
+            server,
+            stopping,
+            workers,
+            port,
+        })
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        self.stopping.store(true, Ordering::Relaxed);
+        for _ in &self.workers {
+            self.server.unblock();
+        }
+        for worker in self.workers.drain(..) {
+            // WHY: A worker only fails by panicking, and the panic has already been reported.
+            if worker.join().is_err() {
+                continue;
+            }
+        }
+    }
+}
+
+fn serve(server: &Server, stopping: &AtomicBool, handler: impl Fn(Request) -> std::io::Result<()>) {
+    loop {
+        match server.recv() {
+            Ok(request) => {
+                // WHY: A failed write means the client hung up; there is no one left to tell.
+                if handler(request).is_err() {
+                    continue;
+                }
+            }
+            Err(_) if stopping.load(Ordering::Relaxed) => return,
+            // WHY: Accept errors are per connection; the listener itself is still usable.
+            Err(_) => continue,
+        }
+    }
+}
+
+fn respond(db: &Database, token: &str, mut request: Request) -> std::io::Result<()> {
+    if *request.method() == Method::Options {
+        return request.respond(with_cors(Response::empty(204)));
+    }
+
+    let (status, body) = match route(db, token, &mut request) {
+        Ok(value) => (200, value),
+        Err(error) => (status_for(&error.code), json!(error)),
+    };
+    let response = Response::from_string(body.to_string()).with_status_code(status);
+
+    request.respond(with_cors(match header("Content-Type", "application/json") {
+        Some(content_type) => response.with_header(content_type),
+        None => response,
+    }))
+}
+
+fn route(db: &Database, token: &str, request: &mut Request) -> Result<Value, AppError> {
+    let path = request.url().split('?').next().unwrap_or_default();
+
+    if path == "/health" {
+        return match request.method() {
+            Method::Get => Ok(json!({ "ok": true })),
+            _ => Err(method_not_allowed(request.method())),
+        };
+    }
+    let Some(command) = path.strip_prefix("/api/").map(str::to_string) else {
+        return Err(AppError::new(
+            error_codes::NOT_FOUND_HTTP_ROUTE,
+            Some(format!("Unknown path: {}", path)),
+        ));
+    };
+    if *request.method() != Method::Post {
+        return Err(method_not_allowed(request.method()));
+    }
+
+    authorize(request, token)?;
+    let params = read_body(request)?;
+    routes::dispatch(db, &command, params)
+}
+
+fn authorize(request: &Request, token: &str) -> Result<(), AppError> {
+    let given = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .and_then(|header| header.value.as_str().strip_prefix("Bearer "));
+
+    match given {
+        Some(given) if tokens_match(given.trim(), token) => Ok(()),
+        _ => Err(AppError::new(error_codes::HTTP_UNAUTHORIZED, None)),
+    }
+}
+
+// WHY: Compares every byte so response time does not reveal how much of the token matched.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// An empty body is `null`, which commands without parameters ignore.
+fn read_body(request: &mut Request) -> Result<Value, AppError> {
+    let mut body = String::new();
+    request.as_reader().take(MAX_BODY_BYTES + 1).read_to_string(&mut body)?;
+
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(AppError::new(
+            error_codes::VALIDATION_COMMON_PAYLOAD,
+            Some(format!("Body exceeds {} bytes", MAX_BODY_BYTES)),
+        ));
+    }
+    if body.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+
+    serde_json::from_str(&body).map_err(|e| AppError::new(error_codes::VALIDATION_COMMON_PAYLOAD, Some(e.to_string())))
+}
+
+fn method_not_allowed(method: &Method) -> AppError {
+    AppError::new(error_codes::HTTP_METHOD_NOT_ALLOWED, Some(method.to_string()))
+}
+
+fn status_for(code: &str) -> u16 {
+    match code {
+        error_codes::HTTP_UNAUTHORIZED => 401,
+        error_codes::HTTP_METHOD_NOT_ALLOWED => 405,
+        error_codes::TASK_CANCELLED => 409,
+        code if code.starts_with("validation.") => 400,
+        code if code.starts_with("not-found.") => 404,
+        _ => 500,
+    }
+}
+
+// WHY: Extensions call from their own origin; the bearer token, not the origin, is the access check.
+fn with_cors<R: Read>(mut response: Response<R>) -> Response<R> {
+    let headers = [
+        ("Access-Control-Allow-Origin", "*"),
+        ("Access-Control-Allow-Methods", "GET, POST, OPTIONS"),
+        ("Access-Control-Allow-Headers", "Authorization, Content-Type"),
+    ];
+    for (field, value) in headers {
+        if let Some(header) = header(field, value) {
+            response.add_header(header);
+        }
+    }
+    response
+}
+
+fn header(field: &str, value: &str) -> Option<Header> {
+    Header::from_bytes(field, value).ok()
+}
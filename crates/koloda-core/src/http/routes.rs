@@ -0,0 +1,92 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app::db::Database;
+use crate::app::error::{error_codes, AppError};
+use crate::domain::cards::{AddCardsOptions, GetCardsParams, InsertCardData};
+use crate::domain::typed_answer::compare_typed_answer;
+use crate::repo::{cards, decks, lessons, reviews, templates};
+
+#[derive(Deserialize)]
+struct IdParams {
+    id: i64,
+}
+
+#[derive(Deserialize)]
+struct AddCardsParams {
+    cards: Vec<InsertCardData>,
+    #[serde(default)]
+    options: AddCardsOptions,
+}
+
+pub(super) fn parse<T: DeserializeOwned>(params: Value) -> Result<T, AppError> {
+    serde_json::from_value(params)
+        .map_err(|e| AppError::new(error_codes::VALIDATION_COMMON_PAYLOAD, Some(e.to_string())))
+}
+
+fn id(params: Value) -> Result<i64, AppError> {
+    parse::<IdParams>(params).map(|params| params.id)
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, AppError> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// Runs one command. Names and payloads match the `KolodaDb` NAPI methods, except `add_cards`,
+/// which takes `{ cards, options? }` in a single body.
+pub fn dispatch(db: &Database, command: &str, params: Value) -> Result<Value, AppError> {
+    match command {
+        "get_decks" => to_value(decks::get_decks(db)?),
+        "get_deck" => to_value(decks::get_deck(db, id(params)?)?),
+        "add_deck" => to_value(decks::add_deck(db, parse(params)?)?),
+        "update_deck" => to_value(decks::update_deck(db, parse(params)?)?),
+        "delete_deck" => to_value(decks::delete_deck(db, parse(params)?)?),
+        "export_deck" => to_value(decks::export_deck(db, id(params)?)?),
+        "import_deck" => to_value(decks::import_deck(db, parse(params)?)?),
+
+        "get_cards" => to_value(cards::get_cards(db, parse::<GetCardsParams>(params)?.deck_id)?),
+        "get_card" => to_value(cards::get_card(db, id(params)?)?),
+        "add_card" => to_value(cards::add_card(db, parse(params)?)?),
+        "add_cards" => {
+            let AddCardsParams { cards: data, options } = parse(params)?;
+            to_value(cards::add_cards(db, data, options)?)
+        }
+        "update_card" => to_value(cards::update_card(db, parse(params)?)?),
+        "delete_card" => to_value(cards::delete_card(db, parse(params)?)?),
+        "delete_cards" => to_value(cards::delete_cards(db, parse(params)?)?),
+        "reset_card_progress" => to_value(cards::reset_card_progress(db, parse(params)?)?),
+        "rebuild_card_progress" => to_value(cards::rebuild_card_progress(db, parse(params)?)?),
+        "render_card" => to_value(cards::render_card(db, id(params)?)?),
+        "get_card_plain_text" => to_value(cards::get_card_plain_text(db, id(params)?)?),
+        "render_deck_cards" => to_value(cards::render_deck_cards(db, id(params)?)?),
+        "move_cards" => to_value(cards::move_cards(db, parse(params)?)?),
+        "copy_cards" => to_value(cards::copy_cards(db, parse(params)?)?),
+        "convert_cards_template" => to_value(cards::convert_cards_template(db, parse(params)?)?),
+        "find_replace" => to_value(cards::find_replace(db, parse(params)?)?),
+        "find_duplicate_cards" => to_value(cards::find_duplicate_cards(db, parse(params)?)?),
+        "merge_cards" => to_value(cards::merge_cards(db, parse(params)?)?),
+
+        "get_lessons" => to_value(lessons::get_lessons(db, parse(params)?)?),
+        "get_lesson_data" => to_value(lessons::get_lesson_data(db, &parse(params)?)?),
+        "submit_lesson_result" => to_value(lessons::submit_lesson_result(db, parse(params)?)?),
+        "compare_typed_answer" => to_value(compare_typed_answer(&parse(params)?)?),
+
+        "get_reviews" => to_value(reviews::get_reviews(db, parse(params)?)?),
+        "get_review_totals" => to_value(reviews::get_review_totals(db, parse(params)?)?),
+        "get_todays_review_totals" => to_value(reviews::get_todays_review_totals(db)?),
+
+        "get_templates" => to_value(templates::get_templates(db)?),
+        "get_template" => to_value(templates::get_template(db, id(params)?)?),
+        "add_template" => to_value(templates::add_template(db, parse(params)?)?),
+        "update_template" => to_value(templates::update_template(db, parse(params)?)?),
+        "clone_template" => to_value(templates::clone_template(db, parse(params)?)?),
+        "delete_template" => to_value(templates::delete_template(db, parse(params)?)?),
+        "get_template_decks" => to_value(templates::get_template_decks(db, id(params)?)?),
+
+        _ => Err(AppError::new(
+            error_codes::NOT_FOUND_HTTP_ROUTE,
+            Some(format!("Unknown command: {}", command)),
+        )),
+    }
+}
@@ -0,0 +1,121 @@
+//! Sync server endpoint: `POST /sync/push` and `POST /sync/pull` with
+//! `Authorization: Bearer <token>`, bodies as in `domain::sync`. `GET /health` needs no token.
+//!
+//! Unlike the other servers it may listen beyond localhost, so devices on the LAN can reach it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::net::{IpAddr, Ipv4Addr};
+use tiny_http::{Method, Request, Response};
+
+use super::routes::parse;
+use super::{authorize, header, method_not_allowed, read_body, status_for, with_cors, Listener};
+use crate::app::db::Database;
+use crate::app::error::{error_codes, AppError};
+use crate::sync::{handle_pull, handle_push};
+
+const DEFAULT_PORT: u16 = 8770;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncServerOptions {
+    /// Defaults to `127.0.0.1`; `0.0.0.0` serves the LAN.
+    #[serde(default)]
+    pub host: Option<IpAddr>,
+    /// Defaults to 8770; `0` picks a free port.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Generated when unset or blank.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncServerInfo {
+    pub host: IpAddr,
+    pub port: u16,
+    pub token: String,
+}
+
+/// Serves until stopped or dropped; both wait for in-flight requests to finish.
+pub struct SyncServer {
+    _listener: Listener,
+    info: SyncServerInfo,
+}
+
+impl SyncServer {
+    pub fn start(db: Database, options: SyncServerOptions) -> Result<Self, AppError> {
+        let host = options.host.unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let token = match options.token {
+            Some(token) if !token.trim().is_empty() => token,
+            _ => uuid::Uuid::new_v4().simple().to_string(),
+        };
+        let handler_token = token.clone();
+        let listener = Listener::bind(host, options.port.unwrap_or(DEFAULT_PORT), move |request| {
+            respond(&db, &handler_token, request)
+        })?;
+
+        Ok(Self {
+            info: SyncServerInfo {
+                host,
+                port: listener.port,
+                token,
+            },
+            _listener: listener,
+        })
+    }
+
+    pub fn info(&self) -> &SyncServerInfo {
+        &self.info
+    }
+
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+fn respond(db: &Database, token: &str, mut request: Request) -> std::io::Result<()> {
+    if *request.method() == Method::Options {
+        return request.respond(with_cors(Response::empty(204)));
+    }
+
+    let (status, body) = match route(db, token, &mut request) {
+        Ok(value) => (200, value),
+        Err(error) => (status_for(&error.code), json!(error)),
+    };
+    let response = Response::from_string(body.to_string()).with_status_code(status);
+
+    request.respond(with_cors(match header("Content-Type", "application/json") {
+        Some(content_type) => response.with_header(content_type),
+        None => response,
+    }))
+}
+
+fn route(db: &Database, token: &str, request: &mut Request) -> Result<Value, AppError> {
+    let path = request.url().split('?').next().unwrap_or_default().to_string();
+
+    if path == "/health" {
+        return match request.method() {
+            Method::Get => Ok(json!({ "ok": true })),
+            _ => Err(method_not_allowed(request.method())),
+        };
+    }
+    if path != "/sync/push" && path != "/sync/pull" {
+        return Err(AppError::new(
+            error_codes::NOT_FOUND_HTTP_ROUTE,
+            Some(format!("Unknown path: {}", path)),
+        ));
+    }
+    if *request.method() != Method::Post {
+        return Err(method_not_allowed(request.method()));
+    }
+
+    authorize(request, token)?;
+    let body = read_body(request)?;
+    if path == "/sync/push" {
+        Ok(json!(handle_push(db, parse(body)?)?))
+    } else {
+        Ok(json!(handle_pull(db, &parse(body)?)?))
+    }
+}
@@ -4,6 +4,11 @@
 //! Schema workflow: `agents/DB.md`. Domain mirroring: `docs/adr/0001-TS-RUST-DOMAIN-MIRRORING.md`.
 
 pub mod app;
+#[cfg(feature = "ts")]
+pub mod bindings;
 pub mod domain;
+#[cfg(feature = "http")]
+pub mod http;
 pub mod migrations;
 pub mod repo;
+pub mod sync;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS settings_history (
+	id integer PRIMARY KEY AUTOINCREMENT NOT NULL,
+	name text NOT NULL,
+	old_content text,
+	new_content text NOT NULL,
+	patch text NOT NULL,
+	created_at integer NOT NULL
+);
+CREATE INDEX IF NOT EXISTS settings_history_name_idx ON settings_history (name, created_at);
@@ -0,0 +1,2 @@
+ALTER TABLE reviews ADD COLUMN typed_answer text;
+ALTER TABLE reviews ADD COLUMN typed_score real;
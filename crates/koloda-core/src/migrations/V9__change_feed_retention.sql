@@ -0,0 +1,6 @@
+DELETE FROM change_feed WHERE id <= (SELECT coalesce(max(id), 0) FROM change_feed) - 10000;
+
+CREATE TRIGGER IF NOT EXISTS change_feed_retention AFTER INSERT ON change_feed
+BEGIN
+	DELETE FROM change_feed WHERE id <= NEW.id - 10000;
+END;
@@ -6,13 +6,22 @@ use serde_json::Value;
 
 use crate::app::db::Database;
 use crate::app::error::{error_codes, throw_known_error, AppError};
-use crate::app::secrets::get_secret_store;
+use crate::app::secrets::{
+    configure_secret_store, get_secret_store, get_secret_store_backend, open_with_passphrase, seal_with_passphrase,
+    SecretStoreBackend, SecretStoreOptions,
+};
 use crate::app::utility::{generate_uuid, get_current_timestamp};
+use crate::domain::ai::{
+    validate_secrets_passphrase, AIProfileSecretsHealth, AISecretsBundle, AISecretsHealth, AISecretsRepair,
+    AISecretsStatus, ImportAISecretsResult, RepairAISecretsData,
+};
 use crate::domain::settings::SettingsName;
 use crate::domain::settings_ai::{AIProfile, AISecrets, AISettings};
 
+const AI_PROFILE_STORE_KEY_PREFIX: &str = "ai-profile-";
+
 fn get_ai_profile_store_key(profile_id: &str) -> String {
-    format!("ai-profile-{}", profile_id)
+    format!("{}{}", AI_PROFILE_STORE_KEY_PREFIX, profile_id)
 }
 
 fn set_api_key(profile_id: &str, api_key: &str) -> Result<(), AppError> {
@@ -27,7 +36,7 @@ fn remove_api_key(profile_id: &str) -> Result<(), AppError> {
     get_secret_store()?.remove(&get_ai_profile_store_key(profile_id))
 }
 
-fn redact_secrets(secrets: &AISecrets) -> AISecrets {
+pub(crate) fn redact_secrets(secrets: &AISecrets) -> AISecrets {
     match secrets {
         AISecrets::OpenRouter { .. } => AISecrets::OpenRouter { api_key: None },
         AISecrets::Ollama { base_url, .. } => AISecrets::Ollama {
@@ -273,3 +282,143 @@ pub fn remove_ai_profile(db: &Database, id: &str) -> Result<(), AppError> {
         set_ai_settings(db, settings)
     })
 }
+
+pub fn configure_ai_secret_store(db: &Database, options: &SecretStoreOptions) -> Result<SecretStoreBackend, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        let keys: Vec<String> = get_ai_settings_or_default(db)?
+            .profiles
+            .iter()
+            .map(|profile| get_ai_profile_store_key(&profile.id))
+            .collect();
+
+        configure_secret_store(options, &keys)
+    })
+}
+
+fn set_has_secrets_flags(db: &Database, flags: &[(&str, bool)]) -> Result<(), AppError> {
+    if flags.is_empty() {
+        return Ok(());
+    }
+
+    let mut settings = get_ai_settings_or_default(db)?;
+    for profile in settings.profiles.iter_mut() {
+        if let Some((_, has_secrets)) = flags.iter().find(|(id, _)| *id == profile.id) {
+            profile.has_secrets = *has_secrets;
+        }
+    }
+    set_ai_settings(db, settings)
+}
+
+pub fn check_ai_secrets(db: &Database) -> Result<AISecretsHealth, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        let settings = get_ai_settings_or_default(db)?;
+        let store = get_secret_store()?;
+
+        let profiles = settings
+            .profiles
+            .iter()
+            .map(|profile| -> Result<AIProfileSecretsHealth, AppError> {
+                let expects_key =
+                    profile.has_secrets || profile.secrets.as_ref().is_some_and(AISecrets::requires_api_key);
+                let has_key = store.get(&get_ai_profile_store_key(&profile.id))?.is_some();
+
+                Ok(AIProfileSecretsHealth {
+                    profile_id: profile.id.clone(),
+                    title: profile.title.clone(),
+                    provider: profile.secrets.as_ref().map(|s| s.provider().to_string()),
+                    status: if expects_key && !has_key {
+                        AISecretsStatus::MissingKey
+                    } else {
+                        AISecretsStatus::Ok
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let orphaned_profile_ids = store.keys()?.map(|keys| {
+            keys.iter()
+                .filter_map(|key| key.strip_prefix(AI_PROFILE_STORE_KEY_PREFIX))
+                .filter(|id| !settings.profiles.iter().any(|p| p.id == *id))
+                .map(str::to_string)
+                .collect()
+        });
+
+        Ok(AISecretsHealth {
+            backend: get_secret_store_backend()?,
+            profiles,
+            orphaned_profile_ids,
+        })
+    })
+}
+
+pub fn repair_ai_secrets(db: &Database, data: RepairAISecretsData) -> Result<AISecretsHealth, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        data.validate()?;
+
+        let settings = get_ai_settings_or_default(db)?;
+        let store = get_secret_store()?;
+        let mut flags: Vec<(&str, bool)> = Vec::new();
+
+        for repair in &data.repairs {
+            match repair {
+                AISecretsRepair::Rekey { profile_id, api_key } => {
+                    if !settings.profiles.iter().any(|p| p.id == *profile_id) {
+                        return Err(AppError::new(
+                            error_codes::NOT_FOUND_AI_PROFILE,
+                            Some(format!("Profile id: {}", profile_id)),
+                        ));
+                    }
+                    store.set(&get_ai_profile_store_key(profile_id), api_key.trim())?;
+                    flags.push((profile_id, true));
+                }
+                // WHY: Purge accepts orphaned ids too — those have no profile left to look up.
+                AISecretsRepair::Purge { profile_id } => {
+                    store.remove(&get_ai_profile_store_key(profile_id))?;
+                    flags.push((profile_id, false));
+                }
+            }
+        }
+
+        set_has_secrets_flags(db, &flags)?;
+        check_ai_secrets(db)
+    })
+}
+
+pub fn export_ai_secrets(db: &Database, passphrase: &str) -> Result<String, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        validate_secrets_passphrase(passphrase)?;
+        let settings = get_ai_settings_or_default(db)?;
+        let store = get_secret_store()?;
+
+        let mut bundle = AISecretsBundle::default();
+        for profile in &settings.profiles {
+            if let Some(api_key) = store.get(&get_ai_profile_store_key(&profile.id))? {
+                bundle.keys.insert(profile.id.clone(), api_key);
+            }
+        }
+
+        seal_with_passphrase(&serde_json::to_vec(&bundle)?, passphrase)
+    })
+}
+
+pub fn import_ai_secrets(db: &Database, bundle: &str, passphrase: &str) -> Result<ImportAISecretsResult, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        let bundle: AISecretsBundle = serde_json::from_slice(&open_with_passphrase(bundle, passphrase)?)?;
+        let settings = get_ai_settings_or_default(db)?;
+        let store = get_secret_store()?;
+
+        let mut result = ImportAISecretsResult::default();
+        for (profile_id, api_key) in &bundle.keys {
+            if settings.profiles.iter().any(|p| p.id == *profile_id) {
+                store.set(&get_ai_profile_store_key(profile_id), api_key)?;
+                result.imported.push(profile_id.clone());
+            } else {
+                result.skipped.push(profile_id.clone());
+            }
+        }
+
+        let flags: Vec<(&str, bool)> = result.imported.iter().map(|id| (id.as_str(), true)).collect();
+        set_has_secrets_flags(db, &flags)?;
+        Ok(result)
+    })
+}
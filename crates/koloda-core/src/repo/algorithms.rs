@@ -1,16 +1,18 @@
 use rusqlite::{params, Connection, OptionalExtension};
 
-use crate::app::db::{parse_json_column, Database};
+use crate::app::db::{parse_versioned_json_column, Database};
 use crate::app::error::{error_codes, throw_known_error, AppError};
 use crate::app::utility::get_current_timestamp;
 use crate::domain::algorithms::{
     Algorithm, AlgorithmDeck, CloneAlgorithmData, DeleteAlgorithmData, InsertAlgorithmData, UpdateAlgorithmData,
+    UpdateAlgorithmValues,
 };
 use crate::domain::algorithms_fsrs::AlgorithmFSRS;
+use crate::domain::versioning::VersionedJson;
 
 fn get_algorithm_row(row: &rusqlite::Row<'_>) -> Result<Algorithm, rusqlite::Error> {
     let content_str: String = row.get(2)?;
-    let content: AlgorithmFSRS = parse_json_column(2, &content_str)?;
+    let content: AlgorithmFSRS = parse_versioned_json_column(2, &content_str)?;
 
     Ok(Algorithm {
         id: row.get(0)?,
@@ -30,7 +32,7 @@ fn get_algorithm_deck_row(row: &rusqlite::Row<'_>) -> Result<AlgorithmDeck, rusq
 
 pub fn get_algorithms(db: &Database) -> Result<Vec<Algorithm>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let mut stmt = conn.prepare(
                 r#"
                 SELECT id, title, content, created_at, updated_at
@@ -63,7 +65,7 @@ pub fn get_algorithms_by_ids(db: &Database, ids: &[i64]) -> Result<Vec<Algorithm
             placeholders.join(", ")
         );
 
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let params: Vec<&dyn rusqlite::ToSql> = ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
             let mut stmt = conn.prepare(&sql)?;
             let algorithms = stmt
@@ -77,7 +79,7 @@ pub fn get_algorithms_by_ids(db: &Database, ids: &[i64]) -> Result<Vec<Algorithm
 
 pub fn get_algorithm(db: &Database, id: i64) -> Result<Option<Algorithm>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             conn.query_row(
                 r#"
                 SELECT id, title, content, created_at, updated_at
@@ -99,7 +101,7 @@ pub fn add_algorithm(db: &Database, data: InsertAlgorithmData) -> Result<Algorit
         data.validate()?;
         let now = get_current_timestamp()?;
 
-        let id = db.with_conn(|conn| insert_algorithm(conn, &data, now))?;
+        let id = db.with_transaction(|conn| insert_algorithm(conn, &data, now))?;
 
         get_algorithm(db, id)?.ok_or_else(|| AppError::new(error_codes::DB_ADD, None))
     })
@@ -114,7 +116,7 @@ pub(crate) fn oldest_algorithm_id(conn: &Connection) -> Result<Option<i64>, AppE
 }
 
 pub(crate) fn insert_algorithm(conn: &Connection, data: &InsertAlgorithmData, now: i64) -> Result<i64, AppError> {
-    let content = serde_json::to_string(&data.content)?;
+    let content = data.content.to_versioned()?.to_string();
     conn.execute(
         r#"
         INSERT INTO algorithms (title, content, created_at, updated_at)
@@ -126,28 +128,35 @@ pub(crate) fn insert_algorithm(conn: &Connection, data: &InsertAlgorithmData, no
     Ok(conn.last_insert_rowid())
 }
 
+pub(crate) fn update_algorithm_row(
+    conn: &Connection,
+    id: i64,
+    values: &UpdateAlgorithmValues,
+    now: i64,
+) -> Result<(), AppError> {
+    let content = values.content.to_versioned()?.to_string();
+    conn.execute(
+        r#"
+        UPDATE algorithms
+        SET
+            title = ?1,
+            content = ?2,
+            updated_at = ?3
+        WHERE id = ?4
+        "#,
+        params![values.title, content, now, id],
+    )?;
+
+    Ok(())
+}
+
 pub fn update_algorithm(db: &Database, data: UpdateAlgorithmData) -> Result<Algorithm, AppError> {
     throw_known_error(error_codes::DB_UPDATE, || {
         data.values.validate()?;
 
         let now = get_current_timestamp()?;
 
-        db.with_conn(|conn| {
-            let content = serde_json::to_string(&data.values.content)?;
-            conn.execute(
-                r#"
-                UPDATE algorithms
-                SET
-                    title = ?1,
-                    content = ?2,
-                    updated_at = ?3
-                WHERE id = ?4
-                "#,
-                params![data.values.title, content, now, data.id],
-            )?;
-
-            Ok(())
-        })?;
+        db.with_transaction(|conn| update_algorithm_row(conn, data.id, &data.values, now))?;
 
         get_algorithm(db, data.id)?.ok_or_else(|| AppError::new(error_codes::DB_UPDATE, None))
     })
@@ -232,7 +241,7 @@ pub fn delete_algorithm(db: &Database, data: DeleteAlgorithmData) -> Result<(),
 
 pub fn get_algorithm_decks(db: &Database, id: i64) -> Result<Vec<AlgorithmDeck>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let mut stmt = conn.prepare(
                 r#"
             SELECT id, title
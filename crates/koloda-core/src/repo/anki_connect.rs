@@ -0,0 +1,245 @@
+//! AnkiConnect actions over decks, templates and cards; see `domain::anki_connect`.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use crate::app::db::Database;
+use crate::app::error::{error_codes, AppError};
+use crate::app::task::TaskContext;
+use crate::domain::anki_connect::{AnkiNote, AnkiNoteInfo, AnkiQuery};
+use crate::domain::cards::{duplicate_key, plain_text_content, AddCardsItemResult, DuplicatePolicy, InsertCardData};
+use crate::domain::decks::Deck;
+use crate::domain::templates::Template;
+use crate::repo::{cards, decks, templates};
+
+pub fn deck_names(db: &Database) -> Result<Vec<String>, AppError> {
+    Ok(decks::get_decks(db)?.into_iter().map(|deck| deck.title).collect())
+}
+
+pub fn model_names(db: &Database) -> Result<Vec<String>, AppError> {
+    Ok(templates::get_templates(db)?
+        .into_iter()
+        .map(|template| template.title)
+        .collect())
+}
+
+pub fn model_field_names(db: &Database, model_name: &str) -> Result<Vec<String>, AppError> {
+    let templates = templates::get_templates(db)?;
+    let template = find_template(&templates, model_name)?;
+    Ok(template
+        .content
+        .fields
+        .iter()
+        .map(|field| field.title.clone())
+        .collect())
+}
+
+pub fn add_note(db: &Database, note: AnkiNote) -> Result<i64, AppError> {
+    add_notes(db, vec![note])?
+        .pop()
+        .unwrap_or_else(|| Err(AppError::new(error_codes::DB_ADD, None)))
+}
+
+/// One result per note, in order. Duplicates are checked on the model's first field within
+/// the target deck unless the note sets `allowDuplicate`. A failing note does not stop the rest.
+pub fn add_notes(db: &Database, notes: Vec<AnkiNote>) -> Result<Vec<Result<i64, AppError>>, AppError> {
+    let decks = decks::get_decks(db)?;
+    let templates = templates::get_templates(db)?;
+
+    let mut results: Vec<Option<Result<i64, AppError>>> = Vec::with_capacity(notes.len());
+    let mut items = Vec::new();
+    for note in &notes {
+        match insert_data(&decks, &templates, note) {
+            Ok(data) => {
+                let policy = if note.options.allow_duplicate {
+                    DuplicatePolicy::Allow
+                } else {
+                    DuplicatePolicy::Reject
+                };
+                items.push((data, policy));
+                results.push(None);
+            }
+            Err(e) => results.push(Some(Err(e))),
+        }
+    }
+
+    let mut added = cards::add_card_items(db, items, None, &TaskContext::default())?.into_iter();
+    Ok(results
+        .into_iter()
+        .map(|result| {
+            result.unwrap_or_else(|| match added.next() {
+                Some(AddCardsItemResult {
+                    card_id: Some(card_id), ..
+                }) => Ok(card_id),
+                Some(AddCardsItemResult { error: Some(error), .. }) => Err(AppError::new(&error.code, error.details)),
+                _ => Err(AppError::new(error_codes::DB_ADD, None)),
+            })
+        })
+        .collect())
+}
+
+/// Whether each note would be added: its deck and model exist, its fields validate and,
+/// unless it allows duplicates, its first field is not already in the deck.
+pub fn can_add_notes(db: &Database, notes: &[AnkiNote]) -> Result<Vec<bool>, AppError> {
+    let decks = decks::get_decks(db)?;
+    let templates = templates::get_templates(db)?;
+    let mut deck_keys: HashMap<(i64, i64, i64), Vec<String>> = HashMap::new();
+
+    notes
+        .iter()
+        .map(|note| {
+            let Ok(data) = insert_data(&decks, &templates, note) else {
+                return Ok(false);
+            };
+            let Some(template) = templates.iter().find(|template| template.id == data.template_id) else {
+                return Ok(false);
+            };
+            if data.validate(&template.content.fields).is_err() {
+                return Ok(false);
+            }
+            if note.options.allow_duplicate {
+                return Ok(true);
+            }
+            let Some(field_id) = template.content.fields.first().map(|field| field.id) else {
+                return Ok(true);
+            };
+            let Some(key) = duplicate_key(
+                &plain_text_content(&data.content, &template.content.fields),
+                Some(&[field_id]),
+            ) else {
+                return Ok(true);
+            };
+
+            let keys = match deck_keys.entry((data.deck_id, template.id, field_id)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(
+                    db.with_read(|conn| {
+                        cards::deck_duplicate_keys(conn, data.deck_id, template.id, &template.content.fields, field_id)
+                    })?
+                    .into_iter()
+                    .map(|(key, _)| key)
+                    .collect(),
+                ),
+            };
+            Ok(!keys.contains(&key))
+        })
+        .collect()
+}
+
+/// Ids of cards matching `query`, ascending. Deck, note type, field presence and id terms
+/// narrow the rows in SQL; only the remaining cards are matched against their content.
+pub fn find_notes(db: &Database, query: &str) -> Result<Vec<i64>, AppError> {
+    let query = AnkiQuery::parse(query)?;
+    let decks: HashMap<i64, String> = decks::get_decks(db)?
+        .into_iter()
+        .filter(|deck| query.matches_deck(&deck.title))
+        .map(|deck| (deck.id, deck.title))
+        .collect();
+    let templates: HashMap<i64, Template> = templates::get_templates(db)?
+        .into_iter()
+        .filter(|template| query.matches_template(template))
+        .map(|template| (template.id, template))
+        .collect();
+    let ids = query.ids();
+    if decks.is_empty() || templates.is_empty() || ids.as_ref().is_some_and(Vec::is_empty) {
+        return Ok(Vec::new());
+    }
+
+    let mut params: Vec<i64> = Vec::new();
+    let mut in_list = |values: &mut dyn Iterator<Item = i64>| {
+        let placeholders: Vec<String> = values
+            .map(|value| {
+                params.push(value);
+                format!("?{}", params.len())
+            })
+            .collect();
+        placeholders.join(", ")
+    };
+    let mut sql = format!(
+        r#"
+        SELECT id, deck_id, template_id, content, state, due_at, stability, difficulty,
+               scheduled_days, learning_steps, reps, lapses, last_reviewed_at, created_at, updated_at
+        FROM cards
+        WHERE deck_id IN ({}) AND template_id IN ({})
+        "#,
+        in_list(&mut decks.keys().copied()),
+        in_list(&mut templates.keys().copied()),
+    );
+    if let Some(ids) = &ids {
+        sql.push_str(&format!(" AND id IN ({})", in_list(&mut ids.iter().copied())));
+    }
+    sql.push_str(" ORDER BY id");
+
+    let cards = db.with_read(|conn| {
+        let mut stmt = conn.prepare(&sql)?;
+        let cards = stmt
+            .query_map(rusqlite::params_from_iter(&params), cards::get_card_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(cards)
+    })?;
+
+    Ok(cards
+        .iter()
+        .filter(
+            |card| match (decks.get(&card.deck_id), templates.get(&card.template_id)) {
+                (Some(deck_title), Some(template)) => query.matches(card, deck_title, template),
+                _ => false,
+            },
+        )
+        .map(|card| card.id)
+        .collect())
+}
+
+/// `None` for ids that do not exist.
+pub fn notes_info(db: &Database, ids: &[i64]) -> Result<Vec<Option<AnkiNoteInfo>>, AppError> {
+    let templates: HashMap<i64, Template> = templates::get_templates(db)?
+        .into_iter()
+        .map(|template| (template.id, template))
+        .collect();
+
+    ids.iter()
+        .map(|id| {
+            Ok(cards::get_card(db, *id)?.and_then(|card| {
+                templates
+                    .get(&card.template_id)
+                    .map(|template| AnkiNoteInfo::new(&card, template))
+            }))
+        })
+        .collect()
+}
+
+fn insert_data(decks: &[Deck], templates: &[Template], note: &AnkiNote) -> Result<InsertCardData, AppError> {
+    let deck = decks
+        .iter()
+        .find(|deck| names_match(&deck.title, &note.deck_name))
+        .ok_or_else(|| AppError::new(error_codes::NOT_FOUND_ANKI_CONNECT_DECK, Some(note.deck_name.clone())))?;
+    let template = find_template(templates, &note.model_name)?;
+
+    Ok(InsertCardData {
+        deck_id: deck.id,
+        template_id: template.id,
+        content: note.content(&template.content.fields),
+        state: None,
+        due_at: None,
+        stability: None,
+        difficulty: None,
+        scheduled_days: None,
+        learning_steps: None,
+        reps: None,
+        lapses: None,
+        last_reviewed_at: None,
+    })
+}
+
+fn find_template<'a>(templates: &'a [Template], model_name: &str) -> Result<&'a Template, AppError> {
+    templates
+        .iter()
+        .find(|template| names_match(&template.title, model_name))
+        .ok_or_else(|| AppError::new(error_codes::NOT_FOUND_ANKI_CONNECT_MODEL, Some(model_name.to_string())))
+}
+
+// WHY: Anki treats deck and note type names case-insensitively, and tools send them back as typed.
+fn names_match(title: &str, name: &str) -> bool {
+    title.trim().to_lowercase() == name.trim().to_lowercase()
+}
@@ -1,19 +1,32 @@
-use crate::domain::cards::{AddCardsItemError, AddCardsItemResult, AddCardsResponse};
-use rusqlite::{params, OptionalExtension};
+use crate::domain::cards::{AddCardsItemError, AddCardsItemResult, AddCardsOptions, AddCardsResponse, DuplicatePolicy};
+use rusqlite::{params, Connection, OptionalExtension};
 
-use crate::app::db::{parse_json_column, Database};
+use crate::app::db::{parse_json_column, parse_versioned_json_column, Database};
 use crate::app::error::{error_codes, throw_known_error, AppError};
+use crate::app::task::TaskContext;
 use crate::app::utility::get_current_timestamp;
+use crate::domain::algorithms_fsrs::AlgorithmFSRS;
 use crate::domain::cards::{
-    Card, CardContent, CardState, DeleteCardData, DeleteCardsData, InsertCardData, ResetCardProgressData,
+    card_plain_text, duplicate_key, group_duplicate_cards, plain_text_content, remap_content, replace_in_content,
+    validate_field_mapping, Card, CardContent, CardFieldMapping, CardSelection, CardState, ContentValidator,
+    ConvertCardsTemplateData, ConvertCardsTemplateFailure, ConvertCardsTemplateResult, CopyCardsData, DeleteCardData,
+    DeleteCardsData, DuplicateCardGroup, FindDuplicateCardsParams, FindReplaceCardChange, FindReplaceData,
+    FindReplaceFailure, FindReplaceMatcher, FindReplaceMode, FindReplaceResult, InsertCardData, MergeCardsData,
+    MoveCardsData, RebuildCardProgressData, RebuildCardProgressResult, RebuildCardProgressScope, ResetCardProgressData,
     UpdateCardData,
 };
-use crate::domain::templates::Template;
-use std::collections::HashMap;
+use crate::domain::decks::Deck;
+use crate::domain::fsrs::FsrsScheduler;
+use crate::domain::reviews::InsertReviewData;
+use crate::domain::template_render::{self, RenderedCard};
+use crate::domain::templates::{Template, TemplateField};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 
-use crate::repo::decks::{get_deck, get_decks_by_ids};
+use crate::repo::decks::{get_deck, get_decks_by_ids, select_deck};
 use crate::repo::fsrs_sql;
-use crate::repo::templates::{get_template, get_templates_by_ids};
+use crate::repo::reviews::{insert_review, select_card_reviews};
+use crate::repo::templates::{get_template, get_templates_by_ids, select_templates_by_ids};
 
 pub fn get_card_row(row: &rusqlite::Row<'_>) -> Result<Card, rusqlite::Error> {
     let content_str: String = row.get(3)?;
@@ -40,7 +53,7 @@ pub fn get_card_row(row: &rusqlite::Row<'_>) -> Result<Card, rusqlite::Error> {
 
 pub fn get_cards(db: &Database, deck_id: i64) -> Result<Vec<Card>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let mut stmt = conn.prepare(
                 r#"
                 SELECT id, deck_id, template_id, content, state, due_at, stability, difficulty,
@@ -62,7 +75,7 @@ pub fn get_cards(db: &Database, deck_id: i64) -> Result<Vec<Card>, AppError> {
 
 pub fn get_card(db: &Database, id: i64) -> Result<Option<Card>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             conn.query_row(
                 r#"
                 SELECT id, deck_id, template_id, content, state, due_at, stability, difficulty,
@@ -80,6 +93,75 @@ pub fn get_card(db: &Database, id: i64) -> Result<Option<Card>, AppError> {
     })
 }
 
+pub fn get_cards_by_ids(db: &Database, ids: &[i64]) -> Result<Vec<Card>, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        db.with_read(|conn| select_cards_by_ids(conn, ids))
+    })
+}
+
+pub(crate) fn select_cards_by_ids(conn: &Connection, ids: &[i64]) -> Result<Vec<Card>, AppError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let placeholders: Vec<String> = ids.iter().enumerate().map(|(i, _)| format!("?{}", i + 1)).collect();
+    let sql = format!(
+        r#"
+        SELECT id, deck_id, template_id, content, state, due_at, stability, difficulty,
+               scheduled_days, learning_steps, reps, lapses, last_reviewed_at, created_at, updated_at
+        FROM cards
+        WHERE id IN ({})
+        ORDER BY created_at, id
+        "#,
+        placeholders.join(", ")
+    );
+
+    let params: Vec<&dyn rusqlite::ToSql> = ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let cards = stmt
+        .query_map(params.as_slice(), get_card_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cards)
+}
+
+pub(crate) fn select_cards_by_selection(conn: &Connection, selection: &CardSelection) -> Result<Vec<Card>, AppError> {
+    let mut filters: Vec<String> = Vec::new();
+    let mut query_params: Vec<i64> = Vec::new();
+
+    for (column, ids) in [("deck_id", &selection.deck_ids), ("id", &selection.card_ids)] {
+        let Some(ids) = ids.as_deref().filter(|ids| !ids.is_empty()) else {
+            continue;
+        };
+        let placeholders: Vec<String> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, _)| format!("?{}", query_params.len() + i + 1))
+            .collect();
+        filters.push(format!("{column} IN ({})", placeholders.join(", ")));
+        query_params.extend_from_slice(ids);
+    }
+
+    let sql = format!(
+        r#"
+        SELECT id, deck_id, template_id, content, state, due_at, stability, difficulty,
+               scheduled_days, learning_steps, reps, lapses, last_reviewed_at, created_at, updated_at
+        FROM cards
+        WHERE {}
+        ORDER BY created_at, id
+        "#,
+        filters.join(" AND ")
+    );
+
+    let params: Vec<&dyn rusqlite::ToSql> = query_params.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let cards = stmt
+        .query_map(params.as_slice(), get_card_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cards)
+}
+
 pub fn add_card(db: &Database, data: InsertCardData) -> Result<Card, AppError> {
     throw_known_error(error_codes::DB_ADD, || {
         get_deck(db, data.deck_id)?.ok_or_else(|| {
@@ -100,46 +182,58 @@ pub fn add_card(db: &Database, data: InsertCardData) -> Result<Card, AppError> {
 
         let now = get_current_timestamp()?;
 
-        let id = db.with_conn(|conn| {
-            conn.execute(
-                r#"
-                INSERT INTO cards (deck_id, template_id, content, state, due_at, stability,
-                                  difficulty, scheduled_days, learning_steps, reps, lapses,
-                                  last_reviewed_at, created_at, updated_at)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, NULL)
-                "#,
-                params![
-                    data.deck_id,
-                    data.template_id,
-                    serde_json::to_string(&data.content)?,
-                    data.state.unwrap_or(0),
-                    data.due_at,
-                    data.stability,
-                    data.difficulty,
-                    data.scheduled_days.unwrap_or(0),
-                    data.learning_steps.unwrap_or(0),
-                    data.reps.unwrap_or(0),
-                    data.lapses.unwrap_or(0),
-                    data.last_reviewed_at,
-                    now
-                ],
+        let id = db.with_transaction(|conn| {
+            UniqueFieldIndex::default().check(
+                conn,
+                None,
+                data.deck_id,
+                template.id,
+                &template.content.fields,
+                &data.content,
             )?;
-
-            Ok(conn.last_insert_rowid())
+            insert_card_row(conn, &data, now)
         })?;
 
         get_card(db, id)?.ok_or_else(|| AppError::new(error_codes::DB_ADD, None))
     })
 }
 
-pub fn add_cards(db: &Database, data: Vec<InsertCardData>) -> Result<AddCardsResponse, AppError> {
+pub fn add_cards(
+    db: &Database,
+    data: Vec<InsertCardData>,
+    options: AddCardsOptions,
+) -> Result<AddCardsResponse, AppError> {
+    add_cards_with_task(db, data, options, &TaskContext::default())
+}
+
+/// Each item commits on its own, so cards inserted before a cancellation are kept.
+pub fn add_cards_with_task(
+    db: &Database,
+    data: Vec<InsertCardData>,
+    options: AddCardsOptions,
+    task: &TaskContext,
+) -> Result<AddCardsResponse, AppError> {
+    let items = data
+        .into_iter()
+        .map(|card_data| (card_data, options.duplicate_policy))
+        .collect();
+    add_card_items(db, items, options.duplicate_field_id, task)
+}
+
+/// `add_cards_with_task` with a duplicate policy per item; one duplicate index serves the batch.
+pub(crate) fn add_card_items(
+    db: &Database,
+    data: Vec<(InsertCardData, DuplicatePolicy)>,
+    duplicate_field_id: Option<i64>,
+    task: &TaskContext,
+) -> Result<AddCardsResponse, AppError> {
     throw_known_error(error_codes::DB_ADD, || {
         if data.is_empty() {
             return Ok(Vec::new());
         }
 
         let distinct_deck_ids: Vec<i64> = {
-            let mut ids: Vec<i64> = data.iter().map(|c| c.deck_id).collect();
+            let mut ids: Vec<i64> = data.iter().map(|(c, _)| c.deck_id).collect();
             ids.sort_unstable();
             ids.dedup();
             ids
@@ -150,82 +244,369 @@ pub fn add_cards(db: &Database, data: Vec<InsertCardData>) -> Result<AddCardsRes
             .collect();
 
         let distinct_template_ids: Vec<i64> = {
-            let mut ids: Vec<i64> = data.iter().map(|c| c.template_id).collect();
+            let mut ids: Vec<i64> = data.iter().map(|(c, _)| c.template_id).collect();
             ids.sort_unstable();
             ids.dedup();
             ids
         };
         let templates: HashMap<i64, Template> = get_templates_by_ids(db, &distinct_template_ids)?;
+        let validators: HashMap<i64, ContentValidator> = templates
+            .iter()
+            .map(|(id, template)| (*id, ContentValidator::new(&template.content.fields)))
+            .collect();
 
-        let mut results = Vec::with_capacity(data.len());
+        let total = data.len();
+        let mut results = Vec::with_capacity(total);
+        let mut duplicates = DuplicateIndex::default();
+        let mut unique = UniqueFieldIndex::default();
 
-        for card_data in data.into_iter() {
+        for (card_data, duplicate_policy) in data.into_iter() {
+            task.step(results.len(), total)?;
             if !decks.contains_key(&card_data.deck_id) {
                 results.push(AddCardsItemResult {
                     error: Some(AddCardsItemError {
                         code: error_codes::NOT_FOUND_CARDS_ADD_DECK.to_string(),
                         details: None,
+                        existing_card_id: None,
                     }),
+                    duplicate_of: None,
+                    card_id: None,
                 });
                 continue;
             }
 
-            match templates.get(&card_data.template_id) {
-                Some(template) => match insert_card_data(db, &card_data, template) {
-                    Ok(_) => results.push(AddCardsItemResult { error: None }),
-                    // WHY: keep the real AppError code/details — flattening to Display
-                    // would strip the code consumers translate.
-                    Err(e) => results.push(AddCardsItemResult { error: Some(e.into()) }),
-                },
-                None => results.push(AddCardsItemResult {
+            let (Some(template), Some(validator)) = (
+                templates.get(&card_data.template_id),
+                validators.get(&card_data.template_id),
+            ) else {
+                results.push(AddCardsItemResult {
                     error: Some(AddCardsItemError {
                         code: error_codes::NOT_FOUND_CARDS_ADD_TEMPLATE.to_string(),
                         details: None,
+                        existing_card_id: None,
+                    }),
+                    duplicate_of: None,
+                    card_id: None,
+                });
+                continue;
+            };
+
+            let existing_id = match duplicate_policy {
+                DuplicatePolicy::Allow => None,
+                DuplicatePolicy::Reject | DuplicatePolicy::Skip => {
+                    let field_id = match duplicate_field_id {
+                        // WHY: an unknown key field would silently turn duplicate detection off.
+                        Some(field_id) if !template.content.fields.iter().any(|f| f.id == field_id) => {
+                            results.push(AddCardsItemResult {
+                                error: Some(AddCardsItemError {
+                                    code: error_codes::VALIDATION_CARDS_ADD_DUPLICATE_FIELD.to_string(),
+                                    details: Some(format!("Field id: {}", field_id)),
+                                    existing_card_id: None,
+                                }),
+                                duplicate_of: None,
+                                card_id: None,
+                            });
+                            continue;
+                        }
+                        Some(field_id) => Some(field_id),
+                        None => template.content.fields.first().map(|f| f.id),
+                    };
+                    let found = match field_id {
+                        Some(field_id) => duplicates.find(
+                            db,
+                            card_data.deck_id,
+                            template,
+                            field_id,
+                            &plain_text_content(&card_data.content, &template.content.fields),
+                        ),
+                        None => Ok(None),
+                    };
+                    match found {
+                        Ok(existing_id) => existing_id,
+                        Err(e) => {
+                            results.push(AddCardsItemResult {
+                                error: Some(e.into()),
+                                duplicate_of: None,
+                                card_id: None,
+                            });
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            match (existing_id, duplicate_policy) {
+                (Some(existing_id), DuplicatePolicy::Reject) => results.push(AddCardsItemResult {
+                    error: Some(AddCardsItemError {
+                        code: error_codes::VALIDATION_CARDS_ADD_DUPLICATE.to_string(),
+                        details: Some(format!("Card id: {}", existing_id)),
+                        existing_card_id: Some(existing_id),
                     }),
+                    duplicate_of: None,
+                    card_id: None,
+                }),
+                (Some(existing_id), DuplicatePolicy::Skip) => results.push(AddCardsItemResult {
+                    error: None,
+                    duplicate_of: Some(existing_id),
+                    card_id: None,
                 }),
+                _ => match insert_card_data(db, &card_data, template, validator, &mut unique) {
+                    Ok(card) => {
+                        duplicates.record(&card, &template.content.fields);
+                        results.push(AddCardsItemResult {
+                            error: None,
+                            duplicate_of: None,
+                            card_id: Some(card.id),
+                        });
+                    }
+                    // WHY: keep the real AppError code/details — flattening to Display
+                    // would strip the code consumers translate.
+                    Err(e) => results.push(AddCardsItemResult {
+                        error: Some(e.into()),
+                        duplicate_of: None,
+                        card_id: None,
+                    }),
+                },
             }
         }
 
+        task.progress(total, total);
         Ok(results)
     })
 }
 
-fn insert_card_data(db: &Database, data: &InsertCardData, template: &Template) -> Result<Card, AppError> {
-    data.validate(&template.content.fields)?;
+// INVARIANT: keys are built lazily per (deck, template, key field) from the deck's stored cards
+// and extended on every insert, so a batch also catches duplicates among its own items.
+// Keys are taken over plain text, so `**hola**` and `hola` collide.
+#[derive(Default)]
+struct DuplicateIndex {
+    keys: HashMap<(i64, i64, i64), HashMap<String, i64>>,
+}
+
+impl DuplicateIndex {
+    fn find(
+        &mut self,
+        db: &Database,
+        deck_id: i64,
+        template: &Template,
+        field_id: i64,
+        content: &CardContent,
+    ) -> Result<Option<i64>, AppError> {
+        let Some(key) = duplicate_key(content, Some(&[field_id])) else {
+            return Ok(None);
+        };
+
+        let keys = match self.keys.entry((deck_id, template.id, field_id)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let mut keys = HashMap::new();
+                let deck_keys = db.with_read(|conn| {
+                    deck_duplicate_keys(conn, deck_id, template.id, &template.content.fields, field_id)
+                })?;
+                for (card_key, card_id) in deck_keys {
+                    keys.entry(card_key).or_insert(card_id);
+                }
+                entry.insert(keys)
+            }
+        };
+
+        Ok(keys.get(&key).copied())
+    }
+
+    fn record(&mut self, card: &Card, fields: &[TemplateField]) {
+        let content = plain_text_content(&card.content, fields);
+        for ((deck_id, template_id, field_id), keys) in self.keys.iter_mut() {
+            if *deck_id != card.deck_id || *template_id != card.template_id {
+                continue;
+            }
+            if let Some(key) = duplicate_key(&content, Some(&[*field_id])) {
+                keys.entry(key).or_insert(card.id);
+            }
+        }
+    }
+}
+
+/// `(duplicate key, card id)` of every card in the deck using `template_id`, over each card's
+/// plain-text content. Field ids only mean something within one template.
+pub(crate) fn deck_duplicate_keys(
+    conn: &Connection,
+    deck_id: i64,
+    template_id: i64,
+    fields: &[TemplateField],
+    field_id: i64,
+) -> Result<Vec<(String, i64)>, AppError> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, deck_id, template_id, content, state, due_at, stability, difficulty,
+               scheduled_days, learning_steps, reps, lapses, last_reviewed_at, created_at, updated_at
+        FROM cards
+        WHERE deck_id = ?1 AND template_id = ?2
+        ORDER BY created_at, id
+        "#,
+    )?;
+    let cards = stmt
+        .query_map(params![deck_id, template_id], get_card_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cards
+        .iter()
+        .filter_map(|card| {
+            duplicate_key(&plain_text_content(&card.content, fields), Some(&[field_id])).map(|key| (key, card.id))
+        })
+        .collect())
+}
+
+// INVARIANT: keys are built lazily per (deck, template, unique field) through the connection
+// doing the writes, and follow every card recorded since, so a batch is checked against the
+// deck and against itself. Keys are taken over plain text, like `DuplicateIndex`.
+#[derive(Default)]
+pub(crate) struct UniqueFieldIndex {
+    keys: HashMap<(i64, i64, i64), UniqueKeys>,
+}
+
+#[derive(Default)]
+struct UniqueKeys {
+    holders: HashMap<String, HashSet<i64>>,
+    by_card: HashMap<i64, String>,
+}
+
+impl UniqueKeys {
+    fn insert(&mut self, key: String, card_id: i64) {
+        self.holders.entry(key.clone()).or_default().insert(card_id);
+        self.by_card.insert(card_id, key);
+    }
+
+    fn remove(&mut self, card_id: i64) {
+        let Some(key) = self.by_card.remove(&card_id) else {
+            return;
+        };
+        if let Entry::Occupied(mut holders) = self.holders.entry(key) {
+            holders.get_mut().remove(&card_id);
+            if holders.get().is_empty() {
+                holders.remove();
+            }
+        }
+    }
+}
+
+impl UniqueFieldIndex {
+    /// Fails with `VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE` when a card of `deck_id` other than
+    /// `card_id` holds the plain text `content` has in one of the template's `unique_in_deck` fields.
+    pub(crate) fn check(
+        &mut self,
+        conn: &Connection,
+        card_id: Option<i64>,
+        deck_id: i64,
+        template_id: i64,
+        fields: &[TemplateField],
+        content: &CardContent,
+    ) -> Result<(), AppError> {
+        let plain = plain_text_content(content, fields);
+        for field in fields.iter().filter(|field| field.is_unique_in_deck()) {
+            let Some(key) = duplicate_key(&plain, Some(&[field.id])) else {
+                continue;
+            };
+            let keys = match self.keys.entry((deck_id, template_id, field.id)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let mut keys = UniqueKeys::default();
+                    for (card_key, id) in deck_duplicate_keys(conn, deck_id, template_id, fields, field.id)? {
+                        keys.insert(card_key, id);
+                    }
+                    entry.insert(keys)
+                }
+            };
+            if keys
+                .holders
+                .get(&key)
+                .is_some_and(|holders| holders.iter().any(|id| Some(*id) != card_id))
+            {
+                return Err(not_unique(field.id));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records `card_id` as now holding `content` in `deck_id`, releasing the keys it held before.
+    pub(crate) fn record(
+        &mut self,
+        card_id: i64,
+        deck_id: i64,
+        template_id: i64,
+        fields: &[TemplateField],
+        content: &CardContent,
+    ) {
+        let plain = plain_text_content(content, fields);
+        for ((key_deck_id, key_template_id, field_id), keys) in self.keys.iter_mut() {
+            keys.remove(card_id);
+            if *key_deck_id != deck_id || *key_template_id != template_id {
+                continue;
+            }
+            if let Some(key) = duplicate_key(&plain, Some(&[*field_id])) {
+                keys.insert(key, card_id);
+            }
+        }
+    }
+}
+
+fn not_unique(field_id: i64) -> AppError {
+    AppError::new(
+        error_codes::VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE,
+        Some(format!("Field id: {}", field_id)),
+    )
+}
+
+fn insert_card_data(
+    db: &Database,
+    data: &InsertCardData,
+    template: &Template,
+    validator: &ContentValidator<'_>,
+    unique: &mut UniqueFieldIndex,
+) -> Result<Card, AppError> {
+    data.validate_with(validator)?;
 
     let now = get_current_timestamp()?;
 
-    let id = db.with_conn(|conn| {
-        conn.execute(
-            r#"
-            INSERT INTO cards (deck_id, template_id, content, state, due_at, stability,
-                              difficulty, scheduled_days, learning_steps, reps, lapses,
-                              last_reviewed_at, created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, NULL)
-            "#,
-            params![
-                data.deck_id,
-                data.template_id,
-                serde_json::to_string(&data.content)?,
-                data.state.unwrap_or(0),
-                data.due_at,
-                data.stability,
-                data.difficulty,
-                data.scheduled_days.unwrap_or(0),
-                data.learning_steps.unwrap_or(0),
-                data.reps.unwrap_or(0),
-                data.lapses.unwrap_or(0),
-                data.last_reviewed_at,
-                now
-            ],
-        )?;
-
-        Ok(conn.last_insert_rowid())
+    let id = db.with_transaction(|conn| {
+        let fields = &template.content.fields;
+        unique.check(conn, None, data.deck_id, template.id, fields, &data.content)?;
+        let id = insert_card_row(conn, data, now)?;
+        unique.record(id, data.deck_id, template.id, fields, &data.content);
+        Ok(id)
     })?;
 
     get_card(db, id)?.ok_or_else(|| AppError::new(error_codes::DB_ADD, None))
 }
 
+pub(crate) fn insert_card_row(conn: &Connection, data: &InsertCardData, now: i64) -> Result<i64, AppError> {
+    conn.execute(
+        r#"
+        INSERT INTO cards (deck_id, template_id, content, state, due_at, stability,
+                          difficulty, scheduled_days, learning_steps, reps, lapses,
+                          last_reviewed_at, created_at, updated_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, NULL)
+        "#,
+        params![
+            data.deck_id,
+            data.template_id,
+            serde_json::to_string(&data.content)?,
+            data.state.unwrap_or(0),
+            data.due_at,
+            data.stability,
+            data.difficulty,
+            data.scheduled_days.unwrap_or(0),
+            data.learning_steps.unwrap_or(0),
+            data.reps.unwrap_or(0),
+            data.lapses.unwrap_or(0),
+            data.last_reviewed_at,
+            now
+        ],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
 pub fn update_card(db: &Database, data: UpdateCardData) -> Result<Card, AppError> {
     throw_known_error(error_codes::DB_UPDATE, || {
         let original = get_card(db, data.id)?.ok_or_else(|| {
@@ -246,7 +627,15 @@ pub fn update_card(db: &Database, data: UpdateCardData) -> Result<Card, AppError
 
         let now = get_current_timestamp()?;
 
-        db.with_conn(|conn| {
+        db.with_transaction(|conn| {
+            UniqueFieldIndex::default().check(
+                conn,
+                Some(original.id),
+                original.deck_id,
+                template.id,
+                &template.content.fields,
+                &data.values.content,
+            )?;
             conn.execute(
                 r#"
                 UPDATE cards
@@ -265,7 +654,7 @@ pub fn update_card(db: &Database, data: UpdateCardData) -> Result<Card, AppError
 
 pub fn delete_card(db: &Database, data: DeleteCardData) -> Result<(), AppError> {
     throw_known_error(error_codes::DB_DELETE, || {
-        db.with_conn(|conn| {
+        db.with_transaction(|conn| {
             conn.execute("DELETE FROM cards WHERE id = ?1", params![data.id])?;
 
             Ok(())
@@ -289,7 +678,7 @@ pub fn delete_cards(db: &Database, data: DeleteCardsData) -> Result<(), AppError
 
         let params: Vec<&dyn rusqlite::ToSql> = data.ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
 
-        db.with_conn(|conn| {
+        db.with_transaction(|conn| {
             conn.execute(&sql, params.as_slice())?;
 
             Ok(())
@@ -324,3 +713,820 @@ pub fn reset_card_progress(db: &Database, data: ResetCardProgressData) -> Result
         get_card(db, data.id)?.ok_or_else(|| AppError::new(error_codes::DB_UPDATE, None))
     })
 }
+
+/// `None` when the card does not exist.
+pub fn render_card(db: &Database, id: i64) -> Result<Option<RenderedCard>, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        let Some(card) = get_card(db, id)? else {
+            return Ok(None);
+        };
+        let template =
+            get_template(db, card.template_id)?.ok_or_else(|| render_template_not_found(card.template_id))?;
+
+        template_render::render_card(&template.content, &card).map(Some)
+    })
+}
+
+/// The card's fields as `Title: text` lines with markdown stripped, for AI prompts.
+pub fn get_card_plain_text(db: &Database, id: i64) -> Result<Option<String>, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        let Some(card) = get_card(db, id)? else {
+            return Ok(None);
+        };
+        let template =
+            get_template(db, card.template_id)?.ok_or_else(|| render_template_not_found(card.template_id))?;
+
+        Ok(Some(card_plain_text(&card.content, &template.content.fields)))
+    })
+}
+
+/// Every card of the deck in creation order, for exports that carry the HTML the UI shows.
+pub fn render_deck_cards(db: &Database, deck_id: i64) -> Result<Vec<RenderedCard>, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        get_deck(db, deck_id)?.ok_or_else(|| {
+            AppError::new(
+                error_codes::NOT_FOUND_CARDS_RENDER_DECK,
+                Some(format!("Deck id: {}", deck_id)),
+            )
+        })?;
+        let cards = get_cards(db, deck_id)?;
+        let mut template_ids: Vec<i64> = cards.iter().map(|card| card.template_id).collect();
+        template_ids.sort_unstable();
+        template_ids.dedup();
+        let templates = get_templates_by_ids(db, &template_ids)?;
+
+        cards
+            .iter()
+            .map(|card| {
+                let template = templates
+                    .get(&card.template_id)
+                    .ok_or_else(|| render_template_not_found(card.template_id))?;
+                template_render::render_card(&template.content, card)
+            })
+            .collect()
+    })
+}
+
+fn render_template_not_found(template_id: i64) -> AppError {
+    AppError::new(
+        error_codes::NOT_FOUND_CARDS_RENDER_TEMPLATE,
+        Some(format!("Template id: {}", template_id)),
+    )
+}
+
+pub fn rebuild_card_progress(
+    db: &Database,
+    data: RebuildCardProgressData,
+) -> Result<RebuildCardProgressResult, AppError> {
+    rebuild_card_progress_with_task(db, data, &TaskContext::default())
+}
+
+/// Replays every card in scope with its deck's current algorithm, e.g. after the weights change.
+pub fn rebuild_card_progress_with_task(
+    db: &Database,
+    data: RebuildCardProgressData,
+    task: &TaskContext,
+) -> Result<RebuildCardProgressResult, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        db.with_transaction(|tx| {
+            let cards = select_cards_in_rebuild_scope(tx, &data)?;
+            let mut schedulers: HashMap<i64, FsrsScheduler> = HashMap::new();
+
+            let total = cards.len();
+            let mut changed = 0;
+            for (done, card) in cards.iter().enumerate() {
+                task.step(done, total)?;
+                let scheduler = match schedulers.entry(card.deck_id) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => {
+                        let algorithm = select_deck_algorithm(tx, card.deck_id)?;
+                        entry.insert(FsrsScheduler::new(&algorithm).map_err(|err| {
+                            AppError::new(
+                                &err.code,
+                                Some(format!(
+                                    "Deck id: {}, {}",
+                                    card.deck_id,
+                                    err.details.unwrap_or_default()
+                                )),
+                            )
+                        })?)
+                    }
+                };
+                if replay_card_progress(tx, card, scheduler)? {
+                    changed += 1;
+                }
+            }
+
+            task.progress(total, total);
+            Ok(RebuildCardProgressResult { total, changed })
+        })
+    })
+}
+
+fn select_cards_in_rebuild_scope(conn: &Connection, data: &RebuildCardProgressData) -> Result<Vec<Card>, AppError> {
+    let (table, filter, not_found, label) = match data.scope {
+        RebuildCardProgressScope::Card => ("cards", "id = ?1", error_codes::NOT_FOUND_CARDS_REBUILD_CARD, "Card"),
+        RebuildCardProgressScope::Deck => (
+            "decks",
+            "deck_id = ?1",
+            error_codes::NOT_FOUND_CARDS_REBUILD_DECK,
+            "Deck",
+        ),
+        RebuildCardProgressScope::Algorithm => (
+            "algorithms",
+            "deck_id IN (SELECT id FROM decks WHERE algorithm_id = ?1)",
+            error_codes::NOT_FOUND_CARDS_REBUILD_ALGORITHM,
+            "Algorithm",
+        ),
+    };
+
+    let exists: bool = conn.query_row(
+        &format!("SELECT EXISTS (SELECT 1 FROM {table} WHERE id = ?1)"),
+        params![data.id],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(AppError::new(not_found, Some(format!("{} id: {}", label, data.id))));
+    }
+
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT id, deck_id, template_id, content, state, due_at, stability, difficulty,
+               scheduled_days, learning_steps, reps, lapses, last_reviewed_at, created_at, updated_at
+        FROM cards
+        WHERE {filter}
+        ORDER BY created_at, id
+        "#
+    ))?;
+    let cards = stmt
+        .query_map(params![data.id], get_card_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cards)
+}
+
+pub(crate) fn select_deck_algorithm(conn: &Connection, deck_id: i64) -> Result<AlgorithmFSRS, rusqlite::Error> {
+    conn.query_row(
+        r#"
+        SELECT a.content
+        FROM decks d
+        JOIN algorithms a ON a.id = d.algorithm_id
+        WHERE d.id = ?1
+        "#,
+        params![deck_id],
+        |row| {
+            let content: String = row.get(0)?;
+            parse_versioned_json_column(0, &content)
+        },
+    )
+}
+
+/// Rewrites `card`'s progress from its reviews; returns whether the stored progress changed.
+/// Cards without a graded review keep their progress.
+///
+/// `updated_at` is left alone: it is the content clock sync resolves edits with.
+pub(crate) fn replay_card_progress(
+    conn: &Connection,
+    card: &Card,
+    scheduler: &FsrsScheduler,
+) -> Result<bool, AppError> {
+    let reviews = select_card_reviews(conn, card.id)?;
+    let Some(replayed) = scheduler.replay(card, &reviews) else {
+        return Ok(false);
+    };
+    if has_same_progress(card, &replayed) {
+        return Ok(false);
+    }
+
+    conn.execute(
+        r#"
+        UPDATE cards
+        SET state = ?1, due_at = ?2, stability = ?3, difficulty = ?4, scheduled_days = ?5,
+            learning_steps = ?6, reps = ?7, lapses = ?8, last_reviewed_at = ?9
+        WHERE id = ?10
+        "#,
+        params![
+            replayed.state,
+            replayed.due_at,
+            replayed.stability,
+            replayed.difficulty,
+            replayed.scheduled_days,
+            replayed.learning_steps,
+            replayed.reps,
+            replayed.lapses,
+            replayed.last_reviewed_at,
+            card.id
+        ],
+    )?;
+    Ok(true)
+}
+
+fn has_same_progress(a: &Card, b: &Card) -> bool {
+    a.state == b.state
+        && a.due_at == b.due_at
+        && a.stability == b.stability
+        && a.difficulty == b.difficulty
+        && a.scheduled_days == b.scheduled_days
+        && a.learning_steps == b.learning_steps
+        && a.reps == b.reps
+        && a.lapses == b.lapses
+        && a.last_reviewed_at == b.last_reviewed_at
+}
+
+pub fn move_cards(db: &Database, data: MoveCardsData) -> Result<Vec<Card>, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        if data.ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let now = get_current_timestamp()?;
+
+        // INVARIANT: card ids are preserved, so `reviews` rows follow the card without copying.
+        db.with_transaction(|tx| {
+            let Relocation {
+                deck,
+                template,
+                cards: relocated,
+            } = plan_relocation(
+                tx,
+                &data.ids,
+                data.deck_id,
+                data.field_mapping.as_deref(),
+                &MOVE_NOT_FOUND_CODES,
+            )?;
+            let fields = &template.content.fields;
+            let mut unique = UniqueFieldIndex::default();
+
+            for (card, content) in &relocated {
+                unique
+                    .check(tx, Some(card.id), deck.id, template.id, fields, content)
+                    .map_err(|err| relocation_error(card.id, err))?;
+                unique.record(card.id, deck.id, template.id, fields, content);
+                tx.execute(
+                    r#"
+                    UPDATE cards
+                    SET deck_id = ?1, template_id = ?2, content = ?3, updated_at = ?4
+                    WHERE id = ?5
+                    "#,
+                    params![deck.id, deck.template_id, serde_json::to_string(content)?, now, card.id],
+                )?;
+            }
+
+            Ok(())
+        })?;
+
+        get_cards_by_ids(db, &data.ids)
+    })
+}
+
+pub fn copy_cards(db: &Database, data: CopyCardsData) -> Result<Vec<Card>, AppError> {
+    throw_known_error(error_codes::DB_CLONE, || {
+        if data.ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let now = get_current_timestamp()?;
+
+        let ids = db.with_transaction(|tx| {
+            let Relocation {
+                deck,
+                template,
+                cards: relocated,
+            } = plan_relocation(
+                tx,
+                &data.ids,
+                data.deck_id,
+                data.field_mapping.as_deref(),
+                &COPY_NOT_FOUND_CODES,
+            )?;
+            let fields = &template.content.fields;
+            let mut unique = UniqueFieldIndex::default();
+            let mut ids = Vec::with_capacity(relocated.len());
+
+            for (card, card_content) in &relocated {
+                unique
+                    .check(tx, None, deck.id, template.id, fields, card_content)
+                    .map_err(|err| relocation_error(card.id, err))?;
+                let content = serde_json::to_string(card_content)?;
+
+                if data.reset_progress {
+                    tx.execute(
+                        r#"
+                        INSERT INTO cards (deck_id, template_id, content, state, due_at, stability,
+                                          difficulty, scheduled_days, learning_steps, reps, lapses,
+                                          last_reviewed_at, created_at, updated_at)
+                        VALUES (?1, ?2, ?3, ?4, NULL, 0, 0, 0, 0, 0, 0, NULL, ?5, NULL)
+                        "#,
+                        params![deck.id, deck.template_id, content, CardState::New.as_i32(), now],
+                    )?;
+                    let copy_id = tx.last_insert_rowid();
+                    unique.record(copy_id, deck.id, template.id, fields, card_content);
+                    ids.push(copy_id);
+                    continue;
+                }
+
+                tx.execute(
+                    r#"
+                    INSERT INTO cards (deck_id, template_id, content, state, due_at, stability,
+                                      difficulty, scheduled_days, learning_steps, reps, lapses,
+                                      last_reviewed_at, created_at, updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, NULL)
+                    "#,
+                    params![
+                        deck.id,
+                        deck.template_id,
+                        content,
+                        card.state,
+                        card.due_at,
+                        card.stability,
+                        card.difficulty,
+                        card.scheduled_days,
+                        card.learning_steps,
+                        card.reps,
+                        card.lapses,
+                        card.last_reviewed_at,
+                        now
+                    ],
+                )?;
+                let copy_id = tx.last_insert_rowid();
+                unique.record(copy_id, deck.id, template.id, fields, card_content);
+
+                // WHY: kept progress without its review log would drift from history on the
+                // first rebuild, so the copy carries the source card's reviews with it.
+                for review in select_card_reviews(tx, card.id)? {
+                    let review_data = InsertReviewData {
+                        card_id: copy_id,
+                        rating: review.rating,
+                        state: review.state,
+                        due_at: review.due_at,
+                        stability: review.stability,
+                        difficulty: review.difficulty,
+                        scheduled_days: review.scheduled_days,
+                        learning_steps: review.learning_steps,
+                        time: review.time,
+                        is_ignored: review.is_ignored,
+                        typed_answer: review.typed_answer,
+                        typed_score: review.typed_score,
+                    };
+                    insert_review(tx, &review_data, review.created_at)?;
+                }
+
+                ids.push(copy_id);
+            }
+
+            Ok(ids)
+        })?;
+
+        get_cards_by_ids(db, &ids)
+    })
+}
+
+pub fn convert_cards_template(
+    db: &Database,
+    data: ConvertCardsTemplateData,
+) -> Result<ConvertCardsTemplateResult, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        let now = get_current_timestamp()?;
+
+        db.with_transaction(|tx| {
+            let cards = select_cards_by_ids(tx, &data.ids)?;
+            if let Some(missing) = data.ids.iter().find(|id| !cards.iter().any(|c| c.id == **id)) {
+                return Err(AppError::new(
+                    error_codes::NOT_FOUND_CARDS_CONVERT_CARD,
+                    Some(format!("Card id: {}", missing)),
+                ));
+            }
+
+            let source_template_ids: Vec<i64> = {
+                let mut ids: Vec<i64> = cards
+                    .iter()
+                    .map(|c| c.template_id)
+                    .filter(|id| *id != data.template_id)
+                    .collect();
+                ids.sort_unstable();
+                ids.dedup();
+                ids
+            };
+            let mut template_ids = source_template_ids.clone();
+            template_ids.push(data.template_id);
+            let templates = select_templates_by_ids(tx, &template_ids)?;
+
+            let target = templates.get(&data.template_id).ok_or_else(|| {
+                AppError::new(
+                    error_codes::NOT_FOUND_CARDS_CONVERT_TEMPLATE,
+                    Some(format!("Template id: {}", data.template_id)),
+                )
+            })?;
+            for source_id in &source_template_ids {
+                let source = templates.get(source_id).ok_or_else(|| {
+                    AppError::new(
+                        error_codes::NOT_FOUND_CARDS_CONVERT_TEMPLATE,
+                        Some(format!("Template id: {}", source_id)),
+                    )
+                })?;
+                validate_field_mapping(&data.field_mapping, &source.content.fields, &target.content.fields)?;
+            }
+
+            let fields = &target.content.fields;
+            let validator = ContentValidator::new(fields);
+            let mut unique = UniqueFieldIndex::default();
+            let mut converted = Vec::new();
+            let mut failed = Vec::new();
+            for card in &cards {
+                if card.template_id == target.id {
+                    continue;
+                }
+
+                let content = remap_content(&card.content, Some(&data.field_mapping));
+                let checked = validator
+                    .validate(&content)
+                    .and_then(|()| unique.check(tx, Some(card.id), card.deck_id, target.id, fields, &content));
+                match checked {
+                    Ok(()) => {
+                        unique.record(card.id, card.deck_id, target.id, fields, &content);
+                        converted.push((card.id, content));
+                    }
+                    Err(err) => failed.push(ConvertCardsTemplateFailure {
+                        card_id: card.id,
+                        code: err.code,
+                        details: err.details,
+                    }),
+                }
+            }
+
+            // INVARIANT: the conversion is all-or-nothing; a single failing card leaves every
+            // card on its old template so the report can be fixed and re-run as-is.
+            if !failed.is_empty() {
+                return Ok(ConvertCardsTemplateResult {
+                    converted: Vec::new(),
+                    failed,
+                    unlocked_template_ids: Vec::new(),
+                });
+            }
+
+            for (id, content) in &converted {
+                tx.execute(
+                    r#"
+                    UPDATE cards
+                    SET template_id = ?1, content = ?2, updated_at = ?3
+                    WHERE id = ?4
+                    "#,
+                    params![target.id, serde_json::to_string(content)?, now, id],
+                )?;
+            }
+
+            let mut unlocked_template_ids = Vec::new();
+            for source_id in source_template_ids {
+                let is_referenced: bool = tx.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM cards WHERE template_id = ?1 LIMIT 1)",
+                    params![source_id],
+                    |row| row.get(0),
+                )?;
+                if !is_referenced {
+                    unlocked_template_ids.push(source_id);
+                }
+            }
+
+            Ok(ConvertCardsTemplateResult {
+                converted: converted.into_iter().map(|(id, _)| id).collect(),
+                failed,
+                unlocked_template_ids,
+            })
+        })
+    })
+}
+
+pub fn find_replace(db: &Database, data: FindReplaceData) -> Result<FindReplaceResult, AppError> {
+    find_replace_with_task(db, data, &TaskContext::default())
+}
+
+/// Preview reads from a snapshot without taking the writer; apply plans and writes in one
+/// transaction, so a cancelled or failing apply leaves every card untouched.
+pub fn find_replace_with_task(
+    db: &Database,
+    data: FindReplaceData,
+    task: &TaskContext,
+) -> Result<FindReplaceResult, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        let matcher = data.validate()?;
+
+        if data.mode == FindReplaceMode::Preview {
+            let (changes, failed) = db.with_read(|conn| plan_find_replace(conn, &data, &matcher, task))?;
+            return Ok(FindReplaceResult {
+                changes,
+                failed,
+                is_applied: false,
+            });
+        }
+
+        let now = get_current_timestamp()?;
+        db.with_transaction(|tx| {
+            let (changes, failed) = plan_find_replace(tx, &data, &matcher, task)?;
+
+            let is_applied = failed.is_empty();
+            if is_applied {
+                task.check()?;
+                for change in &changes {
+                    tx.execute(
+                        "UPDATE cards SET content = ?1, updated_at = ?2 WHERE id = ?3",
+                        params![serde_json::to_string(&change.after)?, now, change.card_id],
+                    )?;
+                }
+            }
+
+            Ok(FindReplaceResult {
+                changes,
+                failed,
+                is_applied,
+            })
+        })
+    })
+}
+
+// WHY: preview and apply share one plan, so preview never shows a change that apply would reject.
+fn plan_find_replace(
+    conn: &Connection,
+    data: &FindReplaceData,
+    matcher: &FindReplaceMatcher,
+    task: &TaskContext,
+) -> Result<(Vec<FindReplaceCardChange>, Vec<FindReplaceFailure>), AppError> {
+    let cards = select_cards_by_selection(conn, &data.selection)?;
+    let template_ids: Vec<i64> = {
+        let mut ids: Vec<i64> = cards.iter().map(|c| c.template_id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    };
+    let templates = select_templates_by_ids(conn, &template_ids)?;
+    let validators: HashMap<i64, ContentValidator> = templates
+        .iter()
+        .map(|(id, template)| (*id, ContentValidator::new(&template.content.fields)))
+        .collect();
+
+    let total = cards.len();
+    let mut changes = Vec::new();
+    let mut failed = Vec::new();
+    let mut unique = UniqueFieldIndex::default();
+    for (done, card) in cards.into_iter().enumerate() {
+        task.step(done, total)?;
+        let Some(after) = replace_in_content(&card.content, data.field_id, matcher, &data.replacement) else {
+            continue;
+        };
+
+        let validator = validators.get(&card.template_id).ok_or_else(|| {
+            AppError::new(
+                error_codes::NOT_FOUND_CARDS_UPDATE_TEMPLATE,
+                Some(format!("Template id: {}", card.template_id)),
+            )
+        })?;
+        let fields = validator.fields();
+        let checked = validator
+            .validate(&after)
+            .and_then(|()| unique.check(conn, Some(card.id), card.deck_id, card.template_id, fields, &after));
+        if let Err(err) = checked {
+            failed.push(FindReplaceFailure {
+                card_id: card.id,
+                code: err.code,
+                details: err.details,
+            });
+            continue;
+        }
+        unique.record(card.id, card.deck_id, card.template_id, fields, &after);
+
+        changes.push(FindReplaceCardChange {
+            card_id: card.id,
+            before: card.content,
+            after,
+        });
+    }
+    task.progress(total, total);
+
+    Ok((changes, failed))
+}
+
+pub fn find_duplicate_cards(
+    db: &Database,
+    params: FindDuplicateCardsParams,
+) -> Result<Vec<DuplicateCardGroup>, AppError> {
+    find_duplicate_cards_with_task(db, params, &TaskContext::default())
+}
+
+pub fn find_duplicate_cards_with_task(
+    db: &Database,
+    params: FindDuplicateCardsParams,
+    task: &TaskContext,
+) -> Result<Vec<DuplicateCardGroup>, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        params.selection.validate()?;
+
+        let (cards, templates) = db.with_read(|conn| {
+            let cards = select_cards_by_selection(conn, &params.selection)?;
+            let mut template_ids: Vec<i64> = cards.iter().map(|card| card.template_id).collect();
+            template_ids.sort_unstable();
+            template_ids.dedup();
+            let templates = select_templates_by_ids(conn, &template_ids)?;
+            Ok((cards, templates))
+        })?;
+        task.check()?;
+
+        let total = cards.len();
+        // WHY: grouping is a pure iterator pass; stopping the iterator early is the only way in.
+        let groups = group_duplicate_cards(
+            cards
+                .iter()
+                .enumerate()
+                .take_while(|_| !task.is_cancelled())
+                .inspect(|(done, _)| task.progress(*done, total))
+                .map(|(_, card)| {
+                    let fields = templates
+                        .get(&card.template_id)
+                        .map(|template| template.content.fields.as_slice())
+                        .unwrap_or_default();
+                    (card.id, card.template_id, plain_text_content(&card.content, fields))
+                })
+                .collect::<Vec<_>>()
+                .iter()
+                .map(|(id, template_id, content)| (*id, *template_id, content)),
+            params.field_ids.as_deref(),
+        );
+        task.check()?;
+        task.progress(total, total);
+
+        Ok(groups)
+    })
+}
+
+/// Moves the reviews of `merge_ids` onto `keep_id`, deletes those cards and replays the kept
+/// card's progress from the combined history. All cards must share a template.
+///
+/// Needs no `unique_in_deck` check: the kept card's content stays as it is and the merged
+/// cards are deleted, so no field value gains a holder.
+pub fn merge_cards(db: &Database, data: MergeCardsData) -> Result<Card, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        data.validate()?;
+
+        let mut ids = data.merge_ids.clone();
+        ids.push(data.keep_id);
+        let now = get_current_timestamp()?;
+
+        db.with_transaction(|tx| {
+            let cards = select_cards_by_ids(tx, &ids)?;
+            if let Some(missing) = ids.iter().find(|id| !cards.iter().any(|c| c.id == **id)) {
+                return Err(AppError::new(
+                    error_codes::NOT_FOUND_CARDS_MERGE_CARD,
+                    Some(format!("Card id: {}", missing)),
+                ));
+            }
+            let keep = cards
+                .iter()
+                .find(|card| card.id == data.keep_id)
+                .ok_or_else(|| AppError::new(error_codes::NOT_FOUND_CARDS_MERGE_CARD, None))?;
+            if let Some(other) = cards.iter().find(|card| card.template_id != keep.template_id) {
+                return Err(AppError::new(
+                    error_codes::VALIDATION_CARDS_MERGE_TEMPLATE,
+                    Some(format!(
+                        "Card id: {}, template id: {}, kept card template id: {}",
+                        other.id, other.template_id, keep.template_id
+                    )),
+                ));
+            }
+
+            let placeholders: Vec<String> = data
+                .merge_ids
+                .iter()
+                .enumerate()
+                .map(|(i, _)| format!("?{}", i + 1))
+                .collect();
+            let merge_params: Vec<&dyn rusqlite::ToSql> =
+                data.merge_ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
+            let keep_param = format!("?{}", data.merge_ids.len() + 1);
+            let mut reassign_params = merge_params.clone();
+            reassign_params.push(&data.keep_id);
+
+            // INVARIANT: reviews are re-pointed, not re-inserted, so their ids and
+            // `created_at` survive.
+            tx.execute(
+                &format!(
+                    "UPDATE reviews SET card_id = {keep_param} WHERE card_id IN ({})",
+                    placeholders.join(", ")
+                ),
+                reassign_params.as_slice(),
+            )?;
+            tx.execute(
+                &format!("DELETE FROM cards WHERE id IN ({})", placeholders.join(", ")),
+                merge_params.as_slice(),
+            )?;
+            tx.execute(
+                "UPDATE cards SET updated_at = ?1 WHERE id = ?2",
+                params![now, data.keep_id],
+            )?;
+
+            let algorithm = select_deck_algorithm(tx, keep.deck_id)?;
+            // WHY: a preset that fails validation cannot schedule, so the stored progress stays.
+            if let Ok(scheduler) = FsrsScheduler::new(&algorithm) {
+                replay_card_progress(tx, keep, &scheduler)?;
+            }
+
+            Ok(())
+        })?;
+
+        get_card(db, data.keep_id)?.ok_or_else(|| AppError::new(error_codes::DB_UPDATE, None))
+    })
+}
+
+struct RelocationNotFoundCodes {
+    deck: &'static str,
+    card: &'static str,
+    template: &'static str,
+}
+
+const MOVE_NOT_FOUND_CODES: RelocationNotFoundCodes = RelocationNotFoundCodes {
+    deck: error_codes::NOT_FOUND_CARDS_MOVE_DECK,
+    card: error_codes::NOT_FOUND_CARDS_MOVE_CARD,
+    template: error_codes::NOT_FOUND_CARDS_MOVE_TEMPLATE,
+};
+
+const COPY_NOT_FOUND_CODES: RelocationNotFoundCodes = RelocationNotFoundCodes {
+    deck: error_codes::NOT_FOUND_CARDS_COPY_DECK,
+    card: error_codes::NOT_FOUND_CARDS_COPY_CARD,
+    template: error_codes::NOT_FOUND_CARDS_COPY_TEMPLATE,
+};
+
+/// Target deck and template, with each card's content remapped onto the template.
+struct Relocation {
+    deck: Deck,
+    template: Template,
+    cards: Vec<(Card, CardContent)>,
+}
+
+// INVARIANT: runs inside the write transaction and validates every card against the target
+// deck's template before any write, so move/copy are all-or-nothing against a stable snapshot.
+fn plan_relocation(
+    conn: &Connection,
+    ids: &[i64],
+    deck_id: i64,
+    field_mapping: Option<&[CardFieldMapping]>,
+    codes: &RelocationNotFoundCodes,
+) -> Result<Relocation, AppError> {
+    let deck =
+        select_deck(conn, deck_id)?.ok_or_else(|| AppError::new(codes.deck, Some(format!("Deck id: {}", deck_id))))?;
+
+    let cards = select_cards_by_ids(conn, ids)?;
+    if let Some(missing) = ids.iter().find(|id| !cards.iter().any(|c| c.id == **id)) {
+        return Err(AppError::new(codes.card, Some(format!("Card id: {}", missing))));
+    }
+
+    let template_ids: Vec<i64> = {
+        let mut ids: Vec<i64> = cards.iter().map(|c| c.template_id).collect();
+        ids.push(deck.template_id);
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    };
+    let templates = select_templates_by_ids(conn, &template_ids)?;
+    let target = templates
+        .get(&deck.template_id)
+        .ok_or_else(|| AppError::new(codes.template, Some(format!("Template id: {}", deck.template_id))))?;
+    let validator = ContentValidator::new(&target.content.fields);
+
+    let relocated = cards
+        .into_iter()
+        .map(|card| {
+            let mapping = if card.template_id == target.id {
+                None
+            } else {
+                let mapping = field_mapping.ok_or_else(|| {
+                    AppError::new(
+                        error_codes::VALIDATION_CARDS_FIELD_MAPPING_MISSING,
+                        Some(format!("Template id: {}", card.template_id)),
+                    )
+                })?;
+                let source = templates
+                    .get(&card.template_id)
+                    .ok_or_else(|| AppError::new(codes.template, Some(format!("Template id: {}", card.template_id))))?;
+                validate_field_mapping(mapping, &source.content.fields, &target.content.fields)?;
+                Some(mapping)
+            };
+
+            let content = remap_content(&card.content, mapping);
+            validator
+                .validate(&content)
+                .map_err(|err| relocation_error(card.id, err))?;
+
+            Ok((card, content))
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    Ok(Relocation {
+        deck,
+        template: target.clone(),
+        cards: relocated,
+    })
+}
+
+fn relocation_error(card_id: i64, err: AppError) -> AppError {
+    AppError::new(
+        &err.code,
+        Some(format!("Card id: {}, {}", card_id, err.details.unwrap_or_default())),
+    )
+}
@@ -0,0 +1,77 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ValueRef};
+use rusqlite::{params, Connection};
+use std::str::FromStr;
+
+use crate::app::db::Database;
+use crate::app::error::{error_codes, throw_known_error, AppError};
+use crate::domain::changes::{
+    ChangeEntity, ChangeEvent, ChangeOperation, ChangesPage, GetChangesParams, DEFAULT_CHANGES_LIMIT,
+};
+
+fn parse_text<T: FromStr>(value: ValueRef<'_>) -> FromSqlResult<T> {
+    match value {
+        ValueRef::Text(text) => T::from_str(std::str::from_utf8(text).map_err(|e| FromSqlError::Other(Box::new(e)))?)
+            .map_err(|_parse_err| FromSqlError::InvalidType),
+        _ => Err(FromSqlError::InvalidType),
+    }
+}
+
+impl FromSql for ChangeEntity {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        parse_text(value)
+    }
+}
+
+impl FromSql for ChangeOperation {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        parse_text(value)
+    }
+}
+
+fn get_change_event_row(row: &rusqlite::Row<'_>) -> Result<ChangeEvent, rusqlite::Error> {
+    Ok(ChangeEvent {
+        id: row.get(0)?,
+        entity: row.get(1)?,
+        entity_id: row.get(2)?,
+        operation: row.get(3)?,
+        changed_at: row.get(4)?,
+    })
+}
+
+pub fn get_changes(db: &Database, params: GetChangesParams) -> Result<ChangesPage, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        db.with_read(|conn| select_changes(conn, &params))
+    })
+}
+
+fn select_changes(conn: &Connection, params: &GetChangesParams) -> Result<ChangesPage, AppError> {
+    let limit = params.limit.unwrap_or(DEFAULT_CHANGES_LIMIT).max(1);
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, entity, entity_id, operation, changed_at
+        FROM change_feed
+        WHERE id > ?1
+        ORDER BY id
+        LIMIT ?2
+        "#,
+    )?;
+    let events = stmt
+        .query_map(params![params.cursor, limit], get_change_event_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ChangesPage {
+        cursor: events.last().map_or(params.cursor, |event| event.id),
+        has_more: events.len() >= limit as usize,
+        events,
+    })
+}
+
+/// Position of the newest event, for subscribers that only want changes from now on.
+pub fn get_latest_change_cursor(db: &Database) -> Result<i64, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        db.with_read(|conn| {
+            let cursor = conn.query_row("SELECT coalesce(max(id), 0) FROM change_feed", [], |row| row.get(0))?;
+            Ok(cursor)
+        })
+    })
+}
@@ -25,7 +25,7 @@ fn get_conversation_row(row: &rusqlite::Row<'_>) -> Result<Conversation, rusqlit
 
 pub fn get_conversation(db: &Database, id: &str) -> Result<Option<Conversation>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             conn.query_row(
                 r#"
                 SELECT id, title, state, created_at, updated_at
@@ -44,7 +44,7 @@ pub fn get_conversation(db: &Database, id: &str) -> Result<Option<Conversation>,
 
 pub fn get_conversations(db: &Database) -> Result<Vec<Conversation>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let mut stmt = conn.prepare(
                 r#"
                 SELECT id, title, state, created_at, updated_at
@@ -64,6 +64,7 @@ pub fn get_conversations(db: &Database) -> Result<Vec<Conversation>, AppError> {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "ts", derive(specta::Type))]
 #[serde(rename_all = "camelCase")]
 pub struct SetConversationInput {
     pub id: String,
@@ -78,7 +79,7 @@ pub fn set_conversation(db: &Database, input: SetConversationInput) -> Result<Co
     throw_known_error(error_codes::DB_UPDATE, || {
         let now = get_current_timestamp()?;
 
-        db.with_conn(|conn| {
+        db.with_transaction(|conn| {
             // WHY: First insert must stamp `updated_at` (caller value or now).
             // Leaving it NULL sorted new chats to the bottom of the sidebar
             // (`ORDER BY updated_at DESC`) until a later upsert — typically
@@ -110,7 +111,7 @@ pub fn set_conversation(db: &Database, input: SetConversationInput) -> Result<Co
 
 pub fn delete_conversation(db: &Database, id: &str) -> Result<(), AppError> {
     throw_known_error(error_codes::DB_DELETE, || {
-        db.with_conn(|conn| {
+        db.with_transaction(|conn| {
             conn.execute("DELETE FROM conversations WHERE id = ?1", params![id])?;
             Ok(())
         })
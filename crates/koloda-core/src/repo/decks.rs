@@ -1,11 +1,17 @@
-use rusqlite::{params, OptionalExtension};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
 
 use crate::app::db::Database;
 use crate::app::error::{error_codes, throw_known_error, AppError};
+use crate::app::task::TaskContext;
 use crate::app::utility::get_current_timestamp;
+use crate::domain::cards::ContentValidator;
+use crate::domain::deck_bundle::{DeckBundle, DeckBundleCard, DeckBundlePreset, DECK_BUNDLE_VERSION};
 use crate::domain::decks::{Deck, DeleteDeckData, InsertDeckData, UpdateDeckData};
-use crate::repo::algorithms::get_algorithm;
-use crate::repo::templates::get_template;
+use crate::domain::versioning::VersionedJson;
+use crate::repo::algorithms::{get_algorithm, insert_algorithm};
+use crate::repo::cards::{get_cards, insert_card_row, UniqueFieldIndex};
+use crate::repo::templates::{get_template, insert_template};
 
 fn get_deck_row(row: &rusqlite::Row<'_>) -> Result<Deck, rusqlite::Error> {
     Ok(Deck {
@@ -20,7 +26,7 @@ fn get_deck_row(row: &rusqlite::Row<'_>) -> Result<Deck, rusqlite::Error> {
 
 pub fn get_decks(db: &Database) -> Result<Vec<Deck>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let mut stmt = conn.prepare(
                 r#"
                 SELECT id, title, algorithm_id, template_id, created_at, updated_at
@@ -53,7 +59,7 @@ pub fn get_decks_by_ids(db: &Database, ids: &[i64]) -> Result<Vec<Deck>, AppErro
             placeholders.join(", ")
         );
 
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let params: Vec<&dyn rusqlite::ToSql> = ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
             let mut stmt = conn.prepare(&sql)?;
             let decks = stmt
@@ -66,22 +72,22 @@ pub fn get_decks_by_ids(db: &Database, ids: &[i64]) -> Result<Vec<Deck>, AppErro
 }
 
 pub fn get_deck(db: &Database, id: i64) -> Result<Option<Deck>, AppError> {
-    throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
-            conn.query_row(
-                r#"
-                SELECT id, title, algorithm_id, template_id, created_at, updated_at
-                FROM decks
-                WHERE id = ?1
-                LIMIT 1
-                "#,
-                params![id],
-                get_deck_row,
-            )
-            .optional()
-            .map_err(AppError::from)
-        })
-    })
+    throw_known_error(error_codes::DB_GET, || db.with_read(|conn| select_deck(conn, id)))
+}
+
+pub(crate) fn select_deck(conn: &Connection, id: i64) -> Result<Option<Deck>, AppError> {
+    conn.query_row(
+        r#"
+        SELECT id, title, algorithm_id, template_id, created_at, updated_at
+        FROM decks
+        WHERE id = ?1
+        LIMIT 1
+        "#,
+        params![id],
+        get_deck_row,
+    )
+    .optional()
+    .map_err(AppError::from)
 }
 
 pub fn add_deck(db: &Database, data: InsertDeckData) -> Result<Deck, AppError> {
@@ -103,7 +109,7 @@ pub fn add_deck(db: &Database, data: InsertDeckData) -> Result<Deck, AppError> {
 
         let now = get_current_timestamp()?;
 
-        let id = db.with_conn(|conn| {
+        let id = db.with_transaction(|conn| {
             conn.execute(
                 r#"
                 INSERT INTO decks (title, algorithm_id, template_id, created_at, updated_at)
@@ -144,7 +150,7 @@ pub fn update_deck(db: &Database, data: UpdateDeckData) -> Result<Deck, AppError
 
         let now = get_current_timestamp()?;
 
-        db.with_conn(|conn| {
+        db.with_transaction(|conn| {
             conn.execute(
                 r#"
                 UPDATE decks
@@ -173,10 +179,143 @@ pub fn update_deck(db: &Database, data: UpdateDeckData) -> Result<Deck, AppError
 
 pub fn delete_deck(db: &Database, data: DeleteDeckData) -> Result<(), AppError> {
     throw_known_error(error_codes::DB_DELETE, || {
-        db.with_conn(|conn| {
+        db.with_transaction(|conn| {
             conn.execute("DELETE FROM decks WHERE id = ?1", params![data.id])?;
 
             Ok(())
         })
     })
 }
+
+pub fn export_deck(db: &Database, id: i64) -> Result<DeckBundle, AppError> {
+    export_deck_with_task(db, id, &TaskContext::default())
+}
+
+pub fn export_deck_with_task(db: &Database, id: i64, task: &TaskContext) -> Result<DeckBundle, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        task.check()?;
+        let deck = get_deck(db, id)?.ok_or_else(|| {
+            AppError::new(
+                error_codes::NOT_FOUND_DECKS_EXPORT_DECK,
+                Some(format!("Deck id: {}", id)),
+            )
+        })?;
+        let algorithm = get_algorithm(db, deck.algorithm_id)?.ok_or_else(|| {
+            AppError::new(
+                error_codes::NOT_FOUND_DECKS_UPDATE_ALGORITHM,
+                Some(format!("Algorithm id: {}", deck.algorithm_id)),
+            )
+        })?;
+        let template = get_template(db, deck.template_id)?.ok_or_else(|| {
+            AppError::new(
+                error_codes::NOT_FOUND_DECKS_UPDATE_TEMPLATE,
+                Some(format!("Template id: {}", deck.template_id)),
+            )
+        })?;
+
+        let cards = get_cards(db, id)?;
+        let total = cards.len();
+        let mut bundle_cards = Vec::with_capacity(total);
+        for (index, card) in cards.into_iter().enumerate() {
+            task.step(index, total)?;
+            bundle_cards.push(DeckBundleCard::from(card));
+        }
+        task.progress(total, total);
+
+        Ok(DeckBundle {
+            version: DECK_BUNDLE_VERSION,
+            exported_at: get_current_timestamp()?,
+            title: deck.title,
+            algorithm: DeckBundlePreset {
+                title: algorithm.title,
+                content: algorithm.content.to_versioned()?,
+            },
+            template: DeckBundlePreset {
+                title: template.title,
+                content: template.content.to_versioned()?,
+            },
+            cards: bundle_cards,
+        })
+    })
+}
+
+/// Always creates a new deck. The algorithm and template are reused when a row with the same
+/// title and content exists, otherwise added. Nothing is written if any card fails validation.
+pub fn import_deck(db: &Database, bundle: DeckBundle) -> Result<Deck, AppError> {
+    import_deck_with_task(db, bundle, &TaskContext::default())
+}
+
+/// Runs in one transaction, so a cancellation leaves no partial deck behind.
+pub fn import_deck_with_task(db: &Database, bundle: DeckBundle, task: &TaskContext) -> Result<Deck, AppError> {
+    throw_known_error(error_codes::DB_ADD, || {
+        let parsed = bundle.parse()?;
+        let now = get_current_timestamp()?;
+        let total = parsed.cards.len();
+
+        let id = db.with_transaction(|tx| {
+            let algorithm_content = parsed.algorithm.content.to_versioned()?;
+            let algorithm_id = match find_preset(tx, "algorithms", &parsed.algorithm.title, &algorithm_content)? {
+                Some(id) => id,
+                None => insert_algorithm(tx, &parsed.algorithm, now)?,
+            };
+            let template_content = parsed.template.content.to_versioned()?;
+            let template_id = match find_preset(tx, "templates", &parsed.template.title, &template_content)? {
+                Some(id) => id,
+                None => insert_template(tx, &parsed.template, now)?,
+            };
+
+            tx.execute(
+                r#"
+                INSERT INTO decks (title, algorithm_id, template_id, created_at, updated_at)
+                VALUES (?1, ?2, ?3, ?4, NULL)
+                "#,
+                params![parsed.title, algorithm_id, template_id, now],
+            )?;
+            let deck_id = tx.last_insert_rowid();
+
+            let fields = &parsed.template.content.fields;
+            let validator = ContentValidator::new(fields);
+            let mut unique = UniqueFieldIndex::default();
+            for (index, card) in parsed.cards.into_iter().enumerate() {
+                task.step(index, total)?;
+                let data = card.into_insert(deck_id, template_id);
+                data.validate_with(&validator)
+                    .and_then(|()| unique.check(tx, None, deck_id, template_id, fields, &data.content))
+                    .map_err(|error| {
+                        let details = match error.details {
+                            Some(details) => format!("Card {}: {}", index, details),
+                            None => format!("Card {}", index),
+                        };
+                        AppError::new(&error.code, Some(details))
+                    })?;
+                let card_id = insert_card_row(tx, &data, now)?;
+                unique.record(card_id, deck_id, template_id, fields, &data.content);
+            }
+            task.progress(total, total);
+
+            Ok(deck_id)
+        })?;
+
+        get_deck(db, id)?.ok_or_else(|| AppError::new(error_codes::DB_ADD, None))
+    })
+}
+
+fn find_preset(conn: &Connection, table: &str, title: &str, content: &Value) -> Result<Option<i64>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, content FROM {} WHERE title = ?1 ORDER BY created_at",
+        table
+    ))?;
+    let rows = stmt
+        .query_map(params![title], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (id, stored) in rows {
+        if serde_json::from_str::<Value>(&stored)? == *content {
+            return Ok(Some(id));
+        }
+    }
+
+    Ok(None)
+}
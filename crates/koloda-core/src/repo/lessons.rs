@@ -36,7 +36,7 @@ fn sum_lesson_amounts(decks: &[LessonDeck]) -> LessonAmounts {
 
 pub fn get_lessons(db: &Database, params: GetLessonsParams) -> Result<LessonsResult, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let deck_ids = params
                 .filters
                 .as_ref()
@@ -89,7 +89,7 @@ pub fn get_lesson_cards(db: &Database, params: &GetLessonDataParams) -> Result<V
     throw_known_error(error_codes::DB_GET, || {
         params.validate()?;
 
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let deck_ids = params.filters.deck_ids.as_deref().filter(|ids| !ids.is_empty());
             let mut next_param = 1;
             let mut query_params: Vec<i64> = Vec::new();
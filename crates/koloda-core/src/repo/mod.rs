@@ -5,11 +5,15 @@
 
 pub mod ai;
 pub mod algorithms;
+pub mod anki_connect;
 pub mod cards;
+pub mod changes;
 pub mod conversations;
+pub mod database_check;
 pub mod decks;
 pub mod fsrs_sql;
 pub mod lessons;
 pub mod reviews;
 pub mod settings;
+pub mod sync;
 pub mod templates;
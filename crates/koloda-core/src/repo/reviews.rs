@@ -2,6 +2,7 @@ use rusqlite::{params, Connection, Row};
 
 use crate::app::db::Database;
 use crate::app::error::{error_codes, throw_known_error, AppError};
+use crate::app::task::TaskContext;
 use crate::domain::learning_day::current_learning_day_range;
 use crate::domain::reviews::{
     calculate_todays_review_totals, GetReviewTotalsParams, GetReviewsData, InsertReviewData, Review, ReviewTotals,
@@ -25,12 +26,14 @@ fn get_review_row(row: &Row) -> Result<Review, rusqlite::Error> {
         learning_steps: row.get(8)?,
         time: row.get(9)?,
         is_ignored: row.get(10)?,
-        created_at: row.get(11)?,
+        typed_answer: row.get(11)?,
+        typed_score: row.get(12)?,
+        created_at: row.get(13)?,
     })
 }
 
-// WHY pub(crate): no NAPI `cmd_*` needs a direct review insert today — the only
-// writer is `submit_lesson_result`, which calls this inside its own transaction.
+// WHY pub(crate): no NAPI `cmd_*` needs a direct review insert today — the writers
+// are `submit_lesson_result` and `copy_cards`, each calling this inside its own transaction.
 // Keeping it internal mirrors the seed helpers (`insert_algorithm`, …) so the
 // public repo surface stays read-side until a real second writer appears.
 // INVARIANT: callers validate `data` (see `InsertReviewData::validate`) and wrap
@@ -39,8 +42,9 @@ pub(crate) fn insert_review(conn: &Connection, data: &InsertReviewData, now: i64
     conn.execute(
         r#"
         INSERT INTO reviews (card_id, rating, state, due_at, stability, difficulty,
-                            scheduled_days, learning_steps, time, is_ignored, created_at)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                            scheduled_days, learning_steps, time, is_ignored, typed_answer, typed_score,
+                            created_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
         "#,
         params![
             data.card_id,
@@ -53,6 +57,8 @@ pub(crate) fn insert_review(conn: &Connection, data: &InsertReviewData, now: i64
             data.learning_steps,
             data.time,
             data.is_ignored,
+            data.typed_answer,
+            data.typed_score,
             now
         ],
     )?;
@@ -60,30 +66,42 @@ pub(crate) fn insert_review(conn: &Connection, data: &InsertReviewData, now: i64
     Ok(conn.last_insert_rowid())
 }
 
-pub fn get_reviews(db: &Database, data: GetReviewsData) -> Result<Vec<Review>, AppError> {
-    throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
-            let mut stmt = conn.prepare(
-                r#"
-                SELECT id, card_id, rating, state, due_at, stability, difficulty,
-                       scheduled_days, learning_steps, time, is_ignored, created_at
-                FROM reviews
-                WHERE card_id = ?1
-                "#,
-            )?;
+pub(crate) fn select_card_reviews(conn: &Connection, card_id: i64) -> Result<Vec<Review>, AppError> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, card_id, rating, state, due_at, stability, difficulty,
+               scheduled_days, learning_steps, time, is_ignored, typed_answer, typed_score, created_at
+        FROM reviews
+        WHERE card_id = ?1
+        "#,
+    )?;
 
-            let reviews = stmt
-                .query_map(params![data.card_id], get_review_row)?
-                .collect::<Result<Vec<_>, _>>()?;
+    let reviews = stmt
+        .query_map(params![card_id], get_review_row)?
+        .collect::<Result<Vec<_>, _>>()?;
 
-            Ok(reviews)
-        })
+    Ok(reviews)
+}
+
+pub fn get_reviews(db: &Database, data: GetReviewsData) -> Result<Vec<Review>, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        db.with_read(|conn| select_card_reviews(conn, data.card_id))
     })
 }
 
 pub fn get_review_totals(db: &Database, params: GetReviewTotalsParams) -> Result<ReviewTotals, AppError> {
+    get_review_totals_with_task(db, params, &TaskContext::default())
+}
+
+/// A single aggregate query, so cancelling only takes effect before it starts.
+pub fn get_review_totals_with_task(
+    db: &Database,
+    params: GetReviewTotalsParams,
+    task: &TaskContext,
+) -> Result<ReviewTotals, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        task.step(0, 1)?;
+        let totals = db.with_read(|conn| {
             // WHY: The outer WHERE already restricts rows to the learning-day window `[from, to)`.
             // `untouched` only needs `state = New` inside its FILTER because that window applies
             // to every bucket. The extra `created_at < ?2` on learn/review/total is redundant
@@ -118,7 +136,10 @@ pub fn get_review_totals(db: &Database, params: GetReviewTotalsParams) -> Result
             )?;
 
             Ok(result)
-        })
+        })?;
+        task.progress(1, 1);
+
+        Ok(totals)
     })
 }
 
@@ -2,12 +2,26 @@ use std::str::FromStr;
 
 use rusqlite::types::{FromSql, FromSqlResult, ValueRef};
 use rusqlite::{params, Connection, OptionalExtension};
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use crate::app::db::{parse_json_column, Database};
 use crate::app::error::{error_codes, throw_known_error, AppError};
+use crate::app::task::TaskContext;
 use crate::app::utility::get_current_timestamp;
-use crate::domain::settings::{Settings, SettingsName};
+use crate::domain::algorithms::UpdateAlgorithmValues;
+use crate::domain::settings::{
+    diff_settings_content, merge_patch_between, DiffSettingsParams, GetSettingsHistoryParams, Settings,
+    SettingsDiffEntry, SettingsHistoryEntry, SettingsName, SETTINGS_HISTORY_LIMIT,
+};
+use crate::domain::settings_ai::AISettings;
+use crate::domain::settings_bundle::{
+    ImportSettingsBundleResult, SettingsBundle, SettingsBundleSlice, SettingsBundleSliceError, SETTINGS_BUNDLE_VERSION,
+};
+use crate::domain::templates::{TemplateContent, UpdateTemplateValues};
+use crate::domain::versioning::VersionedJson;
+use crate::repo::ai::redact_secrets;
+use crate::repo::algorithms::{get_algorithms, insert_algorithm, update_algorithm_row};
+use crate::repo::templates::{get_templates, insert_template, select_template_locked, update_template_row};
 
 impl FromSql for SettingsName {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
@@ -34,41 +48,104 @@ fn get_settings_row(row: &rusqlite::Row<'_>) -> Result<Settings, rusqlite::Error
     })
 }
 
+pub(crate) fn select_settings(conn: &Connection, name: SettingsName) -> Result<Option<Settings>, AppError> {
+    let settings = conn
+        .query_row(
+            r#"
+            SELECT id, name, content, created_at, updated_at
+            FROM settings
+            WHERE name = ?1
+            LIMIT 1
+            "#,
+            params![name.to_string()],
+            get_settings_row,
+        )
+        .optional()?;
+
+    match settings {
+        Some(mut settings) => {
+            settings.content = name.normalize(settings.content)?;
+            Ok(Some(settings))
+        }
+        None => Ok(None),
+    }
+}
+
 pub fn get_settings(db: &Database, name: SettingsName) -> Result<Option<Settings>, AppError> {
-    throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
-            conn.query_row(
-                r#"
-                SELECT id, name, content, created_at, updated_at
-                FROM settings
-                WHERE name = ?1
-                LIMIT 1
-                "#,
-                params![name.to_string()],
-                get_settings_row,
-            )
-            .optional()
-            .map_err(AppError::from)
-            .and_then(|settings| match settings {
-                Some(mut settings) => {
-                    settings.content = name.normalize(settings.content)?;
-                    Ok(Some(settings))
-                }
-                None => Ok(None),
-            })
-        })
-    })
+    throw_known_error(error_codes::DB_GET, || db.with_read(|conn| select_settings(conn, name)))
 }
 
 pub fn set_settings(db: &Database, name: SettingsName, content: Value) -> Result<Settings, AppError> {
-    throw_known_error(error_codes::DB_UPDATE, || {
-        let content = name.normalize(content)?;
-        let now = get_current_timestamp()?;
+    throw_known_error(error_codes::DB_UPDATE, || write_settings(db, name, content, None))
+}
 
-        db.with_conn(|conn| upsert_settings(conn, name, &content, now))?;
+// INVARIANT: every repo-level settings write goes through here so the row update and its
+// `settings_history` entry commit together. Seeding writes through `upsert_settings` and is not history.
+fn write_settings(
+    db: &Database,
+    name: SettingsName,
+    content: Value,
+    patch: Option<Value>,
+) -> Result<Settings, AppError> {
+    let content = name.normalize(content)?;
+    let now = get_current_timestamp()?;
 
-        get_settings(db, name)?.ok_or_else(|| AppError::new(error_codes::DB_UPDATE, None))
-    })
+    db.with_transaction(|tx| write_settings_in(tx, name, &content, patch, now))
+}
+
+/// Transaction-level half of `write_settings`; `content` must already be normalized.
+pub(crate) fn write_settings_in(
+    conn: &Connection,
+    name: SettingsName,
+    content: &Value,
+    patch: Option<Value>,
+    now: i64,
+) -> Result<Settings, AppError> {
+    // WHY: Raw stored JSON, not normalized — a legacy row that no longer validates must not
+    // block replacing it, and history should show what was actually stored.
+    let previous: Option<Value> = conn
+        .query_row(
+            "SELECT content FROM settings WHERE name = ?1",
+            params![name.to_string()],
+            |row| {
+                let content: String = row.get(0)?;
+                parse_json_column(0, &content)
+            },
+        )
+        .optional()?;
+
+    upsert_settings(conn, name, content, now)?;
+
+    if previous.as_ref() != Some(content) {
+        let patch = patch.unwrap_or_else(|| merge_patch_between(previous.as_ref().unwrap_or(&Value::Null), content));
+        conn.execute(
+            r#"
+            INSERT INTO settings_history (name, old_content, new_content, patch, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+            params![
+                name.to_string(),
+                previous.as_ref().map(Value::to_string),
+                content.to_string(),
+                patch.to_string(),
+                now
+            ],
+        )?;
+        conn.execute(
+            r#"
+            DELETE FROM settings_history
+            WHERE name = ?1 AND id NOT IN (
+                SELECT id FROM settings_history
+                WHERE name = ?1
+                ORDER BY created_at DESC, id DESC
+                LIMIT ?2
+            )
+            "#,
+            params![name.to_string(), SETTINGS_HISTORY_LIMIT],
+        )?;
+    }
+
+    select_settings(conn, name)?.ok_or_else(|| AppError::new(error_codes::DB_UPDATE, None))
 }
 
 pub(crate) fn upsert_settings(
@@ -96,8 +173,295 @@ pub fn patch_settings(db: &Database, name: SettingsName, patch: Value) -> Result
         let existing = get_settings(db, name)?.ok_or_else(|| AppError::new(error_codes::DB_UPDATE, None))?;
         let mut merged = existing.content.clone();
         json_patch::merge(&mut merged, &patch);
-        let merged = name.normalize(merged)?;
 
-        set_settings(db, name, merged)
+        write_settings(db, name, merged, Some(patch))
+    })
+}
+
+fn get_settings_history_row(row: &rusqlite::Row<'_>) -> Result<SettingsHistoryEntry, rusqlite::Error> {
+    let old_content: Option<String> = row.get(2)?;
+    let new_content: String = row.get(3)?;
+    let patch: String = row.get(4)?;
+
+    Ok(SettingsHistoryEntry {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        old_content: old_content.map(|c| parse_json_column(2, &c)).transpose()?,
+        new_content: parse_json_column(3, &new_content)?,
+        patch: parse_json_column(4, &patch)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn select_settings_history_entry(conn: &Connection, id: i64) -> Result<SettingsHistoryEntry, AppError> {
+    conn.query_row(
+        r#"
+        SELECT id, name, old_content, new_content, patch, created_at
+        FROM settings_history
+        WHERE id = ?1
+        "#,
+        params![id],
+        get_settings_history_row,
+    )
+    .optional()?
+    .ok_or_else(|| {
+        AppError::new(
+            error_codes::NOT_FOUND_SETTINGS_HISTORY_ENTRY,
+            Some(format!("History id: {}", id)),
+        )
+    })
+}
+
+pub fn get_settings_history(
+    db: &Database,
+    params: GetSettingsHistoryParams,
+) -> Result<Vec<SettingsHistoryEntry>, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        db.with_read(|conn| {
+            let mut stmt = conn.prepare(
+                r#"
+                SELECT id, name, old_content, new_content, patch, created_at
+                FROM settings_history
+                WHERE name = ?1
+                ORDER BY created_at DESC, id DESC
+                LIMIT ?2
+                "#,
+            )?;
+            // WHY: SQLite treats a negative LIMIT as "no limit".
+            let limit = params.limit.map_or(-1, i64::from);
+            let entries = stmt
+                .query_map(params![params.name.to_string(), limit], get_settings_history_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(entries)
+        })
+    })
+}
+
+pub fn diff_settings(db: &Database, params: DiffSettingsParams) -> Result<Vec<SettingsDiffEntry>, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        db.with_read(|conn| {
+            let from = select_settings_history_entry(conn, params.from_id)?;
+            let to_content = match params.to_id {
+                Some(to_id) => {
+                    let to = select_settings_history_entry(conn, to_id)?;
+                    if to.name != from.name {
+                        return Err(AppError::new(
+                            error_codes::VALIDATION_SETTINGS_HISTORY_NAME_MISMATCH,
+                            Some(format!("{} vs {}", from.name, to.name)),
+                        ));
+                    }
+                    to.new_content
+                }
+                None => select_settings(conn, from.name)?
+                    .map(|s| s.content)
+                    .unwrap_or(Value::Null),
+            };
+
+            Ok(diff_settings_content(&from.new_content, &to_content))
+        })
+    })
+}
+
+pub fn rollback_settings(db: &Database, id: i64) -> Result<Settings, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        let entry = db.with_read(|conn| select_settings_history_entry(conn, id))?;
+
+        // WHY: Rollback is a regular write — it re-runs `normalize` and lands in history itself,
+        // so it can be undone the same way.
+        write_settings(db, entry.name, entry.new_content, None)
+    })
+}
+
+pub fn export_settings_bundle(db: &Database) -> Result<SettingsBundle, AppError> {
+    export_settings_bundle_with_task(db, &TaskContext::default())
+}
+
+/// Progress counts the three sections of the bundle: settings, algorithms, templates.
+pub fn export_settings_bundle_with_task(db: &Database, task: &TaskContext) -> Result<SettingsBundle, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        task.step(0, 3)?;
+        let exported_at = get_current_timestamp()?;
+        let settings = db.with_read(|conn| {
+            let mut slices = Vec::new();
+            for name in SettingsName::ALL {
+                if let Some(settings) = select_settings(conn, name)? {
+                    let content = match name {
+                        SettingsName::Ai => redact_ai_settings(settings.content)?,
+                        _ => settings.content,
+                    };
+                    slices.push(SettingsBundleSlice { name, content });
+                }
+            }
+
+            Ok(slices)
+        })?;
+        task.step(1, 3)?;
+
+        let algorithms = get_algorithms(db)?
+            .into_iter()
+            .map(|algorithm| {
+                Ok(json!({
+                    "title": algorithm.title,
+                    "content": algorithm.content.to_versioned()?,
+                }))
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        task.step(2, 3)?;
+        let templates = get_templates(db)?
+            .into_iter()
+            .map(|template| {
+                Ok(json!({
+                    "title": template.title,
+                    "content": template.content.to_versioned()?,
+                }))
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        task.progress(3, 3);
+
+        Ok(SettingsBundle {
+            version: SETTINGS_BUNDLE_VERSION,
+            exported_at,
+            settings,
+            algorithms,
+            templates,
+        })
+    })
+}
+
+// WHY: Stored AI settings are already redacted, but a bundle leaves the machine, so strip keys
+// again rather than trusting the row; `has_secrets` would be a lie on the importing machine.
+fn redact_ai_settings(content: Value) -> Result<Value, AppError> {
+    let mut settings: AISettings = serde_json::from_value(content)?;
+    for profile in &mut settings.profiles {
+        profile.secrets = profile.secrets.as_ref().map(redact_secrets);
+        profile.has_secrets = false;
+    }
+
+    Ok(serde_json::to_value(settings)?)
+}
+
+/// Presets and templates are matched by title: an existing one is updated, otherwise a new one is added.
+pub fn import_settings_bundle(db: &Database, bundle: SettingsBundle) -> Result<ImportSettingsBundleResult, AppError> {
+    import_settings_bundle_with_task(db, bundle, &TaskContext::default())
+}
+
+/// Runs in one transaction, so a cancellation rolls back everything written so far.
+pub fn import_settings_bundle_with_task(
+    db: &Database,
+    bundle: SettingsBundle,
+    task: &TaskContext,
+) -> Result<ImportSettingsBundleResult, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        bundle.validate_version()?;
+        let (parsed, errors) = bundle.parse();
+        let now = get_current_timestamp()?;
+        let total = parsed.settings.len() + parsed.algorithms.len() + parsed.templates.len();
+        let mut done = 0;
+
+        db.with_transaction(|tx| {
+            let mut result = ImportSettingsBundleResult {
+                errors,
+                ..ImportSettingsBundleResult::default()
+            };
+
+            let mut template_targets = Vec::with_capacity(parsed.templates.len());
+            for (index, data) in &parsed.templates {
+                let existing: Option<(i64, String)> = tx
+                    .query_row(
+                        "SELECT id, content FROM templates WHERE title = ?1 ORDER BY created_at LIMIT 1",
+                        params![data.title],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+                let Some((id, content)) = existing else {
+                    template_targets.push(None);
+                    continue;
+                };
+
+                // INVARIANT: a template with cards keeps its field set, same rule as `update_template`.
+                let values = UpdateTemplateValues {
+                    title: data.title.clone(),
+                    content: data.content.clone(),
+                };
+                let original = TemplateContent::from_versioned(serde_json::from_str(&content)?)?;
+                let locked = select_template_locked(tx, id)?;
+                if let Err(error) = values.validate(locked.then_some(&original)) {
+                    result
+                        .errors
+                        .push(SettingsBundleSliceError::new(format!("templates/{}", index), error));
+                }
+                template_targets.push(Some(id));
+            }
+
+            if !result.errors.is_empty() {
+                return Ok(result);
+            }
+
+            for (name, content) in &parsed.settings {
+                task.step(done, total)?;
+                done += 1;
+                let content = match name {
+                    // WHY: bundles carry redacted AI settings; writing them raw would drop local
+                    // profiles (orphaning their keyring entries) and clear `has_secrets`.
+                    SettingsName::Ai => {
+                        let local: AISettings = match select_settings(tx, SettingsName::Ai)? {
+                            Some(settings) => serde_json::from_value(settings.content)?,
+                            None => AISettings::default(),
+                        };
+                        let merged = local.merge_import(serde_json::from_value(content.clone())?);
+                        SettingsName::Ai.normalize(serde_json::to_value(merged)?)?
+                    }
+                    _ => content.clone(),
+                };
+                write_settings_in(tx, *name, &content, None, now)?;
+                result.settings.push(*name);
+            }
+
+            for (_, data) in &parsed.algorithms {
+                task.step(done, total)?;
+                done += 1;
+                let existing: Option<i64> = tx
+                    .query_row(
+                        "SELECT id FROM algorithms WHERE title = ?1 ORDER BY created_at LIMIT 1",
+                        params![data.title],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                let id = match existing {
+                    Some(id) => {
+                        let values = UpdateAlgorithmValues {
+                            title: data.title.clone(),
+                            content: data.content.clone(),
+                        };
+                        update_algorithm_row(tx, id, &values, now)?;
+                        id
+                    }
+                    None => insert_algorithm(tx, data, now)?,
+                };
+                result.algorithm_ids.push(id);
+            }
+
+            for ((_, data), target) in parsed.templates.iter().zip(template_targets) {
+                task.step(done, total)?;
+                done += 1;
+                let id = match target {
+                    Some(id) => {
+                        let values = UpdateTemplateValues {
+                            title: data.title.clone(),
+                            content: data.content.clone(),
+                        };
+                        update_template_row(tx, id, &values, now)?;
+                        id
+                    }
+                    None => insert_template(tx, data, now)?,
+                };
+                result.template_ids.push(id);
+            }
+            task.progress(done, total);
+
+            result.applied = true;
+            Ok(result)
+        })
     })
 }
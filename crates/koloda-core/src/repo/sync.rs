@@ -0,0 +1,735 @@
+//! Sync change log reads and conflict-resolving writes; the rules are described in `domain::sync`.
+//!
+//! Triggers from migration V6 log every row change to `sync_changes` and every delete to
+//! `sync_tombstones`. While changes are applied, `sync_state.applying_device` tags the log
+//! entries with their source so they are not sent back to it.
+//!
+//! Since migration V10 the log keeps only each row's newest entry: reads collapse a row to its
+//! current state anyway, and any cursor that had not reached an older entry has not reached the
+//! newest one either.
+
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension};
+use serde_json::Value;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+use crate::app::db::{parse_json_column, Database};
+use crate::app::error::{error_codes, throw_known_error, AppError};
+use crate::app::utility::get_current_timestamp;
+use crate::domain::fsrs::FsrsScheduler;
+use crate::domain::settings::SettingsName;
+use crate::domain::sync::{
+    content_clock, incoming_wins, SyncApplyResult, SyncBatch, SyncCard, SyncCardProgress, SyncChange, SyncCursors,
+    SyncDeck, SyncPreset, SyncRejectedChange, SyncReview, SyncRole, SyncRow, SyncSetting, SyncTable,
+};
+use crate::repo::cards::{replay_card_progress, select_cards_by_ids, select_deck_algorithm};
+use crate::repo::settings::write_settings_in;
+
+const APPLYING_DEVICE_KEY: &str = "applying_device";
+const DEVICE_ID_KEY: &str = "device_id";
+const SERVER_URL_KEY: &str = "server_url";
+const PUSHED_CURSOR_KEY: &str = "pushed_cursor";
+const PULLED_CURSOR_KEY: &str = "pulled_cursor";
+
+fn get_state(conn: &Connection, key: &str) -> Result<Option<String>, rusqlite::Error> {
+    conn.query_row("SELECT value FROM sync_state WHERE key = ?1", params![key], |row| {
+        row.get(0)
+    })
+    .optional()
+}
+
+fn set_state(conn: &Connection, key: &str, value: &str) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT OR REPLACE INTO sync_state (key, value) VALUES (?1, ?2)",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
+fn get_cursor(conn: &Connection, key: &str) -> Result<i64, rusqlite::Error> {
+    Ok(get_state(conn, key)?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default())
+}
+
+/// This database's stable id, created on first use.
+pub fn device_id(db: &Database) -> Result<String, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        db.with_transaction(|tx| {
+            if let Some(id) = get_state(tx, DEVICE_ID_KEY)? {
+                return Ok(id);
+            }
+            let id = Uuid::new_v4().to_string();
+            set_state(tx, DEVICE_ID_KEY, &id)?;
+            Ok(id)
+        })
+    })
+}
+
+/// Cursors for `server_url`; switching servers starts over so the new one receives everything.
+pub fn get_cursors(db: &Database, server_url: &str) -> Result<SyncCursors, AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        db.with_transaction(|tx| {
+            if get_state(tx, SERVER_URL_KEY)?.as_deref() != Some(server_url) {
+                set_state(tx, SERVER_URL_KEY, server_url)?;
+                set_state(tx, PUSHED_CURSOR_KEY, "0")?;
+                set_state(tx, PULLED_CURSOR_KEY, "0")?;
+            }
+            Ok(SyncCursors {
+                pushed: get_cursor(tx, PUSHED_CURSOR_KEY)?,
+                pulled: get_cursor(tx, PULLED_CURSOR_KEY)?,
+            })
+        })
+    })
+}
+
+pub fn set_cursors(db: &Database, cursors: SyncCursors) -> Result<(), AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        db.with_transaction(|tx| {
+            set_state(tx, PUSHED_CURSOR_KEY, &cursors.pushed.to_string())?;
+            set_state(tx, PULLED_CURSOR_KEY, &cursors.pulled.to_string())?;
+            Ok(())
+        })
+    })
+}
+
+/// Logs the rejected rows again as local changes, so they are pushed once more instead of being
+/// left behind the pushed cursor.
+pub fn requeue_rejected(db: &Database, rejected: &[SyncRejectedChange]) -> Result<(), AppError> {
+    throw_known_error(error_codes::DB_UPDATE, || {
+        let now = get_current_timestamp()?;
+        db.with_transaction(|tx| {
+            for change in rejected {
+                tx.execute(
+                    "INSERT INTO sync_changes (table_name, uid, changed_at) VALUES (?1, ?2, ?3)",
+                    params![change.table.as_str(), change.uid, now],
+                )?;
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Log entries after `cursor` not made by `exclude_device`, collapsed to each row's current state.
+pub fn changes_since(db: &Database, cursor: i64, exclude_device: &str, limit: usize) -> Result<SyncBatch, AppError> {
+    throw_known_error(error_codes::DB_GET, || {
+        db.with_read(|conn| {
+            // WHY: bounding by the current last id lets the cursor skip excluded trailing entries
+            // without skipping ones logged while this batch is read.
+            let last_id: i64 = conn.query_row("SELECT coalesce(max(id), 0) FROM sync_changes", [], |row| row.get(0))?;
+            let mut stmt = conn.prepare(
+                r#"
+                SELECT id, table_name, uid, changed_at
+                FROM sync_changes
+                WHERE id > ?1 AND id <= ?2 AND (device_id IS NULL OR device_id != ?3)
+                ORDER BY id
+                LIMIT ?4
+                "#,
+            )?;
+            let entries = stmt
+                .query_map(params![cursor, last_id, exclude_device, limit], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, i64>(3)?,
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let has_more = entries.len() >= limit;
+            let next_cursor = match entries.last() {
+                Some((id, ..)) if has_more => *id,
+                _ => last_id.max(cursor),
+            };
+
+            let mut rows: Vec<(SyncTable, String, i64)> = Vec::new();
+            let mut positions: HashMap<(SyncTable, String), usize> = HashMap::new();
+            for (_, table_name, uid, changed_at) in entries {
+                let Some(table) = SyncTable::from_name(&table_name) else {
+                    continue;
+                };
+                match positions.entry((table, uid.clone())) {
+                    Entry::Occupied(position) => {
+                        if let Some(row) = rows.get_mut(*position.get()) {
+                            row.2 = row.2.max(changed_at);
+                        }
+                    }
+                    Entry::Vacant(position) => {
+                        position.insert(rows.len());
+                        rows.push((table, uid, changed_at));
+                    }
+                }
+            }
+
+            let mut changes = Vec::with_capacity(rows.len());
+            for (table, uid, changed_at) in rows {
+                let row = read_row(conn, table, &uid)?;
+                // WHY: settings deletes are never applied and unsynced slices read as missing,
+                // so neither is worth sending.
+                if matches!(row, SyncRow::Settings(None)) {
+                    continue;
+                }
+                changes.push(SyncChange { row, uid, changed_at });
+            }
+
+            Ok(SyncBatch {
+                changes,
+                cursor: next_cursor,
+                has_more,
+            })
+        })
+    })
+}
+
+fn read_row(conn: &Connection, table: SyncTable, uid: &str) -> Result<SyncRow, rusqlite::Error> {
+    Ok(match table {
+        SyncTable::Algorithms => SyncRow::Algorithms(read_preset(conn, table, uid)?),
+        SyncTable::Templates => SyncRow::Templates(read_preset(conn, table, uid)?),
+        SyncTable::Decks => SyncRow::Decks(read_deck(conn, uid)?),
+        SyncTable::Cards => SyncRow::Cards(read_card(conn, uid)?),
+        SyncTable::Reviews => SyncRow::Reviews(read_review(conn, uid)?),
+        SyncTable::Settings => SyncRow::Settings(read_setting(conn, uid)?),
+    })
+}
+
+fn read_preset(conn: &Connection, table: SyncTable, uid: &str) -> Result<Option<SyncPreset>, rusqlite::Error> {
+    conn.query_row(
+        &format!(
+            "SELECT title, content, created_at, updated_at FROM {} WHERE uid = ?1",
+            table.as_str()
+        ),
+        params![uid],
+        |row| {
+            let content: String = row.get(1)?;
+            Ok(SyncPreset {
+                title: row.get(0)?,
+                content: parse_json_column(1, &content)?,
+                created_at: row.get(2)?,
+                updated_at: row.get(3)?,
+            })
+        },
+    )
+    .optional()
+}
+
+fn read_deck(conn: &Connection, uid: &str) -> Result<Option<SyncDeck>, rusqlite::Error> {
+    conn.query_row(
+        r#"
+        SELECT d.title, a.uid, t.uid, d.created_at, d.updated_at
+        FROM decks d
+        JOIN algorithms a ON a.id = d.algorithm_id
+        JOIN templates t ON t.id = d.template_id
+        WHERE d.uid = ?1
+        "#,
+        params![uid],
+        |row| {
+            Ok(SyncDeck {
+                title: row.get(0)?,
+                algorithm_uid: row.get(1)?,
+                template_uid: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        },
+    )
+    .optional()
+}
+
+fn read_card(conn: &Connection, uid: &str) -> Result<Option<SyncCard>, rusqlite::Error> {
+    conn.query_row(
+        r#"
+        SELECT d.uid, t.uid, c.content, c.state, c.due_at, c.stability, c.difficulty, c.scheduled_days,
+               c.learning_steps, c.reps, c.lapses, c.last_reviewed_at, c.created_at, c.updated_at
+        FROM cards c
+        JOIN decks d ON d.id = c.deck_id
+        JOIN templates t ON t.id = c.template_id
+        WHERE c.uid = ?1
+        "#,
+        params![uid],
+        |row| {
+            let content: Option<String> = row.get(2)?;
+            Ok(SyncCard {
+                deck_uid: row.get(0)?,
+                template_uid: row.get(1)?,
+                content: content
+                    .map(|content| parse_json_column(2, &content))
+                    .transpose()?
+                    .unwrap_or(Value::Null),
+                progress: SyncCardProgress {
+                    state: row.get::<_, Option<i32>>(3)?.unwrap_or_default(),
+                    due_at: row.get(4)?,
+                    stability: row.get(5)?,
+                    difficulty: row.get(6)?,
+                    scheduled_days: row.get::<_, Option<i32>>(7)?.unwrap_or_default(),
+                    learning_steps: row.get::<_, Option<i32>>(8)?.unwrap_or_default(),
+                    reps: row.get::<_, Option<i32>>(9)?.unwrap_or_default(),
+                    lapses: row.get::<_, Option<i32>>(10)?.unwrap_or_default(),
+                    last_reviewed_at: row.get(11)?,
+                },
+                created_at: row.get(12)?,
+                updated_at: row.get(13)?,
+            })
+        },
+    )
+    .optional()
+}
+
+fn read_review(conn: &Connection, uid: &str) -> Result<Option<SyncReview>, rusqlite::Error> {
+    conn.query_row(
+        r#"
+        SELECT c.uid, r.rating, r.state, r.due_at, r.stability, r.difficulty, r.scheduled_days,
+               r.learning_steps, r.time, r.is_ignored, r.typed_answer, r.typed_score, r.created_at
+        FROM reviews r
+        JOIN cards c ON c.id = r.card_id
+        WHERE r.uid = ?1
+        "#,
+        params![uid],
+        |row| {
+            Ok(SyncReview {
+                card_uid: row.get(0)?,
+                rating: row.get(1)?,
+                state: row.get(2)?,
+                due_at: row.get(3)?,
+                stability: row.get(4)?,
+                difficulty: row.get(5)?,
+                scheduled_days: row.get(6)?,
+                learning_steps: row.get(7)?,
+                time: row.get(8)?,
+                is_ignored: row.get(9)?,
+                typed_answer: row.get(10)?,
+                typed_score: row.get(11)?,
+                created_at: row.get(12)?,
+            })
+        },
+    )
+    .optional()
+}
+
+fn read_setting(conn: &Connection, uid: &str) -> Result<Option<SyncSetting>, rusqlite::Error> {
+    let setting = conn
+        .query_row(
+            "SELECT name, content, created_at, updated_at FROM settings WHERE uid = ?1",
+            params![uid],
+            |row| {
+                let content: String = row.get(1)?;
+                Ok(SyncSetting {
+                    name: row.get(0)?,
+                    content: parse_json_column(1, &content)?,
+                    created_at: row.get(2)?,
+                    updated_at: row.get(3)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(setting.filter(|setting| setting.name.parse::<SettingsName>().is_ok_and(|name| name.is_synced())))
+}
+
+/// Applies changes from `source_device` in one transaction: upserts parents first, then deletes
+/// children first. As the server, cards whose reviews changed get their progress recomputed.
+///
+/// A change that breaks a constraint is left out and listed in `rejected` with the reason; the
+/// rest of the batch still applies.
+pub fn apply_changes(
+    db: &Database,
+    source_device: &str,
+    mut changes: Vec<SyncChange>,
+    role: SyncRole,
+) -> Result<SyncApplyResult, AppError> {
+    changes.sort_by_key(|change| {
+        let rank = change.row.table() as i32;
+        if change.row.is_delete() {
+            (1, -rank)
+        } else {
+            (0, rank)
+        }
+    });
+
+    throw_known_error(error_codes::DB_UPDATE, || {
+        db.with_transaction(|tx| {
+            set_state(tx, APPLYING_DEVICE_KEY, source_device)?;
+
+            let mut result = SyncApplyResult::default();
+            let mut touched_cards = BTreeSet::new();
+            for change in &changes {
+                let applied = match &change.row {
+                    SyncRow::Settings(Some(row)) => apply_setting(tx, row)?,
+                    _ => match apply_change(tx, change, role, &mut touched_cards) {
+                        Ok(applied) => applied,
+                        Err(rusqlite::Error::SqliteFailure(error, message))
+                            if error.code == ErrorCode::ConstraintViolation =>
+                        {
+                            result.rejected.push(SyncRejectedChange {
+                                table: change.row.table(),
+                                uid: change.uid.clone(),
+                                reason: message.unwrap_or_else(|| error.to_string()),
+                            });
+                            continue;
+                        }
+                        Err(error) => return Err(error.into()),
+                    },
+                };
+                if applied {
+                    result.applied += 1;
+                } else {
+                    result.skipped += 1;
+                }
+            }
+
+            tx.execute("DELETE FROM sync_state WHERE key = ?1", params![APPLYING_DEVICE_KEY])?;
+
+            // WHY: recomputed progress is logged without a device, so every device pulls it,
+            // including the one whose reviews caused it.
+            if role == SyncRole::Server {
+                for card_id in touched_cards {
+                    recompute_progress(tx, card_id)?;
+                }
+            }
+
+            Ok(result)
+        })
+    })
+}
+
+fn apply_change(
+    conn: &Connection,
+    change: &SyncChange,
+    role: SyncRole,
+    touched_cards: &mut BTreeSet<i64>,
+) -> Result<bool, rusqlite::Error> {
+    let table = change.row.table();
+    if change.row.is_delete() {
+        return apply_delete(conn, table, change, touched_cards);
+    }
+    if is_tombstoned(conn, table, &change.uid)? {
+        return Ok(false);
+    }
+
+    match &change.row {
+        SyncRow::Algorithms(Some(row)) | SyncRow::Templates(Some(row)) => upsert_preset(conn, table, &change.uid, row),
+        SyncRow::Decks(Some(row)) => upsert_deck(conn, &change.uid, row),
+        SyncRow::Cards(Some(row)) => upsert_card(conn, &change.uid, row, role),
+        SyncRow::Reviews(Some(row)) => upsert_review(conn, &change.uid, row, touched_cards),
+        _ => Ok(false),
+    }
+}
+
+fn is_tombstoned(conn: &Connection, table: SyncTable, uid: &str) -> Result<bool, rusqlite::Error> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sync_tombstones WHERE table_name = ?1 AND uid = ?2)",
+        params![table.as_str(), uid],
+        |row| row.get(0),
+    )
+}
+
+fn id_by_uid(conn: &Connection, table: SyncTable, uid: &str) -> Result<Option<i64>, rusqlite::Error> {
+    conn.query_row(
+        &format!("SELECT id FROM {} WHERE uid = ?1", table.as_str()),
+        params![uid],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Local row id and content clock.
+fn local_row(conn: &Connection, table: SyncTable, uid: &str) -> Result<Option<(i64, i64)>, rusqlite::Error> {
+    conn.query_row(
+        &format!(
+            "SELECT id, coalesce(updated_at, created_at) FROM {} WHERE uid = ?1",
+            table.as_str()
+        ),
+        params![uid],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .optional()
+}
+
+fn apply_delete(
+    conn: &Connection,
+    table: SyncTable,
+    change: &SyncChange,
+    touched_cards: &mut BTreeSet<i64>,
+) -> Result<bool, rusqlite::Error> {
+    // WHY: settings rows are per device and matched by name, so a delete has no counterpart.
+    if table == SyncTable::Settings {
+        return Ok(false);
+    }
+    if table == SyncTable::Reviews {
+        let card_id: Option<i64> = conn
+            .query_row(
+                "SELECT card_id FROM reviews WHERE uid = ?1",
+                params![change.uid],
+                |row| row.get(0),
+            )
+            .optional()?;
+        touched_cards.extend(card_id);
+    }
+
+    conn.execute(
+        &format!("DELETE FROM {} WHERE uid = ?1", table.as_str()),
+        params![change.uid],
+    )?;
+    conn.execute(
+        "INSERT OR IGNORE INTO sync_tombstones (table_name, uid, deleted_at) VALUES (?1, ?2, ?3)",
+        params![table.as_str(), change.uid, change.changed_at],
+    )?;
+    Ok(true)
+}
+
+fn upsert_preset(conn: &Connection, table: SyncTable, uid: &str, row: &SyncPreset) -> Result<bool, rusqlite::Error> {
+    let content = row.content.to_string();
+    match local_row(conn, table, uid)? {
+        Some((id, clock)) => {
+            if !incoming_wins(clock, content_clock(row.created_at, row.updated_at)) {
+                return Ok(false);
+            }
+            conn.execute(
+                &format!(
+                    "UPDATE {} SET title = ?1, content = ?2, created_at = ?3, updated_at = ?4 WHERE id = ?5",
+                    table.as_str()
+                ),
+                params![row.title, content, row.created_at, row.updated_at, id],
+            )?;
+        }
+        None => {
+            conn.execute(
+                &format!(
+                    "INSERT INTO {} (uid, title, content, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    table.as_str()
+                ),
+                params![uid, row.title, content, row.created_at, row.updated_at],
+            )?;
+        }
+    }
+    Ok(true)
+}
+
+fn upsert_deck(conn: &Connection, uid: &str, row: &SyncDeck) -> Result<bool, rusqlite::Error> {
+    let (Some(algorithm_id), Some(template_id)) = (
+        id_by_uid(conn, SyncTable::Algorithms, &row.algorithm_uid)?,
+        id_by_uid(conn, SyncTable::Templates, &row.template_uid)?,
+    ) else {
+        return Ok(false);
+    };
+
+    match local_row(conn, SyncTable::Decks, uid)? {
+        Some((id, clock)) => {
+            if !incoming_wins(clock, content_clock(row.created_at, row.updated_at)) {
+                return Ok(false);
+            }
+            conn.execute(
+                r#"
+                UPDATE decks
+                SET title = ?1, algorithm_id = ?2, template_id = ?3, created_at = ?4, updated_at = ?5
+                WHERE id = ?6
+                "#,
+                params![row.title, algorithm_id, template_id, row.created_at, row.updated_at, id],
+            )?;
+        }
+        None => {
+            conn.execute(
+                r#"
+                INSERT INTO decks (uid, title, algorithm_id, template_id, created_at, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                "#,
+                params![
+                    uid,
+                    row.title,
+                    algorithm_id,
+                    template_id,
+                    row.created_at,
+                    row.updated_at
+                ],
+            )?;
+        }
+    }
+    Ok(true)
+}
+
+fn upsert_card(conn: &Connection, uid: &str, row: &SyncCard, role: SyncRole) -> Result<bool, rusqlite::Error> {
+    let (Some(deck_id), Some(template_id)) = (
+        id_by_uid(conn, SyncTable::Decks, &row.deck_uid)?,
+        id_by_uid(conn, SyncTable::Templates, &row.template_uid)?,
+    ) else {
+        return Ok(false);
+    };
+    let content = (!row.content.is_null()).then(|| row.content.to_string());
+    let progress = &row.progress;
+
+    let Some((id, clock)) = local_row(conn, SyncTable::Cards, uid)? else {
+        conn.execute(
+            r#"
+            INSERT INTO cards (uid, deck_id, template_id, content, state, due_at, stability, difficulty,
+                               scheduled_days, learning_steps, reps, lapses, last_reviewed_at, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+            "#,
+            params![
+                uid,
+                deck_id,
+                template_id,
+                content,
+                progress.state,
+                progress.due_at,
+                progress.stability,
+                progress.difficulty,
+                progress.scheduled_days,
+                progress.learning_steps,
+                progress.reps,
+                progress.lapses,
+                progress.last_reviewed_at,
+                row.created_at,
+                row.updated_at
+            ],
+        )?;
+        return Ok(true);
+    };
+
+    let content_wins = incoming_wins(clock, content_clock(row.created_at, row.updated_at));
+    if content_wins {
+        conn.execute(
+            r#"
+            UPDATE cards
+            SET deck_id = ?1, template_id = ?2, content = ?3, created_at = ?4, updated_at = ?5
+            WHERE id = ?6
+            "#,
+            params![deck_id, template_id, content, row.created_at, row.updated_at, id],
+        )?;
+    }
+
+    // WHY: the server owns progress; it recomputes it from reviews instead of trusting a device.
+    let progress_wins = role == SyncRole::Client;
+    if progress_wins {
+        update_progress(conn, id, progress)?;
+    }
+
+    Ok(content_wins || progress_wins)
+}
+
+fn update_progress(conn: &Connection, card_id: i64, progress: &SyncCardProgress) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        r#"
+        UPDATE cards
+        SET state = ?1, due_at = ?2, stability = ?3, difficulty = ?4, scheduled_days = ?5,
+            learning_steps = ?6, reps = ?7, lapses = ?8, last_reviewed_at = ?9
+        WHERE id = ?10
+        "#,
+        params![
+            progress.state,
+            progress.due_at,
+            progress.stability,
+            progress.difficulty,
+            progress.scheduled_days,
+            progress.learning_steps,
+            progress.reps,
+            progress.lapses,
+            progress.last_reviewed_at,
+            card_id
+        ],
+    )?;
+    Ok(())
+}
+
+fn upsert_review(
+    conn: &Connection,
+    uid: &str,
+    row: &SyncReview,
+    touched_cards: &mut BTreeSet<i64>,
+) -> Result<bool, rusqlite::Error> {
+    let Some(card_id) = id_by_uid(conn, SyncTable::Cards, &row.card_uid)? else {
+        return Ok(false);
+    };
+
+    let local: Option<(i64, i64, bool)> = conn
+        .query_row(
+            "SELECT id, card_id, is_ignored FROM reviews WHERE uid = ?1",
+            params![uid],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+
+    match local {
+        // INVARIANT: a review's outcome never changes; only moving or ignoring it does.
+        Some((id, local_card_id, is_ignored)) => {
+            if local_card_id != card_id || is_ignored != row.is_ignored {
+                conn.execute(
+                    "UPDATE reviews SET card_id = ?1, is_ignored = ?2 WHERE id = ?3",
+                    params![card_id, row.is_ignored, id],
+                )?;
+                touched_cards.extend([local_card_id, card_id]);
+            }
+        }
+        None => {
+            conn.execute(
+                r#"
+                INSERT INTO reviews (uid, card_id, rating, state, due_at, stability, difficulty,
+                                     scheduled_days, learning_steps, time, is_ignored, typed_answer,
+                                     typed_score, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                "#,
+                params![
+                    uid,
+                    card_id,
+                    row.rating,
+                    row.state,
+                    row.due_at,
+                    row.stability,
+                    row.difficulty,
+                    row.scheduled_days,
+                    row.learning_steps,
+                    row.time,
+                    row.is_ignored,
+                    row.typed_answer,
+                    row.typed_score,
+                    row.created_at
+                ],
+            )?;
+            touched_cards.insert(card_id);
+        }
+    }
+    Ok(true)
+}
+
+/// Applies an incoming settings slice through the same normalize and history path as a local
+/// write. Slices this version cannot parse or validate, and the AI slice, are skipped.
+fn apply_setting(conn: &Connection, row: &SyncSetting) -> Result<bool, AppError> {
+    let Ok(name) = row.name.parse::<SettingsName>() else {
+        return Ok(false);
+    };
+    if !name.is_synced() {
+        return Ok(false);
+    }
+    let Ok(content) = name.normalize(row.content.clone()) else {
+        return Ok(false);
+    };
+
+    let clock = content_clock(row.created_at, row.updated_at);
+    let local: Option<i64> = conn
+        .query_row(
+            "SELECT coalesce(updated_at, created_at) FROM settings WHERE name = ?1",
+            params![name.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if local.is_some_and(|local| !incoming_wins(local, clock)) {
+        return Ok(false);
+    }
+
+    write_settings_in(conn, name, &content, None, clock)?;
+    Ok(true)
+}
+
+fn recompute_progress(conn: &Connection, card_id: i64) -> Result<(), AppError> {
+    let Some(card) = select_cards_by_ids(conn, &[card_id])?.pop() else {
+        return Ok(());
+    };
+
+    let algorithm = select_deck_algorithm(conn, card.deck_id)?;
+    // WHY: a preset that fails validation cannot schedule, so the stored progress stays.
+    let Ok(scheduler) = FsrsScheduler::new(&algorithm) else {
+        return Ok(());
+    };
+
+    replay_card_progress(conn, &card, &scheduler)?;
+    Ok(())
+}
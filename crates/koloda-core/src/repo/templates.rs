@@ -1,16 +1,17 @@
 use rusqlite::{params, Connection, OptionalExtension};
 
-use crate::app::db::{parse_json_column, Database};
+use crate::app::db::{parse_versioned_json_column, Database};
 use crate::app::error::{error_codes, throw_known_error, AppError};
 use crate::app::utility::get_current_timestamp;
 use crate::domain::templates::{
     CloneTemplateData, DeleteTemplateData, InsertTemplateData, Template, TemplateContent, TemplateDeck,
-    UpdateTemplateData,
+    UpdateTemplateData, UpdateTemplateValues,
 };
+use crate::domain::versioning::VersionedJson;
 
 fn get_template_row(row: &rusqlite::Row<'_>) -> Result<Template, rusqlite::Error> {
     let content_str: String = row.get(2)?;
-    let content: TemplateContent = parse_json_column(2, &content_str)?;
+    let content: TemplateContent = parse_versioned_json_column(2, &content_str)?;
 
     Ok(Template {
         id: row.get(0)?,
@@ -31,7 +32,7 @@ fn get_template_deck_row(row: &rusqlite::Row<'_>) -> Result<TemplateDeck, rusqli
 
 pub fn get_templates(db: &Database) -> Result<Vec<Template>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let mut stmt = conn.prepare(
                 r#"
                 SELECT
@@ -59,7 +60,7 @@ pub fn get_templates(db: &Database) -> Result<Vec<Template>, AppError> {
 
 pub fn get_template(db: &Database, id: i64) -> Result<Option<Template>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             conn.query_row(
                 r#"
                 SELECT
@@ -88,48 +89,53 @@ pub fn get_template(db: &Database, id: i64) -> Result<Option<Template>, AppError
 
 pub fn get_templates_by_ids(db: &Database, ids: &[i64]) -> Result<std::collections::HashMap<i64, Template>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        if ids.is_empty() {
-            return Ok(std::collections::HashMap::new());
-        }
-
-        let placeholders: Vec<String> = ids.iter().enumerate().map(|(i, _)| format!("?{}", i + 1)).collect();
-        let sql = format!(
-            r#"
-            SELECT
-                t.id,
-                t.title,
-                t.content,
-                t.created_at,
-                t.updated_at,
-                EXISTS(
-                    SELECT 1 FROM cards c
-                    WHERE c.template_id = t.id
-                    LIMIT 1
-                ) as is_locked
-            FROM templates t
-            WHERE t.id IN ({})
-            "#,
-            placeholders.join(", ")
-        );
-
-        db.with_conn(|conn| {
-            let params: Vec<&dyn rusqlite::ToSql> = ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
-            let mut stmt = conn.prepare(&sql)?;
-            let templates = stmt
-                .query_map(params.as_slice(), get_template_row)?
-                .collect::<Result<Vec<_>, _>>()?;
-            let map = templates.into_iter().map(|t| (t.id, t)).collect();
-            Ok(map)
-        })
+        db.with_read(|conn| select_templates_by_ids(conn, ids))
     })
 }
 
+pub(crate) fn select_templates_by_ids(
+    conn: &Connection,
+    ids: &[i64],
+) -> Result<std::collections::HashMap<i64, Template>, AppError> {
+    if ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let placeholders: Vec<String> = ids.iter().enumerate().map(|(i, _)| format!("?{}", i + 1)).collect();
+    let sql = format!(
+        r#"
+        SELECT
+            t.id,
+            t.title,
+            t.content,
+            t.created_at,
+            t.updated_at,
+            EXISTS(
+                SELECT 1 FROM cards c
+                WHERE c.template_id = t.id
+                LIMIT 1
+            ) as is_locked
+        FROM templates t
+        WHERE t.id IN ({})
+        "#,
+        placeholders.join(", ")
+    );
+
+    let params: Vec<&dyn rusqlite::ToSql> = ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let templates = stmt
+        .query_map(params.as_slice(), get_template_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    let map = templates.into_iter().map(|t| (t.id, t)).collect();
+    Ok(map)
+}
+
 pub fn add_template(db: &Database, data: InsertTemplateData) -> Result<Template, AppError> {
     throw_known_error(error_codes::DB_ADD, || {
         data.validate()?;
         let now = get_current_timestamp()?;
 
-        let id = db.with_conn(|conn| insert_template(conn, &data, now))?;
+        let id = db.with_transaction(|conn| insert_template(conn, &data, now))?;
 
         get_template(db, id)?.ok_or_else(|| AppError::new(error_codes::DB_ADD, None))
     })
@@ -149,7 +155,7 @@ pub(crate) fn insert_template(conn: &Connection, data: &InsertTemplateData, now:
         INSERT INTO templates (title, content, created_at, updated_at)
         VALUES (?1, ?2, ?3, NULL)
         "#,
-        params![data.title, serde_json::to_string(&data.content)?, now],
+        params![data.title, data.content.to_versioned()?.to_string(), now],
     )?;
 
     Ok(conn.last_insert_rowid())
@@ -157,18 +163,41 @@ pub(crate) fn insert_template(conn: &Connection, data: &InsertTemplateData, now:
 
 pub fn is_template_locked(db: &Database, id: i64) -> Result<bool, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
-            let count: i64 = conn.query_row(
-                "SELECT COUNT(*) FROM cards WHERE template_id = ?1",
-                params![id],
-                |row| row.get(0),
-            )?;
-
-            Ok(count > 0)
-        })
+        db.with_read(|conn| select_template_locked(conn, id))
     })
 }
 
+pub(crate) fn select_template_locked(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM cards WHERE template_id = ?1",
+        params![id],
+        |row| row.get(0),
+    )?;
+
+    Ok(count > 0)
+}
+
+pub(crate) fn update_template_row(
+    conn: &Connection,
+    id: i64,
+    values: &UpdateTemplateValues,
+    now: i64,
+) -> Result<(), AppError> {
+    conn.execute(
+        r#"
+        UPDATE templates
+        SET
+            title = ?1,
+            content = ?2,
+            updated_at = ?3
+        WHERE id = ?4
+        "#,
+        params![values.title, values.content.to_versioned()?.to_string(), now, id],
+    )?;
+
+    Ok(())
+}
+
 pub fn update_template(db: &Database, data: UpdateTemplateData) -> Result<Template, AppError> {
     throw_known_error(error_codes::DB_UPDATE, || {
         let original = get_template(db, data.id)?.ok_or_else(|| {
@@ -185,26 +214,7 @@ pub fn update_template(db: &Database, data: UpdateTemplateData) -> Result<Templa
         }
         let now = get_current_timestamp()?;
 
-        db.with_conn(|conn| {
-            conn.execute(
-                r#"
-                UPDATE templates
-                SET
-                    title = ?1,
-                    content = ?2,
-                    updated_at = ?3
-                WHERE id = ?4
-                "#,
-                params![
-                    data.values.title,
-                    serde_json::to_string(&data.values.content)?,
-                    now,
-                    data.id
-                ],
-            )?;
-
-            Ok(())
-        })?;
+        db.with_transaction(|conn| update_template_row(conn, data.id, &data.values, now))?;
 
         get_template(db, data.id)?.ok_or_else(|| AppError::new(error_codes::DB_UPDATE, None))
     })
@@ -235,7 +245,7 @@ pub fn delete_template(db: &Database, data: DeleteTemplateData) -> Result<(), Ap
             return Err(AppError::new(error_codes::VALIDATION_TEMPLATES_DELETE_LOCKED, None));
         }
 
-        db.with_conn(|conn| {
+        db.with_transaction(|conn| {
             conn.execute("DELETE FROM templates WHERE id = ?1", params![data.id])?;
             Ok(())
         })
@@ -244,7 +254,7 @@ pub fn delete_template(db: &Database, data: DeleteTemplateData) -> Result<(), Ap
 
 pub fn get_template_decks(db: &Database, id: i64) -> Result<Vec<TemplateDeck>, AppError> {
     throw_known_error(error_codes::DB_GET, || {
-        db.with_conn(|conn| {
+        db.with_read(|conn| {
             let mut stmt = conn.prepare(
                 r#"
                 SELECT id, title
@@ -0,0 +1,64 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
+use ureq::{Agent, AgentBuilder};
+
+use super::SyncTransport;
+use crate::app::error::{error_codes, AppError};
+use crate::domain::sync::{SyncApplyResult, SyncBatch, SyncOptions, SyncPullRequest, SyncPushRequest};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Calls a `http::SyncServer` at `SyncOptions.url`; plain HTTP only, for servers on the LAN.
+pub struct HttpSyncTransport {
+    agent: Agent,
+    url: String,
+    token: String,
+}
+
+impl HttpSyncTransport {
+    pub fn new(options: &SyncOptions) -> Self {
+        Self {
+            agent: AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            url: options.url.trim().trim_end_matches('/').to_string(),
+            token: options.token.clone(),
+        }
+    }
+
+    fn post<T: Serialize, R: DeserializeOwned>(&self, path: &str, body: &T) -> Result<R, AppError> {
+        let response = self
+            .agent
+            .post(&format!("{}{}", self.url, path))
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .send_json(body);
+
+        match response {
+            Ok(response) => response.into_json().map_err(request_error),
+            // WHY: the server answers failures with `AppError` JSON; keep its code.
+            Err(ureq::Error::Status(status, response)) => {
+                Err(response.into_json::<AppError>().unwrap_or_else(|e: std::io::Error| {
+                    AppError::new(error_codes::SYNC_REQUEST, Some(format!("HTTP {}: {}", status, e)))
+                }))
+            }
+            Err(error) => Err(AppError::new(error_codes::SYNC_REQUEST, Some(error.to_string()))),
+        }
+    }
+}
+
+impl SyncTransport for HttpSyncTransport {
+    fn server_id(&self) -> &str {
+        &self.url
+    }
+
+    fn push(&self, request: &SyncPushRequest) -> Result<SyncApplyResult, AppError> {
+        self.post("/sync/push", request)
+    }
+
+    fn pull(&self, request: &SyncPullRequest) -> Result<SyncBatch, AppError> {
+        self.post("/sync/pull", request)
+    }
+}
+
+fn request_error(error: std::io::Error) -> AppError {
+    AppError::new(error_codes::SYNC_REQUEST, Some(error.to_string()))
+}
@@ -0,0 +1,92 @@
+//! Syncing a database with a sync server: a device pushes its own change log, then pulls and
+//! applies the server's.
+//!
+//! The server side (`handle_push` / `handle_pull`) is transport-agnostic; `http::SyncServer`
+//! serves it over HTTP and `HttpSyncTransport` (feature `sync-client`) calls it.
+
+#[cfg(feature = "sync-client")]
+mod http_client;
+
+use crate::app::db::Database;
+use crate::app::error::AppError;
+use crate::domain::sync::{
+    validate_protocol_version, SyncApplyResult, SyncBatch, SyncPullRequest, SyncPushRequest, SyncReport, SyncRole,
+    SYNC_PROTOCOL_VERSION,
+};
+use crate::repo;
+
+#[cfg(feature = "sync-client")]
+pub use http_client::HttpSyncTransport;
+
+/// Most change-log entries sent per push or pull request.
+pub const SYNC_BATCH_SIZE: usize = 500;
+
+pub trait SyncTransport {
+    /// Stable id of the server; keys the device's cursors and tags the changes pulled from it.
+    fn server_id(&self) -> &str;
+    fn push(&self, request: &SyncPushRequest) -> Result<SyncApplyResult, AppError>;
+    fn pull(&self, request: &SyncPullRequest) -> Result<SyncBatch, AppError>;
+}
+
+/// Pushes local changes, then pulls the server's. Cursors are saved after every batch, so an
+/// interrupted sync resumes where it stopped. Changes the server rejects are reported and logged
+/// again, so the next sync retries them.
+pub fn sync(db: &Database, transport: &impl SyncTransport) -> Result<SyncReport, AppError> {
+    let server_id = transport.server_id();
+    let device_id = repo::sync::device_id(db)?;
+    let mut cursors = repo::sync::get_cursors(db, server_id)?;
+    let mut report = SyncReport::default();
+    let mut rejected = Vec::new();
+
+    loop {
+        let batch = repo::sync::changes_since(db, cursors.pushed, server_id, SYNC_BATCH_SIZE)?;
+        if !batch.changes.is_empty() {
+            let result = transport.push(&SyncPushRequest {
+                version: SYNC_PROTOCOL_VERSION,
+                device_id: device_id.clone(),
+                changes: batch.changes,
+            })?;
+            report.pushed += result.applied;
+            report.skipped += result.skipped;
+            rejected.extend(result.rejected);
+        }
+        cursors.pushed = batch.cursor;
+        repo::sync::set_cursors(db, cursors)?;
+        if !batch.has_more {
+            break;
+        }
+    }
+    // WHY: requeued after the last batch, so one sync pushes a rejected row only once.
+    repo::sync::requeue_rejected(db, &rejected)?;
+    report.rejected = rejected;
+
+    loop {
+        let batch = transport.pull(&SyncPullRequest {
+            version: SYNC_PROTOCOL_VERSION,
+            device_id: device_id.clone(),
+            cursor: cursors.pulled,
+        })?;
+        let result = repo::sync::apply_changes(db, server_id, batch.changes, SyncRole::Client)?;
+        report.pulled += result.applied;
+        report.skipped += result.skipped;
+        report.rejected.extend(result.rejected);
+        cursors.pulled = batch.cursor;
+        repo::sync::set_cursors(db, cursors)?;
+        if !batch.has_more {
+            break;
+        }
+    }
+
+    Ok(report)
+}
+
+pub fn handle_push(db: &Database, request: SyncPushRequest) -> Result<SyncApplyResult, AppError> {
+    validate_protocol_version(request.version)?;
+    repo::sync::apply_changes(db, &request.device_id, request.changes, SyncRole::Server)
+}
+
+/// The server's changes after the request cursor, leaving out the requesting device's own.
+pub fn handle_pull(db: &Database, request: &SyncPullRequest) -> Result<SyncBatch, AppError> {
+    validate_protocol_version(request.version)?;
+    repo::sync::changes_since(db, request.cursor, &request.device_id, SYNC_BATCH_SIZE)
+}
@@ -0,0 +1,209 @@
+use koloda_core::app::error::{error_codes, AppError};
+use koloda_core::app::secrets::{set_test_secret_store, SecretStore};
+use koloda_core::domain::ai::{AISecrets, AISecretsRepair, AISecretsStatus, RepairAISecretsData};
+use koloda_core::repo::ai;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+
+mod common;
+use common::test_db;
+
+static LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Default)]
+struct ListingSecretStore {
+    data: Mutex<HashMap<String, String>>,
+}
+
+impl SecretStore for ListingSecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, AppError> {
+        Ok(self.data.lock().unwrap_or_else(|e| e.into_inner()).get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), AppError> {
+        self.data
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), AppError> {
+        self.data.lock().unwrap_or_else(|e| e.into_inner()).remove(key);
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Option<Vec<String>>, AppError> {
+        Ok(Some(
+            self.data
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .keys()
+                .cloned()
+                .collect(),
+        ))
+    }
+}
+
+fn setup() -> (MutexGuard<'static, ()>, Arc<ListingSecretStore>) {
+    let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let store = Arc::new(ListingSecretStore::default());
+    set_test_secret_store(Some(Arc::clone(&store) as Arc<dyn SecretStore>)).expect("set test secret store");
+    (guard, store)
+}
+
+fn teardown(_guard: MutexGuard<'static, ()>) {
+    set_test_secret_store(None).expect("clear test secret store");
+}
+
+fn add_openrouter_profile(db: &koloda_core::app::db::Database, api_key: &str) -> String {
+    ai::add_ai_profile(
+        db,
+        Some("OpenRouter".to_string()),
+        Some(AISecrets::OpenRouter {
+            api_key: Some(api_key.to_string()),
+        }),
+        None,
+    )
+    .expect("profile should be added")
+    .id
+}
+
+#[test]
+fn check_ai_secrets_reports_missing_keys_and_orphans() {
+    let (guard, store) = setup();
+    let db = test_db();
+    let healthy_id = add_openrouter_profile(&db, "sk-healthy");
+    let broken_id = add_openrouter_profile(&db, "sk-broken");
+    store.remove(&format!("ai-profile-{}", broken_id)).unwrap();
+    store.set("ai-profile-gone", "sk-orphan").unwrap();
+    store.set("unrelated", "value").unwrap();
+
+    let health = ai::check_ai_secrets(&db).expect("health check should succeed");
+
+    let status_of = |id: &str| health.profiles.iter().find(|p| p.profile_id == id).unwrap().status;
+    assert_eq!(status_of(&healthy_id), AISecretsStatus::Ok);
+    assert_eq!(status_of(&broken_id), AISecretsStatus::MissingKey);
+    assert_eq!(health.orphaned_profile_ids, Some(vec!["gone".to_string()]));
+
+    teardown(guard);
+}
+
+#[test]
+fn repair_ai_secrets_rekeys_and_purges() {
+    let (guard, store) = setup();
+    let db = test_db();
+    let broken_id = add_openrouter_profile(&db, "sk-broken");
+    store.remove(&format!("ai-profile-{}", broken_id)).unwrap();
+    store.set("ai-profile-gone", "sk-orphan").unwrap();
+
+    let health = ai::repair_ai_secrets(
+        &db,
+        RepairAISecretsData {
+            repairs: vec![
+                AISecretsRepair::Rekey {
+                    profile_id: broken_id.clone(),
+                    api_key: "sk-new".to_string(),
+                },
+                AISecretsRepair::Purge {
+                    profile_id: "gone".to_string(),
+                },
+            ],
+        },
+    )
+    .expect("repair should succeed");
+
+    assert!(health.profiles.iter().all(|p| p.status == AISecretsStatus::Ok));
+    assert_eq!(health.orphaned_profile_ids, Some(Vec::new()));
+    let secrets = ai::get_ai_profile_secrets(&db, &broken_id).unwrap().unwrap();
+    assert_eq!(secrets.api_key(), Some("sk-new"));
+
+    teardown(guard);
+}
+
+#[test]
+fn repair_ai_secrets_rejects_unknown_profile_and_empty_key() {
+    let (guard, _store) = setup();
+    let db = test_db();
+
+    let err = ai::repair_ai_secrets(
+        &db,
+        RepairAISecretsData {
+            repairs: vec![AISecretsRepair::Rekey {
+                profile_id: "missing".to_string(),
+                api_key: "sk-new".to_string(),
+            }],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.code, error_codes::NOT_FOUND_AI_PROFILE);
+
+    let err = ai::repair_ai_secrets(
+        &db,
+        RepairAISecretsData {
+            repairs: vec![AISecretsRepair::Rekey {
+                profile_id: "missing".to_string(),
+                api_key: "  ".to_string(),
+            }],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.code, error_codes::VALIDATION_SETTINGS_AI_PROVIDERS_API_KEY);
+
+    teardown(guard);
+}
+
+#[test]
+fn export_and_import_ai_secrets_round_trip() {
+    let (guard, store) = setup();
+    let db = test_db();
+    let profile_id = add_openrouter_profile(&db, "sk-exported");
+
+    let bundle = ai::export_ai_secrets(&db, "moving day").expect("export should succeed");
+    assert!(!bundle.contains("sk-exported"), "export must be encrypted");
+
+    store.remove(&format!("ai-profile-{}", profile_id)).unwrap();
+    let err = ai::import_ai_secrets(&db, &bundle, "wrong").unwrap_err();
+    assert_eq!(err.code, error_codes::SECRET_STORE_DECRYPT);
+
+    let result = ai::import_ai_secrets(&db, &bundle, "moving day").expect("import should succeed");
+    assert_eq!(result.imported, vec![profile_id.clone()]);
+    assert!(result.skipped.is_empty());
+    let secrets = ai::get_ai_profile_secrets(&db, &profile_id).unwrap().unwrap();
+    assert_eq!(secrets.api_key(), Some("sk-exported"));
+
+    let other_db = test_db();
+    let result = ai::import_ai_secrets(&other_db, &bundle, "moving day").expect("import should succeed");
+    assert!(result.imported.is_empty());
+    assert_eq!(result.skipped, vec![profile_id]);
+
+    teardown(guard);
+}
+
+#[test]
+fn export_ai_secrets_requires_a_passphrase() {
+    let (guard, _store) = setup();
+    let db = test_db();
+    add_openrouter_profile(&db, "sk-exported");
+
+    for passphrase in ["", "   "] {
+        let err = ai::export_ai_secrets(&db, passphrase).unwrap_err();
+        assert_eq!(err.code, error_codes::VALIDATION_AI_SECRETS_PASSPHRASE);
+    }
+
+    teardown(guard);
+}
+
+#[test]
+fn repair_data_parses_tagged_actions() {
+    let data: RepairAISecretsData = serde_json::from_value(serde_json::json!({
+        "repairs": [
+            { "action": "rekey", "profileId": "a", "apiKey": "sk" },
+            { "action": "purge", "profileId": "b" }
+        ]
+    }))
+    .unwrap();
+
+    assert!(matches!(&data.repairs[0], AISecretsRepair::Rekey { profile_id, .. } if profile_id == "a"));
+    assert!(matches!(&data.repairs[1], AISecretsRepair::Purge { profile_id } if profile_id == "b"));
+}
@@ -0,0 +1,157 @@
+#![cfg(feature = "http")]
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+use koloda_core::app::error::error_codes;
+use koloda_core::http::{AnkiConnectServer, AnkiConnectServerOptions};
+use serde_json::{json, Value};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+fn start(key: Option<&str>) -> AnkiConnectServer {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    add_card(&db, deck_id, template_id, "hola");
+
+    AnkiConnectServer::start(
+        db,
+        AnkiConnectServerOptions {
+            port: Some(0),
+            key: key.map(str::to_string),
+        },
+    )
+    .expect("server should start")
+}
+
+fn send(port: u16, method: &str, origin: Option<&str>, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).expect("server should accept");
+    let origin = origin
+        .map(|origin| format!("Origin: {}\r\n", origin))
+        .unwrap_or_default();
+    write!(
+        stream,
+        "{} / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{}Content-Length: {}\r\n\r\n{}",
+        method,
+        origin,
+        body.len(),
+        body
+    )
+    .expect("request should be written");
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("response should be read");
+    let (head, body) = response.split_once("\r\n\r\n").expect("response should have a body");
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .expect("response should have a status");
+    (status, body.to_string())
+}
+
+fn call(server: &AnkiConnectServer, request: Value) -> Value {
+    let (status, body) = send(server.info().port, "POST", None, &request.to_string());
+    assert_eq!(status, 200);
+    serde_json::from_str(&body).expect("body should be JSON")
+}
+
+#[test]
+fn get_reports_protocol_version() {
+    let server = start(None);
+
+    let (status, body) = send(server.info().port, "GET", None, "");
+
+    assert_eq!(status, 200);
+    assert_eq!(body, "AnkiConnect v.6");
+}
+
+#[test]
+fn version_six_wraps_results_and_errors() {
+    let server = start(None);
+
+    let names = call(&server, json!({ "action": "deckNames", "version": 6 }));
+    let unknown = call(&server, json!({ "action": "sync", "version": 6 }));
+
+    assert_eq!(names, json!({ "result": ["Spanish"], "error": null }));
+    assert!(unknown["result"].is_null());
+    assert!(unknown["error"]
+        .as_str()
+        .unwrap()
+        .starts_with(error_codes::NOT_FOUND_ANKI_CONNECT_ACTION));
+}
+
+#[test]
+fn legacy_version_returns_bare_result() {
+    let server = start(None);
+
+    let names = call(&server, json!({ "action": "modelNames" }));
+
+    assert_eq!(names, json!(["Basic"]));
+}
+
+#[test]
+fn add_note_then_find_it() {
+    let server = start(None);
+    let note = json!({
+        "deckName": "Spanish",
+        "modelName": "Basic",
+        "fields": { "Front": "gracias", "Back": "thanks" },
+        "tags": [],
+    });
+
+    let added = call(
+        &server,
+        json!({ "action": "addNote", "version": 6, "params": { "note": note } }),
+    );
+    let found = call(
+        &server,
+        json!({ "action": "findNotes", "version": 6, "params": { "query": "\"Front:gracias\"" } }),
+    );
+    let duplicate = call(
+        &server,
+        json!({ "action": "addNotes", "version": 6, "params": { "notes": [note] } }),
+    );
+
+    assert!(added["result"].is_i64());
+    assert_eq!(found["result"], json!([added["result"]]));
+    assert_eq!(duplicate["result"], json!([null]));
+}
+
+#[test]
+fn key_is_required_when_set() {
+    let server = start(Some("secret"));
+
+    let permission = call(&server, json!({ "action": "requestPermission", "version": 6 }));
+    let missing = call(&server, json!({ "action": "deckNames", "version": 6 }));
+    let given = call(&server, json!({ "action": "deckNames", "version": 6, "key": "secret" }));
+
+    assert_eq!(permission["result"]["permission"], "granted");
+    assert_eq!(permission["result"]["requireApiKey"], true);
+    assert!(missing["error"]
+        .as_str()
+        .unwrap()
+        .starts_with(error_codes::HTTP_UNAUTHORIZED));
+    assert_eq!(given["result"], json!(["Spanish"]));
+}
+
+#[test]
+fn web_origins_other_than_localhost_are_refused() {
+    let server = start(None);
+    let port = server.info().port;
+    let body = json!({ "action": "deckNames", "version": 6 }).to_string();
+
+    let (site, _) = send(port, "POST", Some("https://example.com"), &body);
+    let (sandboxed, _) = send(port, "POST", Some("null"), &body);
+    let (local, _) = send(port, "POST", Some("http://localhost:3000"), &body);
+    let (extension, _) = send(port, "POST", Some("chrome-extension://abcdef"), &body);
+
+    assert_eq!(site, 403);
+    assert_eq!(sandboxed, 403);
+    assert_eq!(local, 200);
+    assert_eq!(extension, 200);
+}
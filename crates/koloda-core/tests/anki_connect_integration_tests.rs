@@ -0,0 +1,169 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::anki_connect::AnkiNote;
+use koloda_core::repo::{anki_connect, cards};
+use serde_json::json;
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+fn note(deck: &str, front: &str, allow_duplicate: bool) -> AnkiNote {
+    serde_json::from_value(json!({
+        "deckName": deck,
+        "modelName": "basic",
+        "fields": { "Front": front, "Back": "meaning" },
+        "options": { "allowDuplicate": allow_duplicate },
+    }))
+    .expect("valid note")
+}
+
+fn setup() -> (koloda_core::app::db::Database, i64) {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    add_deck(&db, algorithm_id, template_id, "French");
+    add_card(&db, deck_id, template_id, "hola");
+    (db, deck_id)
+}
+
+#[test]
+fn names_list_decks_models_and_fields() {
+    let (db, _) = setup();
+
+    assert_eq!(anki_connect::deck_names(&db).unwrap(), vec!["Spanish", "French"]);
+    assert_eq!(anki_connect::model_names(&db).unwrap(), vec!["Basic"]);
+    assert_eq!(
+        anki_connect::model_field_names(&db, "BASIC").unwrap(),
+        vec!["Front", "Back"]
+    );
+    assert_eq!(
+        anki_connect::model_field_names(&db, "Cloze").unwrap_err().code,
+        error_codes::NOT_FOUND_ANKI_CONNECT_MODEL
+    );
+}
+
+#[test]
+fn add_note_returns_card_id() {
+    let (db, deck_id) = setup();
+
+    let id = anki_connect::add_note(&db, note("spanish", "adiós", false)).unwrap();
+
+    let card = cards::get_card(&db, id).unwrap().unwrap();
+    assert_eq!(card.deck_id, deck_id);
+    assert_eq!(card.content["1"].text, "adiós");
+    assert_eq!(card.content["2"].text, "meaning");
+}
+
+#[test]
+fn add_notes_reports_each_note() {
+    let (db, _) = setup();
+
+    let results = anki_connect::add_notes(
+        &db,
+        vec![
+            note("Spanish", "Hola", false),
+            note("Spanish", "hola", true),
+            note("French", "hola", false),
+            note("German", "hallo", false),
+            note("French", "", false),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        results[0].as_ref().unwrap_err().code,
+        error_codes::VALIDATION_CARDS_ADD_DUPLICATE
+    );
+    let allowed_id = *results[1].as_ref().unwrap();
+    let french_id = *results[2].as_ref().unwrap();
+    assert_ne!(allowed_id, french_id);
+    assert_eq!(
+        results[3].as_ref().unwrap_err().code,
+        error_codes::NOT_FOUND_ANKI_CONNECT_DECK
+    );
+    assert_eq!(
+        results[4].as_ref().unwrap_err().code,
+        error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY
+    );
+}
+
+#[test]
+fn can_add_notes_checks_without_writing() {
+    let (db, deck_id) = setup();
+
+    let results = anki_connect::can_add_notes(
+        &db,
+        &[
+            note("Spanish", "hola", false),
+            note("Spanish", "hola", true),
+            note("Spanish", "gracias", false),
+            note("French", "hola", false),
+            note("Spanish", "", false),
+            note("German", "hallo", false),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(results, vec![false, true, true, true, false, false]);
+    assert_eq!(cards::get_cards(&db, deck_id).unwrap().len(), 1);
+}
+
+#[test]
+fn find_notes_and_notes_info() {
+    let (db, _) = setup();
+    let french_id = anki_connect::add_note(&db, note("French", "bonjour", false)).unwrap();
+
+    let ids = anki_connect::find_notes(&db, "deck:French Front:bonjour").unwrap();
+    assert_eq!(ids, vec![french_id]);
+    assert_eq!(anki_connect::find_notes(&db, "meaning").unwrap(), vec![french_id]);
+    assert_eq!(anki_connect::find_notes(&db, "").unwrap().len(), 2);
+
+    let info = anki_connect::notes_info(&db, &[french_id, 404]).unwrap();
+    let note = info[0].as_ref().unwrap();
+    assert_eq!(note.note_id, french_id);
+    assert_eq!(note.model_name, "Basic");
+    assert_eq!(note.cards, vec![french_id]);
+    assert_eq!(note.fields["Front"].value, "bonjour");
+    assert_eq!(note.fields["Back"].order, 1);
+    assert!(info[1].is_none());
+}
+
+#[test]
+fn add_notes_rejects_duplicates_within_the_batch() {
+    let (db, deck_id) = setup();
+
+    let results = anki_connect::add_notes(
+        &db,
+        vec![
+            note("Spanish", "gracias", false),
+            note("Spanish", "Gracias", false),
+            note("Spanish", "gracias", true),
+        ],
+    )
+    .unwrap();
+
+    let first_id = *results[0].as_ref().unwrap();
+    let error = results[1].as_ref().unwrap_err();
+    assert_eq!(error.code, error_codes::VALIDATION_CARDS_ADD_DUPLICATE);
+    assert_eq!(error.details, Some(format!("Card id: {}", first_id)));
+    assert_ne!(*results[2].as_ref().unwrap(), first_id);
+    assert_eq!(cards::get_cards(&db, deck_id).unwrap().len(), 3);
+}
+
+#[test]
+fn find_notes_narrows_by_deck_model_field_and_id() {
+    let (db, _) = setup();
+    let spanish_id = anki_connect::find_notes(&db, "deck:Spanish").unwrap()[0];
+    let french_id = anki_connect::add_note(&db, note("French", "bonjour", false)).unwrap();
+
+    assert_eq!(anki_connect::find_notes(&db, "-deck:Spanish").unwrap(), vec![french_id]);
+    assert_eq!(
+        anki_connect::find_notes(&db, &format!("nid:{},{} deck:french", spanish_id, french_id)).unwrap(),
+        vec![french_id]
+    );
+    assert!(anki_connect::find_notes(&db, "note:Cloze").unwrap().is_empty());
+    assert!(anki_connect::find_notes(&db, "Missing:*").unwrap().is_empty());
+    assert_eq!(anki_connect::find_notes(&db, "-Missing:*").unwrap().len(), 2);
+    assert!(anki_connect::find_notes(&db, "tag:verbs").unwrap().is_empty());
+}
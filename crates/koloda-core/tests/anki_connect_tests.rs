@@ -0,0 +1,120 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::anki_connect::{wildcard_match, AnkiNote, AnkiQuery};
+use koloda_core::domain::cards::Card;
+use koloda_core::domain::templates::Template;
+use serde_json::json;
+
+mod common;
+use common::{card_content, simple_template_content};
+
+fn template() -> Template {
+    Template {
+        id: 1,
+        title: "Basic".to_string(),
+        content: simple_template_content(),
+        created_at: 0,
+        updated_at: None,
+        is_locked: false,
+    }
+}
+
+fn card(id: i64, front: &str, back: &str) -> Card {
+    serde_json::from_value(json!({
+        "id": id,
+        "deckId": 1,
+        "templateId": 1,
+        "content": card_content(front, back),
+        "state": 0,
+        "scheduledDays": 0,
+        "learningSteps": 0,
+        "reps": 0,
+        "lapses": 0,
+    }))
+    .expect("valid card fixture")
+}
+
+fn matches(query: &str, card: &Card) -> bool {
+    AnkiQuery::parse(query)
+        .expect("query should parse")
+        .matches(card, "Spanish Mining", &template())
+}
+
+#[test]
+fn note_content_matches_field_titles_ignoring_case() {
+    let note: AnkiNote = serde_json::from_value(json!({
+        "deckName": "Spanish",
+        "modelName": "Basic",
+        "fields": { "front": "hola", "Back": "hello", "Audio": "[sound:x.mp3]" },
+        "tags": ["yomitan"],
+    }))
+    .unwrap();
+
+    let content = note.content(&simple_template_content().fields);
+
+    assert_eq!(content.len(), 2);
+    assert_eq!(content["1"].text, "hola");
+    assert_eq!(content["2"].text, "hello");
+}
+
+#[test]
+fn empty_query_matches_everything() {
+    assert!(matches("", &card(1, "hola", "hello")));
+}
+
+#[test]
+fn deck_and_note_terms_support_quotes_and_wildcards() {
+    let card = card(1, "hola", "hello");
+
+    assert!(matches("\"deck:spanish mining\"", &card));
+    assert!(matches("deck:\"Spanish Mining\" note:basic", &card));
+    assert!(matches("deck:Span*", &card));
+    assert!(!matches("deck:Spanish", &card));
+    assert!(!matches("note:Cloze", &card));
+}
+
+#[test]
+fn field_terms_match_the_whole_field() {
+    let card = card(1, "Hola", "hello");
+
+    assert!(matches("Front:hola", &card));
+    assert!(matches("front:h_la", &card));
+    assert!(!matches("Front:hol", &card));
+    assert!(!matches("Missing:hola", &card));
+}
+
+#[test]
+fn bare_text_matches_any_field_substring() {
+    let card = card(1, "hola", "hello there");
+
+    assert!(matches("there", &card));
+    assert!(matches("HOLA there", &card));
+    assert!(!matches("adiós", &card));
+}
+
+#[test]
+fn negation_and_id_terms() {
+    let card = card(7, "hola", "hello");
+
+    assert!(matches("nid:3,7", &card));
+    assert!(matches("cid:7", &card));
+    assert!(!matches("-nid:7", &card));
+    assert!(matches("-tag:marked", &card));
+    assert!(!matches("tag:marked", &card));
+}
+
+#[test]
+fn unsupported_or_malformed_queries_fail() {
+    for query in ["a or b", "(deck:x)", "\"open", "nid:abc"] {
+        let error = AnkiQuery::parse(query).unwrap_err();
+        assert_eq!(error.code, error_codes::VALIDATION_ANKI_CONNECT_QUERY, "{}", query);
+    }
+}
+
+#[test]
+fn wildcard_match_is_whole_string() {
+    assert!(wildcard_match("*", ""));
+    assert!(wildcard_match("a*c", "abbbc"));
+    assert!(wildcard_match("É_", "éx"));
+    assert!(!wildcard_match("a*c", "abcd"));
+    assert!(!wildcard_match("_", ""));
+}
@@ -0,0 +1,179 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{AddCardsOptions, DuplicatePolicy, InsertCardData};
+use koloda_core::repo::cards;
+use serde_json::json;
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+fn insert_data(deck_id: i64, template_id: i64, front: &str, back: &str) -> InsertCardData {
+    InsertCardData {
+        deck_id,
+        template_id,
+        content: common::card_content(front, back),
+        state: None,
+        due_at: None,
+        stability: None,
+        difficulty: None,
+        scheduled_days: None,
+        learning_steps: None,
+        reps: None,
+        lapses: None,
+        last_reviewed_at: None,
+    }
+}
+
+fn options(duplicate_policy: DuplicatePolicy, duplicate_field_id: Option<i64>) -> AddCardsOptions {
+    AddCardsOptions {
+        duplicate_policy,
+        duplicate_field_id,
+    }
+}
+
+#[test]
+fn add_cards_allow_policy_inserts_duplicates() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    add_card(&db, deck_id, template_id, "question");
+
+    let results = cards::add_cards(
+        &db,
+        vec![insert_data(deck_id, template_id, "question", "answer")],
+        AddCardsOptions::default(),
+    )
+    .unwrap();
+
+    assert!(results[0].error.is_none());
+    assert!(results[0].duplicate_of.is_none());
+    assert_eq!(cards::get_cards(&db, deck_id).unwrap().len(), 2);
+}
+
+#[test]
+fn add_cards_reject_policy_reports_existing_card_id() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let existing_id = add_card(&db, deck_id, template_id, "Question");
+
+    let results = cards::add_cards(
+        &db,
+        vec![
+            insert_data(deck_id, template_id, "  question ", "other answer"),
+            insert_data(deck_id, template_id, "fresh", "answer"),
+        ],
+        options(DuplicatePolicy::Reject, None),
+    )
+    .unwrap();
+
+    let error = results[0].error.as_ref().expect("duplicate should be rejected");
+    assert_eq!(error.code, error_codes::VALIDATION_CARDS_ADD_DUPLICATE);
+    assert_eq!(error.existing_card_id, Some(existing_id));
+    assert!(results[1].error.is_none());
+    assert_eq!(cards::get_cards(&db, deck_id).unwrap().len(), 2);
+}
+
+#[test]
+fn add_cards_skip_policy_leaves_duplicates_out() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let existing_id = add_card(&db, deck_id, template_id, "question");
+
+    let results = cards::add_cards(
+        &db,
+        vec![insert_data(deck_id, template_id, "question", "answer")],
+        options(DuplicatePolicy::Skip, None),
+    )
+    .unwrap();
+
+    assert!(results[0].error.is_none());
+    assert_eq!(results[0].duplicate_of, Some(existing_id));
+    assert_eq!(cards::get_cards(&db, deck_id).unwrap().len(), 1);
+}
+
+#[test]
+fn add_cards_detects_duplicates_within_the_batch() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+
+    let results = cards::add_cards(
+        &db,
+        vec![
+            insert_data(deck_id, template_id, "question", "answer"),
+            insert_data(deck_id, template_id, "QUESTION", "answer"),
+        ],
+        options(DuplicatePolicy::Reject, None),
+    )
+    .unwrap();
+
+    assert!(results[0].error.is_none());
+    let inserted = cards::get_cards(&db, deck_id).unwrap();
+    assert_eq!(inserted.len(), 1);
+    assert_eq!(
+        results[1].error.as_ref().and_then(|e| e.existing_card_id),
+        Some(inserted[0].id)
+    );
+}
+
+#[test]
+fn add_cards_duplicate_check_uses_configured_field_and_deck_scope() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let other_deck_id = add_deck(&db, algorithm_id, template_id, "Other");
+    let existing_id = add_card(&db, deck_id, template_id, "question");
+
+    let results = cards::add_cards(
+        &db,
+        vec![
+            insert_data(deck_id, template_id, "different", "answer"),
+            insert_data(other_deck_id, template_id, "other", "answer"),
+        ],
+        options(DuplicatePolicy::Skip, Some(2)),
+    )
+    .unwrap();
+
+    assert_eq!(results[0].duplicate_of, Some(existing_id));
+    assert!(results[1].duplicate_of.is_none());
+    assert_eq!(cards::get_cards(&db, other_deck_id).unwrap().len(), 1);
+}
+
+#[test]
+fn add_cards_rejects_duplicate_field_missing_from_template() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+
+    let results = cards::add_cards(
+        &db,
+        vec![insert_data(deck_id, template_id, "question", "answer")],
+        options(DuplicatePolicy::Reject, Some(99)),
+    )
+    .unwrap();
+
+    let error = results[0].error.as_ref().expect("item should fail");
+    assert_eq!(error.code, error_codes::VALIDATION_CARDS_ADD_DUPLICATE_FIELD);
+    assert_eq!(error.details.as_deref(), Some("Field id: 99"));
+    assert!(cards::get_cards(&db, deck_id).unwrap().is_empty());
+}
+
+#[test]
+fn add_cards_options_deserialize_with_defaults() {
+    let options: AddCardsOptions = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(options.duplicate_policy, DuplicatePolicy::Allow);
+    assert!(options.duplicate_field_id.is_none());
+
+    let options: AddCardsOptions =
+        serde_json::from_value(json!({ "duplicatePolicy": "reject", "duplicateFieldId": 2 })).unwrap();
+    assert_eq!(options.duplicate_policy, DuplicatePolicy::Reject);
+    assert_eq!(options.duplicate_field_id, Some(2));
+}
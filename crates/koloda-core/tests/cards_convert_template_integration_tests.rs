@@ -0,0 +1,219 @@
+use koloda_core::app::db::Database;
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{CardFieldMapping, ConvertCardsTemplateData};
+use koloda_core::domain::templates::{InsertTemplateData, TemplateContent, TemplateField, TemplateLayoutItem};
+use koloda_core::repo::{cards, templates};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+fn add_three_field_template(db: &Database) -> i64 {
+    let field = |id: i64, title: &str, is_required: bool| TemplateField {
+        id,
+        title: title.to_string(),
+        field_type: "text".to_string(),
+        is_required,
+        rules: None,
+    };
+
+    templates::add_template(
+        db,
+        InsertTemplateData {
+            title: "Sentence".to_string(),
+            content: TemplateContent {
+                fields: vec![
+                    field(1, "Sentence", true),
+                    field(2, "Translation", true),
+                    field(3, "Note", true),
+                ],
+                layout: vec![TemplateLayoutItem {
+                    field: 1,
+                    operation: "display".to_string(),
+                }],
+                sides: None,
+            },
+        },
+    )
+    .expect("template should be created")
+    .id
+}
+
+fn full_mapping() -> Vec<CardFieldMapping> {
+    vec![
+        CardFieldMapping { source: 1, target: 1 },
+        CardFieldMapping { source: 2, target: 2 },
+        CardFieldMapping { source: 2, target: 3 },
+    ]
+}
+
+#[test]
+fn convert_cards_template_migrates_content_and_unlocks_old_template() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let basic_id = add_template(&db, "Basic");
+    let sentence_id = add_three_field_template(&db);
+    let deck_id = add_deck(&db, algorithm_id, basic_id, "Deck");
+    let card_id = add_card(&db, deck_id, basic_id, "question");
+
+    let result = cards::convert_cards_template(
+        &db,
+        ConvertCardsTemplateData {
+            ids: vec![card_id],
+            template_id: sentence_id,
+            field_mapping: full_mapping(),
+        },
+    )
+    .expect("conversion should succeed");
+
+    assert_eq!(result.converted, vec![card_id]);
+    assert!(result.failed.is_empty());
+    assert_eq!(result.unlocked_template_ids, vec![basic_id]);
+
+    let card = cards::get_card(&db, card_id).unwrap().unwrap();
+    assert_eq!(card.template_id, sentence_id);
+    assert_eq!(card.content["1"].text, "question");
+    assert_eq!(card.content["3"].text, "answer");
+    assert!(!templates::is_template_locked(&db, basic_id).unwrap());
+    assert!(templates::is_template_locked(&db, sentence_id).unwrap());
+}
+
+#[test]
+fn convert_cards_template_keeps_old_template_locked_while_referenced() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let basic_id = add_template(&db, "Basic");
+    let sentence_id = add_three_field_template(&db);
+    let deck_id = add_deck(&db, algorithm_id, basic_id, "Deck");
+    let converted_id = add_card(&db, deck_id, basic_id, "first");
+    let _kept_id = add_card(&db, deck_id, basic_id, "second");
+
+    let result = cards::convert_cards_template(
+        &db,
+        ConvertCardsTemplateData {
+            ids: vec![converted_id],
+            template_id: sentence_id,
+            field_mapping: full_mapping(),
+        },
+    )
+    .expect("conversion should succeed");
+
+    assert_eq!(result.converted, vec![converted_id]);
+    assert!(result.unlocked_template_ids.is_empty());
+    assert!(templates::is_template_locked(&db, basic_id).unwrap());
+}
+
+#[test]
+fn convert_cards_template_reports_failures_without_writing() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let basic_id = add_template(&db, "Basic");
+    let sentence_id = add_three_field_template(&db);
+    let deck_id = add_deck(&db, algorithm_id, basic_id, "Deck");
+    let first = add_card(&db, deck_id, basic_id, "first");
+    let second = add_card(&db, deck_id, basic_id, "second");
+
+    let result = cards::convert_cards_template(
+        &db,
+        ConvertCardsTemplateData {
+            ids: vec![first, second],
+            template_id: sentence_id,
+            field_mapping: vec![
+                CardFieldMapping { source: 1, target: 1 },
+                CardFieldMapping { source: 2, target: 2 },
+            ],
+        },
+    )
+    .expect("conversion should return a report");
+
+    assert!(result.converted.is_empty());
+    assert_eq!(result.failed.len(), 2);
+    assert!(result
+        .failed
+        .iter()
+        .all(|f| f.code == error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY));
+    assert_eq!(
+        cards::get_card(&db, first).unwrap().unwrap().template_id,
+        basic_id,
+        "no card should be converted when any card fails"
+    );
+}
+
+#[test]
+fn convert_cards_template_rejects_invalid_mapping() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let basic_id = add_template(&db, "Basic");
+    let sentence_id = add_three_field_template(&db);
+    let deck_id = add_deck(&db, algorithm_id, basic_id, "Deck");
+    let card_id = add_card(&db, deck_id, basic_id, "question");
+
+    let err = cards::convert_cards_template(
+        &db,
+        ConvertCardsTemplateData {
+            ids: vec![card_id],
+            template_id: sentence_id,
+            field_mapping: vec![CardFieldMapping { source: 1, target: 42 }],
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_FIELD_MAPPING_INVALID);
+}
+
+#[test]
+fn convert_cards_template_rejects_missing_template_and_card() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let basic_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, basic_id, "Deck");
+    let card_id = add_card(&db, deck_id, basic_id, "question");
+
+    let err = cards::convert_cards_template(
+        &db,
+        ConvertCardsTemplateData {
+            ids: vec![card_id],
+            template_id: 999_999,
+            field_mapping: full_mapping(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.code, error_codes::NOT_FOUND_CARDS_CONVERT_TEMPLATE);
+
+    let err = cards::convert_cards_template(
+        &db,
+        ConvertCardsTemplateData {
+            ids: vec![999_999],
+            template_id: basic_id,
+            field_mapping: full_mapping(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.code, error_codes::NOT_FOUND_CARDS_CONVERT_CARD);
+}
+
+#[test]
+fn convert_cards_template_skips_cards_already_on_target() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let basic_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, basic_id, "Deck");
+    let card_id = add_card(&db, deck_id, basic_id, "question");
+
+    let result = cards::convert_cards_template(
+        &db,
+        ConvertCardsTemplateData {
+            ids: vec![card_id],
+            template_id: basic_id,
+            field_mapping: Vec::new(),
+        },
+    )
+    .expect("conversion should succeed");
+
+    assert!(result.converted.is_empty());
+    assert!(result.failed.is_empty());
+    assert_eq!(
+        cards::get_card(&db, card_id).unwrap().unwrap().content["1"].text,
+        "question"
+    );
+}
@@ -0,0 +1,148 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{CardSelection, FindDuplicateCardsParams, MergeCardsData};
+use koloda_core::domain::reviews::GetReviewsData;
+use koloda_core::repo::{cards, reviews};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template, insert_review_row};
+use common::test_db;
+
+#[test]
+fn find_duplicate_cards_groups_within_and_across_decks() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let first_deck = add_deck(&db, algorithm_id, template_id, "First");
+    let second_deck = add_deck(&db, algorithm_id, template_id, "Second");
+    let original = add_card(&db, first_deck, template_id, "Straße");
+    let in_deck = add_card(&db, first_deck, template_id, "straße ");
+    let _unique = add_card(&db, first_deck, template_id, "Weg");
+    let across = add_card(&db, second_deck, template_id, "STRASSE");
+    let across_accent = add_card(&db, second_deck, template_id, "Straße");
+
+    let groups = cards::find_duplicate_cards(
+        &db,
+        FindDuplicateCardsParams {
+            selection: CardSelection {
+                deck_ids: Some(vec![first_deck]),
+                card_ids: None,
+            },
+            field_ids: None,
+        },
+    )
+    .expect("duplicate search should succeed");
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].card_ids, vec![original, in_deck]);
+
+    let groups = cards::find_duplicate_cards(
+        &db,
+        FindDuplicateCardsParams {
+            selection: CardSelection {
+                deck_ids: Some(vec![first_deck, second_deck]),
+                card_ids: None,
+            },
+            field_ids: Some(vec![1]),
+        },
+    )
+    .expect("duplicate search should succeed");
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].card_ids, vec![original, in_deck, across_accent]);
+    assert!(!groups[0].card_ids.contains(&across));
+}
+
+#[test]
+fn find_duplicate_cards_rejects_empty_selection() {
+    let db = test_db();
+
+    let err = cards::find_duplicate_cards(
+        &db,
+        FindDuplicateCardsParams {
+            selection: CardSelection::default(),
+            field_ids: None,
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_SELECTION_EMPTY);
+}
+
+#[test]
+fn merge_cards_moves_reviews_and_deletes_duplicates() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let keep = add_card(&db, deck_id, template_id, "word");
+    let duplicate = add_card(&db, deck_id, template_id, "word");
+    let another = add_card(&db, deck_id, template_id, "Word");
+    insert_review_row(&db, keep, 0, 0, 1_000);
+    insert_review_row(&db, duplicate, 1, 0, 2_000);
+    insert_review_row(&db, another, 2, 1, 3_000);
+
+    let merged = cards::merge_cards(
+        &db,
+        MergeCardsData {
+            keep_id: keep,
+            merge_ids: vec![duplicate, another],
+        },
+    )
+    .expect("merge should succeed");
+
+    assert_eq!(merged.id, keep);
+    assert!(merged.updated_at.is_some());
+    assert!(cards::get_card(&db, duplicate).unwrap().is_none());
+    assert!(cards::get_card(&db, another).unwrap().is_none());
+
+    let history = reviews::get_reviews(&db, GetReviewsData { card_id: keep }).unwrap();
+    let mut created: Vec<i64> = history.iter().map(|r| r.created_at).collect();
+    created.sort_unstable();
+    assert_eq!(created, vec![1_000, 2_000, 3_000]);
+    // WHY: the ignored review of `another` moves over but does not count towards progress.
+    assert_eq!(merged.reps, 2);
+    assert_eq!(merged.last_reviewed_at, Some(2_000));
+}
+
+#[test]
+fn merge_cards_rejects_cards_of_another_template_without_changes() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let other_template_id = add_template(&db, "Other");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let keep = add_card(&db, deck_id, template_id, "word");
+    let other = add_card(&db, deck_id, other_template_id, "word");
+
+    let err = cards::merge_cards(
+        &db,
+        MergeCardsData {
+            keep_id: keep,
+            merge_ids: vec![other],
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_MERGE_TEMPLATE);
+    assert!(cards::get_card(&db, other).unwrap().is_some());
+}
+
+#[test]
+fn merge_cards_rejects_missing_card_without_changes() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let keep = add_card(&db, deck_id, template_id, "word");
+    let duplicate = add_card(&db, deck_id, template_id, "word");
+
+    let err = cards::merge_cards(
+        &db,
+        MergeCardsData {
+            keep_id: keep,
+            merge_ids: vec![duplicate, 999_999],
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::NOT_FOUND_CARDS_MERGE_CARD);
+    assert!(cards::get_card(&db, duplicate).unwrap().is_some());
+}
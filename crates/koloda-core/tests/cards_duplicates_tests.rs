@@ -0,0 +1,115 @@
+mod common;
+
+use common::card_content;
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{duplicate_key, group_duplicate_cards, normalize_card_text, MergeCardsData};
+
+// ============================================================================
+// NORMALIZE CARD TEXT
+// ============================================================================
+
+#[test]
+fn test_normalize_card_text_folds_case_whitespace_and_diacritics() {
+    assert_eq!(normalize_card_text("  Crème   Brûlée\n"), "creme brulee");
+    assert_eq!(normalize_card_text("ЁЖИК"), "ежик");
+    assert_eq!(normalize_card_text("\tHello\u{1f}World "), "helloworld");
+}
+
+#[test]
+fn test_normalize_card_text_empty() {
+    assert_eq!(normalize_card_text("   "), "");
+}
+
+// ============================================================================
+// DUPLICATE KEY
+// ============================================================================
+
+#[test]
+fn test_duplicate_key_matches_normalized_content() {
+    let a = card_content("Café", "Coffee  shop");
+    let b = card_content("cafe", "coffee shop");
+
+    assert_eq!(duplicate_key(&a, None), duplicate_key(&b, None));
+}
+
+#[test]
+fn test_duplicate_key_per_field() {
+    let a = card_content("cafe", "coffee shop");
+    let b = card_content("cafe", "bistro");
+
+    assert_ne!(duplicate_key(&a, None), duplicate_key(&b, None));
+    assert_eq!(duplicate_key(&a, Some(&[1])), duplicate_key(&b, Some(&[1])));
+}
+
+#[test]
+fn test_duplicate_key_does_not_collide_across_field_splits() {
+    let a = card_content("a b", "c");
+    let b = card_content("a", "b c");
+
+    assert_ne!(duplicate_key(&a, None), duplicate_key(&b, None));
+}
+
+#[test]
+fn test_duplicate_key_skips_blank_content() {
+    let blank = card_content(" ", "");
+    assert!(duplicate_key(&blank, None).is_none());
+    assert!(duplicate_key(&card_content("front", ""), Some(&[2])).is_none());
+}
+
+// ============================================================================
+// GROUP DUPLICATE CARDS
+// ============================================================================
+
+#[test]
+fn test_group_duplicate_cards_drops_singletons_and_keeps_order() {
+    let first = card_content("Hund", "dog");
+    let unique = card_content("Katze", "cat");
+    let second = card_content("hund", "DOG");
+    let third = card_content("HUND", "dog ");
+
+    let groups = group_duplicate_cards([(1, 1, &first), (2, 1, &unique), (3, 1, &second), (4, 1, &third)], None);
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].card_ids, vec![1, 3, 4]);
+}
+
+#[test]
+fn test_group_duplicate_cards_never_groups_across_templates() {
+    let first = card_content("Hund", "dog");
+    let second = card_content("hund", "dog");
+    let third = card_content("HUND", "dog");
+
+    let groups = group_duplicate_cards([(1, 1, &first), (2, 2, &second), (3, 1, &third)], Some(&[1]));
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].template_id, 1);
+    assert_eq!(groups[0].card_ids, vec![1, 3]);
+}
+
+// ============================================================================
+// MERGE CARDS DATA
+// ============================================================================
+
+#[test]
+fn test_merge_cards_data_rejects_empty_sources() {
+    let err = MergeCardsData {
+        keep_id: 1,
+        merge_ids: vec![],
+    }
+    .validate()
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_MERGE_SOURCES);
+}
+
+#[test]
+fn test_merge_cards_data_rejects_keep_in_sources() {
+    let err = MergeCardsData {
+        keep_id: 1,
+        merge_ids: vec![2, 1],
+    }
+    .validate()
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_MERGE_SOURCES);
+}
@@ -0,0 +1,199 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{CardSelection, FindReplaceData, FindReplaceMode};
+use koloda_core::repo::cards;
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+fn data(
+    selection: CardSelection,
+    field_id: Option<i64>,
+    pattern: &str,
+    replacement: &str,
+    mode: FindReplaceMode,
+) -> FindReplaceData {
+    FindReplaceData {
+        selection,
+        field_id,
+        pattern: pattern.to_string(),
+        is_regex: false,
+        replacement: replacement.to_string(),
+        mode,
+    }
+}
+
+fn deck_selection(deck_id: i64) -> CardSelection {
+    CardSelection {
+        deck_ids: Some(vec![deck_id]),
+        card_ids: None,
+    }
+}
+
+#[test]
+fn find_replace_preview_reports_changes_without_writing() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let matching = add_card(&db, deck_id, template_id, "colour");
+    let _other = add_card(&db, deck_id, template_id, "shape");
+
+    let result = cards::find_replace(
+        &db,
+        data(
+            deck_selection(deck_id),
+            Some(1),
+            "colour",
+            "color",
+            FindReplaceMode::Preview,
+        ),
+    )
+    .expect("preview should succeed");
+
+    assert!(!result.is_applied);
+    assert_eq!(result.changes.len(), 1);
+    assert_eq!(result.changes[0].card_id, matching);
+    assert_eq!(result.changes[0].before["1"].text, "colour");
+    assert_eq!(result.changes[0].after["1"].text, "color");
+
+    let card = cards::get_card(&db, matching).unwrap().unwrap();
+    assert_eq!(card.content["1"].text, "colour");
+    assert!(card.updated_at.is_none());
+}
+
+#[test]
+fn find_replace_apply_updates_content_and_timestamp() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let card_id = add_card(&db, deck_id, template_id, "colour");
+
+    let result = cards::find_replace(
+        &db,
+        data(deck_selection(deck_id), None, "colour", "color", FindReplaceMode::Apply),
+    )
+    .expect("apply should succeed");
+
+    assert!(result.is_applied);
+    let card = cards::get_card(&db, card_id).unwrap().unwrap();
+    assert_eq!(card.content["1"].text, "color");
+    assert!(card.updated_at.is_some());
+}
+
+#[test]
+fn find_replace_respects_deck_and_card_filters() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let first_deck = add_deck(&db, algorithm_id, template_id, "First");
+    let second_deck = add_deck(&db, algorithm_id, template_id, "Second");
+    let first = add_card(&db, first_deck, template_id, "colour");
+    let second = add_card(&db, first_deck, template_id, "colour");
+    let other_deck_card = add_card(&db, second_deck, template_id, "colour");
+
+    let result = cards::find_replace(
+        &db,
+        data(
+            CardSelection {
+                deck_ids: Some(vec![first_deck]),
+                card_ids: Some(vec![second, other_deck_card]),
+            },
+            None,
+            "colour",
+            "color",
+            FindReplaceMode::Apply,
+        ),
+    )
+    .expect("apply should succeed");
+
+    assert_eq!(result.changes.len(), 1);
+    assert_eq!(result.changes[0].card_id, second);
+    assert_eq!(
+        cards::get_card(&db, first).unwrap().unwrap().content["1"].text,
+        "colour"
+    );
+    assert_eq!(
+        cards::get_card(&db, other_deck_card).unwrap().unwrap().content["1"].text,
+        "colour"
+    );
+}
+
+#[test]
+fn find_replace_regex_across_cards() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let first = add_card(&db, deck_id, template_id, "shi");
+    let second = add_card(&db, deck_id, template_id, "shita");
+
+    let mut request = data(deck_selection(deck_id), Some(1), r"^shi", "si", FindReplaceMode::Apply);
+    request.is_regex = true;
+    let result = cards::find_replace(&db, request).expect("apply should succeed");
+
+    assert_eq!(result.changes.len(), 2);
+    assert_eq!(cards::get_card(&db, first).unwrap().unwrap().content["1"].text, "si");
+    assert_eq!(cards::get_card(&db, second).unwrap().unwrap().content["1"].text, "sita");
+}
+
+#[test]
+fn find_replace_apply_writes_nothing_when_a_card_fails_validation() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let emptied = add_card(&db, deck_id, template_id, "typo");
+    let fixed = add_card(&db, deck_id, template_id, "typo here");
+
+    let result = cards::find_replace(
+        &db,
+        data(deck_selection(deck_id), Some(1), "typo", "", FindReplaceMode::Apply),
+    )
+    .unwrap();
+
+    assert!(!result.is_applied);
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].card_id, emptied);
+    assert_eq!(result.failed[0].code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY);
+    assert_eq!(
+        cards::get_card(&db, fixed).unwrap().unwrap().content["1"].text,
+        "typo here"
+    );
+}
+
+#[test]
+fn find_replace_preview_reports_failures_per_card() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let emptied = add_card(&db, deck_id, template_id, "typo");
+    let fixed = add_card(&db, deck_id, template_id, "typo here");
+
+    let result = cards::find_replace(
+        &db,
+        data(deck_selection(deck_id), Some(1), "typo", "", FindReplaceMode::Preview),
+    )
+    .unwrap();
+
+    assert_eq!(result.changes.len(), 1);
+    assert_eq!(result.changes[0].card_id, fixed);
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].card_id, emptied);
+    assert_eq!(result.failed[0].details.as_deref(), Some("Field id: 1"));
+}
+
+#[test]
+fn find_replace_rejects_empty_selection() {
+    let db = test_db();
+
+    let err = cards::find_replace(
+        &db,
+        data(CardSelection::default(), None, "a", "b", FindReplaceMode::Preview),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_SELECTION_EMPTY);
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::card_content;
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{
+    replace_in_content, CardSelection, FindReplaceData, FindReplaceMatcher, FindReplaceMode,
+};
+use serde_json::json;
+
+fn find_replace_data(pattern: &str, is_regex: bool) -> FindReplaceData {
+    FindReplaceData {
+        selection: CardSelection {
+            deck_ids: Some(vec![1]),
+            card_ids: None,
+        },
+        field_id: None,
+        pattern: pattern.to_string(),
+        is_regex,
+        replacement: "x".to_string(),
+        mode: FindReplaceMode::Preview,
+    }
+}
+
+// ============================================================================
+// VALIDATION
+// ============================================================================
+
+#[test]
+fn test_card_selection_rejects_empty_selection() {
+    let err = CardSelection::default().validate().unwrap_err();
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_SELECTION_EMPTY);
+
+    let err = CardSelection {
+        deck_ids: Some(vec![]),
+        card_ids: Some(vec![]),
+    }
+    .validate()
+    .unwrap_err();
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_SELECTION_EMPTY);
+}
+
+#[test]
+fn test_find_replace_rejects_empty_pattern() {
+    let err = find_replace_data("", false).validate().unwrap_err();
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_FIND_REPLACE_PATTERN_EMPTY);
+}
+
+#[test]
+fn test_find_replace_rejects_invalid_regex() {
+    let err = find_replace_data("(unclosed", true).validate().unwrap_err();
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_FIND_REPLACE_PATTERN_INVALID);
+}
+
+#[test]
+fn test_find_replace_literal_pattern_is_not_a_regex() {
+    let matcher = find_replace_data("(unclosed", false).validate().unwrap();
+    assert!(matches!(matcher, FindReplaceMatcher::Literal(_)));
+}
+
+#[test]
+fn test_find_replace_data_deserializes_with_defaults() {
+    let data: FindReplaceData = serde_json::from_value(json!({
+        "selection": { "cardIds": [1, 2] },
+        "pattern": "a",
+        "replacement": "b",
+        "mode": "apply"
+    }))
+    .unwrap();
+
+    assert_eq!(data.mode, FindReplaceMode::Apply);
+    assert!(!data.is_regex);
+    assert!(data.field_id.is_none());
+    assert!(data.selection.deck_ids.is_none());
+}
+
+// ============================================================================
+// REPLACE IN CONTENT
+// ============================================================================
+
+#[test]
+fn test_replace_in_content_literal_all_fields() {
+    let content = card_content("colour", "the colour red");
+    let matcher = FindReplaceMatcher::Literal("colour".to_string());
+    let replaced = replace_in_content(&content, None, &matcher, "color").unwrap();
+
+    assert_eq!(replaced["1"].text, "color");
+    assert_eq!(replaced["2"].text, "the color red");
+}
+
+#[test]
+fn test_replace_in_content_limits_to_field() {
+    let content = card_content("colour", "colour");
+    let matcher = FindReplaceMatcher::Literal("colour".to_string());
+    let replaced = replace_in_content(&content, Some(2), &matcher, "color").unwrap();
+
+    assert_eq!(replaced["1"].text, "colour");
+    assert_eq!(replaced["2"].text, "color");
+}
+
+#[test]
+fn test_replace_in_content_regex_expands_captures() {
+    let content = card_content("ko-lo-da", "b");
+    let matcher = FindReplaceMatcher::Regex(regex::Regex::new(r"(\w+)-(\w+)").unwrap());
+    let replaced = replace_in_content(&content, Some(1), &matcher, "$2$1").unwrap();
+
+    assert_eq!(replaced["1"].text, "loko-da");
+}
+
+#[test]
+fn test_replace_in_content_literal_does_not_expand_dollar() {
+    let content = card_content("price", "b");
+    let matcher = FindReplaceMatcher::Literal("price".to_string());
+    let replaced = replace_in_content(&content, None, &matcher, "$1").unwrap();
+
+    assert_eq!(replaced["1"].text, "$1");
+}
+
+#[test]
+fn test_replace_in_content_returns_none_without_match() {
+    let content = card_content("front", "back");
+    let matcher = FindReplaceMatcher::Literal("missing".to_string());
+
+    assert!(replace_in_content(&content, None, &matcher, "x").is_none());
+}
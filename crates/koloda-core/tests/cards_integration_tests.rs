@@ -1,6 +1,6 @@
 use koloda_core::app::error::error_codes;
 use koloda_core::domain::cards::{
-    DeleteCardData, DeleteCardsData, InsertCardData, ResetCardProgressData, UpdateCardProgress,
+    AddCardsOptions, DeleteCardData, DeleteCardsData, InsertCardData, ResetCardProgressData, UpdateCardProgress,
 };
 use koloda_core::domain::lessons::LessonResultData;
 use koloda_core::domain::reviews::{GetReviewsData, InsertReviewData};
@@ -89,6 +89,7 @@ fn add_cards_rejects_missing_deck_per_item() {
             lapses: None,
             last_reviewed_at: None,
         }],
+        AddCardsOptions::default(),
     )
     .expect("batch add should return per-item results");
 
@@ -141,6 +142,7 @@ fn add_cards_keeps_previously_inserted_cards_on_failure() {
                 last_reviewed_at: None,
             },
         ],
+        AddCardsOptions::default(),
     );
 
     let results = result.unwrap();
@@ -204,6 +206,7 @@ fn add_cards_supports_mixed_templates_in_one_batch() {
                 last_reviewed_at: None,
             },
         ],
+        AddCardsOptions::default(),
     );
 
     let results = result.unwrap();
@@ -253,6 +256,8 @@ fn reset_card_progress_removes_reviews_and_resets_progress_fields() {
                 learning_steps: 0,
                 time: 12,
                 is_ignored: false,
+                typed_answer: None,
+                typed_score: None,
             },
         },
     )
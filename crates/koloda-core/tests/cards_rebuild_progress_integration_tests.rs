@@ -0,0 +1,144 @@
+use koloda_core::app::db::Database;
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::algorithms::{UpdateAlgorithmData, UpdateAlgorithmValues};
+use koloda_core::domain::cards::{CardState, RebuildCardProgressData, RebuildCardProgressScope};
+use koloda_core::repo::{algorithms, cards};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template, insert_review_row};
+use common::{fsrs_algorithm_content, test_db};
+
+const DAY_MS: i64 = 86_400_000;
+const REVIEWED_AT: i64 = 1_700_000_000_000;
+
+fn rebuild(db: &Database, scope: RebuildCardProgressScope, id: i64) -> (usize, usize) {
+    let result =
+        cards::rebuild_card_progress(db, RebuildCardProgressData { scope, id }).expect("rebuild should succeed");
+    (result.total, result.changed)
+}
+
+fn reviewed_card(db: &Database, deck_id: i64, template_id: i64, front: &str) -> i64 {
+    let card_id = add_card(db, deck_id, template_id, front);
+    insert_review_row(db, card_id, CardState::New.as_i32(), 0, REVIEWED_AT);
+    insert_review_row(db, card_id, CardState::Learning.as_i32(), 1, REVIEWED_AT + DAY_MS);
+    insert_review_row(db, card_id, CardState::Learning.as_i32(), 0, REVIEWED_AT + 2 * DAY_MS);
+    card_id
+}
+
+#[test]
+fn rebuild_replays_non_ignored_reviews() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    let card_id = reviewed_card(&db, deck_id, template_id, "hola");
+
+    let first = rebuild(&db, RebuildCardProgressScope::Deck, deck_id);
+    let again = rebuild(&db, RebuildCardProgressScope::Card, card_id);
+
+    assert_eq!(first, (1, 1));
+    assert_eq!(again, (1, 0));
+    let card = cards::get_card(&db, card_id).unwrap().unwrap();
+    assert_eq!(card.reps, 2);
+    assert_eq!(card.last_reviewed_at, Some(REVIEWED_AT + 2 * DAY_MS));
+    assert_ne!(card.state, CardState::New.as_i32());
+    assert!(card.due_at.unwrap() > REVIEWED_AT + 2 * DAY_MS);
+}
+
+#[test]
+fn cards_without_graded_reviews_keep_their_progress() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    let card_id = add_card(&db, deck_id, template_id, "hola");
+    insert_review_row(&db, card_id, CardState::New.as_i32(), 1, REVIEWED_AT);
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE cards SET state = ?1, due_at = ?2, reps = 5 WHERE id = ?3",
+            rusqlite::params![CardState::Review.as_i32(), REVIEWED_AT + DAY_MS, card_id],
+        )?;
+        Ok(())
+    })
+    .unwrap();
+
+    let result = rebuild(&db, RebuildCardProgressScope::Deck, deck_id);
+
+    assert_eq!(result, (1, 0));
+    let card = cards::get_card(&db, card_id).unwrap().unwrap();
+    assert_eq!(card.reps, 5);
+    assert_eq!(card.state, CardState::Review.as_i32());
+}
+
+#[test]
+fn algorithm_scope_covers_every_deck_using_it() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let other_algorithm_id = add_algorithm(&db, "Other");
+    let template_id = add_template(&db, "Basic");
+    let spanish_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    let french_id = add_deck(&db, algorithm_id, template_id, "French");
+    let german_id = add_deck(&db, other_algorithm_id, template_id, "German");
+    reviewed_card(&db, spanish_id, template_id, "hola");
+    reviewed_card(&db, french_id, template_id, "bonjour");
+    let german_card_id = reviewed_card(&db, german_id, template_id, "hallo");
+
+    let result = rebuild(&db, RebuildCardProgressScope::Algorithm, algorithm_id);
+
+    assert_eq!(result, (2, 2));
+    assert_eq!(cards::get_card(&db, german_card_id).unwrap().unwrap().reps, 0);
+}
+
+#[test]
+fn edited_weights_change_rebuilt_progress() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    let card_id = reviewed_card(&db, deck_id, template_id, "hola");
+    rebuild(&db, RebuildCardProgressScope::Card, card_id);
+    let before = cards::get_card(&db, card_id).unwrap().unwrap();
+
+    let mut content = fsrs_algorithm_content();
+    content.retention = 97.0;
+    algorithms::update_algorithm(
+        &db,
+        UpdateAlgorithmData {
+            id: algorithm_id,
+            values: UpdateAlgorithmValues {
+                title: "FSRS".to_string(),
+                content,
+            },
+        },
+    )
+    .unwrap();
+    let result = rebuild(&db, RebuildCardProgressScope::Algorithm, algorithm_id);
+
+    assert_eq!(result, (1, 1));
+    let after = cards::get_card(&db, card_id).unwrap().unwrap();
+    assert!(after.due_at.unwrap() < before.due_at.unwrap());
+    assert_eq!(after.updated_at, before.updated_at);
+}
+
+#[test]
+fn missing_scope_target_is_not_found() {
+    let db = test_db();
+
+    for (scope, code) in [
+        (
+            RebuildCardProgressScope::Card,
+            error_codes::NOT_FOUND_CARDS_REBUILD_CARD,
+        ),
+        (
+            RebuildCardProgressScope::Deck,
+            error_codes::NOT_FOUND_CARDS_REBUILD_DECK,
+        ),
+        (
+            RebuildCardProgressScope::Algorithm,
+            error_codes::NOT_FOUND_CARDS_REBUILD_ALGORITHM,
+        ),
+    ] {
+        let err = cards::rebuild_card_progress(&db, RebuildCardProgressData { scope, id: 404 }).unwrap_err();
+        assert_eq!(err.code, code);
+    }
+}
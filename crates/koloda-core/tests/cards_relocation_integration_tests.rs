@@ -0,0 +1,295 @@
+use koloda_core::app::db::Database;
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{CardFieldMapping, CopyCardsData, MoveCardsData};
+use koloda_core::domain::reviews::GetReviewsData;
+use koloda_core::domain::templates::{InsertTemplateData, TemplateContent, TemplateField, TemplateLayoutItem};
+use koloda_core::repo::{cards, reviews, templates};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template, insert_review_row};
+use common::test_db;
+
+fn add_vocabulary_template(db: &Database) -> i64 {
+    templates::add_template(
+        db,
+        InsertTemplateData {
+            title: "Vocabulary".to_string(),
+            content: TemplateContent {
+                fields: vec![
+                    TemplateField {
+                        id: 10,
+                        title: "Word".to_string(),
+                        field_type: "text".to_string(),
+                        is_required: true,
+                        rules: None,
+                    },
+                    TemplateField {
+                        id: 20,
+                        title: "Meaning".to_string(),
+                        field_type: "text".to_string(),
+                        is_required: true,
+                        rules: None,
+                    },
+                ],
+                layout: vec![TemplateLayoutItem {
+                    field: 10,
+                    operation: "display".to_string(),
+                }],
+                sides: None,
+            },
+        },
+    )
+    .expect("template should be created")
+    .id
+}
+
+fn set_card_progress(db: &Database, card_id: i64) {
+    db.with_conn(|conn| {
+        conn.execute(
+            "UPDATE cards SET state = 2, due_at = 5000, stability = 3.5, reps = 4 WHERE id = ?1",
+            rusqlite::params![card_id],
+        )?;
+        Ok(())
+    })
+    .expect("progress should update");
+}
+
+#[test]
+fn move_cards_same_template_keeps_content_and_reviews() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let source_deck = add_deck(&db, algorithm_id, template_id, "Source");
+    let target_deck = add_deck(&db, algorithm_id, template_id, "Target");
+    let card_id = add_card(&db, source_deck, template_id, "question");
+    insert_review_row(&db, card_id, 0, 0, 1_000);
+
+    let moved = cards::move_cards(
+        &db,
+        MoveCardsData {
+            ids: vec![card_id],
+            deck_id: target_deck,
+            field_mapping: None,
+        },
+    )
+    .expect("move should succeed");
+
+    assert_eq!(moved.len(), 1);
+    assert_eq!(moved[0].id, card_id);
+    assert_eq!(moved[0].deck_id, target_deck);
+    assert_eq!(moved[0].content["1"].text, "question");
+    assert!(moved[0].updated_at.is_some());
+    assert!(cards::get_cards(&db, source_deck).unwrap().is_empty());
+
+    let history = reviews::get_reviews(&db, GetReviewsData { card_id }).unwrap();
+    assert_eq!(history.len(), 1);
+}
+
+#[test]
+fn move_cards_remaps_fields_to_target_template() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let vocabulary_id = add_vocabulary_template(&db);
+    let source_deck = add_deck(&db, algorithm_id, template_id, "Source");
+    let target_deck = add_deck(&db, algorithm_id, vocabulary_id, "Target");
+    let card_id = add_card(&db, source_deck, template_id, "question");
+
+    let moved = cards::move_cards(
+        &db,
+        MoveCardsData {
+            ids: vec![card_id],
+            deck_id: target_deck,
+            field_mapping: Some(vec![
+                CardFieldMapping { source: 1, target: 10 },
+                CardFieldMapping { source: 2, target: 20 },
+            ]),
+        },
+    )
+    .expect("move should succeed");
+
+    assert_eq!(moved[0].template_id, vocabulary_id);
+    assert_eq!(moved[0].content["10"].text, "question");
+    assert_eq!(moved[0].content["20"].text, "answer");
+    assert!(!moved[0].content.contains_key("1"));
+}
+
+#[test]
+fn move_cards_requires_mapping_when_templates_differ() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let vocabulary_id = add_vocabulary_template(&db);
+    let source_deck = add_deck(&db, algorithm_id, template_id, "Source");
+    let target_deck = add_deck(&db, algorithm_id, vocabulary_id, "Target");
+    let card_id = add_card(&db, source_deck, template_id, "question");
+
+    let err = cards::move_cards(
+        &db,
+        MoveCardsData {
+            ids: vec![card_id],
+            deck_id: target_deck,
+            field_mapping: None,
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_FIELD_MAPPING_MISSING);
+}
+
+#[test]
+fn move_cards_is_all_or_nothing_on_validation_failure() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let vocabulary_id = add_vocabulary_template(&db);
+    let source_deck = add_deck(&db, algorithm_id, template_id, "Source");
+    let target_deck = add_deck(&db, algorithm_id, vocabulary_id, "Target");
+    let first = add_card(&db, source_deck, template_id, "first");
+    let second = add_card(&db, source_deck, template_id, "second");
+
+    let err = cards::move_cards(
+        &db,
+        MoveCardsData {
+            ids: vec![first, second],
+            deck_id: target_deck,
+            field_mapping: Some(vec![CardFieldMapping { source: 1, target: 10 }]),
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY);
+    assert_eq!(cards::get_cards(&db, source_deck).unwrap().len(), 2);
+    assert!(cards::get_cards(&db, target_deck).unwrap().is_empty());
+}
+
+#[test]
+fn move_cards_rejects_missing_deck_and_card() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let card_id = add_card(&db, deck_id, template_id, "question");
+
+    let err = cards::move_cards(
+        &db,
+        MoveCardsData {
+            ids: vec![card_id],
+            deck_id: 999_999,
+            field_mapping: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.code, error_codes::NOT_FOUND_CARDS_MOVE_DECK);
+
+    let err = cards::move_cards(
+        &db,
+        MoveCardsData {
+            ids: vec![card_id, 999_999],
+            deck_id,
+            field_mapping: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.code, error_codes::NOT_FOUND_CARDS_MOVE_CARD);
+}
+
+#[test]
+fn copy_cards_keeps_progress_and_copies_reviews() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let source_deck = add_deck(&db, algorithm_id, template_id, "Source");
+    let target_deck = add_deck(&db, algorithm_id, template_id, "Target");
+    let card_id = add_card(&db, source_deck, template_id, "question");
+    set_card_progress(&db, card_id);
+    insert_review_row(&db, card_id, 0, 0, 1_000);
+    insert_review_row(&db, card_id, 1, 1, 2_000);
+
+    let copies = cards::copy_cards(
+        &db,
+        CopyCardsData {
+            ids: vec![card_id],
+            deck_id: target_deck,
+            field_mapping: None,
+            reset_progress: false,
+        },
+    )
+    .expect("copy should succeed");
+
+    assert_eq!(copies.len(), 1);
+    let copy = &copies[0];
+    assert_ne!(copy.id, card_id);
+    assert_eq!(copy.deck_id, target_deck);
+    assert_eq!(copy.state, 2);
+    assert_eq!(copy.due_at, Some(5000));
+    assert_eq!(copy.reps, 4);
+
+    let copied_reviews = reviews::get_reviews(&db, GetReviewsData { card_id: copy.id }).unwrap();
+    assert_eq!(copied_reviews.len(), 2);
+    assert!(copied_reviews.iter().any(|r| r.created_at == 1_000 && !r.is_ignored));
+    assert!(copied_reviews.iter().any(|r| r.created_at == 2_000 && r.is_ignored));
+
+    assert_eq!(cards::get_cards(&db, source_deck).unwrap().len(), 1);
+    assert_eq!(reviews::get_reviews(&db, GetReviewsData { card_id }).unwrap().len(), 2);
+}
+
+#[test]
+fn copy_cards_reset_progress_starts_new_without_reviews() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let vocabulary_id = add_vocabulary_template(&db);
+    let source_deck = add_deck(&db, algorithm_id, template_id, "Source");
+    let target_deck = add_deck(&db, algorithm_id, vocabulary_id, "Target");
+    let card_id = add_card(&db, source_deck, template_id, "question");
+    set_card_progress(&db, card_id);
+    insert_review_row(&db, card_id, 0, 0, 1_000);
+
+    let copies = cards::copy_cards(
+        &db,
+        CopyCardsData {
+            ids: vec![card_id],
+            deck_id: target_deck,
+            field_mapping: Some(vec![
+                CardFieldMapping { source: 2, target: 10 },
+                CardFieldMapping { source: 1, target: 20 },
+            ]),
+            reset_progress: true,
+        },
+    )
+    .expect("copy should succeed");
+
+    let copy = &copies[0];
+    assert_eq!(copy.template_id, vocabulary_id);
+    assert_eq!(copy.content["10"].text, "answer");
+    assert_eq!(copy.content["20"].text, "question");
+    assert_eq!(copy.state, 0);
+    assert_eq!(copy.due_at, None);
+    assert_eq!(copy.reps, 0);
+    assert!(reviews::get_reviews(&db, GetReviewsData { card_id: copy.id })
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn copy_cards_rejects_missing_deck() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let card_id = add_card(&db, deck_id, template_id, "question");
+
+    let err = cards::copy_cards(
+        &db,
+        CopyCardsData {
+            ids: vec![card_id],
+            deck_id: 999_999,
+            field_mapping: None,
+            reset_progress: false,
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::NOT_FOUND_CARDS_COPY_DECK);
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::{card_content, valid_template_fields};
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{
+    remap_content, validate_field_mapping, CardFieldMapping, CopyCardsData, MoveCardsData,
+};
+use koloda_core::domain::templates::TemplateField;
+use serde_json::json;
+
+fn target_fields() -> Vec<TemplateField> {
+    vec![
+        TemplateField {
+            id: 10,
+            title: "Word".to_string(),
+            field_type: "text".to_string(),
+            is_required: true,
+            rules: None,
+        },
+        TemplateField {
+            id: 20,
+            title: "Meaning".to_string(),
+            field_type: "text".to_string(),
+            is_required: false,
+            rules: None,
+        },
+    ]
+}
+
+// ============================================================================
+// REMAP CONTENT
+// ============================================================================
+
+#[test]
+fn test_remap_content_without_mapping_keeps_content() {
+    let content = card_content("front", "back");
+    let remapped = remap_content(&content, None);
+
+    assert_eq!(remapped.len(), 2);
+    assert_eq!(remapped["1"].text, "front");
+    assert_eq!(remapped["2"].text, "back");
+}
+
+#[test]
+fn test_remap_content_moves_values_to_target_ids() {
+    let content = card_content("front", "back");
+    let mapping = [
+        CardFieldMapping { source: 1, target: 20 },
+        CardFieldMapping { source: 2, target: 10 },
+    ];
+    let remapped = remap_content(&content, Some(&mapping));
+
+    assert_eq!(remapped.len(), 2);
+    assert_eq!(remapped["10"].text, "back");
+    assert_eq!(remapped["20"].text, "front");
+}
+
+#[test]
+fn test_remap_content_drops_unmapped_source_fields() {
+    let content = card_content("front", "back");
+    let mapping = [CardFieldMapping { source: 1, target: 10 }];
+    let remapped = remap_content(&content, Some(&mapping));
+
+    assert_eq!(remapped.len(), 1);
+    assert_eq!(remapped["10"].text, "front");
+}
+
+// ============================================================================
+// VALIDATE FIELD MAPPING
+// ============================================================================
+
+#[test]
+fn test_validate_field_mapping_accepts_known_ids() {
+    let mapping = [
+        CardFieldMapping { source: 1, target: 10 },
+        CardFieldMapping { source: 2, target: 20 },
+    ];
+    validate_field_mapping(&mapping, &valid_template_fields(), &target_fields()).unwrap();
+}
+
+#[test]
+fn test_validate_field_mapping_rejects_unknown_source() {
+    let mapping = [CardFieldMapping { source: 99, target: 10 }];
+    let err = validate_field_mapping(&mapping, &valid_template_fields(), &target_fields()).unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_FIELD_MAPPING_INVALID);
+}
+
+#[test]
+fn test_validate_field_mapping_rejects_unknown_target() {
+    let mapping = [CardFieldMapping { source: 1, target: 99 }];
+    let err = validate_field_mapping(&mapping, &valid_template_fields(), &target_fields()).unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_FIELD_MAPPING_INVALID);
+}
+
+#[test]
+fn test_validate_field_mapping_rejects_duplicate_target() {
+    let mapping = [
+        CardFieldMapping { source: 1, target: 10 },
+        CardFieldMapping { source: 2, target: 10 },
+    ];
+    let err = validate_field_mapping(&mapping, &valid_template_fields(), &target_fields()).unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_CARDS_FIELD_MAPPING_INVALID);
+}
+
+// ============================================================================
+// SERDE
+// ============================================================================
+
+#[test]
+fn test_move_cards_data_field_mapping_defaults_to_none() {
+    let data: MoveCardsData = serde_json::from_value(json!({ "ids": [1, 2], "deckId": 3 })).unwrap();
+
+    assert_eq!(data.ids, vec![1, 2]);
+    assert!(data.field_mapping.is_none());
+}
+
+#[test]
+fn test_copy_cards_data_reset_progress_defaults_to_false() {
+    let data: CopyCardsData = serde_json::from_value(json!({
+        "ids": [1],
+        "deckId": 3,
+        "fieldMapping": [{ "source": 1, "target": 10 }]
+    }))
+    .unwrap();
+
+    assert!(!data.reset_progress);
+    assert_eq!(data.field_mapping.unwrap().len(), 1);
+}
@@ -0,0 +1,147 @@
+use std::sync::mpsc;
+use std::time::Duration;
+
+use koloda_core::app::watch::ChangeWatcher;
+use koloda_core::domain::cards::{DeleteCardData, UpdateCardData, UpdateCardValues};
+use koloda_core::domain::changes::{ChangeEntity, ChangeOperation, GetChangesParams};
+use koloda_core::repo::{cards, changes};
+use serde_json::json;
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_conversation, add_deck, add_template};
+use common::{card_content, test_db};
+
+fn events_after(db: &koloda_core::app::db::Database, cursor: i64) -> Vec<(ChangeEntity, String, ChangeOperation)> {
+    changes::get_changes(db, GetChangesParams { cursor, limit: None })
+        .expect("changes should load")
+        .events
+        .into_iter()
+        .map(|event| (event.entity, event.entity_id, event.operation))
+        .collect()
+}
+
+#[test]
+fn repo_writes_are_recorded_in_order() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    let cursor = changes::get_latest_change_cursor(&db).unwrap();
+
+    let card_id = add_card(&db, deck_id, template_id, "hola");
+    cards::update_card(
+        &db,
+        UpdateCardData {
+            id: card_id,
+            values: UpdateCardValues {
+                content: card_content("adiós", "bye"),
+            },
+        },
+    )
+    .unwrap();
+    cards::delete_card(&db, DeleteCardData { id: card_id }).unwrap();
+
+    let id = card_id.to_string();
+    assert_eq!(
+        events_after(&db, cursor),
+        vec![
+            (ChangeEntity::Card, id.clone(), ChangeOperation::Insert),
+            (ChangeEntity::Card, id.clone(), ChangeOperation::Update),
+            (ChangeEntity::Card, id, ChangeOperation::Delete),
+        ]
+    );
+}
+
+#[test]
+fn conversations_use_their_string_ids() {
+    let db = test_db();
+
+    add_conversation(&db, "chat-1", json!({ "messages": [] }));
+
+    assert_eq!(
+        events_after(&db, 0),
+        vec![(
+            ChangeEntity::Conversation,
+            "chat-1".to_string(),
+            ChangeOperation::Insert
+        )]
+    );
+}
+
+#[test]
+fn pages_follow_the_cursor() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    add_template(&db, "Basic");
+    add_algorithm(&db, "Second");
+
+    let first = changes::get_changes(
+        &db,
+        GetChangesParams {
+            cursor: 0,
+            limit: Some(2),
+        },
+    )
+    .unwrap();
+    let rest = changes::get_changes(
+        &db,
+        GetChangesParams {
+            cursor: first.cursor,
+            limit: Some(2),
+        },
+    )
+    .unwrap();
+
+    assert!(first.has_more);
+    assert_eq!(first.events.len(), 2);
+    assert_eq!(first.events[0].entity_id, algorithm_id.to_string());
+    assert!(!rest.has_more);
+    assert_eq!(rest.events.len(), 1);
+    assert_eq!(rest.cursor, changes::get_latest_change_cursor(&db).unwrap());
+}
+
+#[test]
+fn watcher_delivers_new_events() {
+    let db = test_db();
+    let (sender, receiver) = mpsc::channel();
+    let watcher = ChangeWatcher::start(db.clone(), 0, Duration::from_millis(10), move |events| {
+        sender.send(events).expect("receiver should be alive");
+    });
+
+    let algorithm_id = add_algorithm(&db, "FSRS");
+
+    let events = receiver
+        .recv_timeout(Duration::from_secs(5))
+        .expect("events should arrive");
+    watcher.stop();
+    assert_eq!(events[0].entity, ChangeEntity::Algorithm);
+    assert_eq!(events[0].entity_id, algorithm_id.to_string());
+}
+
+#[test]
+fn feed_keeps_its_newest_events() {
+    let db = test_db();
+    db.with_conn(|conn| {
+        conn.execute_batch(
+            r#"
+            WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 10005)
+            INSERT INTO change_feed (entity, entity_id, operation, changed_at)
+            SELECT 'card', CAST(i AS TEXT), 'insert', 0 FROM n;
+            "#,
+        )?;
+        Ok(())
+    })
+    .unwrap();
+
+    let latest = changes::get_latest_change_cursor(&db).unwrap();
+    let page = changes::get_changes(
+        &db,
+        GetChangesParams {
+            cursor: 0,
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(page.events.first().expect("an event").id, latest - 9_999);
+}
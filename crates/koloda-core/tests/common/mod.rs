@@ -54,12 +54,14 @@ pub fn simple_template_content() -> koloda_core::domain::templates::TemplateCont
                 title: "Front".to_string(),
                 field_type: "text".to_string(),
                 is_required: true,
+                rules: None,
             },
             TemplateField {
                 id: 2,
                 title: "Back".to_string(),
                 field_type: "text".to_string(),
                 is_required: false,
+                rules: None,
             },
         ],
         layout: vec![
@@ -72,6 +74,7 @@ pub fn simple_template_content() -> koloda_core::domain::templates::TemplateCont
                 operation: "reveal".to_string(),
             },
         ],
+        sides: None,
     }
 }
 
@@ -190,12 +193,14 @@ pub fn valid_template_fields() -> [koloda_core::domain::templates::TemplateField
             title: "Front".to_string(),
             field_type: "text".to_string(),
             is_required: true,
+            rules: None,
         },
         TemplateField {
             id: 2,
             title: "Back".to_string(),
             field_type: "text".to_string(),
             is_required: false,
+            rules: None,
         },
     ]
 }
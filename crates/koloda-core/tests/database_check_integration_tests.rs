@@ -0,0 +1,178 @@
+use koloda_core::app::db::Database;
+use koloda_core::domain::cards::CardState;
+use koloda_core::domain::database_check::{DatabaseIssue, DatabaseIssueKind, RepairDatabaseData};
+use koloda_core::domain::settings::SettingsName;
+use koloda_core::migrations;
+use koloda_core::repo::{cards, database_check, settings, templates};
+use rusqlite::Connection;
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template, insert_card_row, insert_review_row};
+use common::{interface_settings, test_db};
+
+// WHY: foreign keys stay off so the tests can store the dangling rows the checker looks for.
+fn unchecked_db() -> Database {
+    let mut conn = Connection::open_in_memory().expect("database should open");
+    migrations::runner().run(&mut conn).expect("migrations should run");
+    conn.pragma_update(None, "foreign_keys", "OFF")
+        .expect("foreign keys should turn off");
+    Database::new(conn)
+}
+
+fn execute(db: &Database, sql: &str) {
+    db.with_transaction(|tx| Ok(tx.execute_batch(sql)?))
+        .expect("statement should run");
+}
+
+fn kinds(issues: &[DatabaseIssue]) -> Vec<DatabaseIssueKind> {
+    issues.iter().map(|issue| issue.kind).collect()
+}
+
+fn repair(db: &Database, kinds: &[DatabaseIssueKind]) -> (usize, Vec<DatabaseIssue>) {
+    let result = database_check::repair_database(db, RepairDatabaseData { kinds: kinds.to_vec() })
+        .expect("repair should succeed");
+    (result.fixed, result.report.issues)
+}
+
+#[test]
+fn clean_database_is_ok() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    add_card(&db, deck_id, template_id, "hola");
+
+    let report = database_check::check_database(&db).unwrap();
+
+    assert!(report.is_ok(), "{:?}", report.issues);
+}
+
+#[test]
+fn dangling_references_are_reported_once_and_fixed() {
+    let db = unchecked_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    insert_review_row(&db, 404, CardState::New.as_i32(), 0, 1);
+    execute(
+        &db,
+        &format!("UPDATE decks SET algorithm_id = 404 WHERE id = {}", deck_id),
+    );
+
+    let report = database_check::check_database(&db).unwrap();
+    let (fixed, left) = repair(
+        &db,
+        &[DatabaseIssueKind::OrphanReview, DatabaseIssueKind::DeckAlgorithm],
+    );
+
+    assert_eq!(
+        kinds(&report.issues),
+        vec![DatabaseIssueKind::OrphanReview, DatabaseIssueKind::DeckAlgorithm]
+    );
+    assert!(report.issues.iter().all(|issue| issue.is_fixable));
+    assert_eq!(report.issues[1].row_id, Some(deck_id));
+    assert_eq!(fixed, 2);
+    assert!(left.is_empty(), "{:?}", left);
+}
+
+#[test]
+fn card_issues_fix_progress_but_not_content() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    let card_id = insert_card_row(&db, deck_id, template_id, CardState::Review.as_i32(), None, 1);
+    insert_review_row(&db, card_id, CardState::New.as_i32(), 0, 1);
+    let empty_id = add_card(&db, deck_id, template_id, "hola");
+    execute(
+        &db,
+        &format!(
+            r#"UPDATE cards SET content = '{{"1":{{"text":""}}}}' WHERE id = {}"#,
+            empty_id
+        ),
+    );
+
+    let report = database_check::check_database(&db).unwrap();
+    let (fixed, left) = repair(&db, &[DatabaseIssueKind::CardProgress, DatabaseIssueKind::CardContent]);
+
+    assert_eq!(
+        kinds(&report.issues),
+        vec![DatabaseIssueKind::CardContent, DatabaseIssueKind::CardProgress]
+    );
+    assert!(!report.issues[0].is_fixable);
+    assert_eq!(fixed, 1);
+    assert_eq!(kinds(&left), vec![DatabaseIssueKind::CardContent]);
+    let card = cards::get_card(&db, card_id).unwrap().unwrap();
+    assert_eq!(card.reps, 1);
+    assert!(card.due_at.is_some());
+}
+
+#[test]
+fn card_progress_without_graded_reviews_is_not_fixable() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    let card_id = insert_card_row(&db, deck_id, template_id, CardState::Review.as_i32(), None, 1);
+    insert_review_row(&db, card_id, CardState::New.as_i32(), 1, 1);
+
+    let report = database_check::check_database(&db).unwrap();
+    let (fixed, left) = repair(&db, &[DatabaseIssueKind::CardProgress]);
+
+    assert_eq!(kinds(&report.issues), vec![DatabaseIssueKind::CardProgress]);
+    assert!(!report.issues[0].is_fixable);
+    assert_eq!(fixed, 0);
+    assert_eq!(kinds(&left), vec![DatabaseIssueKind::CardProgress]);
+    let card = cards::get_card(&db, card_id).unwrap().unwrap();
+    assert_eq!(card.state, CardState::Review.as_i32());
+}
+
+#[test]
+fn layout_items_for_missing_fields_are_dropped() {
+    let db = test_db();
+    let template_id = add_template(&db, "Basic");
+    execute(
+        &db,
+        &format!(
+            r#"UPDATE templates SET content = json_set(content, '$.layout[#]', json('{{"field":9,"operation":"display"}}')) WHERE id = {}"#,
+            template_id
+        ),
+    );
+
+    let report = database_check::check_database(&db).unwrap();
+    let (untouched, _) = repair(&db, &[DatabaseIssueKind::Settings]);
+    let (fixed, left) = repair(&db, &[DatabaseIssueKind::TemplateLayout]);
+
+    assert_eq!(kinds(&report.issues), vec![DatabaseIssueKind::TemplateLayout]);
+    assert!(report.issues[0].details.contains('9'));
+    assert_eq!(untouched, 0);
+    assert_eq!(fixed, 1);
+    assert!(left.is_empty(), "{:?}", left);
+    let template = templates::get_template(&db, template_id).unwrap().unwrap();
+    assert!(template.content.layout.iter().all(|item| item.field != 9));
+}
+
+#[test]
+fn broken_settings_are_restored_from_history() {
+    let db = test_db();
+    settings::set_settings(
+        &db,
+        SettingsName::Interface,
+        interface_settings("en", "system", "system"),
+    )
+    .unwrap();
+    execute(
+        &db,
+        r#"UPDATE settings SET content = '{"scheme":"purple"}' WHERE name = 'interface'"#,
+    );
+
+    let report = database_check::check_database(&db).unwrap();
+    let (fixed, left) = repair(&db, &[DatabaseIssueKind::Settings]);
+
+    assert_eq!(kinds(&report.issues), vec![DatabaseIssueKind::Settings]);
+    assert!(report.issues[0].is_fixable);
+    assert_eq!(fixed, 1);
+    assert!(left.is_empty(), "{:?}", left);
+    let interface = settings::get_settings(&db, SettingsName::Interface).unwrap().unwrap();
+    assert_eq!(interface.content["scheme"], "system");
+}
@@ -0,0 +1,89 @@
+use koloda_core::app::db::Database;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn unique_db_path(prefix: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock went backwards")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("koloda-{}-{}-{}", prefix, std::process::id(), nanos));
+    fs::create_dir_all(&dir).expect("temp dir should be created");
+    dir.join("koloda.db")
+}
+
+#[test]
+fn with_read_rejects_writes() {
+    let path = unique_db_path("read-pool-readonly");
+    let db = Database::init(&path).unwrap();
+
+    db.with_read(|conn| {
+        conn.execute(
+            "INSERT INTO decks (title, algorithm_id, template_id, created_at) VALUES ('x', 1, 1, 0)",
+            [],
+        )?;
+        Ok(())
+    })
+    .unwrap_err();
+
+    drop(db);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn in_memory_with_read_sees_writes() {
+    let db = Database::in_memory().unwrap();
+
+    db.with_conn(|conn| {
+        conn.execute("CREATE TABLE scratch (id INTEGER)", [])?;
+        Ok(())
+    })
+    .unwrap();
+    let count: i64 = db
+        .with_read(|conn| Ok(conn.query_row("SELECT COUNT(*) FROM scratch", [], |row| row.get(0))?))
+        .unwrap();
+
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn reads_proceed_while_a_transaction_is_open() {
+    let path = unique_db_path("read-pool-concurrent");
+    let db = Database::init(&path).unwrap();
+    let (started_tx, started_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+
+    let writer_db = db.clone();
+    let writer = thread::spawn(move || {
+        writer_db
+            .with_transaction(|conn| {
+                conn.execute("CREATE TABLE scratch (id INTEGER)", [])?;
+                started_tx.send(()).expect("main thread should be waiting");
+                release_rx.recv().expect("main thread should release the writer");
+                Ok(())
+            })
+            .expect("transaction should commit");
+    });
+
+    started_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("writer should start");
+    let scratch_visible: i64 = db
+        .with_read(|conn| {
+            Ok(
+                conn.query_row("SELECT COUNT(*) FROM sqlite_master WHERE name = 'scratch'", [], |row| {
+                    row.get(0)
+                })?,
+            )
+        })
+        .expect("read should not wait for the writer");
+    release_tx.send(()).unwrap();
+    writer.join().unwrap();
+
+    assert_eq!(scratch_visible, 0);
+    drop(db);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
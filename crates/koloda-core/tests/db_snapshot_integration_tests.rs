@@ -0,0 +1,167 @@
+use koloda_core::app::db::{pre_migration_snapshot_path, restore_pre_migration_snapshot, Database, MIGRATIONS_TABLE};
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::settings::SettingsName;
+use koloda_core::repo::settings;
+use rusqlite::params;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+mod common;
+use common::interface_settings;
+
+fn unique_db_path(prefix: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock went backwards")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("koloda-{}-{}-{}", prefix, std::process::id(), nanos));
+    fs::create_dir_all(&dir).expect("temp dir should be created");
+    dir.join("koloda.db")
+}
+
+fn max_applied_version(db: &Database) -> u32 {
+    db.with_conn(|conn| {
+        Ok(
+            conn.query_row(&format!("SELECT MAX(version) FROM {}", MIGRATIONS_TABLE), [], |row| {
+                row.get(0)
+            })?,
+        )
+    })
+    .expect("migration version should load")
+}
+
+// WHY: Rolls the file back to "latest migration pending" so the next `init` has work to do.
+fn unapply_latest_migration(db: &Database) -> u32 {
+    let latest = max_applied_version(db);
+    db.with_conn(|conn| {
+        conn.execute("ALTER TABLE reviews DROP COLUMN typed_answer", [])?;
+        conn.execute("ALTER TABLE reviews DROP COLUMN typed_score", [])?;
+        conn.execute(
+            &format!("DELETE FROM {} WHERE version = ?1", MIGRATIONS_TABLE),
+            params![latest],
+        )?;
+        Ok(())
+    })
+    .expect("latest migration should be unapplied");
+    latest
+}
+
+#[test]
+fn first_run_does_not_snapshot() {
+    let path = unique_db_path("snapshot-fresh");
+    Database::init(&path).unwrap();
+
+    assert!(!pre_migration_snapshot_path(&path).exists());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn pending_migrations_snapshot_and_restore() {
+    let path = unique_db_path("snapshot-pending");
+    let db = Database::init(&path).unwrap();
+    settings::set_settings(&db, SettingsName::Interface, interface_settings("ru", "dark", "system")).unwrap();
+    let latest = unapply_latest_migration(&db);
+    drop(db);
+
+    let db = Database::init(&path).expect("pending migration should apply");
+    assert_eq!(max_applied_version(&db), latest);
+    assert!(pre_migration_snapshot_path(&path).exists());
+    drop(db);
+
+    restore_pre_migration_snapshot(&path).expect("restore should succeed");
+
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    let restored: u32 = conn
+        .query_row(&format!("SELECT MAX(version) FROM {}", MIGRATIONS_TABLE), [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(restored, latest - 1);
+    let language: String = conn
+        .query_row(
+            "SELECT json_extract(content, '$.language') FROM settings WHERE name = 'interface'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(language, "ru");
+    drop(conn);
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn newer_schema_is_reported_as_downgrade() {
+    let path = unique_db_path("snapshot-newer");
+    let db = Database::init(&path).unwrap();
+    db.with_conn(|conn| {
+        conn.execute(
+            &format!(
+                "INSERT INTO {} (version, name, applied_on, checksum) VALUES (999, 'future', '2030-01-01T00:00:00Z', '0')",
+                MIGRATIONS_TABLE
+            ),
+            [],
+        )?;
+        Ok(())
+    })
+    .unwrap();
+    drop(db);
+
+    let err = Database::init(&path).err().expect("newer schema should be rejected");
+
+    assert_eq!(err.code, error_codes::DB_SCHEMA_NEWER);
+    assert!(!pre_migration_snapshot_path(&path).exists());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn restore_without_snapshot_fails() {
+    let path = unique_db_path("snapshot-missing");
+
+    let err = restore_pre_migration_snapshot(&path).unwrap_err();
+
+    assert_eq!(err.code, error_codes::NOT_FOUND_DB_SNAPSHOT);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn backup_writes_a_readable_copy() {
+    let path = unique_db_path("backup");
+    let db = Database::init(&path).unwrap();
+    settings::set_settings(&db, SettingsName::Interface, interface_settings("ru", "dark", "system")).unwrap();
+    let backup_path = path.with_file_name("backup.db");
+    fs::write(&backup_path, b"stale").unwrap();
+
+    db.backup(&backup_path).unwrap();
+
+    let copy = Database::init(&backup_path).unwrap();
+    let interface = settings::get_settings(&copy, SettingsName::Interface).unwrap().unwrap();
+    assert_eq!(interface.content["language"], "ru");
+    assert!(copy.integrity_check().unwrap().is_empty());
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn integrity_check_reports_dangling_foreign_keys() {
+    let path = unique_db_path("integrity");
+    drop(Database::init(&path).unwrap());
+    let raw = rusqlite::Connection::open(&path).unwrap();
+    raw.execute_batch(
+        "PRAGMA foreign_keys = OFF;
+         INSERT INTO decks (title, algorithm_id, template_id, created_at) VALUES ('Orphan', 404, 404, 0);",
+    )
+    .unwrap();
+    drop(raw);
+    let db = Database::init(&path).unwrap();
+
+    let problems = db.integrity_check().unwrap();
+
+    assert!(!problems.is_empty());
+    assert!(
+        problems.iter().all(|problem| problem.starts_with("decks row")),
+        "{:?}",
+        problems
+    );
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
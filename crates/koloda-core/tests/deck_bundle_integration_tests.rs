@@ -0,0 +1,116 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::deck_bundle::{DeckBundle, DECK_BUNDLE_VERSION};
+use koloda_core::repo::{algorithms, cards, decks, templates};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+fn bundle(value: serde_json::Value) -> DeckBundle {
+    serde_json::from_value(value).expect("bundle should parse")
+}
+
+#[test]
+fn export_and_import_deck_round_trip() {
+    let source = test_db();
+    let algorithm_id = add_algorithm(&source, "Shared FSRS");
+    let template_id = add_template(&source, "Shared Basic");
+    let deck_id = add_deck(&source, algorithm_id, template_id, "Spanish");
+    add_card(&source, deck_id, template_id, "hola");
+    add_card(&source, deck_id, template_id, "adiós");
+
+    let exported = decks::export_deck(&source, deck_id).expect("export should succeed");
+    assert_eq!(exported.version, DECK_BUNDLE_VERSION);
+    assert_eq!(exported.cards.len(), 2);
+
+    let target = test_db();
+    let json = serde_json::to_value(&exported).unwrap();
+    let deck = decks::import_deck(&target, bundle(json)).expect("import should succeed");
+
+    assert_eq!(deck.title, "Spanish");
+    assert_eq!(algorithms::get_algorithms(&target).unwrap()[0].title, "Shared FSRS");
+    assert_eq!(templates::get_templates(&target).unwrap()[0].title, "Shared Basic");
+    let imported = cards::get_cards(&target, deck.id).unwrap();
+    let mut fronts: Vec<_> = imported.iter().map(|card| card.content["1"].text.clone()).collect();
+    fronts.sort();
+    assert_eq!(fronts, vec!["adiós", "hola"]);
+}
+
+#[test]
+fn import_deck_reuses_matching_algorithm_and_template() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "Shared FSRS");
+    let template_id = add_template(&db, "Shared Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    add_card(&db, deck_id, template_id, "hola");
+
+    let exported = decks::export_deck(&db, deck_id).unwrap();
+    let copy = decks::import_deck(&db, exported).unwrap();
+
+    assert_ne!(copy.id, deck_id);
+    assert_eq!(copy.algorithm_id, algorithm_id);
+    assert_eq!(copy.template_id, template_id);
+    assert_eq!(algorithms::get_algorithms(&db).unwrap().len(), 1);
+    assert_eq!(templates::get_templates(&db).unwrap().len(), 1);
+    assert_eq!(cards::get_cards(&db, copy.id).unwrap().len(), 1);
+}
+
+#[test]
+fn import_deck_adds_presets_with_changed_content() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "Shared FSRS");
+    let template_id = add_template(&db, "Shared Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+
+    let mut exported = serde_json::to_value(decks::export_deck(&db, deck_id).unwrap()).unwrap();
+    exported["algorithm"]["content"]["retention"] = serde_json::json!(85.0);
+    let copy = decks::import_deck(&db, bundle(exported)).unwrap();
+
+    assert_ne!(copy.algorithm_id, algorithm_id);
+    assert_eq!(copy.template_id, template_id);
+    assert_eq!(algorithms::get_algorithms(&db).unwrap().len(), 2);
+}
+
+#[test]
+fn export_deck_missing_deck_fails() {
+    let db = test_db();
+
+    let error = decks::export_deck(&db, 404).unwrap_err();
+
+    assert_eq!(error.code, error_codes::NOT_FOUND_DECKS_EXPORT_DECK);
+}
+
+#[test]
+fn import_deck_rejects_unknown_version() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "Shared FSRS");
+    let template_id = add_template(&db, "Shared Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    let mut exported = decks::export_deck(&db, deck_id).unwrap();
+    exported.version = DECK_BUNDLE_VERSION + 1;
+
+    let error = decks::import_deck(&test_db(), exported).unwrap_err();
+
+    assert_eq!(error.code, error_codes::VALIDATION_DECK_BUNDLE_VERSION);
+}
+
+#[test]
+fn import_deck_invalid_card_writes_nothing() {
+    let source = test_db();
+    let algorithm_id = add_algorithm(&source, "Shared FSRS");
+    let template_id = add_template(&source, "Shared Basic");
+    let deck_id = add_deck(&source, algorithm_id, template_id, "Spanish");
+    add_card(&source, deck_id, template_id, "hola");
+    add_card(&source, deck_id, template_id, "adiós");
+
+    let mut exported = serde_json::to_value(decks::export_deck(&source, deck_id).unwrap()).unwrap();
+    exported["cards"][1]["content"]["1"]["text"] = serde_json::json!("");
+    let target = test_db();
+    let error = decks::import_deck(&target, bundle(exported)).unwrap_err();
+
+    assert_eq!(error.code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY);
+    assert!(error.details.unwrap().starts_with("Card 1"));
+    assert!(decks::get_decks(&target).unwrap().is_empty());
+    assert!(algorithms::get_algorithms(&target).unwrap().is_empty());
+    assert!(templates::get_templates(&target).unwrap().is_empty());
+}
@@ -118,6 +118,8 @@ fn e2e_full_learning_workflow() {
                     learning_steps: 0,
                     time: 10,
                     is_ignored: false,
+                    typed_answer: None,
+                    typed_score: None,
                 },
             },
         )
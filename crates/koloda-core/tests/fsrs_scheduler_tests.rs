@@ -0,0 +1,175 @@
+use koloda_core::domain::cards::{Card, CardState};
+use koloda_core::domain::fsrs::{FsrsScheduler, Rating};
+
+mod common;
+use common::{card_content, fsrs_algorithm_content};
+
+const NOW: i64 = 1_700_000_000_000;
+const MINUTE_MS: i64 = 60 * 1000;
+const DAY_MS: i64 = 24 * 60 * MINUTE_MS;
+
+fn scheduler() -> FsrsScheduler {
+    FsrsScheduler::new(&fsrs_algorithm_content()).expect("fixture algorithm should build a scheduler")
+}
+
+fn new_card() -> Card {
+    Card {
+        id: 7,
+        deck_id: 1,
+        template_id: 1,
+        content: card_content("q", "a"),
+        state: CardState::New.as_i32(),
+        due_at: None,
+        stability: None,
+        difficulty: None,
+        scheduled_days: 0,
+        learning_steps: 0,
+        reps: 0,
+        lapses: 0,
+        last_reviewed_at: None,
+        created_at: NOW,
+        updated_at: None,
+    }
+}
+
+fn review_card(stability: f64, last_reviewed_at: i64) -> Card {
+    Card {
+        state: CardState::Review.as_i32(),
+        due_at: Some(NOW),
+        stability: Some(stability),
+        difficulty: Some(5.0),
+        scheduled_days: 10,
+        reps: 4,
+        last_reviewed_at: Some(last_reviewed_at),
+        ..new_card()
+    }
+}
+
+// ===== RATING =====
+
+#[test]
+fn test_rating_round_trips_through_i32() {
+    for rating in Rating::ALL {
+        assert_eq!(Rating::from_i32(rating.as_i32()), Some(rating));
+    }
+    assert_eq!(Rating::from_i32(0), None);
+    assert_eq!(Rating::from_i32(5), None);
+}
+
+// ===== CONSTRUCTION =====
+
+#[test]
+fn test_new_rejects_invalid_algorithm() {
+    let mut algorithm = fsrs_algorithm_content();
+    algorithm.weights = "1,2,3".to_string();
+
+    let error = FsrsScheduler::new(&algorithm).unwrap_err();
+
+    assert!(error.code.starts_with("validation.algorithm"), "got {}", error.code);
+}
+
+// ===== NEW CARDS =====
+
+#[test]
+fn test_new_card_good_moves_to_next_learning_step() {
+    let result = scheduler().repeat(&new_card(), Rating::Good, NOW);
+
+    assert_eq!(result.card.state, CardState::Learning.as_i32());
+    assert_eq!(result.card.learning_steps, 1);
+    assert_eq!(result.card.due_at, NOW + DAY_MS);
+    assert!((result.card.stability - 3.0412).abs() < 1e-9);
+    assert_eq!(result.card.reps, 1);
+    assert_eq!(result.card.last_reviewed_at, Some(NOW));
+}
+
+#[test]
+fn test_new_card_again_stays_on_first_step() {
+    let result = scheduler().repeat(&new_card(), Rating::Again, NOW);
+
+    assert_eq!(result.card.state, CardState::Learning.as_i32());
+    assert_eq!(result.card.learning_steps, 0);
+    assert_eq!(result.card.due_at, NOW + 10 * MINUTE_MS);
+}
+
+#[test]
+fn test_new_card_easy_graduates() {
+    let result = scheduler().repeat(&new_card(), Rating::Easy, NOW);
+
+    assert_eq!(result.card.state, CardState::Review.as_i32());
+    assert!(result.card.scheduled_days >= 1);
+    assert_eq!(result.card.due_at, NOW + i64::from(result.card.scheduled_days) * DAY_MS);
+}
+
+#[test]
+fn test_last_learning_step_good_graduates() {
+    let card = Card {
+        state: CardState::Learning.as_i32(),
+        stability: Some(3.0),
+        difficulty: Some(5.0),
+        learning_steps: 1,
+        reps: 1,
+        last_reviewed_at: Some(NOW - DAY_MS),
+        ..new_card()
+    };
+
+    let result = scheduler().repeat(&card, Rating::Good, NOW);
+
+    assert_eq!(result.card.state, CardState::Review.as_i32());
+    assert_eq!(result.card.learning_steps, 0);
+}
+
+// ===== REVIEW CARDS =====
+
+#[test]
+fn test_review_again_lapses_into_relearning() {
+    let card = review_card(10.0, NOW - 10 * DAY_MS);
+
+    let result = scheduler().repeat(&card, Rating::Again, NOW);
+
+    assert_eq!(result.card.state, CardState::Relearning.as_i32());
+    assert_eq!(result.card.lapses, 1);
+    assert_eq!(result.card.due_at, NOW + 10 * MINUTE_MS);
+    assert!(result.card.stability < 10.0);
+}
+
+#[test]
+fn test_review_intervals_are_ordered() {
+    let card = review_card(10.0, NOW - 10 * DAY_MS);
+    let scheduler = scheduler();
+
+    let days = |rating| scheduler.repeat(&card, rating, NOW).card.scheduled_days;
+    let (hard, good, easy) = (days(Rating::Hard), days(Rating::Good), days(Rating::Easy));
+
+    assert!(hard < good, "hard {} good {}", hard, good);
+    assert!(good < easy, "good {} easy {}", good, easy);
+}
+
+#[test]
+fn test_graduating_interval_respects_maximum() {
+    let mut algorithm = fsrs_algorithm_content();
+    algorithm.maximum_interval = 5;
+    let scheduler = FsrsScheduler::new(&algorithm).unwrap();
+
+    let result = scheduler.repeat(&new_card(), Rating::Easy, NOW);
+
+    assert_eq!(result.card.scheduled_days, 5);
+}
+
+// ===== REVIEW LOG =====
+
+#[test]
+fn test_review_log_keeps_previous_progress() {
+    let last_reviewed_at = NOW - 10 * DAY_MS;
+    let card = review_card(10.0, last_reviewed_at);
+
+    let result = scheduler().repeat(&card, Rating::Good, NOW);
+
+    assert_eq!(result.review.card_id, card.id);
+    assert_eq!(result.review.rating, Rating::Good.as_i32());
+    assert_eq!(result.review.state, CardState::Review.as_i32());
+    assert_eq!(result.review.due_at, Some(last_reviewed_at));
+    assert!((result.review.stability - 10.0).abs() < 1e-9);
+    assert_eq!(result.review.scheduled_days, 10);
+    assert_eq!(result.review.time, 0);
+    result.validate().unwrap();
+}
@@ -0,0 +1,198 @@
+#![cfg(feature = "http")]
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+use koloda_core::app::error::error_codes;
+use koloda_core::http::{HttpServer, HttpServerOptions};
+use serde_json::{json, Value};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+const TOKEN: &str = "test-token";
+
+fn start() -> (HttpServer, i64, i64) {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Spanish");
+    add_card(&db, deck_id, template_id, "hola");
+
+    let server = HttpServer::start(
+        db,
+        HttpServerOptions {
+            port: 0,
+            token: Some(TOKEN.to_string()),
+        },
+    )
+    .expect("server should start");
+    (server, deck_id, template_id)
+}
+
+fn send(port: u16, method: &str, path: &str, token: Option<&str>, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).expect("server should accept");
+    let auth = token
+        .map(|token| format!("Authorization: Bearer {}\r\n", token))
+        .unwrap_or_default();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{}Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        auth,
+        body.len(),
+        body
+    )
+    .expect("request should be written");
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("response should be read");
+    let (head, body) = response.split_once("\r\n\r\n").expect("response should have a body");
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .expect("response should have a status");
+    let body = if body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(body).expect("body should be JSON")
+    };
+    (status, body)
+}
+
+fn post(port: u16, command: &str, body: Value) -> (u16, Value) {
+    send(
+        port,
+        "POST",
+        &format!("/api/{}", command),
+        Some(TOKEN),
+        &body.to_string(),
+    )
+}
+
+#[test]
+fn health_needs_no_token() {
+    let (server, _, _) = start();
+
+    let (status, body) = send(server.info().port, "GET", "/health", None, "");
+
+    assert_eq!(status, 200);
+    assert_eq!(body["ok"], true);
+}
+
+#[test]
+fn api_rejects_missing_or_wrong_token() {
+    let (server, _, _) = start();
+    let port = server.info().port;
+
+    let (missing, body) = send(port, "POST", "/api/get_decks", None, "");
+    let (wrong, _) = send(port, "POST", "/api/get_decks", Some("nope"), "");
+
+    assert_eq!(missing, 401);
+    assert_eq!(body["code"], error_codes::HTTP_UNAUTHORIZED);
+    assert_eq!(wrong, 401);
+}
+
+#[test]
+fn get_decks_returns_repo_result() {
+    let (server, deck_id, _) = start();
+
+    let (status, body) = send(server.info().port, "POST", "/api/get_decks", Some(TOKEN), "");
+
+    assert_eq!(status, 200);
+    assert_eq!(body[0]["id"], deck_id);
+    assert_eq!(body[0]["title"], "Spanish");
+}
+
+#[test]
+fn add_cards_then_get_cards() {
+    let (server, deck_id, template_id) = start();
+    let port = server.info().port;
+
+    let (status, results) = post(
+        port,
+        "add_cards",
+        json!({
+            "cards": [
+                { "deckId": deck_id, "templateId": template_id, "content": { "1": { "text": "adiós" } } },
+                { "deckId": deck_id, "templateId": template_id, "content": { "1": { "text": "hola" } } },
+            ],
+            "options": { "duplicatePolicy": "skip" },
+        }),
+    );
+    assert_eq!(status, 200);
+    assert!(results[0]["error"].is_null());
+    assert!(results[0]["cardId"].is_i64());
+    assert!(!results[1]["duplicateOf"].is_null());
+    assert!(results[1]["cardId"].is_null());
+
+    let (_, cards) = post(port, "get_cards", json!({ "deckId": deck_id }));
+    assert_eq!(cards.as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn errors_keep_app_error_codes() {
+    let (server, deck_id, template_id) = start();
+    let port = server.info().port;
+
+    let (invalid, body) = post(
+        port,
+        "add_card",
+        json!({ "deckId": deck_id, "templateId": template_id, "content": { "1": { "text": "" } } }),
+    );
+    assert_eq!(invalid, 400);
+    assert_eq!(body["code"], error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY);
+
+    let (missing, body) = post(port, "export_deck", json!({ "id": 404 }));
+    assert_eq!(missing, 404);
+    assert_eq!(body["code"], error_codes::NOT_FOUND_DECKS_EXPORT_DECK);
+
+    let (malformed, body) = send(port, "POST", "/api/get_card", Some(TOKEN), "{");
+    assert_eq!(malformed, 400);
+    assert_eq!(body["code"], error_codes::VALIDATION_COMMON_PAYLOAD);
+}
+
+#[test]
+fn unknown_routes_and_methods_are_rejected() {
+    let (server, _, _) = start();
+    let port = server.info().port;
+
+    let (unknown_command, body) = post(port, "drop_database", Value::Null);
+    let (unknown_path, _) = send(port, "GET", "/nope", None, "");
+    let (wrong_method, body_method) = send(port, "GET", "/api/get_decks", Some(TOKEN), "");
+
+    assert_eq!(unknown_command, 404);
+    assert_eq!(body["code"], error_codes::NOT_FOUND_HTTP_ROUTE);
+    assert_eq!(unknown_path, 404);
+    assert_eq!(wrong_method, 405);
+    assert_eq!(body_method["code"], error_codes::HTTP_METHOD_NOT_ALLOWED);
+}
+
+#[test]
+fn generates_token_when_unset() {
+    let server = HttpServer::start(test_db(), HttpServerOptions::default()).unwrap();
+    let info = server.info().clone();
+
+    assert!(info.token.len() >= 32);
+    let (status, _) = send(info.port, "POST", "/api/get_decks", Some(&info.token), "");
+    assert_eq!(status, 200);
+}
+
+#[test]
+fn stop_releases_the_port() {
+    let (server, _, _) = start();
+    let port = server.info().port;
+
+    server.stop();
+
+    // WHY: `tiny_http` closes the listener from its accept thread shortly after the server drops.
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while TcpStream::connect(("127.0.0.1", port)).is_ok() {
+        assert!(Instant::now() < deadline, "port {} is still accepting", port);
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
@@ -44,6 +44,8 @@ fn submit_lesson_result_updates_card_and_inserts_review() {
                 learning_steps: 0,
                 time: 12,
                 is_ignored: false,
+                typed_answer: Some("questoin".to_string()),
+                typed_score: Some(0.875),
             },
         },
     )
@@ -62,6 +64,8 @@ fn submit_lesson_result_updates_card_and_inserts_review() {
     assert_eq!(saved_reviews.len(), 1);
     assert_eq!(saved_reviews[0].card_id, card_id);
     assert_eq!(saved_reviews[0].rating, 3);
+    assert_eq!(saved_reviews[0].typed_answer.as_deref(), Some("questoin"));
+    assert_eq!(saved_reviews[0].typed_score, Some(0.875));
 }
 
 #[test]
@@ -98,6 +102,8 @@ fn submit_lesson_result_rolls_back_when_review_insert_fails() {
                 learning_steps: 0,
                 time: 12,
                 is_ignored: false,
+                typed_answer: None,
+                typed_score: None,
             },
         },
     );
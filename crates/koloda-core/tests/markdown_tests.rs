@@ -0,0 +1,177 @@
+mod common;
+
+use common::fixtures::{add_algorithm, add_deck};
+use common::{card_content, simple_template_content, test_db};
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{AddCardsOptions, DuplicatePolicy, InsertCardData};
+use koloda_core::domain::markdown::{is_markdown_blank, markdown_to_text, render_markdown, sanitize_html};
+use koloda_core::domain::template_render::render_content;
+use koloda_core::domain::templates::{InsertTemplateData, TemplateContent, FIELD_TYPE_MARKDOWN};
+use koloda_core::repo::{cards, templates};
+
+fn markdown_template_content() -> TemplateContent {
+    let mut content = simple_template_content();
+    for field in &mut content.fields {
+        field.field_type = FIELD_TYPE_MARKDOWN.to_string();
+    }
+    content
+}
+
+fn insert_data(deck_id: i64, template_id: i64, front: &str) -> InsertCardData {
+    InsertCardData {
+        deck_id,
+        template_id,
+        content: card_content(front, ""),
+        state: None,
+        due_at: None,
+        stability: None,
+        difficulty: None,
+        scheduled_days: None,
+        learning_steps: None,
+        reps: None,
+        lapses: None,
+        last_reviewed_at: None,
+    }
+}
+
+// ============================================================================
+// RENDERING
+// ============================================================================
+
+#[test]
+fn test_render_blocks_and_inline_markup() {
+    let html = render_markdown("# Verbs\n\n- **ser**\n- *estar*\n\nSee [docs](https://example.com) and `x < y`");
+
+    assert_eq!(
+        html,
+        concat!(
+            "<h1>Verbs</h1>",
+            "<ul><li><strong>ser</strong></li><li><em>estar</em></li></ul>",
+            r#"<p>See <a href="https://example.com">docs</a> and <code>x &lt; y</code></p>"#
+        )
+    );
+}
+
+#[test]
+fn test_markdown_fields_render_as_html_in_layout() {
+    let (question, _) = render_content(&markdown_template_content(), &card_content("**hola**", "")).unwrap();
+
+    assert_eq!(question, r#"<div class="field"><p><strong>hola</strong></p></div>"#);
+}
+
+// ============================================================================
+// SANITIZING
+// ============================================================================
+
+#[test]
+fn test_sanitize_drops_scripts_handlers_and_unsafe_urls() {
+    let html = sanitize_html(concat!(
+        r#"<b onclick="steal()">hi</b><script>alert(1)</script>"#,
+        r#"<a href="javascript:alert(1)">x</a><a href="&#106;avascript:alert(1)">y</a>"#,
+        r#"<img src="https://example.com/a.png" onerror="steal()">"#
+    ));
+
+    assert_eq!(
+        html,
+        r#"<b>hi</b><a>x</a><a>y</a><img src="https://example.com/a.png">"#
+    );
+}
+
+#[test]
+fn test_sanitize_reads_references_without_semicolon_as_browsers_do() {
+    let decimal = render_markdown(r#"<a href="javascript&#58alert(1)">x</a>"#);
+    let hex = render_markdown(r#"<a href="javascript&#x3Aalert(1)">x</a>"#);
+
+    assert_eq!(decimal, "<p><a>x</a></p>");
+    // WHY: `a` is a hex digit, so a browser reads `&#x3Aa` as one reference and no scheme is left.
+    assert_eq!(hex, "<p><a href=\"javascript\u{3aa}lert(1)\">x</a></p>");
+}
+
+#[test]
+fn test_sanitize_escapes_ampersands_in_attributes() {
+    let html = sanitize_html(r#"<img src="a.png?x=1&amp;y=2" alt='"a" & b'>"#);
+
+    assert_eq!(html, r#"<img src="a.png?x=1&amp;y=2" alt="&quot;a&quot; &amp; b">"#);
+}
+
+#[test]
+fn test_embedded_html_in_markdown_is_sanitized() {
+    let html = render_markdown(r#"hola <span style="color:red" class="w">mundo</span><iframe src="x"></iframe>"#);
+
+    assert_eq!(html, r#"<p>hola <span class="w">mundo</span></p>"#);
+}
+
+// ============================================================================
+// PLAIN TEXT
+// ============================================================================
+
+#[test]
+fn test_plain_text_strips_markup_and_keeps_image_alt() {
+    assert_eq!(markdown_to_text("**hola** _mundo_"), "hola mundo");
+    assert_eq!(markdown_to_text("- uno\n- dos"), "uno\ndos");
+    assert_eq!(markdown_to_text("![perro](dog.png) &amp; <b>gato</b>"), "perro & gato");
+}
+
+#[test]
+fn test_blank_markdown_has_no_text_or_image() {
+    assert!(is_markdown_blank("**"));
+    assert!(is_markdown_blank("** **\n\n#"));
+    assert!(is_markdown_blank("<b></b>"));
+    assert!(!is_markdown_blank("![](dog.png)"));
+    assert!(!is_markdown_blank("="));
+}
+
+#[test]
+fn test_required_markdown_field_must_have_text() {
+    let fields = markdown_template_content().fields;
+
+    let error = insert_data(1, 1, "**").validate(&fields).unwrap_err();
+    assert_eq!(error.code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_EMPTY);
+    assert_eq!(error.details.as_deref(), Some("Field id: 1"));
+
+    insert_data(1, 1, "**hola**").validate(&fields).unwrap();
+    // WHY: plain text fields keep their value as typed, markers included.
+    insert_data(1, 1, "**")
+        .validate(&simple_template_content().fields)
+        .unwrap();
+}
+
+// ============================================================================
+// DUPLICATES AND AI CONTEXT
+// ============================================================================
+
+#[test]
+fn test_duplicates_and_plain_text_ignore_formatting() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = templates::add_template(
+        &db,
+        InsertTemplateData {
+            title: "Markdown".to_string(),
+            content: markdown_template_content(),
+        },
+    )
+    .unwrap()
+    .id;
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+
+    let results = cards::add_cards(
+        &db,
+        vec![
+            insert_data(deck_id, template_id, "**hola**"),
+            insert_data(deck_id, template_id, "hola"),
+        ],
+        AddCardsOptions {
+            duplicate_policy: DuplicatePolicy::Skip,
+            duplicate_field_id: None,
+        },
+    )
+    .unwrap();
+
+    let first_id = results[0].card_id.unwrap();
+    assert_eq!(results[1].duplicate_of, Some(first_id));
+    assert_eq!(
+        cards::get_card_plain_text(&db, first_id).unwrap().as_deref(),
+        Some("Front: hola")
+    );
+}
@@ -562,6 +562,52 @@ fn test_time_negative_fails() {
     assert_eq!(result.unwrap_err().code, "validation.reviews.time");
 }
 
+// ============================================================================
+// TYPED ANSWER FIELDS
+// ============================================================================
+
+#[test]
+fn test_typed_answer_defaults_to_none() {
+    let json = r#"{
+        "cardId": 1,
+        "rating": 1,
+        "state": 0,
+        "dueAt": null,
+        "stability": 5.0,
+        "difficulty": 5.0,
+        "scheduledDays": 0,
+        "learningSteps": 0,
+        "time": 0,
+        "isIgnored": false
+    }"#;
+
+    let data: InsertReviewData = serde_json::from_str(json).expect("Should deserialize");
+    assert_eq!(data.typed_answer, None);
+    assert_eq!(data.typed_score, None);
+}
+
+#[test]
+fn test_typed_score_above_one_fails() {
+    let json = r#"{
+        "cardId": 1,
+        "rating": 1,
+        "state": 0,
+        "dueAt": null,
+        "stability": 5.0,
+        "difficulty": 5.0,
+        "scheduledDays": 0,
+        "learningSteps": 0,
+        "time": 0,
+        "isIgnored": false,
+        "typedAnswer": "hola",
+        "typedScore": 1.5
+    }"#;
+
+    let data: InsertReviewData = serde_json::from_str(json).expect("Should deserialize");
+    let result = data.validate();
+    assert_eq!(result.unwrap_err().code, "validation.reviews.typed-score");
+}
+
 // ============================================================================
 // IS IGNORED FIELD
 // ============================================================================
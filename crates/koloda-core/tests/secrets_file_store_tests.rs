@@ -0,0 +1,192 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::app::secrets::{
+    create_secret_store, migrate_secrets, secret_file_path, EncryptedFileSecretStore, FileSecretKey, SecretStore,
+    SecretStoreBackend, SecretStoreOptions,
+};
+use koloda_core::domain::ai::AISecrets;
+use koloda_core::repo::ai;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+mod common;
+use common::test_db;
+
+fn unique_dir(prefix: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock went backwards")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("koloda-{}-{}-{}", prefix, std::process::id(), nanos));
+    fs::create_dir_all(&dir).expect("temp dir should be created");
+    dir
+}
+
+fn file_options(directory: &std::path::Path, passphrase: Option<&str>) -> SecretStoreOptions {
+    SecretStoreOptions {
+        backend: SecretStoreBackend::EncryptedFile,
+        directory: Some(directory.to_path_buf()),
+        passphrase: passphrase.map(str::to_string),
+    }
+}
+
+#[test]
+fn key_file_store_persists_across_reopen() {
+    let dir = unique_dir("key-file");
+    let path = dir.join("test.secrets");
+    let key_path = dir.join("test.key");
+
+    let store = EncryptedFileSecretStore::open(&path, FileSecretKey::KeyFile(key_path.clone())).unwrap();
+    store.set("alpha", "v1").unwrap();
+    store.set("beta", "v2").unwrap();
+    store.remove("beta").unwrap();
+
+    let raw = fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("v1"), "secret file must not hold plaintext");
+    assert!(key_path.exists(), "key file should be created on first use");
+
+    let reopened = EncryptedFileSecretStore::open(&path, FileSecretKey::KeyFile(key_path)).unwrap();
+    assert_eq!(reopened.get("alpha").unwrap(), Some("v1".to_string()));
+    assert_eq!(reopened.get("beta").unwrap(), None);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn passphrase_store_rejects_wrong_passphrase() {
+    let dir = unique_dir("passphrase");
+    let path = dir.join("test.secrets");
+
+    let store = EncryptedFileSecretStore::open(&path, FileSecretKey::Passphrase("correct".to_string())).unwrap();
+    store.set("alpha", "v1").unwrap();
+
+    let reopened = EncryptedFileSecretStore::open(&path, FileSecretKey::Passphrase("correct".to_string())).unwrap();
+    assert_eq!(reopened.get("alpha").unwrap(), Some("v1".to_string()));
+
+    let err = EncryptedFileSecretStore::open(&path, FileSecretKey::Passphrase("wrong".to_string()))
+        .err()
+        .expect("wrong passphrase should fail");
+    assert_eq!(err.code, error_codes::SECRET_STORE_DECRYPT);
+
+    let err = EncryptedFileSecretStore::open(&path, FileSecretKey::KeyFile(dir.join("test.key")))
+        .err()
+        .expect("key file should not open a passphrase file");
+    assert_eq!(err.code, error_codes::SECRET_STORE_DECRYPT);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn create_secret_store_selects_encrypted_file() {
+    let dir = unique_dir("select-file");
+
+    let selected = create_secret_store("koloda-test", &file_options(&dir, None)).unwrap();
+    assert_eq!(selected.backend, SecretStoreBackend::EncryptedFile);
+    selected.store.set("alpha", "v1").unwrap();
+    assert!(secret_file_path(&dir, "koloda-test").exists());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn create_secret_store_auto_keeps_existing_file() {
+    let dir = unique_dir("auto-existing");
+    create_secret_store("koloda-test", &file_options(&dir, None))
+        .unwrap()
+        .store
+        .set("alpha", "v1")
+        .unwrap();
+
+    let options = SecretStoreOptions {
+        backend: SecretStoreBackend::Auto,
+        directory: Some(dir.clone()),
+        passphrase: None,
+    };
+    let selected = create_secret_store("koloda-test", &options).unwrap();
+
+    assert_eq!(selected.backend, SecretStoreBackend::EncryptedFile);
+    assert_eq!(selected.store.get("alpha").unwrap(), Some("v1".to_string()));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn create_secret_store_encrypted_file_requires_directory() {
+    let options = SecretStoreOptions {
+        backend: SecretStoreBackend::EncryptedFile,
+        directory: None,
+        passphrase: None,
+    };
+    let err = create_secret_store("koloda-test", &options)
+        .err()
+        .expect("missing directory should fail");
+
+    assert_eq!(err.code, error_codes::SECRET_STORE_UNAVAILABLE);
+}
+
+#[test]
+fn migrate_secrets_moves_present_keys() {
+    let source_dir = unique_dir("migrate-source");
+    let target_dir = unique_dir("migrate-target");
+    let source = create_secret_store("koloda-test", &file_options(&source_dir, None))
+        .unwrap()
+        .store;
+    let target = create_secret_store("koloda-test", &file_options(&target_dir, Some("passphrase")))
+        .unwrap()
+        .store;
+    source.set("alpha", "v1").unwrap();
+    source.set("unlisted", "v2").unwrap();
+
+    let keys = vec!["alpha".to_string(), "missing".to_string()];
+    let migrated = migrate_secrets(&*source, &*target, &keys).unwrap();
+
+    assert_eq!(migrated, vec!["alpha".to_string()]);
+    assert_eq!(target.get("alpha").unwrap(), Some("v1".to_string()));
+    assert_eq!(source.get("alpha").unwrap(), None);
+    assert_eq!(source.get("unlisted").unwrap(), Some("v2".to_string()));
+    assert_eq!(target.get("missing").unwrap(), None);
+
+    fs::remove_dir_all(source_dir).unwrap();
+    fs::remove_dir_all(target_dir).unwrap();
+}
+
+#[test]
+fn parsed_options_default_to_auto() {
+    let options: SecretStoreOptions = serde_json::from_value(serde_json::json!({})).unwrap();
+    assert_eq!(options.backend, SecretStoreBackend::Auto);
+
+    let options: SecretStoreOptions =
+        serde_json::from_value(serde_json::json!({ "backend": "encrypted-file", "passphrase": "p" })).unwrap();
+    assert_eq!(options.backend, SecretStoreBackend::EncryptedFile);
+    assert_eq!(options.passphrase.as_deref(), Some("p"));
+}
+
+// WHY: The only test in this binary that touches the process-wide active store.
+#[test]
+fn configure_ai_secret_store_migrates_profile_keys() {
+    let dir = unique_dir("configure");
+    let db = test_db();
+    let profile = ai::add_ai_profile(
+        &db,
+        Some("Remote".to_string()),
+        Some(AISecrets::OpenRouter {
+            api_key: Some("sk-migrated".to_string()),
+        }),
+        None,
+    )
+    .unwrap();
+
+    let backend = ai::configure_ai_secret_store(&db, &file_options(&dir, None)).unwrap();
+    assert_eq!(backend, SecretStoreBackend::EncryptedFile);
+
+    let secrets = ai::get_ai_profile_secrets(&db, &profile.id).unwrap().unwrap();
+    assert_eq!(secrets.api_key(), Some("sk-migrated"));
+
+    let reopened = create_secret_store("koloda", &file_options(&dir, None)).unwrap().store;
+    assert_eq!(
+        reopened.get(&format!("ai-profile-{}", profile.id)).unwrap(),
+        Some("sk-migrated".to_string())
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
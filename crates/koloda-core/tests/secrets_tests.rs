@@ -1,4 +1,4 @@
-use koloda_core::app::secrets::{create_secret_store, SecretStore};
+use koloda_core::app::secrets::{create_secret_store, SecretStore, SecretStoreBackend, SecretStoreOptions};
 use std::sync::{Arc, Barrier};
 use std::thread;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -11,6 +11,16 @@ fn unique_service_name(prefix: &str) -> String {
     format!("{}-{}-{}", prefix, std::process::id(), nanos)
 }
 
+fn keyring_store(service: String) -> Arc<dyn SecretStore> {
+    let options = SecretStoreOptions {
+        backend: SecretStoreBackend::Keyring,
+        ..SecretStoreOptions::default()
+    };
+    create_secret_store(Box::leak(service.into_boxed_str()), &options)
+        .expect("keyring store should be created")
+        .store
+}
+
 fn cleanup_key(store: &dyn SecretStore, key: &str) {
     if let Err(_cleanup_err) = store.remove(key) {}
 }
@@ -18,7 +28,7 @@ fn cleanup_key(store: &dyn SecretStore, key: &str) {
 #[test]
 fn runtime_store_set_get_remove_round_trip() {
     let service = unique_service_name("runtime-secrets-round-trip");
-    let store = create_secret_store(Box::leak(service.into_boxed_str()));
+    let store = keyring_store(service);
     let key = "alpha";
 
     cleanup_key(&*store, key);
@@ -32,7 +42,7 @@ fn runtime_store_set_get_remove_round_trip() {
 #[test]
 fn runtime_store_overwrite_updates_value() {
     let service = unique_service_name("runtime-secrets-overwrite");
-    let store = create_secret_store(Box::leak(service.into_boxed_str()));
+    let store = keyring_store(service);
     let key = "alpha";
 
     cleanup_key(&*store, key);
@@ -47,8 +57,8 @@ fn runtime_store_overwrite_updates_value() {
 fn runtime_store_is_isolated_by_service() {
     let service_a = unique_service_name("runtime-secrets-service-a");
     let service_b = unique_service_name("runtime-secrets-service-b");
-    let store_a = create_secret_store(Box::leak(service_a.into_boxed_str()));
-    let store_b = create_secret_store(Box::leak(service_b.into_boxed_str()));
+    let store_a = keyring_store(service_a);
+    let store_b = keyring_store(service_b);
     let key = "shared-key";
 
     cleanup_key(&*store_a, key);
@@ -67,7 +77,7 @@ fn runtime_store_is_isolated_by_service() {
 #[test]
 fn runtime_store_concurrent_set_and_get() {
     let service = unique_service_name("runtime-secrets-concurrent");
-    let store = create_secret_store(Box::leak(service.into_boxed_str()));
+    let store = keyring_store(service);
     let workers = 10;
     let start = Arc::new(Barrier::new(workers));
     let mut handles = Vec::new();
@@ -105,8 +115,21 @@ fn runtime_store_concurrent_set_and_get() {
 #[test]
 fn runtime_store_get_nonexistent_key() {
     let service = unique_service_name("runtime-secrets-missing");
-    let store = create_secret_store(Box::leak(service.into_boxed_str()));
+    let store = keyring_store(service);
 
     let result = store.get("nonexistent-key").expect("get should work");
     assert_eq!(result, None, "nonexistent key should return None");
 }
+
+#[test]
+fn runtime_store_lists_written_keys() {
+    let service = unique_service_name("runtime-secrets-keys");
+    let store = keyring_store(service);
+
+    store.set("alpha", "v1").expect("set should work");
+    store.set("beta", "v2").expect("set should work");
+    store.remove("alpha").expect("remove should work");
+
+    assert_eq!(store.keys().expect("keys should work"), Some(vec!["beta".to_string()]));
+    store.remove("beta").expect("cleanup remove should work");
+}
@@ -53,12 +53,14 @@ fn seed_db_reuses_oldest_existing_algorithm_and_template_ids() {
                             title: "Front".to_string(),
                             field_type: "text".to_string(),
                             is_required: true,
+                            rules: None,
                         },
                         TemplateField {
                             id: 2,
                             title: "Back".to_string(),
                             field_type: "text".to_string(),
                             is_required: false,
+                            rules: None,
                         },
                     ],
                     layout: vec![
@@ -71,6 +73,7 @@ fn seed_db_reuses_oldest_existing_algorithm_and_template_ids() {
                             operation: "reveal".to_string(),
                         },
                     ],
+                    sides: None,
                 })
                 .expect("template content should serialize"),
                 100_i64
@@ -88,12 +91,14 @@ fn seed_db_reuses_oldest_existing_algorithm_and_template_ids() {
                             title: "Front".to_string(),
                             field_type: "text".to_string(),
                             is_required: true,
+                            rules: None,
                         },
                         TemplateField {
                             id: 2,
                             title: "Back".to_string(),
                             field_type: "text".to_string(),
                             is_required: false,
+                            rules: None,
                         },
                     ],
                     layout: vec![
@@ -106,6 +111,7 @@ fn seed_db_reuses_oldest_existing_algorithm_and_template_ids() {
                             operation: "reveal".to_string(),
                         },
                     ],
+                    sides: None,
                 })
                 .expect("template content should serialize"),
                 200_i64
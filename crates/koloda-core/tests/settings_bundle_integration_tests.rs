@@ -0,0 +1,216 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::settings::SettingsName;
+use koloda_core::domain::settings_bundle::{SettingsBundle, SETTINGS_BUNDLE_VERSION};
+use koloda_core::repo::{algorithms, settings, templates};
+use serde_json::json;
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::{hotkeys_settings, interface_settings, learning_settings, test_db};
+
+fn bundle(value: serde_json::Value) -> SettingsBundle {
+    serde_json::from_value(value).expect("bundle should parse")
+}
+
+#[test]
+fn export_and_import_settings_bundle_round_trip() {
+    let source = test_db();
+    settings::set_settings(
+        &source,
+        SettingsName::Interface,
+        interface_settings("ru", "dark", "system"),
+    )
+    .unwrap();
+    settings::set_settings(&source, SettingsName::Learning, learning_settings(80, 20, 30, 50)).unwrap();
+    settings::set_settings(&source, SettingsName::Hotkeys, hotkeys_settings()).unwrap();
+    add_algorithm(&source, "Team FSRS");
+    add_template(&source, "Team Basic");
+
+    let exported = settings::export_settings_bundle(&source).expect("export should succeed");
+    assert_eq!(exported.version, SETTINGS_BUNDLE_VERSION);
+
+    let target = test_db();
+    let json = serde_json::to_value(&exported).unwrap();
+    let result = settings::import_settings_bundle(&target, bundle(json)).expect("import should succeed");
+
+    assert!(result.applied);
+    assert!(result.errors.is_empty());
+    assert_eq!(
+        result.settings,
+        vec![SettingsName::Interface, SettingsName::Learning, SettingsName::Hotkeys]
+    );
+    let interface = settings::get_settings(&target, SettingsName::Interface)
+        .unwrap()
+        .unwrap();
+    assert_eq!(interface.content["language"], "ru");
+    let learning = settings::get_settings(&target, SettingsName::Learning)
+        .unwrap()
+        .unwrap();
+    assert_eq!(learning.content["dailyLimits"]["total"], 80);
+    assert_eq!(algorithms::get_algorithms(&target).unwrap()[0].title, "Team FSRS");
+    assert_eq!(templates::get_templates(&target).unwrap()[0].title, "Team Basic");
+}
+
+#[test]
+fn export_settings_bundle_redacts_ai_profiles() {
+    let db = test_db();
+    settings::set_settings(
+        &db,
+        SettingsName::Ai,
+        json!({
+            "profiles": [
+                {
+                    "id": "profile-1",
+                    "title": "OpenRouter",
+                    "secrets": { "provider": "openrouter", "apiKey": null },
+                    "hasSecrets": true,
+                    "createdAt": "2026-01-01T00:00:00Z"
+                }
+            ]
+        }),
+    )
+    .unwrap();
+
+    let exported = settings::export_settings_bundle(&db).unwrap();
+
+    let ai = exported.settings.iter().find(|s| s.name == SettingsName::Ai).unwrap();
+    assert_eq!(ai.content["profiles"][0]["hasSecrets"], false);
+    assert!(ai.content["profiles"][0]["secrets"]["apiKey"].is_null());
+}
+
+#[test]
+fn import_settings_bundle_merges_ai_profiles_and_keeps_local_secrets() {
+    let db = test_db();
+    let profile = |id: &str, title: &str, has_secrets: bool| {
+        json!({
+            "id": id,
+            "title": title,
+            "secrets": { "provider": "openrouter", "apiKey": null },
+            "hasSecrets": has_secrets,
+            "createdAt": "2026-01-01T00:00:00Z"
+        })
+    };
+    settings::set_settings(
+        &db,
+        SettingsName::Ai,
+        json!({ "profiles": [profile("shared", "Local", true), profile("local-only", "Mine", true)] }),
+    )
+    .unwrap();
+
+    let result = settings::import_settings_bundle(
+        &db,
+        bundle(json!({
+            "version": SETTINGS_BUNDLE_VERSION,
+            "exportedAt": 0,
+            "settings": [{
+                "name": "ai",
+                "content": { "profiles": [profile("shared", "Team", false), profile("new", "New", false)] }
+            }],
+            "algorithms": [],
+            "templates": []
+        })),
+    )
+    .unwrap();
+
+    assert!(result.applied);
+    let ai = settings::get_settings(&db, SettingsName::Ai).unwrap().unwrap();
+    let profiles = ai.content["profiles"].as_array().unwrap();
+    let ids: Vec<&str> = profiles.iter().map(|p| p["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, vec!["shared", "local-only", "new"]);
+    assert_eq!(profiles[0]["title"], "Team");
+    assert_eq!(profiles[0]["hasSecrets"], true);
+    assert_eq!(profiles[1]["hasSecrets"], true);
+    assert_eq!(profiles[2]["hasSecrets"], false);
+}
+
+#[test]
+fn import_settings_bundle_reports_slice_errors_and_applies_nothing() {
+    let db = test_db();
+    settings::set_settings(
+        &db,
+        SettingsName::Interface,
+        interface_settings("en", "system", "system"),
+    )
+    .unwrap();
+
+    let result = settings::import_settings_bundle(
+        &db,
+        bundle(json!({
+            "version": SETTINGS_BUNDLE_VERSION,
+            "exportedAt": "2026-01-01T00:00:00Z",
+            "settings": [
+                { "name": "interface", "content": interface_settings("ru", "dark", "system") },
+                { "name": "learning", "content": learning_settings(10, 20, 30, 50) },
+                { "name": "interface", "content": interface_settings("en", "light", "system") }
+            ],
+            "algorithms": [{ "title": "Broken" }],
+            "templates": []
+        })),
+    )
+    .expect("slice errors are reported, not thrown");
+
+    assert!(!result.applied);
+    let slices: Vec<&str> = result.errors.iter().map(|e| e.slice.as_str()).collect();
+    assert_eq!(slices, vec!["settings/learning", "settings/interface", "algorithms/0"]);
+    assert_eq!(
+        result.errors[0].code,
+        error_codes::VALIDATION_SETTINGS_LEARNING_DAILY_LIMITS_UNTOUCHED_EXCEEDS_TOTAL
+    );
+    assert_eq!(
+        result.errors[1].code,
+        error_codes::VALIDATION_SETTINGS_BUNDLE_DUPLICATE_SLICE
+    );
+
+    let interface = settings::get_settings(&db, SettingsName::Interface).unwrap().unwrap();
+    assert_eq!(interface.content["language"], "en");
+    assert!(algorithms::get_algorithms(&db).unwrap().is_empty());
+}
+
+#[test]
+fn import_settings_bundle_rejects_unknown_version() {
+    let db = test_db();
+
+    let err = settings::import_settings_bundle(
+        &db,
+        bundle(json!({ "version": 99, "exportedAt": "2026-01-01T00:00:00Z" })),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_SETTINGS_BUNDLE_VERSION);
+}
+
+#[test]
+fn import_settings_bundle_updates_presets_by_title() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "Team FSRS");
+    let mut exported = settings::export_settings_bundle(&db).unwrap();
+    exported.algorithms[0]["content"]["retention"] = json!(85.0);
+
+    let result = settings::import_settings_bundle(&db, exported).unwrap();
+
+    assert!(result.applied);
+    assert_eq!(result.algorithm_ids, vec![algorithm_id]);
+    let stored = algorithms::get_algorithms(&db).unwrap();
+    assert_eq!(stored.len(), 1);
+    assert!((stored[0].content.retention - 85.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn import_settings_bundle_keeps_locked_template_fields() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    add_card(&db, deck_id, template_id, "question");
+    let mut exported = settings::export_settings_bundle(&db).unwrap();
+    exported.templates[0]["content"]["fields"][1]["type"] = json!("markdown");
+
+    let result = settings::import_settings_bundle(&db, exported).unwrap();
+
+    assert!(!result.applied);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].slice, "templates/0");
+    assert_eq!(result.errors[0].code, error_codes::VALIDATION_TEMPLATES_UPDATE_LOCKED);
+    let template = templates::get_template(&db, template_id).unwrap().unwrap();
+    assert_eq!(template.content.fields[1].field_type, "text");
+}
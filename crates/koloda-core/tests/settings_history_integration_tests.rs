@@ -0,0 +1,192 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::settings::{
+    DiffSettingsParams, GetSettingsHistoryParams, SettingsName, SETTINGS_HISTORY_LIMIT,
+};
+use koloda_core::repo::settings;
+use serde_json::json;
+
+mod common;
+use common::{interface_settings, learning_settings, test_db};
+
+fn history(
+    db: &koloda_core::app::db::Database,
+    name: SettingsName,
+) -> Vec<koloda_core::domain::settings::SettingsHistoryEntry> {
+    settings::get_settings_history(db, GetSettingsHistoryParams { name, limit: None }).expect("history should load")
+}
+
+#[test]
+fn set_and_patch_settings_record_history_newest_first() {
+    let db = test_db();
+
+    settings::set_settings(&db, SettingsName::Learning, learning_settings(100, 20, 30, 50)).unwrap();
+    settings::patch_settings(&db, SettingsName::Learning, json!({ "dailyLimits": { "total": 80 } })).unwrap();
+
+    let entries = history(&db, SettingsName::Learning);
+    assert_eq!(entries.len(), 2);
+
+    let latest = &entries[0];
+    assert_eq!(latest.patch, json!({ "dailyLimits": { "total": 80 } }));
+    assert_eq!(latest.old_content.as_ref().unwrap()["dailyLimits"]["total"], 100);
+    assert_eq!(latest.new_content["dailyLimits"]["total"], 80);
+
+    let first = &entries[1];
+    assert!(first.old_content.is_none());
+    assert_eq!(first.patch, first.new_content);
+}
+
+#[test]
+fn unchanged_write_and_failed_write_do_not_record_history() {
+    let db = test_db();
+
+    settings::set_settings(
+        &db,
+        SettingsName::Interface,
+        interface_settings("en", "system", "system"),
+    )
+    .unwrap();
+    settings::set_settings(
+        &db,
+        SettingsName::Interface,
+        interface_settings("en", "system", "system"),
+    )
+    .unwrap();
+    let err = settings::patch_settings(&db, SettingsName::Interface, json!({ "scheme": "purple" })).unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_SETTINGS_INTERFACE_SCHEME);
+    assert_eq!(history(&db, SettingsName::Interface).len(), 1);
+    assert!(history(&db, SettingsName::Learning).is_empty());
+}
+
+#[test]
+fn get_settings_history_respects_limit() {
+    let db = test_db();
+    for scheme in ["system", "light", "dark"] {
+        settings::set_settings(&db, SettingsName::Interface, interface_settings("en", scheme, "system")).unwrap();
+    }
+
+    let entries = settings::get_settings_history(
+        &db,
+        GetSettingsHistoryParams {
+            name: SettingsName::Interface,
+            limit: Some(2),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].new_content["scheme"], "dark");
+}
+
+#[test]
+fn history_keeps_only_the_newest_entries_per_slice() {
+    let db = test_db();
+    settings::set_settings(
+        &db,
+        SettingsName::Interface,
+        interface_settings("en", "light", "system"),
+    )
+    .unwrap();
+    for total in 0..SETTINGS_HISTORY_LIMIT + 5 {
+        settings::set_settings(&db, SettingsName::Learning, learning_settings(100 + total, 20, 30, 50)).unwrap();
+    }
+
+    let entries = history(&db, SettingsName::Learning);
+    assert_eq!(entries.len(), SETTINGS_HISTORY_LIMIT as usize);
+    assert_eq!(
+        entries[0].new_content["dailyLimits"]["total"],
+        100 + SETTINGS_HISTORY_LIMIT + 4
+    );
+    assert_eq!(history(&db, SettingsName::Interface).len(), 1);
+}
+
+#[test]
+fn diff_settings_compares_versions_and_current_content() {
+    let db = test_db();
+    settings::set_settings(
+        &db,
+        SettingsName::Interface,
+        interface_settings("en", "system", "system"),
+    )
+    .unwrap();
+    settings::set_settings(
+        &db,
+        SettingsName::Interface,
+        interface_settings("ru", "system", "system"),
+    )
+    .unwrap();
+    settings::set_settings(&db, SettingsName::Interface, interface_settings("ru", "dark", "system")).unwrap();
+    let entries = history(&db, SettingsName::Interface);
+    let (oldest, middle) = (entries[2].id, entries[1].id);
+
+    let diff = settings::diff_settings(
+        &db,
+        DiffSettingsParams {
+            from_id: oldest,
+            to_id: Some(middle),
+        },
+    )
+    .unwrap();
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff[0].path, "/language");
+
+    let diff = settings::diff_settings(
+        &db,
+        DiffSettingsParams {
+            from_id: oldest,
+            to_id: None,
+        },
+    )
+    .unwrap();
+    let paths: Vec<&str> = diff.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(paths, vec!["/language", "/scheme"]);
+}
+
+#[test]
+fn diff_settings_rejects_versions_of_different_slices() {
+    let db = test_db();
+    settings::set_settings(
+        &db,
+        SettingsName::Interface,
+        interface_settings("en", "system", "system"),
+    )
+    .unwrap();
+    settings::set_settings(&db, SettingsName::Learning, learning_settings(100, 20, 30, 50)).unwrap();
+    let interface_id = history(&db, SettingsName::Interface)[0].id;
+    let learning_id = history(&db, SettingsName::Learning)[0].id;
+
+    let err = settings::diff_settings(
+        &db,
+        DiffSettingsParams {
+            from_id: interface_id,
+            to_id: Some(learning_id),
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::VALIDATION_SETTINGS_HISTORY_NAME_MISMATCH);
+}
+
+#[test]
+fn rollback_settings_restores_version_and_records_it() {
+    let db = test_db();
+    settings::set_settings(&db, SettingsName::Learning, learning_settings(100, 20, 30, 50)).unwrap();
+    settings::patch_settings(&db, SettingsName::Learning, json!({ "dailyLimits": { "total": 80 } })).unwrap();
+    let original_id = history(&db, SettingsName::Learning)[1].id;
+
+    let restored = settings::rollback_settings(&db, original_id).expect("rollback should succeed");
+
+    assert_eq!(restored.content["dailyLimits"]["total"], 100);
+    let entries = history(&db, SettingsName::Learning);
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].patch, json!({ "dailyLimits": { "total": 100 } }));
+}
+
+#[test]
+fn rollback_settings_rejects_missing_entry() {
+    let db = test_db();
+
+    let err = settings::rollback_settings(&db, 999_999).unwrap_err();
+
+    assert_eq!(err.code, error_codes::NOT_FOUND_SETTINGS_HISTORY_ENTRY);
+}
@@ -0,0 +1,82 @@
+use koloda_core::domain::settings::{diff_settings_content, merge_patch_between, SettingsDiffEntry};
+use serde_json::json;
+
+// ============================================================================
+// MERGE PATCH
+// ============================================================================
+
+#[test]
+fn test_merge_patch_between_records_changed_added_and_removed_keys() {
+    let old = json!({ "a": 1, "b": { "c": 2, "d": 3 }, "gone": true });
+    let new = json!({ "a": 1, "b": { "c": 5, "d": 3 }, "added": [1, 2] });
+
+    let patch = merge_patch_between(&old, &new);
+
+    assert_eq!(patch, json!({ "b": { "c": 5 }, "gone": null, "added": [1, 2] }));
+}
+
+#[test]
+fn test_merge_patch_between_round_trips_through_merge() {
+    let old = json!({ "dailyLimits": { "total": 100, "learn": { "value": 5 } }, "dayStartsAt": "04:00" });
+    let new = json!({ "dailyLimits": { "total": 80, "learn": { "value": 5 } }, "dayStartsAt": "05:00" });
+
+    let mut merged = old.clone();
+    json_patch::merge(&mut merged, &merge_patch_between(&old, &new));
+
+    assert_eq!(merged, new);
+}
+
+#[test]
+fn test_merge_patch_between_replaces_non_objects() {
+    assert_eq!(merge_patch_between(&json!(null), &json!({ "a": 1 })), json!({ "a": 1 }));
+    assert_eq!(merge_patch_between(&json!([1]), &json!([2])), json!([2]));
+}
+
+// ============================================================================
+// DIFF
+// ============================================================================
+
+#[test]
+fn test_diff_settings_content_lists_leaf_paths() {
+    let before = json!({ "dailyLimits": { "total": 100, "review": 50 }, "motion": "on" });
+    let after = json!({ "dailyLimits": { "total": 80, "review": 50 }, "scheme": "dark" });
+
+    let diff = diff_settings_content(&before, &after);
+
+    assert_eq!(
+        diff,
+        vec![
+            SettingsDiffEntry {
+                path: "/dailyLimits/total".to_string(),
+                before: Some(json!(100)),
+                after: Some(json!(80)),
+            },
+            SettingsDiffEntry {
+                path: "/motion".to_string(),
+                before: Some(json!("on")),
+                after: None,
+            },
+            SettingsDiffEntry {
+                path: "/scheme".to_string(),
+                before: None,
+                after: Some(json!("dark")),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_diff_settings_content_compares_arrays_whole_and_escapes_keys() {
+    let before = json!({ "a/b": [1, 2] });
+    let after = json!({ "a/b": [1, 3] });
+
+    let diff = diff_settings_content(&before, &after);
+
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff[0].path, "/a~1b");
+}
+
+#[test]
+fn test_diff_settings_content_is_empty_for_equal_values() {
+    assert!(diff_settings_content(&json!({ "a": { "b": 1 } }), &json!({ "a": { "b": 1 } })).is_empty());
+}
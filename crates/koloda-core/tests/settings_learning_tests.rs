@@ -39,8 +39,12 @@ fn test_legacy_numeric_daily_limits_are_accepted() {
         "learnAheadLimit": [4, 0]
     }"#;
 
-    let settings: LearningSettings = serde_json::from_str(json).expect("Should deserialize legacy JSON");
-    settings.validate().unwrap();
+    let content: serde_json::Value = serde_json::from_str(json).unwrap();
+    let normalized = SettingsName::Learning
+        .normalize(content)
+        .expect("Should migrate legacy JSON");
+    assert_eq!(normalized["dailyLimits"]["learn"]["value"], 30);
+    assert_eq!(normalized["dailyLimits"]["learn"]["counts"], true);
 }
 
 #[test]
@@ -0,0 +1,59 @@
+#![cfg(all(feature = "http", feature = "sync-client"))]
+
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::sync::SyncOptions;
+use koloda_core::http::{SyncServer, SyncServerOptions};
+use koloda_core::repo::{cards, decks};
+use koloda_core::sync::{sync, HttpSyncTransport};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+fn start() -> SyncServer {
+    SyncServer::start(
+        test_db(),
+        SyncServerOptions {
+            host: None,
+            port: Some(0),
+            token: Some("secret".to_string()),
+        },
+    )
+    .expect("server should start")
+}
+
+fn transport(server: &SyncServer, token: &str) -> HttpSyncTransport {
+    HttpSyncTransport::new(&SyncOptions {
+        url: format!("http://127.0.0.1:{}/", server.info().port),
+        token: token.to_string(),
+    })
+}
+
+#[test]
+fn devices_sync_through_the_server() {
+    let server = start();
+    let (a, b) = (test_db(), test_db());
+    let algorithm_id = add_algorithm(&a, "FSRS");
+    let template_id = add_template(&a, "Basic");
+    let deck_id = add_deck(&a, algorithm_id, template_id, "Spanish");
+    add_card(&a, deck_id, template_id, "hola");
+
+    let pushed = sync(&a, &transport(&server, "secret")).unwrap();
+    let pulled = sync(&b, &transport(&server, "secret")).unwrap();
+
+    assert_eq!(pushed.pushed, 4);
+    assert_eq!(pulled.pulled, 4);
+    let deck = decks::get_decks(&b).unwrap().pop().expect("a deck");
+    assert_eq!(cards::get_cards(&b, deck.id).unwrap()[0].content["1"].text, "hola");
+}
+
+#[test]
+fn wrong_token_is_refused() {
+    let server = start();
+    let db = test_db();
+    add_algorithm(&db, "FSRS");
+
+    let error = sync(&db, &transport(&server, "guess")).unwrap_err();
+
+    assert_eq!(error.code, error_codes::HTTP_UNAUTHORIZED);
+}
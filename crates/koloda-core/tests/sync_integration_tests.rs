@@ -0,0 +1,308 @@
+use std::thread;
+use std::time::Duration;
+
+use koloda_core::app::db::Database;
+use koloda_core::app::error::AppError;
+use koloda_core::domain::cards::{DeleteCardData, UpdateCardData, UpdateCardValues};
+use koloda_core::domain::settings::{GetSettingsHistoryParams, SettingsName};
+use koloda_core::domain::sync::{SyncApplyResult, SyncBatch, SyncPullRequest, SyncPushRequest, SyncTable};
+use koloda_core::repo::{cards, decks, settings};
+use koloda_core::sync::{handle_pull, handle_push, sync, SyncTransport};
+
+mod common;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template, insert_review_row};
+use common::{card_content, interface_settings, test_db};
+
+struct InProcess<'a> {
+    server: &'a Database,
+}
+
+impl SyncTransport for InProcess<'_> {
+    fn server_id(&self) -> &str {
+        "in-process"
+    }
+
+    fn push(&self, request: &SyncPushRequest) -> Result<SyncApplyResult, AppError> {
+        handle_push(self.server, request.clone())
+    }
+
+    fn pull(&self, request: &SyncPullRequest) -> Result<SyncBatch, AppError> {
+        handle_pull(self.server, request)
+    }
+}
+
+/// Server plus two devices sharing one card, already synced.
+fn setup() -> (Database, Database, Database) {
+    let server = test_db();
+    let (a, b) = (test_db(), test_db());
+    let algorithm_id = add_algorithm(&a, "FSRS");
+    let template_id = add_template(&a, "Basic");
+    let deck_id = add_deck(&a, algorithm_id, template_id, "Spanish");
+    add_card(&a, deck_id, template_id, "hola");
+
+    let transport = InProcess { server: &server };
+    sync(&a, &transport).expect("device a should sync");
+    sync(&b, &transport).expect("device b should sync");
+    (server, a, b)
+}
+
+fn only_card(db: &Database) -> koloda_core::domain::cards::Card {
+    let deck = decks::get_decks(db).expect("decks should load").pop().expect("a deck");
+    cards::get_cards(db, deck.id)
+        .expect("cards should load")
+        .pop()
+        .expect("a card")
+}
+
+// WHY: content clocks have millisecond resolution; edits in one test must not tie.
+fn tick() {
+    thread::sleep(Duration::from_millis(5));
+}
+
+#[test]
+fn rows_created_on_one_device_reach_another() {
+    let server = test_db();
+    let (a, b) = (test_db(), test_db());
+    let algorithm_id = add_algorithm(&a, "FSRS");
+    let template_id = add_template(&a, "Basic");
+    let deck_id = add_deck(&a, algorithm_id, template_id, "Spanish");
+    add_card(&a, deck_id, template_id, "hola");
+    let transport = InProcess { server: &server };
+
+    let pushed = sync(&a, &transport).unwrap();
+    let pulled = sync(&b, &transport).unwrap();
+
+    assert_eq!((pushed.pushed, pushed.pulled), (4, 0));
+    assert_eq!((pulled.pushed, pulled.pulled), (0, 4));
+    assert_eq!(decks::get_decks(&b).unwrap()[0].title, "Spanish");
+    assert_eq!(only_card(&b).content["1"].text, "hola");
+}
+
+#[test]
+fn synced_devices_exchange_nothing_more() {
+    let (server, a, b) = setup();
+    let transport = InProcess { server: &server };
+
+    let again_a = sync(&a, &transport).unwrap();
+    let again_b = sync(&b, &transport).unwrap();
+
+    assert_eq!((again_a.pushed, again_a.pulled), (0, 0));
+    assert_eq!((again_b.pushed, again_b.pulled), (0, 0));
+}
+
+#[test]
+fn later_content_edit_wins() {
+    let (server, a, b) = setup();
+    let transport = InProcess { server: &server };
+    let edit = |db: &Database, front: &str| {
+        cards::update_card(
+            db,
+            UpdateCardData {
+                id: only_card(db).id,
+                values: UpdateCardValues {
+                    content: card_content(front, "answer"),
+                },
+            },
+        )
+        .unwrap();
+    };
+
+    edit(&a, "from a");
+    tick();
+    edit(&b, "from b");
+    sync(&a, &transport).unwrap();
+    sync(&b, &transport).unwrap();
+    sync(&a, &transport).unwrap();
+
+    assert_eq!(only_card(&a).content["1"].text, "from b");
+    assert_eq!(only_card(&b).content["1"].text, "from b");
+}
+
+#[test]
+fn reviews_from_every_device_are_kept_and_progress_is_recomputed() {
+    let (server, a, b) = setup();
+    let transport = InProcess { server: &server };
+
+    insert_review_row(&a, only_card(&a).id, 0, 0, 1_000);
+    insert_review_row(&b, only_card(&b).id, 1, 0, 2_000);
+    sync(&a, &transport).unwrap();
+    sync(&b, &transport).unwrap();
+    sync(&a, &transport).unwrap();
+
+    for db in [&server, &a, &b] {
+        let card = only_card(db);
+        assert_eq!(card.reps, 2);
+        assert_eq!(card.last_reviewed_at, Some(2_000));
+    }
+    assert_eq!(only_card(&a).due_at, only_card(&b).due_at);
+}
+
+#[test]
+fn deletes_win_over_edits() {
+    let (server, a, b) = setup();
+    let transport = InProcess { server: &server };
+
+    cards::delete_card(&a, DeleteCardData { id: only_card(&a).id }).unwrap();
+    tick();
+    cards::update_card(
+        &b,
+        UpdateCardData {
+            id: only_card(&b).id,
+            values: UpdateCardValues {
+                content: card_content("edited", "answer"),
+            },
+        },
+    )
+    .unwrap();
+    sync(&a, &transport).unwrap();
+    let report = sync(&b, &transport).unwrap();
+
+    assert_eq!(report.skipped, 1);
+    for db in [&server, &a, &b] {
+        let deck = decks::get_decks(db).unwrap().pop().expect("a deck");
+        assert!(cards::get_cards(db, deck.id).unwrap().is_empty());
+    }
+}
+
+#[test]
+fn rejected_changes_are_reported_and_pushed_again() {
+    let (server, a, _b) = setup();
+    let transport = InProcess { server: &server };
+    server
+        .with_conn(|conn| {
+            conn.execute_batch(
+                r#"
+                CREATE TRIGGER reject_card_edits BEFORE UPDATE OF content ON cards
+                BEGIN
+                    SELECT RAISE(ABORT, 'card edits are rejected');
+                END;
+                "#,
+            )?;
+            Ok(())
+        })
+        .unwrap();
+    let card = only_card(&a);
+    tick();
+    cards::update_card(
+        &a,
+        UpdateCardData {
+            id: card.id,
+            values: UpdateCardValues {
+                content: card_content("adiós", "bye"),
+            },
+        },
+    )
+    .unwrap();
+
+    let report = sync(&a, &transport).unwrap();
+
+    assert_eq!(report.pushed, 0);
+    assert_eq!(report.rejected.len(), 1);
+    assert_eq!(report.rejected[0].table, SyncTable::Cards);
+    assert_eq!(report.rejected[0].reason, "card edits are rejected");
+    assert_eq!(only_card(&server).content["1"].text, "hola");
+
+    server
+        .with_conn(|conn| {
+            conn.execute_batch("DROP TRIGGER reject_card_edits")?;
+            Ok(())
+        })
+        .unwrap();
+    let retried = sync(&a, &transport).unwrap();
+
+    assert_eq!((retried.pushed, retried.rejected.len()), (1, 0));
+    assert_eq!(only_card(&server).content["1"].text, "adiós");
+}
+
+#[test]
+fn log_keeps_one_entry_per_row() {
+    let (_server, a, _b) = setup();
+    let card = only_card(&a);
+    for front in ["adiós", "hasta luego"] {
+        cards::update_card(
+            &a,
+            UpdateCardData {
+                id: card.id,
+                values: UpdateCardValues {
+                    content: card_content(front, "bye"),
+                },
+            },
+        )
+        .unwrap();
+    }
+
+    let entries: i64 = a
+        .with_read(|conn| {
+            Ok(conn.query_row(
+                "SELECT count(*) FROM sync_changes WHERE table_name = 'cards' AND uid = (SELECT uid FROM cards WHERE id = ?1)",
+                [card.id],
+                |row| row.get(0),
+            )?)
+        })
+        .unwrap();
+    assert_eq!(entries, 1);
+}
+
+#[test]
+fn settings_are_matched_by_name() {
+    let (server, a, b) = setup();
+    let transport = InProcess { server: &server };
+
+    settings::set_settings(&b, SettingsName::Interface, interface_settings("en", "light", "on")).unwrap();
+    tick();
+    settings::set_settings(&a, SettingsName::Interface, interface_settings("ru", "dark", "off")).unwrap();
+    sync(&a, &transport).unwrap();
+    sync(&b, &transport).unwrap();
+
+    let synced = settings::get_settings(&b, SettingsName::Interface).unwrap().unwrap();
+    assert_eq!(synced.content["language"], "ru");
+}
+
+#[test]
+fn synced_settings_are_recorded_in_history() {
+    let (server, a, b) = setup();
+    let transport = InProcess { server: &server };
+
+    settings::set_settings(&a, SettingsName::Interface, interface_settings("ru", "dark", "off")).unwrap();
+    sync(&a, &transport).unwrap();
+    sync(&b, &transport).unwrap();
+
+    let history = settings::get_settings_history(
+        &b,
+        GetSettingsHistoryParams {
+            name: SettingsName::Interface,
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(history.first().expect("a history entry").new_content["language"], "ru");
+}
+
+#[test]
+fn ai_settings_stay_on_their_device() {
+    let (server, a, b) = setup();
+    let transport = InProcess { server: &server };
+
+    settings::set_settings(
+        &a,
+        SettingsName::Ai,
+        serde_json::json!({
+            "profiles": [
+                {
+                    "id": "profile-1",
+                    "title": "OpenRouter",
+                    "secrets": { "provider": "openrouter", "apiKey": null },
+                    "createdAt": "2026-01-01T00:00:00Z"
+                }
+            ]
+        }),
+    )
+    .unwrap();
+    sync(&a, &transport).unwrap();
+    sync(&b, &transport).unwrap();
+
+    for db in [&server, &b] {
+        let ai = settings::get_settings(db, SettingsName::Ai).unwrap();
+        assert!(ai.is_none_or(|ai| ai.content["profiles"].as_array().is_none_or(Vec::is_empty)));
+    }
+}
@@ -0,0 +1,60 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::sync::{
+    content_clock, incoming_wins, validate_protocol_version, SyncChange, SyncRow, SyncTable, SYNC_PROTOCOL_VERSION,
+};
+use serde_json::json;
+
+#[test]
+fn change_serializes_table_and_row_side_by_side() {
+    let change: SyncChange = serde_json::from_value(json!({
+        "uid": "abc",
+        "changedAt": 5,
+        "table": "decks",
+        "row": {
+            "title": "Spanish",
+            "algorithmUid": "a",
+            "templateUid": "t",
+            "createdAt": 1,
+            "updatedAt": null,
+        },
+    }))
+    .unwrap();
+
+    assert_eq!(change.row.table(), SyncTable::Decks);
+    assert!(!change.row.is_delete());
+    assert_eq!(serde_json::to_value(&change).unwrap()["row"]["algorithmUid"], "a");
+}
+
+#[test]
+fn null_row_is_a_delete() {
+    let change: SyncChange =
+        serde_json::from_value(json!({ "uid": "abc", "changedAt": 5, "table": "cards", "row": null })).unwrap();
+
+    assert_eq!(change.row, SyncRow::Cards(None));
+    assert!(change.row.is_delete());
+}
+
+#[test]
+fn table_names_round_trip() {
+    for table in SyncTable::ALL {
+        assert_eq!(SyncTable::from_name(table.as_str()), Some(table));
+    }
+    assert_eq!(SyncTable::from_name("sync_changes"), None);
+}
+
+#[test]
+fn newer_content_wins_and_ties_keep_local() {
+    assert_eq!(content_clock(10, None), 10);
+    assert_eq!(content_clock(10, Some(20)), 20);
+    assert!(incoming_wins(10, 11));
+    assert!(!incoming_wins(10, 10));
+    assert!(!incoming_wins(11, 10));
+}
+
+#[test]
+fn other_protocol_versions_are_rejected() {
+    validate_protocol_version(SYNC_PROTOCOL_VERSION).unwrap();
+
+    let error = validate_protocol_version(SYNC_PROTOCOL_VERSION + 1).unwrap_err();
+    assert_eq!(error.code, error_codes::VALIDATION_SYNC_VERSION);
+}
@@ -0,0 +1,178 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::app::task::{CancellationToken, TaskContext, TaskProgress};
+use koloda_core::domain::cards::{
+    AddCardsOptions, CardSelection, FindDuplicateCardsParams, FindReplaceData, FindReplaceMode, InsertCardData,
+};
+use koloda_core::repo::{cards, decks};
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::card_content;
+use common::fixtures::{add_algorithm, add_card, add_deck, add_template};
+use common::test_db;
+
+fn insert_data(deck_id: i64, template_id: i64, front: &str) -> InsertCardData {
+    InsertCardData {
+        deck_id,
+        template_id,
+        content: card_content(front, "answer"),
+        state: None,
+        due_at: None,
+        stability: None,
+        difficulty: None,
+        scheduled_days: None,
+        learning_steps: None,
+        reps: None,
+        lapses: None,
+        last_reviewed_at: None,
+    }
+}
+
+fn deck_selection(deck_id: i64) -> CardSelection {
+    CardSelection {
+        deck_ids: Some(vec![deck_id]),
+        card_ids: None,
+    }
+}
+
+fn recording_task() -> (TaskContext, Arc<Mutex<Vec<TaskProgress>>>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&events);
+    let task = TaskContext::default().with_progress(move |progress| {
+        recorded.lock().expect("progress lock").push(progress);
+    });
+
+    (task, events)
+}
+
+// WHY: Cancels from inside the progress callback so the cut-off point is deterministic.
+fn cancelling_task(after: usize) -> TaskContext {
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    TaskContext::new(token).with_progress(move |progress| {
+        if progress.done == after {
+            cancel.cancel();
+        }
+    })
+}
+
+#[test]
+fn add_cards_reports_progress_up_to_total() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let (task, events) = recording_task();
+
+    let items = ["one", "two", "three"]
+        .iter()
+        .map(|front| insert_data(deck_id, template_id, front))
+        .collect();
+    cards::add_cards_with_task(&db, items, AddCardsOptions::default(), &task).unwrap();
+
+    let events = events.lock().unwrap();
+    let done: Vec<usize> = events.iter().map(|progress| progress.done).collect();
+    assert_eq!(done, vec![0, 1, 2, 3]);
+    assert!(events.iter().all(|progress| progress.total == 3));
+}
+
+#[test]
+fn add_cards_stops_at_cancellation_and_keeps_earlier_items() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+
+    let items = ["one", "two", "three"]
+        .iter()
+        .map(|front| insert_data(deck_id, template_id, front))
+        .collect();
+    let err = cards::add_cards_with_task(&db, items, AddCardsOptions::default(), &cancelling_task(1)).unwrap_err();
+
+    assert_eq!(err.code, error_codes::TASK_CANCELLED);
+    assert_eq!(cards::get_cards(&db, deck_id).unwrap().len(), 1);
+}
+
+#[test]
+fn cancelled_find_replace_apply_rolls_back() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    let first = add_card(&db, deck_id, template_id, "colour one");
+    add_card(&db, deck_id, template_id, "colour two");
+
+    let err = cards::find_replace_with_task(
+        &db,
+        FindReplaceData {
+            selection: deck_selection(deck_id),
+            field_id: Some(1),
+            pattern: "colour".to_string(),
+            is_regex: false,
+            replacement: "color".to_string(),
+            mode: FindReplaceMode::Apply,
+        },
+        &cancelling_task(1),
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::TASK_CANCELLED);
+    let card = cards::get_card(&db, first).unwrap().unwrap();
+    assert_eq!(card.content["1"].text, "colour one");
+}
+
+#[test]
+fn find_duplicate_cards_honours_pre_cancelled_token() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    add_card(&db, deck_id, template_id, "same");
+    add_card(&db, deck_id, template_id, "same");
+    let task = TaskContext::default();
+    task.token().cancel();
+
+    let err = cards::find_duplicate_cards_with_task(
+        &db,
+        FindDuplicateCardsParams {
+            selection: deck_selection(deck_id),
+            field_ids: None,
+        },
+        &task,
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code, error_codes::TASK_CANCELLED);
+}
+
+#[test]
+fn export_deck_reports_progress_per_card() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    add_card(&db, deck_id, template_id, "one");
+    add_card(&db, deck_id, template_id, "two");
+    let (task, events) = recording_task();
+
+    decks::export_deck_with_task(&db, deck_id, &task).unwrap();
+
+    let done: Vec<usize> = events.lock().unwrap().iter().map(|progress| progress.done).collect();
+    assert_eq!(done, vec![0, 1, 2]);
+}
+
+#[test]
+fn cancelled_import_deck_leaves_no_deck() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    add_card(&db, deck_id, template_id, "one");
+    add_card(&db, deck_id, template_id, "two");
+    let bundle = decks::export_deck(&db, deck_id).unwrap();
+
+    let err = decks::import_deck_with_task(&db, bundle, &cancelling_task(1)).unwrap_err();
+
+    assert_eq!(err.code, error_codes::TASK_CANCELLED);
+    assert_eq!(decks::get_decks(&db).unwrap().len(), 1);
+}
@@ -0,0 +1,331 @@
+mod common;
+
+use common::fixtures::{add_algorithm, add_deck};
+use common::{card_content, simple_template_content, test_db};
+use koloda_core::app::db::Database;
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::cards::{
+    AddCardsOptions, CardSelection, CopyCardsData, FindReplaceData, FindReplaceMode, InsertCardData, MoveCardsData,
+    UpdateCardData, UpdateCardValues,
+};
+use koloda_core::domain::templates::{
+    FieldScript, InsertTemplateData, TemplateContent, TemplateFieldRules, FIELD_TYPE_MARKDOWN,
+};
+use koloda_core::repo::{cards, decks, templates};
+
+fn with_front_rules(rules: TemplateFieldRules) -> TemplateContent {
+    let mut content = simple_template_content();
+    if let Some(front) = content.fields.first_mut() {
+        front.rules = Some(rules);
+    }
+    content
+}
+
+fn insert_data(deck_id: i64, template_id: i64, front: &str, back: &str) -> InsertCardData {
+    InsertCardData {
+        deck_id,
+        template_id,
+        content: card_content(front, back),
+        state: None,
+        due_at: None,
+        stability: None,
+        difficulty: None,
+        scheduled_days: None,
+        learning_steps: None,
+        reps: None,
+        lapses: None,
+        last_reviewed_at: None,
+    }
+}
+
+fn validation_code(content: &TemplateContent, front: &str) -> Option<String> {
+    insert_data(1, 1, front, "")
+        .validate(&content.fields)
+        .err()
+        .map(|error| {
+            assert_eq!(error.details.as_deref(), Some("Field id: 1"));
+            error.code
+        })
+}
+
+/// Deck whose template marks `Front` as unique within the deck.
+fn unique_front_deck() -> (Database, i64, i64) {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = templates::add_template(
+        &db,
+        InsertTemplateData {
+            title: "Unique".to_string(),
+            content: with_front_rules(TemplateFieldRules {
+                unique_in_deck: true,
+                ..TemplateFieldRules::default()
+            }),
+        },
+    )
+    .expect("template should be created")
+    .id;
+    let deck_id = add_deck(&db, algorithm_id, template_id, "Deck");
+    (db, deck_id, template_id)
+}
+
+// ============================================================================
+// TEMPLATE VALIDATION
+// ============================================================================
+
+#[test]
+fn test_invalid_rules_are_rejected_with_the_template() {
+    for rules in [
+        TemplateFieldRules {
+            max_length: Some(0),
+            ..TemplateFieldRules::default()
+        },
+        TemplateFieldRules {
+            pattern: Some("[a-".to_string()),
+            ..TemplateFieldRules::default()
+        },
+    ] {
+        let data = InsertTemplateData {
+            title: "Basic".to_string(),
+            content: with_front_rules(rules),
+        };
+        let error = data.validate().unwrap_err();
+
+        assert_eq!(error.code, error_codes::VALIDATION_TEMPLATES_FIELDS_RULES);
+        assert!(error.details.unwrap_or_default().starts_with("Field id: 1: "));
+    }
+}
+
+#[test]
+fn test_rules_round_trip_and_stay_optional() {
+    let content = with_front_rules(TemplateFieldRules {
+        allowed_scripts: vec![FieldScript::Cyrillic],
+        ..TemplateFieldRules::default()
+    });
+
+    let json = serde_json::to_value(&content).unwrap();
+
+    assert_eq!(json["fields"][0]["rules"]["allowedScripts"][0], "cyrillic");
+    assert!(json["fields"][1].get("rules").is_none());
+}
+
+// ============================================================================
+// CARD VALIDATION
+// ============================================================================
+
+#[test]
+fn test_max_length_counts_plain_text_characters() {
+    let mut content = with_front_rules(TemplateFieldRules {
+        max_length: Some(4),
+        ..TemplateFieldRules::default()
+    });
+
+    assert_eq!(validation_code(&content, "niño"), None);
+    assert_eq!(
+        validation_code(&content, "niños").as_deref(),
+        Some(error_codes::VALIDATION_CARDS_CONTENT_FIELD_TOO_LONG)
+    );
+
+    if let Some(front) = content.fields.first_mut() {
+        front.field_type = FIELD_TYPE_MARKDOWN.to_string();
+    }
+    assert_eq!(validation_code(&content, "**niño**"), None);
+}
+
+#[test]
+fn test_pattern_must_match_the_whole_value() {
+    let content = with_front_rules(TemplateFieldRules {
+        pattern: Some("[a-z]+".to_string()),
+        ..TemplateFieldRules::default()
+    });
+
+    assert_eq!(validation_code(&content, "hola"), None);
+    assert_eq!(
+        validation_code(&content, "hola!").as_deref(),
+        Some(error_codes::VALIDATION_CARDS_CONTENT_FIELD_PATTERN)
+    );
+}
+
+#[test]
+fn test_allowed_scripts_accept_neutral_characters() {
+    let content = with_front_rules(TemplateFieldRules {
+        allowed_scripts: vec![FieldScript::Cyrillic],
+        ..TemplateFieldRules::default()
+    });
+
+    assert_eq!(validation_code(&content, "Привет, мир! 2"), None);
+    assert_eq!(validation_code(&content, "ё\u{301}"), None);
+    assert_eq!(
+        validation_code(&content, "Привет, world").as_deref(),
+        Some(error_codes::VALIDATION_CARDS_CONTENT_FIELD_SCRIPT)
+    );
+}
+
+#[test]
+fn test_update_values_apply_the_same_rules() {
+    let mut content = with_front_rules(TemplateFieldRules {
+        max_length: Some(3),
+        ..TemplateFieldRules::default()
+    });
+    if let Some(front) = content.fields.first_mut() {
+        front.is_required = false;
+    }
+    let values = |front: &str| UpdateCardValues {
+        content: card_content(front, ""),
+    };
+
+    values("").validate(&content.fields).unwrap();
+    assert_eq!(
+        values("hola").validate(&content.fields).unwrap_err().code,
+        error_codes::VALIDATION_CARDS_CONTENT_FIELD_TOO_LONG
+    );
+}
+
+// ============================================================================
+// UNIQUE WITHIN DECK
+// ============================================================================
+
+#[test]
+fn test_unique_field_rejects_same_normalized_text() {
+    let (db, deck_id, template_id) = unique_front_deck();
+    cards::add_card(&db, insert_data(deck_id, template_id, "Hola", "a")).unwrap();
+
+    let error = cards::add_card(&db, insert_data(deck_id, template_id, " hola ", "b")).unwrap_err();
+
+    assert_eq!(error.code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE);
+    assert_eq!(error.details.as_deref(), Some("Field id: 1"));
+}
+
+#[test]
+fn test_unique_field_allows_updating_the_card_itself() {
+    let (db, deck_id, template_id) = unique_front_deck();
+    let card = cards::add_card(&db, insert_data(deck_id, template_id, "hola", "a")).unwrap();
+    let other = cards::add_card(&db, insert_data(deck_id, template_id, "adiós", "b")).unwrap();
+
+    cards::update_card(
+        &db,
+        UpdateCardData {
+            id: card.id,
+            values: UpdateCardValues {
+                content: card_content("Hola", "hello"),
+            },
+        },
+    )
+    .unwrap();
+    let error = cards::update_card(
+        &db,
+        UpdateCardData {
+            id: other.id,
+            values: UpdateCardValues {
+                content: card_content("hola", "b"),
+            },
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(error.code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE);
+}
+
+#[test]
+fn test_unique_field_catches_duplicates_within_a_batch() {
+    let (db, deck_id, template_id) = unique_front_deck();
+
+    let results = cards::add_cards(
+        &db,
+        vec![
+            insert_data(deck_id, template_id, "hola", "a"),
+            insert_data(deck_id, template_id, "HOLA", "b"),
+        ],
+        AddCardsOptions::default(),
+    )
+    .unwrap();
+
+    assert!(results[0].error.is_none());
+    assert_eq!(
+        results[1].error.as_ref().map(|error| error.code.as_str()),
+        Some(error_codes::VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE)
+    );
+    assert_eq!(cards::get_cards(&db, deck_id).unwrap().len(), 1);
+}
+
+#[test]
+fn test_unique_field_is_enforced_on_move_and_copy() {
+    let (db, deck_id, template_id) = unique_front_deck();
+    let algorithm_id = add_algorithm(&db, "Second");
+    let other_deck_id = add_deck(&db, algorithm_id, template_id, "Other");
+    cards::add_card(&db, insert_data(deck_id, template_id, "hola", "a")).unwrap();
+    let other = cards::add_card(&db, insert_data(other_deck_id, template_id, "Hola", "b")).unwrap();
+
+    let moved = cards::move_cards(
+        &db,
+        MoveCardsData {
+            ids: vec![other.id],
+            deck_id,
+            field_mapping: None,
+        },
+    )
+    .unwrap_err();
+    let copied = cards::copy_cards(
+        &db,
+        CopyCardsData {
+            ids: vec![other.id],
+            deck_id,
+            field_mapping: None,
+            reset_progress: true,
+        },
+    )
+    .unwrap_err();
+
+    assert_eq!(moved.code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE);
+    assert_eq!(copied.code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE);
+    assert_eq!(cards::get_cards(&db, deck_id).unwrap().len(), 1);
+    assert_eq!(cards::get_cards(&db, other_deck_id).unwrap().len(), 1);
+}
+
+#[test]
+fn test_find_replace_fails_values_that_collide() {
+    let (db, deck_id, template_id) = unique_front_deck();
+    cards::add_card(&db, insert_data(deck_id, template_id, "hola", "a")).unwrap();
+    let hole = cards::add_card(&db, insert_data(deck_id, template_id, "hole", "b")).unwrap();
+
+    let result = cards::find_replace(
+        &db,
+        FindReplaceData {
+            selection: CardSelection {
+                deck_ids: Some(vec![deck_id]),
+                card_ids: None,
+            },
+            field_id: Some(1),
+            pattern: "hole".to_string(),
+            is_regex: false,
+            replacement: "hola".to_string(),
+            mode: FindReplaceMode::Apply,
+        },
+    )
+    .unwrap();
+
+    assert!(!result.is_applied);
+    assert_eq!(result.failed.len(), 1);
+    assert_eq!(result.failed[0].card_id, hole.id);
+    assert_eq!(
+        result.failed[0].code,
+        error_codes::VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE
+    );
+}
+
+#[test]
+fn test_import_deck_rejects_duplicates_within_the_bundle() {
+    let (db, deck_id, template_id) = unique_front_deck();
+    cards::add_card(&db, insert_data(deck_id, template_id, "hola", "a")).unwrap();
+    cards::add_card(&db, insert_data(deck_id, template_id, "adiós", "b")).unwrap();
+    let mut bundle = decks::export_deck(&db, deck_id).unwrap();
+    if let Some(card) = bundle.cards.last_mut() {
+        card.content = card_content("HOLA", "b");
+    }
+    let decks_before = decks::get_decks(&db).unwrap().len();
+
+    let error = decks::import_deck(&db, bundle).unwrap_err();
+
+    assert_eq!(error.code, error_codes::VALIDATION_CARDS_CONTENT_FIELD_NOT_UNIQUE);
+    assert_eq!(error.details.as_deref(), Some("Card 1: Field id: 1"));
+    assert_eq!(decks::get_decks(&db).unwrap().len(), decks_before);
+}
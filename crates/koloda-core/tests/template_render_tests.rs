@@ -0,0 +1,125 @@
+mod common;
+
+use common::{card_content, simple_template_content};
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::template_render::render_content;
+use koloda_core::domain::templates::{InsertTemplateData, TemplateContent, TemplateSides};
+
+fn with_sides(question: &str, answer: &str) -> TemplateContent {
+    TemplateContent {
+        sides: Some(TemplateSides {
+            question: question.to_string(),
+            answer: answer.to_string(),
+        }),
+        ..simple_template_content()
+    }
+}
+
+fn validation_error(content: TemplateContent) -> String {
+    let data = InsertTemplateData {
+        title: "Basic".to_string(),
+        content,
+    };
+    let error = data.validate().expect_err("sides should be rejected");
+    assert_eq!(error.code, error_codes::VALIDATION_TEMPLATES_SIDES_SYNTAX);
+    error.details.unwrap_or_default()
+}
+
+// ============================================================================
+// RENDERING
+// ============================================================================
+
+#[test]
+fn test_layout_renders_display_then_reveal_fields() {
+    let (question, answer) = render_content(&simple_template_content(), &card_content("a < b", "yes")).unwrap();
+
+    assert_eq!(question, r#"<div class="field">a &lt; b</div>"#);
+    assert_eq!(
+        answer,
+        r#"<div class="field">a &lt; b</div><hr id="answer"><div class="field">yes</div>"#
+    );
+}
+
+#[test]
+fn test_layout_skips_blank_fields() {
+    let (_, answer) = render_content(&simple_template_content(), &card_content("hola", "  ")).unwrap();
+
+    assert!(answer.ends_with(r#"<hr id="answer">"#));
+}
+
+#[test]
+fn test_sections_follow_field_blankness() {
+    let template = with_sides(
+        "{{Front}}{{#Back}} +{{/Back}}{{^Back}} -{{/Back}}",
+        "{{FrontSide}}|{{Back}}",
+    );
+
+    let (filled, answer) = render_content(&template, &card_content("hola", "hello")).unwrap();
+    let (blank, _) = render_content(&template, &card_content("hola", "")).unwrap();
+
+    assert_eq!(filled, "hola +");
+    assert_eq!(answer, "hola +|hello");
+    assert_eq!(blank, "hola -");
+}
+
+#[test]
+fn test_filters_apply_right_to_left() {
+    let template = with_sides("{{furigana:Front}}", "{{text:furigana:Front}}{{hint:Back}}");
+
+    let (question, answer) = render_content(&template, &card_content("日本[にほん] 語[ご]", "country")).unwrap();
+
+    assert_eq!(
+        question,
+        "<ruby><rb>日本</rb><rt>にほん</rt></ruby><ruby><rb>語</rb><rt>ご</rt></ruby>"
+    );
+    assert!(answer.starts_with("日本にほん語ご"));
+    assert!(answer.ends_with(r#"<details class="hint"><summary>Back</summary>country</details>"#));
+}
+
+#[test]
+fn test_hint_of_blank_field_is_empty() {
+    let template = with_sides("{{Front}}{{hint:Back}}", "{{Back}}");
+
+    let (question, _) = render_content(&template, &card_content("hola", "")).unwrap();
+
+    assert_eq!(question, "hola");
+}
+
+// ============================================================================
+// SAVE-TIME VALIDATION
+// ============================================================================
+
+#[test]
+fn test_valid_sides_pass_validation() {
+    let data = InsertTemplateData {
+        title: "Basic".to_string(),
+        content: with_sides("{{! note }}{{ Front }}", "{{FrontSide}}{{#Back}}{{hint:Back}}{{/Back}}"),
+    };
+
+    data.validate().unwrap();
+}
+
+#[test]
+fn test_syntax_errors_name_the_side() {
+    assert_eq!(
+        validation_error(with_sides("{{Nope}}", "")),
+        "question: Unknown field: Nope"
+    );
+    assert_eq!(validation_error(with_sides("{{Front", "")), "question: Unclosed tag");
+    assert_eq!(
+        validation_error(with_sides("", "{{#Back}}{{Front}}")),
+        "answer: Unclosed section: Back"
+    );
+    assert_eq!(
+        validation_error(with_sides("{{#Front}}{{/Back}}", "")),
+        "question: Unexpected closing tag: Back"
+    );
+    assert_eq!(
+        validation_error(with_sides("{{bold:Front}}", "")),
+        "question: Unknown filter: bold"
+    );
+    assert_eq!(
+        validation_error(with_sides("{{FrontSide}}", "")),
+        "question: Unknown field: FrontSide"
+    );
+}
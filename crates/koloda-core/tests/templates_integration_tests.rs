@@ -46,6 +46,7 @@ fn update_template_locked_rejects_field_type_and_required_changes() {
                 content: TemplateContent {
                     fields: changed_type_fields,
                     layout: original.content.layout.clone(),
+                    sides: None,
                 },
             },
         },
@@ -71,6 +72,7 @@ fn update_template_locked_rejects_field_type_and_required_changes() {
                 content: TemplateContent {
                     fields: changed_required_fields,
                     layout: original.content.layout.clone(),
+                    sides: None,
                 },
             },
         },
@@ -103,6 +105,7 @@ fn update_template_locked_allows_title_change_and_adding_new_field() {
         title: "Hint".to_string(),
         field_type: "text".to_string(),
         is_required: false,
+        rules: None,
     });
     let mut layout = original.content.layout.clone();
     layout.push(TemplateLayoutItem {
@@ -116,7 +119,11 @@ fn update_template_locked_allows_title_change_and_adding_new_field() {
             id: template_id,
             values: UpdateTemplateValues {
                 title: "Basic v2".to_string(),
-                content: TemplateContent { fields, layout },
+                content: TemplateContent {
+                    fields,
+                    layout,
+                    sides: None,
+                },
             },
         },
     )
@@ -163,6 +170,7 @@ fn update_template_locked_rejects_removing_existing_field() {
                 content: TemplateContent {
                     fields: filtered_fields,
                     layout: filtered_layout,
+                    sides: None,
                 },
             },
         },
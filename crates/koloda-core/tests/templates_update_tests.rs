@@ -16,12 +16,14 @@ fn create_original_template_content() -> TemplateContent {
                 title: "Front".to_string(),
                 field_type: "markdown".to_string(),
                 is_required: true,
+                rules: None,
             },
             TemplateField {
                 id: 2,
                 title: "Back".to_string(),
                 field_type: "text".to_string(),
                 is_required: false,
+                rules: None,
             },
         ],
         layout: vec![
@@ -34,6 +36,7 @@ fn create_original_template_content() -> TemplateContent {
                 operation: "reveal".to_string(),
             },
         ],
+        sides: None,
     }
 }
 
@@ -0,0 +1,39 @@
+// WHY: the committed file includes the HTTP server types, so rendering without `http` is always stale.
+#![cfg(all(feature = "ts", feature = "http"))]
+
+use koloda_core::bindings::type_collection;
+use specta_typescript::{BigIntExportBehavior, Typescript};
+use std::path::PathBuf;
+
+const UPDATE_ENV: &str = "KOLODA_UPDATE_BINDINGS";
+
+fn bindings_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../apps/native-electron/src/native-types.ts")
+}
+
+// WHY: IDs and timestamps are i64 in Rust but plain JSON numbers on the wire.
+fn render() -> String {
+    Typescript::default()
+        .bigint(BigIntExportBehavior::Number)
+        .export(&type_collection())
+        .expect("bindings should render")
+}
+
+#[test]
+fn generated_bindings_are_up_to_date() {
+    let rendered = render();
+    let path = bindings_path();
+
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        std::fs::write(&path, &rendered).expect("bindings should be written");
+        return;
+    }
+
+    let committed = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        committed == rendered,
+        "{} is stale; rerun with {}=1 cargo test -p koloda-core --features ts,http --test ts_bindings_tests",
+        path.display(),
+        UPDATE_ENV
+    );
+}
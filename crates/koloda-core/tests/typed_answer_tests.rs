@@ -0,0 +1,152 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::fsrs::Rating;
+use koloda_core::domain::typed_answer::{
+    compare_typed_answer, CompareTypedAnswerData, TypedAnswerComparison, TypedAnswerDiffKind, TypedAnswerDiffPart,
+    TypedAnswerOptions, MAX_TYPED_ANSWER_LENGTH,
+};
+
+fn compare(expected: &str, typed: &str, options: TypedAnswerOptions) -> TypedAnswerComparison {
+    compare_typed_answer(&CompareTypedAnswerData {
+        expected: expected.to_string(),
+        typed: typed.to_string(),
+        options,
+    })
+    .expect("answers within the length limit")
+}
+
+fn part(kind: TypedAnswerDiffKind, text: &str) -> TypedAnswerDiffPart {
+    TypedAnswerDiffPart {
+        kind,
+        text: text.to_string(),
+    }
+}
+
+// ============================================================================
+// NORMALIZATION
+// ============================================================================
+
+#[test]
+fn test_default_options_fold_case_and_whitespace() {
+    let comparison = compare("Buenos  días", " buenos días ", TypedAnswerOptions::default());
+
+    assert!(comparison.is_correct);
+    assert!((comparison.similarity - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_diacritics_count_unless_ignored() {
+    let strict = compare("café", "cafe", TypedAnswerOptions::default());
+    let loose = compare(
+        "café",
+        "cafe",
+        TypedAnswerOptions {
+            ignore_diacritics: true,
+            ..TypedAnswerOptions::default()
+        },
+    );
+    let decomposed = compare("café", "cafe\u{301}", TypedAnswerOptions::default());
+
+    assert!(!strict.is_correct);
+    assert!(loose.is_correct);
+    assert!(decomposed.is_correct);
+}
+
+#[test]
+fn test_ignored_punctuation_stays_in_the_diff() {
+    let comparison = compare(
+        "¡Hola, mundo!",
+        "hola mundo",
+        TypedAnswerOptions {
+            ignore_punctuation: true,
+            ..TypedAnswerOptions::default()
+        },
+    );
+
+    assert!(comparison.is_correct);
+    assert_eq!(comparison.diff, vec![part(TypedAnswerDiffKind::Equal, "¡Hola, mundo!")]);
+}
+
+#[test]
+fn test_best_alternative_is_reported() {
+    let comparison = compare(
+        "color / colour",
+        "colour",
+        TypedAnswerOptions {
+            alternatives_delimiter: Some("/".to_string()),
+            ..TypedAnswerOptions::default()
+        },
+    );
+
+    assert!(comparison.is_correct);
+    assert_eq!(comparison.expected, "colour");
+}
+
+// ============================================================================
+// DIFF AND SCORE
+// ============================================================================
+
+#[test]
+fn test_diff_marks_missing_and_extra_characters() {
+    let comparison = compare("gato", "gatto", TypedAnswerOptions::default());
+
+    assert!(!comparison.is_correct);
+    assert_eq!(
+        comparison.diff,
+        vec![
+            part(TypedAnswerDiffKind::Equal, "gat"),
+            part(TypedAnswerDiffKind::Extra, "t"),
+            part(TypedAnswerDiffKind::Equal, "o"),
+        ]
+    );
+    assert!((comparison.similarity - 8.0 / 9.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_empty_typed_answer_is_all_missing() {
+    let comparison = compare("perro", "", TypedAnswerOptions::default());
+
+    assert_eq!(comparison.diff, vec![part(TypedAnswerDiffKind::Missing, "perro")]);
+    assert!(comparison.similarity.abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_rating_is_suggested_only_on_request() {
+    let suggest = TypedAnswerOptions {
+        suggest_rating: true,
+        ..TypedAnswerOptions::default()
+    };
+
+    assert_eq!(
+        compare("gato", "gato", suggest.clone()).suggested_rating,
+        Some(Rating::Good.as_i32())
+    );
+    assert_eq!(
+        compare("gato", "gatto", suggest.clone()).suggested_rating,
+        Some(Rating::Hard.as_i32())
+    );
+    assert_eq!(
+        compare("gato", "perro", suggest).suggested_rating,
+        Some(Rating::Again.as_i32())
+    );
+    assert_eq!(
+        compare("gato", "gato", TypedAnswerOptions::default()).suggested_rating,
+        None
+    );
+}
+
+#[test]
+fn test_answers_over_the_length_limit_are_rejected() {
+    let long = "é".repeat(MAX_TYPED_ANSWER_LENGTH + 1);
+    let at_limit = "é".repeat(MAX_TYPED_ANSWER_LENGTH);
+
+    for (expected, typed) in [(long.as_str(), "e"), ("e", long.as_str())] {
+        let error = compare_typed_answer(&CompareTypedAnswerData {
+            expected: expected.to_string(),
+            typed: typed.to_string(),
+            options: TypedAnswerOptions::default(),
+        })
+        .unwrap_err();
+        assert_eq!(error.code, error_codes::VALIDATION_TYPED_ANSWER_TOO_LONG);
+    }
+    assert!(compare(&at_limit, &at_limit, TypedAnswerOptions::default()).is_correct);
+}
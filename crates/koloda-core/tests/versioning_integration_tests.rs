@@ -0,0 +1,91 @@
+use koloda_core::domain::settings::SettingsName;
+use koloda_core::repo::{algorithms, settings, templates};
+use rusqlite::params;
+use serde_json::json;
+
+mod common;
+use common::fixtures::{add_algorithm, add_template};
+use common::test_db;
+
+fn stored_content(db: &koloda_core::app::db::Database, table: &str, id: i64) -> serde_json::Value {
+    db.with_conn(|conn| {
+        let content: String = conn.query_row(
+            &format!("SELECT content FROM {} WHERE id = ?1", table),
+            params![id],
+            |row| row.get(0),
+        )?;
+        Ok(serde_json::from_str(&content)?)
+    })
+    .expect("stored content should load")
+}
+
+fn set_stored_content(db: &koloda_core::app::db::Database, table: &str, id: i64, content: &serde_json::Value) {
+    db.with_conn(|conn| {
+        conn.execute(
+            &format!("UPDATE {} SET content = ?1 WHERE id = ?2", table),
+            params![content.to_string(), id],
+        )?;
+        Ok(())
+    })
+    .expect("stored content should update");
+}
+
+#[test]
+fn algorithm_and_template_writes_stamp_version() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let template_id = add_template(&db, "Basic");
+
+    assert_eq!(stored_content(&db, "algorithms", algorithm_id)["version"], 1);
+    assert_eq!(stored_content(&db, "templates", template_id)["version"], 1);
+}
+
+#[test]
+fn unversioned_rows_still_read() {
+    let db = test_db();
+    let algorithm_id = add_algorithm(&db, "FSRS");
+    let mut content = stored_content(&db, "algorithms", algorithm_id);
+    content.as_object_mut().unwrap().remove("version");
+    set_stored_content(&db, "algorithms", algorithm_id, &content);
+
+    let algorithm = algorithms::get_algorithm(&db, algorithm_id).unwrap().unwrap();
+    assert_eq!(algorithm.content.algorithm_type, "fsrs");
+}
+
+#[test]
+fn rows_from_a_newer_version_fail_to_read() {
+    let db = test_db();
+    let template_id = add_template(&db, "Basic");
+    let mut content = stored_content(&db, "templates", template_id);
+    content["version"] = json!(99);
+    set_stored_content(&db, "templates", template_id, &content);
+
+    templates::get_template(&db, template_id).unwrap_err();
+}
+
+#[test]
+fn legacy_learning_settings_row_is_migrated_on_read() {
+    let db = test_db();
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO settings (name, content, created_at) VALUES ('learning', ?1, 0)",
+            params![json!({
+                "defaults": {},
+                "dailyLimits": { "total": 100, "untouched": 20, "learn": 30, "review": 50 },
+                "dayStartsAt": "04:00",
+                "learnAheadLimit": [4, 0]
+            })
+            .to_string()],
+        )?;
+        Ok(())
+    })
+    .unwrap();
+
+    let learning = settings::get_settings(&db, SettingsName::Learning).unwrap().unwrap();
+
+    assert_eq!(learning.content["version"], 2);
+    assert_eq!(
+        learning.content["dailyLimits"]["review"],
+        json!({ "value": 50, "counts": true })
+    );
+}
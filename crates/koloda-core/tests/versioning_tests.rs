@@ -0,0 +1,127 @@
+use koloda_core::app::error::error_codes;
+use koloda_core::domain::algorithms_fsrs::AlgorithmFSRS;
+use koloda_core::domain::settings::SettingsName;
+use koloda_core::domain::settings_hotkeys::HotkeysSettings;
+use koloda_core::domain::settings_interface::InterfaceSettings;
+use koloda_core::domain::settings_learning::LearningSettings;
+use koloda_core::domain::templates::TemplateContent;
+use koloda_core::domain::versioning::{migrate_json, VersionedJson};
+use serde_json::json;
+
+mod common;
+use common::{fsrs_algorithm_content, simple_template_content};
+
+fn legacy_learning_settings() -> serde_json::Value {
+    json!({
+        "defaults": {},
+        "dailyLimits": { "total": 100, "untouched": 20, "learn": { "value": 30, "counts": false }, "review": 50 },
+        "dayStartsAt": "04:00",
+        "learnAheadLimit": [4, 0]
+    })
+}
+
+// ===== CHAIN =====
+
+#[test]
+fn test_missing_version_is_treated_as_v1_and_stamped() {
+    let migrated = migrate_json(json!({ "a": 1 }), &[]).unwrap();
+    assert_eq!(migrated, json!({ "a": 1, "version": 1 }));
+}
+
+#[test]
+fn test_newer_version_is_rejected() {
+    let err = migrate_json(json!({ "version": 2 }), &[]).unwrap_err();
+    assert_eq!(err.code, error_codes::VALIDATION_COMMON_CONTENT_VERSION);
+}
+
+#[test]
+fn test_invalid_version_is_rejected() {
+    for version in [json!(0), json!("2"), json!(-1)] {
+        let err = migrate_json(json!({ "version": version }), &[]).unwrap_err();
+        assert_eq!(err.code, error_codes::VALIDATION_COMMON_CONTENT_VERSION);
+    }
+}
+
+#[test]
+fn test_non_object_content_is_rejected() {
+    let err = migrate_json(json!([1, 2]), &[]).unwrap_err();
+    assert_eq!(err.code, error_codes::VALIDATION_COMMON_CONTENT_VERSION);
+}
+
+#[test]
+fn test_steps_before_stored_version_are_skipped() {
+    fn add_b(mut content: serde_json::Value) -> Result<serde_json::Value, koloda_core::app::error::AppError> {
+        content["b"] = json!(true);
+        Ok(content)
+    }
+    fn add_c(mut content: serde_json::Value) -> Result<serde_json::Value, koloda_core::app::error::AppError> {
+        content["c"] = json!(true);
+        Ok(content)
+    }
+
+    let migrated = migrate_json(json!({ "version": 2 }), &[add_b, add_c]).unwrap();
+    assert_eq!(migrated, json!({ "version": 3, "c": true }));
+}
+
+// ===== LEARNING =====
+
+#[test]
+fn test_learning_current_version_is_2() {
+    assert_eq!(LearningSettings::current_version(), 2);
+}
+
+#[test]
+fn test_learning_v1_to_v2_wraps_numeric_limits() {
+    let migrated = migrate_json(legacy_learning_settings(), LearningSettings::MIGRATIONS).unwrap();
+
+    assert_eq!(migrated["version"], 2);
+    assert_eq!(
+        migrated["dailyLimits"]["untouched"],
+        json!({ "value": 20, "counts": true })
+    );
+    assert_eq!(
+        migrated["dailyLimits"]["learn"],
+        json!({ "value": 30, "counts": false })
+    );
+    assert_eq!(
+        migrated["dailyLimits"]["review"],
+        json!({ "value": 50, "counts": true })
+    );
+}
+
+#[test]
+fn test_learning_v2_numeric_limits_are_not_migrated() {
+    let mut content = legacy_learning_settings();
+    content["version"] = json!(2);
+
+    LearningSettings::from_versioned(content).unwrap_err();
+}
+
+#[test]
+fn test_learning_normalize_stamps_current_version() {
+    let normalized = SettingsName::Learning.normalize(legacy_learning_settings()).unwrap();
+    assert_eq!(normalized["version"], 2);
+    assert_eq!(normalized["dailyLimits"]["untouched"]["value"], 20);
+}
+
+// ===== OTHER SLICES =====
+
+#[test]
+fn test_unmigrated_documents_round_trip_at_v1() {
+    let interface = InterfaceSettings::from_versioned(json!({
+        "language": "en",
+        "scheme": "system",
+        "motion": "system"
+    }))
+    .unwrap();
+    assert_eq!(interface.to_versioned().unwrap()["version"], 1);
+
+    let hotkeys = HotkeysSettings::from_versioned(json!({ "version": 1 })).unwrap();
+    assert_eq!(hotkeys.to_versioned().unwrap()["version"], 1);
+
+    let algorithm = AlgorithmFSRS::from_versioned(serde_json::to_value(fsrs_algorithm_content()).unwrap()).unwrap();
+    assert_eq!(algorithm.to_versioned().unwrap()["version"], 1);
+
+    let template = TemplateContent::from_versioned(serde_json::to_value(simple_template_content()).unwrap()).unwrap();
+    assert_eq!(template.to_versioned().unwrap()["version"], 1);
+}
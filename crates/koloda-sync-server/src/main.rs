@@ -0,0 +1,64 @@
+//! `koloda-sync-server` — reference sync server: devices push their changes to it and pull
+//! everyone else's. It keeps its own koloda database, created on first start.
+//!
+//! Serves until interrupted; the startup line on stdout is JSON with the address and token.
+
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+
+use clap::Parser;
+use koloda_core::app::db::Database;
+use koloda_core::app::error::AppError;
+use koloda_core::http::{SyncServer, SyncServerOptions};
+use serde_json::json;
+
+#[derive(Parser)]
+#[command(name = "koloda-sync-server", version, about = "Sync server for koloda devices")]
+struct Cli {
+    /// Path to the server's SQLite database; created when missing.
+    #[arg(long, env = "KOLODA_SYNC_DB")]
+    db: PathBuf,
+    /// Address to listen on; `0.0.0.0` serves the LAN.
+    #[arg(long, env = "KOLODA_SYNC_HOST", default_value = "127.0.0.1")]
+    host: IpAddr,
+    #[arg(long, env = "KOLODA_SYNC_PORT", default_value_t = 8770)]
+    port: u16,
+    /// Bearer token devices must send; generated when unset.
+    #[arg(long, env = "KOLODA_SYNC_TOKEN")]
+    token: Option<String>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match start(cli) {
+        Ok(server) => {
+            let info = server.info();
+            println!(
+                "{}",
+                json!({ "host": info.host, "port": info.port, "token": info.token })
+            );
+            loop {
+                thread::park();
+            }
+        }
+        Err(error) => {
+            eprintln!("{}", json!(error));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn start(cli: Cli) -> Result<SyncServer, AppError> {
+    let db = Database::init(&cli.db)?;
+    SyncServer::start(
+        db,
+        SyncServerOptions {
+            host: Some(cli.host),
+            port: Some(cli.port),
+            token: cli.token,
+        },
+    )
+}
@@ -0,0 +1,10 @@
+CREATE TABLE "settings_history" (
+	"id" integer PRIMARY KEY GENERATED ALWAYS AS IDENTITY (sequence name "settings_history_id_seq" INCREMENT BY 1 MINVALUE 1 MAXVALUE 2147483647 START WITH 1 CACHE 1),
+	"name" varchar NOT NULL,
+	"old_content" jsonb,
+	"new_content" jsonb NOT NULL,
+	"patch" jsonb NOT NULL,
+	"created_at" timestamp DEFAULT now() NOT NULL
+);
+--> statement-breakpoint
+CREATE INDEX "settings_history_name_idx" ON "settings_history" USING btree ("name","created_at");
\ No newline at end of file
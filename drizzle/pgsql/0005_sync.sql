@@ -0,0 +1,45 @@
+CREATE TABLE "sync_state" (
+	"key" varchar PRIMARY KEY NOT NULL,
+	"value" varchar NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "sync_changes" (
+	"id" integer PRIMARY KEY GENERATED ALWAYS AS IDENTITY (sequence name "sync_changes_id_seq" INCREMENT BY 1 MINVALUE 1 MAXVALUE 2147483647 START WITH 1 CACHE 1),
+	"table_name" varchar NOT NULL,
+	"uid" varchar(32) NOT NULL,
+	"device_id" varchar,
+	"changed_at" timestamp DEFAULT now() NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE "sync_tombstones" (
+	"table_name" varchar NOT NULL,
+	"uid" varchar(32) NOT NULL,
+	"deleted_at" timestamp DEFAULT now() NOT NULL,
+	CONSTRAINT "sync_tombstones_table_name_uid_pk" PRIMARY KEY("table_name","uid")
+);
+--> statement-breakpoint
+ALTER TABLE "algorithms" ADD COLUMN "uid" varchar(32);
+--> statement-breakpoint
+ALTER TABLE "templates" ADD COLUMN "uid" varchar(32);
+--> statement-breakpoint
+ALTER TABLE "decks" ADD COLUMN "uid" varchar(32);
+--> statement-breakpoint
+ALTER TABLE "cards" ADD COLUMN "uid" varchar(32);
+--> statement-breakpoint
+ALTER TABLE "reviews" ADD COLUMN "uid" varchar(32);
+--> statement-breakpoint
+ALTER TABLE "settings" ADD COLUMN "uid" varchar(32);
+--> statement-breakpoint
+CREATE INDEX "sync_changes_uid_idx" ON "sync_changes" USING btree ("table_name","uid");
+--> statement-breakpoint
+CREATE UNIQUE INDEX "algorithms_uid_idx" ON "algorithms" USING btree ("uid");
+--> statement-breakpoint
+CREATE UNIQUE INDEX "templates_uid_idx" ON "templates" USING btree ("uid");
+--> statement-breakpoint
+CREATE UNIQUE INDEX "decks_uid_idx" ON "decks" USING btree ("uid");
+--> statement-breakpoint
+CREATE UNIQUE INDEX "cards_uid_idx" ON "cards" USING btree ("uid");
+--> statement-breakpoint
+CREATE UNIQUE INDEX "reviews_uid_idx" ON "reviews" USING btree ("uid");
+--> statement-breakpoint
+CREATE UNIQUE INDEX "settings_uid_idx" ON "settings" USING btree ("uid");
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE "change_feed" (
+	"id" integer PRIMARY KEY GENERATED ALWAYS AS IDENTITY (sequence name "change_feed_id_seq" INCREMENT BY 1 MINVALUE 1 MAXVALUE 2147483647 START WITH 1 CACHE 1),
+	"entity" varchar NOT NULL,
+	"entity_id" varchar NOT NULL,
+	"operation" varchar NOT NULL,
+	"changed_at" timestamp DEFAULT now() NOT NULL
+);
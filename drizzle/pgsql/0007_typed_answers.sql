@@ -0,0 +1,2 @@
+ALTER TABLE "reviews" ADD COLUMN "typed_answer" text;--> statement-breakpoint
+ALTER TABLE "reviews" ADD COLUMN "typed_score" real;
\ No newline at end of file
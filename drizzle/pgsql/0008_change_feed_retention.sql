@@ -0,0 +1,2 @@
+-- Custom SQL migration file, put your code below! --
+-- The retention trigger is SQLite-only; kept so both dialects number their migrations alike.
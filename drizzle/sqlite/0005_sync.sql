@@ -0,0 +1,219 @@
+CREATE TABLE `sync_state` (
+	`key` text PRIMARY KEY NOT NULL,
+	`value` text NOT NULL
+);
+--> statement-breakpoint
+CREATE TABLE `sync_changes` (
+	`id` integer PRIMARY KEY AUTOINCREMENT NOT NULL,
+	`table_name` text NOT NULL,
+	`uid` text NOT NULL,
+	`device_id` text,
+	`changed_at` integer NOT NULL
+);
+--> statement-breakpoint
+CREATE INDEX `sync_changes_uid_idx` ON `sync_changes` (`table_name`,`uid`);
+--> statement-breakpoint
+CREATE TABLE `sync_tombstones` (
+	`table_name` text NOT NULL,
+	`uid` text NOT NULL,
+	`deleted_at` integer NOT NULL,
+	PRIMARY KEY(`table_name`, `uid`)
+);
+--> statement-breakpoint
+ALTER TABLE `algorithms` ADD `uid` text;
+--> statement-breakpoint
+UPDATE algorithms SET uid = lower(hex(randomblob(16)));
+--> statement-breakpoint
+CREATE UNIQUE INDEX `algorithms_uid_idx` ON `algorithms` (`uid`);
+--> statement-breakpoint
+INSERT INTO sync_changes (table_name, uid, changed_at) SELECT 'algorithms', uid, coalesce(updated_at, created_at) FROM algorithms;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS algorithms_sync_uid AFTER INSERT ON algorithms WHEN NEW.uid IS NULL
+BEGIN
+	UPDATE algorithms SET uid = lower(hex(randomblob(16))) WHERE id = NEW.id;
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS algorithms_sync_insert AFTER INSERT ON algorithms WHEN NEW.uid IS NOT NULL
+BEGIN
+	DELETE FROM sync_tombstones WHERE table_name = 'algorithms' AND uid = NEW.uid;
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('algorithms', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS algorithms_sync_update AFTER UPDATE ON algorithms WHEN NEW.uid IS NOT NULL
+BEGIN
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('algorithms', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS algorithms_sync_delete AFTER DELETE ON algorithms WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT OR REPLACE INTO sync_tombstones (table_name, uid, deleted_at) VALUES ('algorithms', OLD.uid, CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('algorithms', OLD.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+ALTER TABLE `templates` ADD `uid` text;
+--> statement-breakpoint
+UPDATE templates SET uid = lower(hex(randomblob(16)));
+--> statement-breakpoint
+CREATE UNIQUE INDEX `templates_uid_idx` ON `templates` (`uid`);
+--> statement-breakpoint
+INSERT INTO sync_changes (table_name, uid, changed_at) SELECT 'templates', uid, coalesce(updated_at, created_at) FROM templates;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS templates_sync_uid AFTER INSERT ON templates WHEN NEW.uid IS NULL
+BEGIN
+	UPDATE templates SET uid = lower(hex(randomblob(16))) WHERE id = NEW.id;
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS templates_sync_insert AFTER INSERT ON templates WHEN NEW.uid IS NOT NULL
+BEGIN
+	DELETE FROM sync_tombstones WHERE table_name = 'templates' AND uid = NEW.uid;
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('templates', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS templates_sync_update AFTER UPDATE ON templates WHEN NEW.uid IS NOT NULL
+BEGIN
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('templates', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS templates_sync_delete AFTER DELETE ON templates WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT OR REPLACE INTO sync_tombstones (table_name, uid, deleted_at) VALUES ('templates', OLD.uid, CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('templates', OLD.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+ALTER TABLE `decks` ADD `uid` text;
+--> statement-breakpoint
+UPDATE decks SET uid = lower(hex(randomblob(16)));
+--> statement-breakpoint
+CREATE UNIQUE INDEX `decks_uid_idx` ON `decks` (`uid`);
+--> statement-breakpoint
+INSERT INTO sync_changes (table_name, uid, changed_at) SELECT 'decks', uid, coalesce(updated_at, created_at) FROM decks;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS decks_sync_uid AFTER INSERT ON decks WHEN NEW.uid IS NULL
+BEGIN
+	UPDATE decks SET uid = lower(hex(randomblob(16))) WHERE id = NEW.id;
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS decks_sync_insert AFTER INSERT ON decks WHEN NEW.uid IS NOT NULL
+BEGIN
+	DELETE FROM sync_tombstones WHERE table_name = 'decks' AND uid = NEW.uid;
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('decks', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS decks_sync_update AFTER UPDATE ON decks WHEN NEW.uid IS NOT NULL
+BEGIN
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('decks', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS decks_sync_delete AFTER DELETE ON decks WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT OR REPLACE INTO sync_tombstones (table_name, uid, deleted_at) VALUES ('decks', OLD.uid, CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('decks', OLD.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+ALTER TABLE `cards` ADD `uid` text;
+--> statement-breakpoint
+UPDATE cards SET uid = lower(hex(randomblob(16)));
+--> statement-breakpoint
+CREATE UNIQUE INDEX `cards_uid_idx` ON `cards` (`uid`);
+--> statement-breakpoint
+INSERT INTO sync_changes (table_name, uid, changed_at) SELECT 'cards', uid, coalesce(updated_at, created_at) FROM cards;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS cards_sync_uid AFTER INSERT ON cards WHEN NEW.uid IS NULL
+BEGIN
+	UPDATE cards SET uid = lower(hex(randomblob(16))) WHERE id = NEW.id;
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS cards_sync_insert AFTER INSERT ON cards WHEN NEW.uid IS NOT NULL
+BEGIN
+	DELETE FROM sync_tombstones WHERE table_name = 'cards' AND uid = NEW.uid;
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('cards', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS cards_sync_update AFTER UPDATE ON cards WHEN NEW.uid IS NOT NULL
+BEGIN
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('cards', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS cards_sync_delete AFTER DELETE ON cards WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT OR REPLACE INTO sync_tombstones (table_name, uid, deleted_at) VALUES ('cards', OLD.uid, CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('cards', OLD.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+ALTER TABLE `reviews` ADD `uid` text;
+--> statement-breakpoint
+UPDATE reviews SET uid = lower(hex(randomblob(16)));
+--> statement-breakpoint
+CREATE UNIQUE INDEX `reviews_uid_idx` ON `reviews` (`uid`);
+--> statement-breakpoint
+INSERT INTO sync_changes (table_name, uid, changed_at) SELECT 'reviews', uid, created_at FROM reviews;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS reviews_sync_uid AFTER INSERT ON reviews WHEN NEW.uid IS NULL
+BEGIN
+	UPDATE reviews SET uid = lower(hex(randomblob(16))) WHERE id = NEW.id;
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS reviews_sync_insert AFTER INSERT ON reviews WHEN NEW.uid IS NOT NULL
+BEGIN
+	DELETE FROM sync_tombstones WHERE table_name = 'reviews' AND uid = NEW.uid;
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('reviews', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS reviews_sync_update AFTER UPDATE ON reviews WHEN NEW.uid IS NOT NULL
+BEGIN
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('reviews', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS reviews_sync_delete AFTER DELETE ON reviews WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT OR REPLACE INTO sync_tombstones (table_name, uid, deleted_at) VALUES ('reviews', OLD.uid, CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('reviews', OLD.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+ALTER TABLE `settings` ADD `uid` text;
+--> statement-breakpoint
+UPDATE settings SET uid = lower(hex(randomblob(16)));
+--> statement-breakpoint
+CREATE UNIQUE INDEX `settings_uid_idx` ON `settings` (`uid`);
+--> statement-breakpoint
+INSERT INTO sync_changes (table_name, uid, changed_at) SELECT 'settings', uid, coalesce(updated_at, created_at) FROM settings;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS settings_sync_uid AFTER INSERT ON settings WHEN NEW.uid IS NULL
+BEGIN
+	UPDATE settings SET uid = lower(hex(randomblob(16))) WHERE id = NEW.id;
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS settings_sync_insert AFTER INSERT ON settings WHEN NEW.uid IS NOT NULL
+BEGIN
+	DELETE FROM sync_tombstones WHERE table_name = 'settings' AND uid = NEW.uid;
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('settings', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS settings_sync_update AFTER UPDATE ON settings WHEN NEW.uid IS NOT NULL
+BEGIN
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('settings', NEW.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS settings_sync_delete AFTER DELETE ON settings WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT OR REPLACE INTO sync_tombstones (table_name, uid, deleted_at) VALUES ('settings', OLD.uid, CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+	INSERT INTO sync_changes (table_name, uid, device_id, changed_at)
+	VALUES ('settings', OLD.uid, (SELECT value FROM sync_state WHERE key = 'applying_device'), CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
\ No newline at end of file
@@ -0,0 +1,112 @@
+CREATE TABLE `change_feed` (
+	`id` integer PRIMARY KEY AUTOINCREMENT NOT NULL,
+	`entity` text NOT NULL,
+	`entity_id` text NOT NULL,
+	`operation` text NOT NULL,
+	`changed_at` integer NOT NULL
+);
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS algorithms_feed_insert AFTER INSERT ON algorithms
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('algorithm', CAST(NEW.id AS TEXT), 'insert', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS algorithms_feed_update AFTER UPDATE ON algorithms WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('algorithm', CAST(NEW.id AS TEXT), 'update', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS algorithms_feed_delete AFTER DELETE ON algorithms
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('algorithm', CAST(OLD.id AS TEXT), 'delete', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS templates_feed_insert AFTER INSERT ON templates
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('template', CAST(NEW.id AS TEXT), 'insert', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS templates_feed_update AFTER UPDATE ON templates WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('template', CAST(NEW.id AS TEXT), 'update', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS templates_feed_delete AFTER DELETE ON templates
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('template', CAST(OLD.id AS TEXT), 'delete', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS decks_feed_insert AFTER INSERT ON decks
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('deck', CAST(NEW.id AS TEXT), 'insert', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS decks_feed_update AFTER UPDATE ON decks WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('deck', CAST(NEW.id AS TEXT), 'update', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS decks_feed_delete AFTER DELETE ON decks
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('deck', CAST(OLD.id AS TEXT), 'delete', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS cards_feed_insert AFTER INSERT ON cards
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('card', CAST(NEW.id AS TEXT), 'insert', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS cards_feed_update AFTER UPDATE ON cards WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('card', CAST(NEW.id AS TEXT), 'update', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS cards_feed_delete AFTER DELETE ON cards
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('card', CAST(OLD.id AS TEXT), 'delete', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS reviews_feed_insert AFTER INSERT ON reviews
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('review', CAST(NEW.id AS TEXT), 'insert', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS reviews_feed_update AFTER UPDATE ON reviews WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('review', CAST(NEW.id AS TEXT), 'update', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS reviews_feed_delete AFTER DELETE ON reviews
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('review', CAST(OLD.id AS TEXT), 'delete', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS settings_feed_insert AFTER INSERT ON settings
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('settings', CAST(NEW.id AS TEXT), 'insert', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS settings_feed_update AFTER UPDATE ON settings WHEN OLD.uid IS NOT NULL
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('settings', CAST(NEW.id AS TEXT), 'update', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS settings_feed_delete AFTER DELETE ON settings
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('settings', CAST(OLD.id AS TEXT), 'delete', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS conversations_feed_insert AFTER INSERT ON conversations
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('conversation', CAST(NEW.id AS TEXT), 'insert', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS conversations_feed_update AFTER UPDATE ON conversations
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('conversation', CAST(NEW.id AS TEXT), 'update', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS conversations_feed_delete AFTER DELETE ON conversations
+BEGIN
+	INSERT INTO change_feed (entity, entity_id, operation, changed_at) VALUES ('conversation', CAST(OLD.id AS TEXT), 'delete', CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER));
+END;
\ No newline at end of file
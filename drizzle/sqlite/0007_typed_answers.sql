@@ -0,0 +1,2 @@
+ALTER TABLE `reviews` ADD `typed_answer` text;--> statement-breakpoint
+ALTER TABLE `reviews` ADD `typed_score` real;
\ No newline at end of file
@@ -0,0 +1,6 @@
+DELETE FROM sync_changes WHERE id NOT IN (SELECT max(id) FROM sync_changes GROUP BY table_name, uid);
+--> statement-breakpoint
+CREATE TRIGGER IF NOT EXISTS sync_changes_retention AFTER INSERT ON sync_changes
+BEGIN
+	DELETE FROM sync_changes WHERE table_name = NEW.table_name AND uid = NEW.uid AND id < NEW.id;
+END;
\ No newline at end of file
@@ -28,6 +28,7 @@ export const ERROR_MESSAGES = {
   "db.update": msg`db.update`,
   "db.delete": msg`db.delete`,
   "db.clone": msg`db.clone`,
+  "db.schema-newer": msg`db.schema-newer`,
   "not-found.algorithms.clone.source": msg`not-found.algorithms.clone.source`,
   "not-found.algorithms.update.algorithm": msg`not-found.algorithms.update.algorithm`,
   "not-found.algorithms.delete.successor": msg`not-found.algorithms.delete.successor`,
@@ -38,14 +39,39 @@ export const ERROR_MESSAGES = {
   "not-found.cards.update.card": msg`not-found.cards.update.card`,
   "not-found.cards.reset.card": msg`not-found.cards.reset.card`,
   "not-found.cards.update.template": msg`not-found.cards.update.template`,
+  "not-found.cards.move.card": msg`not-found.cards.move.card`,
+  "not-found.cards.move.deck": msg`not-found.cards.move.deck`,
+  "not-found.cards.move.template": msg`not-found.cards.move.template`,
+  "not-found.cards.copy.card": msg`not-found.cards.copy.card`,
+  "not-found.cards.copy.deck": msg`not-found.cards.copy.deck`,
+  "not-found.cards.copy.template": msg`not-found.cards.copy.template`,
+  "not-found.cards.convert.card": msg`not-found.cards.convert.card`,
+  "not-found.cards.convert.template": msg`not-found.cards.convert.template`,
+  "not-found.cards.merge.card": msg`not-found.cards.merge.card`,
+  "not-found.cards.rebuild.card": msg`not-found.cards.rebuild.card`,
+  "not-found.cards.rebuild.deck": msg`not-found.cards.rebuild.deck`,
+  "not-found.cards.rebuild.algorithm": msg`not-found.cards.rebuild.algorithm`,
+  "not-found.cards.render.card": msg`not-found.cards.render.card`,
+  "not-found.cards.render.deck": msg`not-found.cards.render.deck`,
+  "not-found.cards.render.template": msg`not-found.cards.render.template`,
   "not-found.decks.add.algorithm": msg`not-found.decks.add.algorithm`,
   "not-found.decks.add.template": msg`not-found.decks.add.template`,
   "not-found.decks.update.deck": msg`not-found.decks.update.deck`,
   "not-found.decks.update.algorithm": msg`not-found.decks.update.algorithm`,
   "not-found.decks.update.template": msg`not-found.decks.update.template`,
+  "not-found.decks.export.deck": msg`not-found.decks.export.deck`,
+  "not-found.settings.history.entry": msg`not-found.settings.history.entry`,
+  "not-found.db.snapshot": msg`not-found.db.snapshot`,
+  "not-found.db.file": msg`not-found.db.file`,
+  "not-found.http.route": msg`not-found.http.route`,
+  "not-found.anki-connect.deck": msg`not-found.anki-connect.deck`,
+  "not-found.anki-connect.model": msg`not-found.anki-connect.model`,
+  "not-found.anki-connect.action": msg`not-found.anki-connect.action`,
   "validation.common.title.too-short": msg`validation.common.title.too-short`,
   "validation.common.title.too-long": ({ maximum }: any) =>
     msg`${plural(maximum, { other: "validation.common.title.too-long" })}`,
+  "validation.common.content.version": msg`validation.common.content.version`,
+  "validation.common.payload": msg`validation.common.payload`,
   "validation.settings-learning.daily-limits.untouched-exceeds-total": msg`validation.settings-learning.daily-limits.untouched-exceeds-total`,
   "validation.settings-learning.daily-limits.learn-exceeds-total": msg`validation.settings-learning.daily-limits.learn-exceeds-total`,
   "validation.settings-learning.daily-limits.review-exceeds-total": msg`validation.settings-learning.daily-limits.review-exceeds-total`,
@@ -62,10 +88,41 @@ export const ERROR_MESSAGES = {
   "validation.ai-providers.profile-id.duplicate": msg`validation.ai-providers.profile-id.duplicate`,
   "validation.assistant-settings.temperature-range": msg`validation.assistant-settings.temperature-range`,
   "validation.templates.fields.too-few": msg`validation.templates.fields.too-few`,
+  "validation.templates.fields.rules": msg`validation.templates.fields.rules`,
   "validation.templates.layout.too-few": msg`validation.templates.layout.too-few`,
   "validation.templates.update-locked": msg`validation.templates.update-locked`,
   "validation.templates.delete-locked": msg`validation.templates.delete-locked`,
+  "validation.templates.sides.syntax": msg`validation.templates.sides.syntax`,
   "validation.cards.content.field-empty": msg`validation.cards.content.field-empty`,
+  "validation.cards.content.field-too-long": msg`validation.cards.content.field-too-long`,
+  "validation.cards.content.field-pattern": msg`validation.cards.content.field-pattern`,
+  "validation.cards.content.field-script": msg`validation.cards.content.field-script`,
+  "validation.cards.content.field-not-unique": msg`validation.cards.content.field-not-unique`,
+  "validation.cards.field-mapping.missing": msg`validation.cards.field-mapping.missing`,
+  "validation.cards.field-mapping.invalid": msg`validation.cards.field-mapping.invalid`,
+  "validation.cards.selection.empty": msg`validation.cards.selection.empty`,
+  "validation.cards.find-replace.pattern-empty": msg`validation.cards.find-replace.pattern-empty`,
+  "validation.cards.find-replace.pattern-invalid": msg`validation.cards.find-replace.pattern-invalid`,
+  "validation.cards.merge.sources": msg`validation.cards.merge.sources`,
+  "validation.cards.merge.template": msg`validation.cards.merge.template`,
+  "validation.cards.add.duplicate": msg`validation.cards.add.duplicate`,
+  "validation.cards.add.duplicate-field": msg`validation.cards.add.duplicate-field`,
+  "validation.ai-secrets.passphrase": msg`validation.ai-secrets.passphrase`,
+  "validation.settings.history.name-mismatch": msg`validation.settings.history.name-mismatch`,
+  "validation.settings.bundle.version": msg`validation.settings.bundle.version`,
+  "validation.settings.bundle.duplicate-slice": msg`validation.settings.bundle.duplicate-slice`,
+  "validation.deck.bundle.version": msg`validation.deck.bundle.version`,
+  "validation.anki-connect.query": msg`validation.anki-connect.query`,
+  "validation.sync.version": msg`validation.sync.version`,
+  "validation.reviews.typed-score": msg`validation.reviews.typed-score`,
+  "validation.typed-answer.too-long": msg`validation.typed-answer.too-long`,
+  "secret-store.unavailable": msg`secret-store.unavailable`,
+  "secret-store.decrypt": msg`secret-store.decrypt`,
+  "task.cancelled": msg`task.cancelled`,
+  "http.bind": msg`http.bind`,
+  "http.unauthorized": msg`http.unauthorized`,
+  "http.method-not-allowed": msg`http.method-not-allowed`,
+  "sync.request": msg`sync.request`,
 } as const;
 
 export type ErrorCode = keyof typeof ERROR_MESSAGES;
@@ -5,8 +5,10 @@ import {
   integer,
   jsonb,
   pgTable as table,
+  primaryKey,
   real,
   smallint,
+  text,
   timestamp,
   uniqueIndex,
   varchar,
@@ -23,9 +25,23 @@ export const settings = table(
     id: integer().primaryKey().generatedAlwaysAsIdentity(),
     name: varchar().notNull(),
     content: jsonb().notNull().default({}),
+    uid: varchar({ length: 32 }),
     ...timestamps,
   },
-  (table) => [uniqueIndex("settings_name_idx").on(table.name)],
+  (table) => [uniqueIndex("settings_name_idx").on(table.name), uniqueIndex("settings_uid_idx").on(table.uid)],
+);
+
+export const settingsHistory = table(
+  "settings_history",
+  {
+    id: integer().primaryKey().generatedAlwaysAsIdentity(),
+    name: varchar().notNull(),
+    oldContent: jsonb("old_content"),
+    newContent: jsonb("new_content").notNull(),
+    patch: jsonb().notNull(),
+    createdAt: timestamp("created_at").defaultNow().notNull(),
+  },
+  (table) => [index("settings_history_name_idx").on(table.name, table.createdAt)],
 );
 
 export const algorithms = table(
@@ -34,9 +50,10 @@ export const algorithms = table(
     id: integer().primaryKey().generatedAlwaysAsIdentity(),
     title: varchar({ length: 255 }).notNull(),
     content: jsonb().notNull().$type<object>(),
+    uid: varchar({ length: 32 }),
     ...timestamps,
   },
-  (table) => [index("algorithms_title_idx").on(table.title)],
+  (table) => [index("algorithms_title_idx").on(table.title), uniqueIndex("algorithms_uid_idx").on(table.uid)],
 );
 
 export const templates = table(
@@ -45,9 +62,10 @@ export const templates = table(
     id: integer().primaryKey().generatedAlwaysAsIdentity(),
     title: varchar({ length: 255 }).notNull(),
     content: jsonb().notNull(),
+    uid: varchar({ length: 32 }),
     ...timestamps,
   },
-  (table) => [index("templates_title_idx").on(table.title)],
+  (table) => [index("templates_title_idx").on(table.title), uniqueIndex("templates_uid_idx").on(table.uid)],
 );
 
 export const decks = table(
@@ -61,9 +79,10 @@ export const decks = table(
     templateId: integer("template_id")
       .notNull()
       .references(() => templates.id),
+    uid: varchar({ length: 32 }),
     ...timestamps,
   },
-  (table) => [index("decks_title_idx").on(table.title)],
+  (table) => [index("decks_title_idx").on(table.title), uniqueIndex("decks_uid_idx").on(table.uid)],
 );
 
 export const cards = table(
@@ -86,27 +105,35 @@ export const cards = table(
     reps: integer().default(0),
     lapses: integer().default(0),
     lastReviewedAt: timestamp("last_reviewed_at"),
+    uid: varchar({ length: 32 }),
     ...timestamps,
   },
-  (table) => [index("cards_due_at_idx").on(table.dueAt)],
+  (table) => [index("cards_due_at_idx").on(table.dueAt), uniqueIndex("cards_uid_idx").on(table.uid)],
 );
 
-export const reviews = table("reviews", {
-  id: bigint({ mode: "bigint" }).primaryKey().generatedAlwaysAsIdentity(),
-  cardId: integer("card_id")
-    .notNull()
-    .references(() => cards.id, { onDelete: "cascade" }),
-  state: smallint().notNull().default(0),
-  rating: smallint().notNull().default(0),
-  dueAt: timestamp("due_at"),
-  stability: real().notNull().default(0),
-  difficulty: real().notNull().default(0),
-  scheduledDays: integer("scheduled_days").notNull().default(0),
-  learningSteps: integer().notNull().default(0),
-  time: integer().notNull().default(0),
-  isIgnored: boolean("is_ignored").default(false).notNull(),
-  createdAt: timestamp("created_at").defaultNow().notNull(),
-});
+export const reviews = table(
+  "reviews",
+  {
+    id: bigint({ mode: "bigint" }).primaryKey().generatedAlwaysAsIdentity(),
+    cardId: integer("card_id")
+      .notNull()
+      .references(() => cards.id, { onDelete: "cascade" }),
+    state: smallint().notNull().default(0),
+    rating: smallint().notNull().default(0),
+    dueAt: timestamp("due_at"),
+    stability: real().notNull().default(0),
+    difficulty: real().notNull().default(0),
+    scheduledDays: integer("scheduled_days").notNull().default(0),
+    learningSteps: integer().notNull().default(0),
+    time: integer().notNull().default(0),
+    isIgnored: boolean("is_ignored").default(false).notNull(),
+    typedAnswer: text("typed_answer"),
+    typedScore: real("typed_score"),
+    uid: varchar({ length: 32 }),
+    createdAt: timestamp("created_at").defaultNow().notNull(),
+  },
+  (table) => [uniqueIndex("reviews_uid_idx").on(table.uid)],
+);
 
 export const conversations = table(
   "conversations",
@@ -119,12 +146,54 @@ export const conversations = table(
   (table) => [index("conversations_updated_at_idx").on(table.updatedAt, table.createdAt)],
 );
 
+// WHY: only the SQLite desktop database records changes; these tables keep the two schemas aligned.
+export const syncState = table("sync_state", {
+  key: varchar().primaryKey(),
+  value: varchar().notNull(),
+});
+
+export const syncChanges = table(
+  "sync_changes",
+  {
+    id: integer().primaryKey().generatedAlwaysAsIdentity(),
+    tableName: varchar("table_name").notNull(),
+    uid: varchar({ length: 32 }).notNull(),
+    deviceId: varchar("device_id"),
+    changedAt: timestamp("changed_at").defaultNow().notNull(),
+  },
+  (table) => [index("sync_changes_uid_idx").on(table.tableName, table.uid)],
+);
+
+export const syncTombstones = table(
+  "sync_tombstones",
+  {
+    tableName: varchar("table_name").notNull(),
+    uid: varchar({ length: 32 }).notNull(),
+    deletedAt: timestamp("deleted_at").defaultNow().notNull(),
+  },
+  (table) => [primaryKey({ columns: [table.tableName, table.uid] })],
+);
+
+// WHY: only the SQLite desktop database fills the feed; the table keeps the two schemas aligned.
+export const changeFeed = table("change_feed", {
+  id: integer().primaryKey().generatedAlwaysAsIdentity(),
+  entity: varchar().notNull(),
+  entityId: varchar("entity_id").notNull(),
+  operation: varchar().notNull(),
+  changedAt: timestamp("changed_at").defaultNow().notNull(),
+});
+
 export const schema = {
   settings,
+  settingsHistory,
   algorithms,
   decks,
   templates,
   cards,
   reviews,
   conversations,
+  syncState,
+  syncChanges,
+  syncTombstones,
+  changeFeed,
 };
@@ -1,4 +1,4 @@
-import { index, integer, real, sqliteTable as table, text, uniqueIndex } from "drizzle-orm/sqlite-core";
+import { index, integer, primaryKey, real, sqliteTable as table, text, uniqueIndex } from "drizzle-orm/sqlite-core";
 
 const timestamps = {
   updatedAt: integer("updated_at", { mode: "timestamp_ms" }),
@@ -13,9 +13,25 @@ export const settings = table(
     id: integer("id", { mode: "number" }).primaryKey({ autoIncrement: true }),
     name: text("name").notNull(),
     content: text("content", { mode: "json" }).notNull().default("{}"),
+    uid: text("uid"),
     ...timestamps,
   },
-  (t) => [uniqueIndex("settings_name_idx").on(t.name)],
+  (t) => [uniqueIndex("settings_name_idx").on(t.name), uniqueIndex("settings_uid_idx").on(t.uid)],
+);
+
+export const settingsHistory = table(
+  "settings_history",
+  {
+    id: integer("id", { mode: "number" }).primaryKey({ autoIncrement: true }),
+    name: text("name").notNull(),
+    oldContent: text("old_content", { mode: "json" }),
+    newContent: text("new_content", { mode: "json" }).notNull(),
+    patch: text("patch", { mode: "json" }).notNull(),
+    createdAt: integer("created_at", { mode: "timestamp_ms" })
+      .$defaultFn(() => new Date())
+      .notNull(),
+  },
+  (t) => [index("settings_history_name_idx").on(t.name, t.createdAt)],
 );
 
 export const algorithms = table(
@@ -24,9 +40,10 @@ export const algorithms = table(
     id: integer("id", { mode: "number" }).primaryKey({ autoIncrement: true }),
     title: text("title").notNull(),
     content: text("content", { mode: "json" }).notNull(),
+    uid: text("uid"),
     ...timestamps,
   },
-  (t) => [index("algorithms_title_idx").on(t.title)],
+  (t) => [index("algorithms_title_idx").on(t.title), uniqueIndex("algorithms_uid_idx").on(t.uid)],
 );
 
 export const templates = table(
@@ -35,9 +52,10 @@ export const templates = table(
     id: integer("id", { mode: "number" }).primaryKey({ autoIncrement: true }),
     title: text("title").notNull(),
     content: text("content", { mode: "json" }).notNull(),
+    uid: text("uid"),
     ...timestamps,
   },
-  (t) => [index("templates_title_idx").on(t.title)],
+  (t) => [index("templates_title_idx").on(t.title), uniqueIndex("templates_uid_idx").on(t.uid)],
 );
 
 export const decks = table(
@@ -51,9 +69,10 @@ export const decks = table(
     templateId: integer("template_id")
       .notNull()
       .references(() => templates.id),
+    uid: text("uid"),
     ...timestamps,
   },
-  (t) => [index("decks_title_idx").on(t.title)],
+  (t) => [index("decks_title_idx").on(t.title), uniqueIndex("decks_uid_idx").on(t.uid)],
 );
 
 export const cards = table(
@@ -76,29 +95,37 @@ export const cards = table(
     reps: integer("reps").default(0),
     lapses: integer("lapses").default(0),
     lastReviewedAt: integer("last_reviewed_at", { mode: "timestamp_ms" }),
+    uid: text("uid"),
     ...timestamps,
   },
-  (t) => [index("cards_due_at_idx").on(t.dueAt)],
+  (t) => [index("cards_due_at_idx").on(t.dueAt), uniqueIndex("cards_uid_idx").on(t.uid)],
 );
 
-export const reviews = table("reviews", {
-  id: integer("id", { mode: "number" }).primaryKey({ autoIncrement: true }),
-  cardId: integer("card_id")
-    .notNull()
-    .references(() => cards.id, { onDelete: "cascade" }),
-  state: integer("state").notNull().default(0),
-  rating: integer("rating").notNull().default(0),
-  dueAt: integer("due_at", { mode: "timestamp_ms" }),
-  stability: real("stability").notNull().default(0),
-  difficulty: real("difficulty").notNull().default(0),
-  scheduledDays: integer("scheduled_days").notNull().default(0),
-  learningSteps: integer("learning_steps").notNull().default(0),
-  time: integer("time").notNull().default(0),
-  isIgnored: integer("is_ignored", { mode: "boolean" }).notNull().default(false),
-  createdAt: integer("created_at", { mode: "timestamp_ms" })
-    .$defaultFn(() => new Date())
-    .notNull(),
-});
+export const reviews = table(
+  "reviews",
+  {
+    id: integer("id", { mode: "number" }).primaryKey({ autoIncrement: true }),
+    cardId: integer("card_id")
+      .notNull()
+      .references(() => cards.id, { onDelete: "cascade" }),
+    state: integer("state").notNull().default(0),
+    rating: integer("rating").notNull().default(0),
+    dueAt: integer("due_at", { mode: "timestamp_ms" }),
+    stability: real("stability").notNull().default(0),
+    difficulty: real("difficulty").notNull().default(0),
+    scheduledDays: integer("scheduled_days").notNull().default(0),
+    learningSteps: integer("learning_steps").notNull().default(0),
+    time: integer("time").notNull().default(0),
+    isIgnored: integer("is_ignored", { mode: "boolean" }).notNull().default(false),
+    typedAnswer: text("typed_answer"),
+    typedScore: real("typed_score"),
+    uid: text("uid"),
+    createdAt: integer("created_at", { mode: "timestamp_ms" })
+      .$defaultFn(() => new Date())
+      .notNull(),
+  },
+  (t) => [uniqueIndex("reviews_uid_idx").on(t.uid)],
+);
 
 export const conversations = table(
   "conversations",
@@ -111,12 +138,55 @@ export const conversations = table(
   (t) => [index("conversations_updated_at_idx").on(t.updatedAt, t.createdAt)],
 );
 
+// WHY: the uid backfill and the change-tracking triggers are hand-written in `0005_sync`;
+// drizzle only knows the columns and tables.
+export const syncState = table("sync_state", {
+  key: text("key").primaryKey(),
+  value: text("value").notNull(),
+});
+
+export const syncChanges = table(
+  "sync_changes",
+  {
+    id: integer("id", { mode: "number" }).primaryKey({ autoIncrement: true }),
+    tableName: text("table_name").notNull(),
+    uid: text("uid").notNull(),
+    deviceId: text("device_id"),
+    changedAt: integer("changed_at", { mode: "timestamp_ms" }).notNull(),
+  },
+  (t) => [index("sync_changes_uid_idx").on(t.tableName, t.uid)],
+);
+
+export const syncTombstones = table(
+  "sync_tombstones",
+  {
+    tableName: text("table_name").notNull(),
+    uid: text("uid").notNull(),
+    deletedAt: integer("deleted_at", { mode: "timestamp_ms" }).notNull(),
+  },
+  (t) => [primaryKey({ columns: [t.tableName, t.uid] })],
+);
+
+// WHY: the triggers that fill the feed are hand-written in `0006_change_feed`.
+export const changeFeed = table("change_feed", {
+  id: integer("id", { mode: "number" }).primaryKey({ autoIncrement: true }),
+  entity: text("entity").notNull(),
+  entityId: text("entity_id").notNull(),
+  operation: text("operation").notNull(),
+  changedAt: integer("changed_at", { mode: "timestamp_ms" }).notNull(),
+});
+
 export const schema = {
   settings,
+  settingsHistory,
   algorithms,
   templates,
   decks,
   cards,
   reviews,
   conversations,
+  syncState,
+  syncChanges,
+  syncTombstones,
+  changeFeed,
 };
@@ -56,14 +56,16 @@ export const insertCardSchema = cardValidation.omit({ id: true });
 
 export type InsertCardData = z.input<typeof insertCardSchema>;
 
-// Mirrors koloda-core `AddCardsItemResult`: success items omit `error`, failures
-// carry the structured `{ code, details? }` using AppError-catalog codes.
+// Mirrors koloda-core `AddCardsItemResult`: success items omit `error` and carry the
+// new `cardId`, failures carry the structured `{ code, details? }` using AppError-catalog codes.
+// Skipped duplicates carry `duplicateOf`; rejected ones carry `existingCardId` in the error.
 export type InsertCardsItemError = {
   code: string;
   details?: string;
+  existingCardId?: number;
 };
 
-export type InsertCardsResponse = Array<{ error?: InsertCardsItemError }>;
+export type InsertCardsResponse = Array<{ error?: InsertCardsItemError; duplicateOf?: number; cardId?: number }>;
 
 export function getUpdateCardSchema(template: Template) {
   const contentValidation = getCardContentValidation(template.content.fields);
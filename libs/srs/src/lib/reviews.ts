@@ -22,6 +22,12 @@ export const reviewValidation = z.object({
   learningSteps: z.int().default(0),
   time: z.int().default(0),
   isIgnored: z.boolean().default(false),
+  typedAnswer: z.string().nullish(),
+  typedScore: z
+    .number()
+    .min(0, "validation.reviews.typed-score")
+    .max(1, "validation.reviews.typed-score")
+    .nullish(),
   createdAt: z.date(),
 });
 
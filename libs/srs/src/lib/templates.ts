@@ -5,9 +5,23 @@ import { z } from "zod";
 
 export const TEMPLATE_FIELD_TYPES = ["text", "markdown"] as const;
 export const TEMPLATE_OPERATIONS = ["display", "reveal", "type"] as const;
+export const TEMPLATE_FIELD_SCRIPTS = [
+  "latin",
+  "cyrillic",
+  "greek",
+  "arabic",
+  "hebrew",
+  "devanagari",
+  "thai",
+  "han",
+  "hiragana",
+  "katakana",
+  "hangul",
+] as const;
 
 export type TemplateFieldType = (typeof TEMPLATE_FIELD_TYPES)[number];
 export type TemplateOperation = (typeof TEMPLATE_OPERATIONS)[number];
+export type TemplateFieldScript = (typeof TEMPLATE_FIELD_SCRIPTS)[number];
 
 export const TEMPLATE_FIELD_TYPES_MESSAGES = [
   { id: "text", value: msg`templates.field-types.text` },
@@ -31,6 +45,15 @@ export const templateValidation = z.object({
           title: z.string(),
           type: z.enum(TEMPLATE_FIELD_TYPES),
           isRequired: z.boolean(),
+          // WHY: cards are checked against the rules by the core on write (`validation.cards.content.*`).
+          rules: z
+            .object({
+              maxLength: z.int().min(1, "validation.templates.fields.rules").nullish(),
+              pattern: z.string().nullish(),
+              allowedScripts: z.array(z.enum(TEMPLATE_FIELD_SCRIPTS)).default([]),
+              uniqueInDeck: z.boolean().default(false),
+            })
+            .optional(),
         }),
       )
       .min(1, "validation.templates.fields.too-few"),
@@ -42,6 +65,13 @@ export const templateValidation = z.object({
         }),
       )
       .min(1, "validation.templates.layout.too-few"),
+    // WHY: the markup syntax is checked by the core on save (`validation.templates.sides.syntax`).
+    sides: z
+      .object({
+        question: z.string(),
+        answer: z.string(),
+      })
+      .optional(),
   }),
 });
 
@@ -68,6 +98,10 @@ export type TemplateLayout = Template["content"]["layout"];
 
 export type TemplateLayoutItem = TemplateLayout[number];
 
+export type TemplateSides = NonNullable<Template["content"]["sides"]>;
+
+export type TemplateFieldRules = NonNullable<TemplateField["rules"]>;
+
 export const DEFAULT_TEMPLATE: InsertTemplateData = {
   title: "Default",
   content: {
@@ -122,7 +156,8 @@ export function validateLockedTemplateFields(original: TemplateField[], updated:
 
     const originalKeys = Object.keys(originalField) as (keyof TemplateField)[];
     for (const key of originalKeys) {
-      if (key === "title") continue;
+      // WHY: rules only constrain future writes, so the core lets them change on a locked template.
+      if (key === "title" || key === "rules") continue;
 
       if (updatedField[key] !== originalField[key]) {
         errors.push(`Field (id: ${originalField.id}): property '${key}' changed`);